@@ -175,6 +175,27 @@ impl BufferDiffSnapshot {
         &self.inner.base_text
     }
 
+    /// Returns the row in the base text that corresponds to the given buffer
+    /// row. Rows inside of a hunk correspond to the start of the hunk's base
+    /// text.
+    pub fn base_text_row_for_buffer_row(&self, row: u32, buffer: &text::BufferSnapshot) -> u32 {
+        // Only the last hunk starting on or before the row matters.
+        let row_end = buffer.clip_point(Point::new(row, u32::MAX), Bias::Left);
+        let Some(hunk) = self
+            .inner
+            .hunks_intersecting_range_rev(Anchor::MIN..buffer.anchor_after(row_end), buffer)
+            .find(|hunk| hunk.range.start.row <= row)
+        else {
+            return row;
+        };
+        let base_text_range = hunk.diff_base_byte_range.to_point(&self.inner.base_text);
+        if row < hunk.range.end.row {
+            base_text_range.start.row
+        } else {
+            row - hunk.range.end.row + base_text_range.end.row
+        }
+    }
+
     pub fn base_texts_eq(&self, other: &Self) -> bool {
         if self.inner.base_text_exists != other.inner.base_text_exists {
             return false;
@@ -371,6 +392,98 @@ impl BufferDiffInner {
         (Some(new_index_text), pending_hunks)
     }
 
+    /// Returns the new index text after staging or unstaging only the lines
+    /// of each hunk that fall within `rows`, along with the buffer range the
+    /// affected hunks span. Returns `None` if no such lines are found.
+    fn stage_or_unstage_lines(
+        &self,
+        unstaged_diff: &Self,
+        stage: bool,
+        rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+    ) -> Option<(Rope, Range<Anchor>)> {
+        let index_text = unstaged_diff.base_text.as_rope();
+        let mut edits = Vec::<(Range<usize>, String)>::new();
+        let mut changed_range: Option<Range<Anchor>> = None;
+
+        if stage {
+            // Unstaged hunks are diffed against the index, so their base
+            // ranges are exactly the index text to replace.
+            for hunk in unstaged_diff.hunks.iter() {
+                let Some(selected) = selected_hunk_lines(&hunk.buffer_range, rows, buffer) else {
+                    continue;
+                };
+                let old_text = index_text
+                    .chunks_in_range(hunk.diff_base_byte_range.clone())
+                    .collect::<String>();
+                let new_text = buffer
+                    .text_for_range(hunk.buffer_range.clone())
+                    .collect::<String>();
+                edits.push((
+                    hunk.diff_base_byte_range.clone(),
+                    apply_selected_lines(&old_text, &new_text, |line| selected[line]),
+                ));
+                let changed_range = changed_range.get_or_insert(hunk.buffer_range.clone());
+                changed_range.end = hunk.buffer_range.end;
+            }
+        } else {
+            // Only hunks that are entirely staged can have lines unstaged. The
+            // index contains their buffer text, and reverting the selected
+            // lines is the same as staging the unselected ones from HEAD.
+            let head_text = self.base_text.as_rope();
+            let mut unstaged_hunks = unstaged_diff.hunks.iter().peekable();
+            let mut prev_unstaged_hunk_buffer_offset = 0;
+            let mut prev_unstaged_hunk_base_text_offset = 0;
+            for hunk in
+                self.hunks_intersecting_range(Anchor::MIN..Anchor::MAX, buffer, Some(unstaged_diff))
+            {
+                if hunk.secondary_status != DiffHunkSecondaryStatus::NoSecondaryHunk {
+                    continue;
+                }
+                let Some(selected) = selected_hunk_lines(&hunk.buffer_range, rows, buffer) else {
+                    continue;
+                };
+
+                let buffer_offset_range = hunk.buffer_range.to_offset(buffer);
+                while let Some(unstaged_hunk) = unstaged_hunks.next_if(|unstaged_hunk| {
+                    unstaged_hunk.buffer_range.end.to_offset(buffer) <= buffer_offset_range.start
+                }) {
+                    prev_unstaged_hunk_buffer_offset =
+                        unstaged_hunk.buffer_range.end.to_offset(buffer);
+                    prev_unstaged_hunk_base_text_offset = unstaged_hunk.diff_base_byte_range.end;
+                }
+                let index_range = buffer_offset_range.start - prev_unstaged_hunk_buffer_offset
+                    + prev_unstaged_hunk_base_text_offset
+                    ..buffer_offset_range.end - prev_unstaged_hunk_buffer_offset
+                        + prev_unstaged_hunk_base_text_offset;
+
+                let old_text = head_text
+                    .chunks_in_range(hunk.diff_base_byte_range.clone())
+                    .collect::<String>();
+                let new_text = buffer
+                    .text_for_range(buffer_offset_range)
+                    .collect::<String>();
+                edits.push((
+                    index_range,
+                    apply_selected_lines(&old_text, &new_text, |line| !selected[line]),
+                ));
+                let changed_range = changed_range.get_or_insert(hunk.buffer_range.clone());
+                changed_range.end = hunk.buffer_range.end;
+            }
+        }
+
+        let changed_range = changed_range?;
+        let mut new_index_text = Rope::new();
+        let mut index_cursor = index_text.cursor(0);
+        for (old_range, replacement_text) in edits {
+            new_index_text.append(index_cursor.slice(old_range.start));
+            index_cursor.seek_forward(old_range.end);
+            new_index_text.push(&replacement_text);
+        }
+        new_index_text.append(index_cursor.suffix());
+        Some((new_index_text, changed_range))
+    }
+
     fn hunks_intersecting_range<'a>(
         &'a self,
         range: Range<Anchor>,
@@ -725,6 +838,63 @@ fn process_patch_hunk(
     }
 }
 
+/// Returns which of a hunk's added lines fall within `rows`, or `None` if
+/// none do. A hunk that only deletes lines is treated as a single line at the
+/// row where the deletion happened.
+fn selected_hunk_lines(
+    buffer_range: &Range<Anchor>,
+    rows: &[Range<u32>],
+    buffer: &text::BufferSnapshot,
+) -> Option<Vec<bool>> {
+    let hunk_rows = buffer_range.to_point(buffer);
+    let hunk_rows = hunk_rows.start.row..hunk_rows.end.row + (hunk_rows.end.column > 0) as u32;
+    let line_count = hunk_rows.len().max(1);
+    let selected = (0..line_count as u32)
+        .map(|line| {
+            let row = hunk_rows.start + line;
+            rows.iter().any(|range| range.contains(&row))
+        })
+        .collect::<Vec<_>>();
+    selected.contains(&true).then_some(selected)
+}
+
+/// Applies part of the change from `old_text` to `new_text`, keeping only the
+/// new lines for which `is_selected` returns true.
+///
+/// Each new line replaces the old line at the same position in the hunk, and
+/// any old lines beyond the last new line go along with it. Old lines whose
+/// replacement isn't selected are kept.
+fn apply_selected_lines(
+    old_text: &str,
+    new_text: &str,
+    is_selected: impl Fn(usize) -> bool,
+) -> String {
+    let old_lines = old_text.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new_text.split_inclusive('\n').collect::<Vec<_>>();
+    let last_new_line = new_lines.len().saturating_sub(1);
+
+    let mut result = String::new();
+    let mut push_line = |line: &str| {
+        if !result.is_empty() && !result.ends_with('\n') {
+            result.push('\n');
+        }
+        result.push_str(line);
+    };
+    for ix in 0..old_lines.len().max(new_lines.len()) {
+        if let Some(old_line) = old_lines.get(ix) {
+            if !is_selected(ix.min(last_new_line)) {
+                push_line(old_line);
+            }
+        }
+        if let Some(new_line) = new_lines.get(ix) {
+            if is_selected(ix) {
+                push_line(new_line);
+            }
+        }
+    }
+    result
+}
+
 impl std::fmt::Debug for BufferDiff {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BufferChangeSet")
@@ -879,6 +1049,34 @@ impl BufferDiff {
         new_index_text
     }
 
+    /// Stages or unstages the lines of this diff's hunks that fall within
+    /// the given buffer rows, splitting hunks at the selection's boundaries.
+    ///
+    /// Unlike [`Self::stage_or_unstage_hunks`], only hunks whose changes are
+    /// entirely unstaged (or staged, when unstaging) are split, and no hunks
+    /// are marked as pending: the new index text is written as a whole.
+    pub fn stage_or_unstage_lines(
+        &mut self,
+        stage: bool,
+        rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let (new_index_text, changed_range) = self.inner.stage_or_unstage_lines(
+            &self.secondary_diff.as_ref()?.read(cx).inner,
+            stage,
+            rows,
+            buffer,
+        )?;
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(Some(
+            new_index_text.clone(),
+        )));
+        cx.emit(BufferDiffEvent::DiffChanged {
+            changed_range: Some(changed_range),
+        });
+        Some(new_index_text)
+    }
+
     pub fn range_to_hunk_range(
         &self,
         range: Range<Anchor>,
@@ -1344,6 +1542,39 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_base_text_row_for_buffer_row(cx: &mut gpui::TestAppContext) {
+        let diff_base = "
+            one
+            two
+            three
+            four
+            five
+        "
+        .unindent();
+
+        let buffer_text = "
+            zero
+            one
+            TWO
+            THREE
+            THREE AND A HALF
+            five
+        "
+        .unindent();
+
+        let buffer = Buffer::new(0, BufferId::new(1).unwrap(), buffer_text);
+        let diff = BufferDiff::build_sync(buffer.clone(), diff_base.clone(), cx);
+        let diff = BufferDiffSnapshot {
+            inner: diff,
+            secondary_diff: None,
+        };
+        let base_text_rows = (0..6)
+            .map(|row| diff.base_text_row_for_buffer_row(row, &buffer))
+            .collect::<Vec<_>>();
+        assert_eq!(base_text_rows, [0, 0, 1, 1, 1, 4]);
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_with_secondary(cx: &mut gpui::TestAppContext) {
         let head_text = "
@@ -1705,6 +1936,254 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_stage_and_unstage_lines(cx: &mut TestAppContext) {
+        struct Example {
+            name: &'static str,
+            head_text: String,
+            index_text: String,
+            buffer_text: String,
+            stage: bool,
+            rows: Vec<Range<u32>>,
+            final_index_text: String,
+        }
+
+        let table = [
+            Example {
+                name: "stage second line of modified hunk",
+                head_text: "
+                    one
+                    two
+                    three
+                    four
+                "
+                .unindent(),
+                index_text: "
+                    one
+                    two
+                    three
+                    four
+                "
+                .unindent(),
+                buffer_text: "
+                    one
+                    TWO
+                    THREE
+                    four
+                "
+                .unindent(),
+                stage: true,
+                rows: vec![2..3],
+                final_index_text: "
+                    one
+                    two
+                    THREE
+                    four
+                "
+                .unindent(),
+            },
+            Example {
+                name: "stage some added lines",
+                head_text: "
+                    one
+                    two
+                "
+                .unindent(),
+                index_text: "
+                    one
+                    two
+                "
+                .unindent(),
+                buffer_text: "
+                    one
+                    A
+                    B
+                    C
+                    two
+                "
+                .unindent(),
+                stage: true,
+                rows: vec![1..2, 3..4],
+                final_index_text: "
+                    one
+                    A
+                    C
+                    two
+                "
+                .unindent(),
+            },
+            Example {
+                name: "stage last line of hunk that removes more lines than it adds",
+                head_text: "
+                    one
+                    two
+                    three
+                    four
+                    five
+                "
+                .unindent(),
+                index_text: "
+                    one
+                    two
+                    three
+                    four
+                    five
+                "
+                .unindent(),
+                buffer_text: "
+                    one
+                    TWO
+                    THREE
+                    five
+                "
+                .unindent(),
+                stage: true,
+                rows: vec![2..3],
+                final_index_text: "
+                    one
+                    two
+                    THREE
+                    five
+                "
+                .unindent(),
+            },
+            Example {
+                name: "stage deletion",
+                head_text: "
+                    one
+                    two
+                    three
+                "
+                .unindent(),
+                index_text: "
+                    one
+                    two
+                    three
+                "
+                .unindent(),
+                buffer_text: "
+                    one
+                    three
+                "
+                .unindent(),
+                stage: true,
+                rows: vec![1..2],
+                final_index_text: "
+                    one
+                    three
+                "
+                .unindent(),
+            },
+            Example {
+                name: "unstage first line of staged hunk",
+                head_text: "
+                    one
+                    two
+                    three
+                    four
+                "
+                .unindent(),
+                index_text: "
+                    one
+                    TWO
+                    THREE
+                    four
+                "
+                .unindent(),
+                buffer_text: "
+                    one
+                    TWO
+                    THREE
+                    four
+                "
+                .unindent(),
+                stage: false,
+                rows: vec![1..2],
+                final_index_text: "
+                    one
+                    two
+                    THREE
+                    four
+                "
+                .unindent(),
+            },
+            Example {
+                name: "stage last line of file without trailing newline",
+                head_text: "one\ntwo\nthree".to_string(),
+                index_text: "one\ntwo\nthree".to_string(),
+                buffer_text: "one\nTWO\nTHREE".to_string(),
+                stage: true,
+                rows: vec![2..3],
+                final_index_text: "one\ntwo\nTHREE".to_string(),
+            },
+            Example {
+                name: "unstage line of staged hunk after unstaged hunk",
+                head_text: "
+                    one
+                    two
+                    three
+                    four
+                "
+                .unindent(),
+                index_text: "
+                    one
+                    two
+                    THREE
+                    FOUR
+                "
+                .unindent(),
+                buffer_text: "
+                    ZERO
+                    one
+                    two
+                    THREE
+                    FOUR
+                "
+                .unindent(),
+                stage: false,
+                rows: vec![4..5],
+                final_index_text: "
+                    one
+                    two
+                    THREE
+                    four
+                "
+                .unindent(),
+            },
+        ];
+
+        for example in table {
+            let buffer = Buffer::new(0, BufferId::new(1).unwrap(), example.buffer_text);
+            let unstaged = BufferDiff::build_sync(buffer.clone(), example.index_text.clone(), cx);
+            let uncommitted = BufferDiff::build_sync(buffer.clone(), example.head_text.clone(), cx);
+
+            let unstaged_diff = cx.new(|cx| {
+                let mut diff = BufferDiff::new(&buffer, cx);
+                diff.set_state(unstaged, &buffer);
+                diff
+            });
+
+            let uncommitted_diff = cx.new(|cx| {
+                let mut diff = BufferDiff::new(&buffer, cx);
+                diff.set_state(uncommitted, &buffer);
+                diff.set_secondary_diff(unstaged_diff);
+                diff
+            });
+
+            let new_index_text = uncommitted_diff.update(cx, |diff, cx| {
+                diff.stage_or_unstage_lines(example.stage, &example.rows, &buffer, cx)
+                    .unwrap()
+                    .to_string()
+            });
+            pretty_assertions::assert_eq!(
+                new_index_text,
+                example.final_index_text,
+                "example: {}",
+                example.name
+            );
+        }
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_compare(cx: &mut TestAppContext) {
         let base_text = "
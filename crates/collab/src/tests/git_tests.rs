@@ -4,7 +4,10 @@ use std::{
 };
 
 use call::ActiveCall;
-use git::status::{FileStatus, StatusCode, TrackedStatus};
+use git::{
+    repository::{ConflictVersions, LogEntry, RebaseAction, SequencerOperation},
+    status::{FileStatus, StatusCode, TrackedStatus},
+};
 use git_ui::project_diff::ProjectDiff;
 use gpui::{TestAppContext, VisualTestContext};
 use project::ProjectPath;
@@ -128,3 +131,174 @@ async fn test_project_diff(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
         );
     });
 }
+
+#[gpui::test]
+async fn test_load_conflict_versions_with_missing_sides(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(cx_a.background_executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+
+    client_a
+        .fs()
+        .insert_tree(
+            "/a",
+            json!({
+                ".git": {},
+                "added.txt": "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> theirs\n",
+                "deleted.txt": "base\n",
+                "clean.txt": "clean\n",
+            }),
+        )
+        .await;
+    let added = ConflictVersions {
+        base: None,
+        ours: Some("ours\n".into()),
+        theirs: Some("theirs\n".into()),
+    };
+    let deleted = ConflictVersions {
+        base: Some("base\n".into()),
+        ours: Some("base\n".into()),
+        theirs: None,
+    };
+    client_a
+        .fs()
+        .with_git_state(Path::new("/a/.git"), false, |state| {
+            state
+                .conflict_versions
+                .insert("added.txt".into(), added.clone());
+            state
+                .conflict_versions
+                .insert("deleted.txt".into(), deleted.clone());
+        });
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    cx_b.run_until_parked();
+
+    // The guest's requests are forwarded to the host's repository, and missing versions survive
+    // the round trip.
+    let repo_b = cx_b.update(|cx| project_b.read(cx).active_repository(cx).unwrap());
+    for (path, versions) in [("added.txt", added), ("deleted.txt", deleted)] {
+        let loaded = repo_b
+            .update(cx_b, |repo, _| repo.load_conflict_versions(path.into()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, versions);
+    }
+
+    let result = repo_b
+        .update(cx_b, |repo, _| {
+            repo.load_conflict_versions("clean.txt".into())
+        })
+        .await
+        .unwrap();
+    assert!(result.is_err());
+}
+
+#[gpui::test]
+async fn test_guest_cherry_pick_and_rebase_from_root(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(cx_a.background_executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ ".git": {}, "a.txt": "a\n" }))
+        .await;
+    client_a
+        .fs()
+        .with_git_state(Path::new("/a/.git"), false, |state| {
+            state.commit_history = ["bbb", "aaa"]
+                .into_iter()
+                .map(|sha| LogEntry {
+                    sha: sha.into(),
+                    parents: Vec::new(),
+                    subject: format!("commit {sha}").into(),
+                    author_name: "Test".into(),
+                    author_email: "test@example.com".into(),
+                    commit_timestamp: 0,
+                })
+                .collect();
+            state
+                .conflict_versions
+                .insert("a.txt".into(), ConflictVersions::default());
+        });
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    cx_b.run_until_parked();
+
+    let repo_b = cx_b.update(|cx| project_b.read(cx).active_repository(cx).unwrap());
+    repo_b
+        .update(cx_b, |repo, cx| repo.cherry_pick(vec!["bbb".into()], cx))
+        .await
+        .unwrap()
+        .unwrap();
+    let operation = repo_b
+        .update(cx_b, |repo, _| repo.sequencer_operation())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(operation, Some(SequencerOperation::CherryPick));
+    repo_b
+        .update(cx_b, |repo, cx| repo.abort_operation(cx))
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut todo = repo_b
+        .update(cx_b, |repo, _| repo.rebase_todo(None))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        todo.iter()
+            .map(|entry| (entry.sha.as_ref(), entry.subject.as_ref()))
+            .collect::<Vec<_>>(),
+        [("aaa", "commit aaa"), ("bbb", "commit bbb")]
+    );
+    todo[0].action = RebaseAction::Drop;
+    todo[1].action = RebaseAction::Reword;
+    todo[1].message = Some("reworded".into());
+    repo_b
+        .update(cx_b, |repo, cx| repo.rebase(None, todo.clone(), cx))
+        .await
+        .unwrap()
+        .unwrap();
+
+    client_a
+        .fs()
+        .with_git_state(Path::new("/a/.git"), false, |state| {
+            assert_eq!(
+                state.sequencer_commands,
+                [
+                    "cherry-pick bbb",
+                    "cherry-pick --abort",
+                    "rebase --interactive --root",
+                ]
+            );
+            assert_eq!(state.rebase_todo, todo);
+            assert_eq!(state.sequencer_operation, None);
+        });
+}
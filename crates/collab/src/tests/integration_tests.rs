@@ -4951,6 +4951,139 @@ async fn test_references(
     });
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_type_hierarchy_items_outside_worktree(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a.language_registry().add(rust_lang());
+    let mut fake_language_servers = client_a
+        .language_registry()
+        .register_fake_lsp("Rust", Default::default());
+
+    client_a
+        .fs()
+        .insert_tree(
+            "/root",
+            json!({
+                "dir-1": {
+                    "shape.rs": "trait Shape {}",
+                },
+                "dir-2": {
+                    "circle.rs": "struct Circle;\nimpl Shape for Circle {}",
+                }
+            }),
+        )
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/root/dir-1", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    // Open the file on client B.
+    let (buffer_b, _handle) = project_b
+        .update(cx_b, |p, cx| {
+            p.open_buffer_with_lsp((worktree_id, "shape.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_language_server = fake_language_servers.next().await.unwrap();
+    fake_language_server
+        .request::<lsp::request::RegisterCapability>(lsp::RegistrationParams {
+            registrations: vec![lsp::Registration {
+                id: "type-hierarchy".to_string(),
+                method: "textDocument/prepareTypeHierarchy".to_string(),
+                register_options: None,
+            }],
+        })
+        .await
+        .unwrap();
+    let lsp_item = |name: &str, path: &str, row: u32, column: u32| lsp::TypeHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::STRUCT,
+        tags: None,
+        detail: Some(format!("detail of {name}")),
+        uri: lsp::Url::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(row, 0), lsp::Position::new(row, 14)),
+        selection_range: lsp::Range::new(
+            lsp::Position::new(row, column),
+            lsp::Position::new(row, column + name.len() as u32),
+        ),
+        data: Some(json!({ "id": name })),
+    };
+    let shape = lsp_item("Shape", "/root/dir-1/shape.rs", 0, 6);
+    let circle = lsp_item("Circle", "/root/dir-2/circle.rs", 0, 7);
+    fake_language_server.handle_request::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let shape = shape.clone();
+        move |params, _| {
+            let shape = shape.clone();
+            async move {
+                assert_eq!(
+                    params
+                        .text_document_position_params
+                        .text_document
+                        .uri
+                        .as_str(),
+                    "file:///root/dir-1/shape.rs"
+                );
+                Ok(Some(vec![shape]))
+            }
+        }
+    });
+    fake_language_server.handle_request::<lsp::request::TypeHierarchySubtypes, _, _>({
+        let shape = shape.clone();
+        let circle = circle.clone();
+        move |params, _| {
+            // The item is sent back to the server as it reported it.
+            assert_eq!(params.item, shape);
+            let circle = circle.clone();
+            async move { Ok(Some(vec![circle])) }
+        }
+    });
+
+    // Request the type hierarchy as the guest.
+    let items = project_b
+        .update(cx_b, |p, cx| p.prepare_type_hierarchy(&buffer_b, 8, cx))
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].lsp_item, shape);
+    assert_eq!(items[0].buffer, buffer_b);
+
+    let subtypes = project_b
+        .update(cx_b, |p, cx| p.subtypes(&items[0], cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    project_b.read_with(cx_b, |project, cx| {
+        let subtype = &subtypes[0];
+        assert_eq!(subtype.name, "Circle");
+        assert_eq!(subtype.detail.as_deref(), Some("detail of Circle"));
+        assert_eq!(subtype.lsp_item, circle);
+
+        // The guest is given the buffer of the subtype, which is outside of its worktrees.
+        let circle_buffer = subtype.buffer.read(cx);
+        assert_eq!(
+            circle_buffer.file().unwrap().full_path(cx),
+            Path::new("/root/dir-2/circle.rs")
+        );
+        assert_eq!(subtype.selection_range.to_offset(circle_buffer), 7..13);
+        assert_eq!(project.worktrees(cx).count(), 2);
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_project_search(
     executor: BackgroundExecutor,
@@ -0,0 +1,808 @@
+use crate::{
+    events,
+    requests::{self, Disconnect},
+    transport::{self, EventMessage, Message, RequestMessage, ResponseMessage, CONTENT_LEN_HEADER},
+    DebugAdapterBinary, DebugAdapterClientId, DebugAdapterName, DisconnectArguments,
+};
+use anyhow::{anyhow, Context as _, Result};
+use collections::HashMap;
+use futures::{channel::oneshot, io::BufWriter, select, AsyncRead, AsyncWrite, Future, FutureExt};
+use gpui::{AsyncApp, BackgroundExecutor, Task};
+use parking_lot::Mutex;
+use serde_json::Value;
+use smol::{
+    channel,
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::Child,
+};
+use std::{
+    fmt,
+    io::Write,
+    process::Stdio,
+    sync::{
+        atomic::{AtomicU64, Ordering::SeqCst},
+        Arc,
+    },
+    time::Duration,
+};
+use util::ResultExt;
+
+const DAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(60 * 2);
+const ADAPTER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+type EventHandler = Box<dyn Send + FnMut(Value, AsyncApp)>;
+type RequestHandler = Box<dyn Send + FnMut(u64, Value, AsyncApp)>;
+type ResponseHandler = Box<dyn Send + FnOnce(Result<Value>)>;
+
+/// A running debug adapter process.
+pub struct DebugAdapterClient {
+    id: DebugAdapterClientId,
+    name: DebugAdapterName,
+    binary: DebugAdapterBinary,
+    next_seq: Arc<AtomicU64>,
+    outbound_tx: channel::Sender<String>,
+    event_handlers: Arc<Mutex<HashMap<&'static str, EventHandler>>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
+    response_handlers: Arc<Mutex<Option<HashMap<u64, ResponseHandler>>>>,
+    executor: BackgroundExecutor,
+    #[allow(clippy::type_complexity)]
+    io_tasks: Mutex<Option<(Task<Option<()>>, Task<Option<()>>)>>,
+    adapter: Arc<Mutex<Option<Child>>>,
+}
+
+/// Handle to a debug adapter event or reverse request subscription.
+pub enum Subscription {
+    Event {
+        event: &'static str,
+        event_handlers: Option<Arc<Mutex<HashMap<&'static str, EventHandler>>>>,
+    },
+    Request {
+        command: &'static str,
+        request_handlers: Option<Arc<Mutex<HashMap<&'static str, RequestHandler>>>>,
+    },
+}
+
+impl DebugAdapterClient {
+    /// Starts a debug adapter process.
+    pub fn start(
+        id: DebugAdapterClientId,
+        name: DebugAdapterName,
+        binary: DebugAdapterBinary,
+        cx: AsyncApp,
+    ) -> Result<Self> {
+        log::info!(
+            "starting debug adapter process. binary path: {:?}, working directory: {:?}, args: {:?}",
+            binary.command,
+            binary.cwd,
+            &binary.arguments
+        );
+
+        let mut command = util::command::new_smol_command(&binary.command);
+        if let Some(cwd) = &binary.cwd {
+            command.current_dir(cwd);
+        }
+        let mut adapter = command
+            .args(&binary.arguments)
+            .envs(binary.env.clone().unwrap_or_default())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| {
+                format!(
+                    "failed to spawn debug adapter. path: {:?}, working directory: {:?}, args: {:?}",
+                    binary.command, binary.cwd, &binary.arguments
+                )
+            })?;
+
+        let stdin = adapter.stdin.take().unwrap();
+        let stdout = adapter.stdout.take().unwrap();
+        let stderr = adapter.stderr.take().unwrap();
+        Ok(Self::new_internal(
+            id,
+            name,
+            binary,
+            stdin,
+            stdout,
+            Some(stderr),
+            Some(adapter),
+            cx,
+        ))
+    }
+
+    fn new_internal<Stdin, Stdout, Stderr>(
+        id: DebugAdapterClientId,
+        name: DebugAdapterName,
+        binary: DebugAdapterBinary,
+        stdin: Stdin,
+        stdout: Stdout,
+        stderr: Option<Stderr>,
+        adapter: Option<Child>,
+        cx: AsyncApp,
+    ) -> Self
+    where
+        Stdin: AsyncWrite + Unpin + Send + 'static,
+        Stdout: AsyncRead + Unpin + Send + 'static,
+        Stderr: AsyncRead + Unpin + Send + 'static,
+    {
+        let (outbound_tx, outbound_rx) = channel::unbounded::<String>();
+        let next_seq = Arc::new(AtomicU64::new(1));
+        let event_handlers = Arc::new(Mutex::new(HashMap::<_, EventHandler>::default()));
+        let request_handlers = Arc::new(Mutex::new(HashMap::<_, RequestHandler>::default()));
+        let response_handlers =
+            Arc::new(Mutex::new(Some(HashMap::<_, ResponseHandler>::default())));
+
+        let stdout_input_task = cx.spawn({
+            let name = name.clone();
+            let next_seq = next_seq.clone();
+            let outbound_tx = outbound_tx.clone();
+            let event_handlers = event_handlers.clone();
+            let request_handlers = request_handlers.clone();
+            let response_handlers = response_handlers.clone();
+            move |cx| {
+                Self::handle_input(
+                    stdout,
+                    name,
+                    next_seq,
+                    outbound_tx,
+                    event_handlers,
+                    request_handlers,
+                    response_handlers,
+                    cx,
+                )
+                .log_err()
+            }
+        });
+        let stderr_input_task = stderr
+            .map(|stderr| cx.background_spawn(Self::handle_stderr(stderr, name.clone()).log_err()))
+            .unwrap_or_else(|| Task::ready(None));
+        let input_task = cx.spawn(|_| async move {
+            let (stdout, stderr) = futures::join!(stdout_input_task, stderr_input_task);
+            stdout.or(stderr)
+        });
+        let output_task = cx.background_spawn(
+            Self::handle_output(stdin, outbound_rx, response_handlers.clone()).log_err(),
+        );
+
+        Self {
+            id,
+            name,
+            binary,
+            next_seq,
+            outbound_tx,
+            event_handlers,
+            request_handlers,
+            response_handlers,
+            executor: cx.background_executor().clone(),
+            io_tasks: Mutex::new(Some((input_task, output_task))),
+            adapter: Arc::new(Mutex::new(adapter)),
+        }
+    }
+
+    async fn handle_input<Stdout>(
+        stdout: Stdout,
+        name: DebugAdapterName,
+        next_seq: Arc<AtomicU64>,
+        outbound_tx: channel::Sender<String>,
+        event_handlers: Arc<Mutex<HashMap<&'static str, EventHandler>>>,
+        request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
+        response_handlers: Arc<Mutex<Option<HashMap<u64, ResponseHandler>>>>,
+        cx: AsyncApp,
+    ) -> Result<()>
+    where
+        Stdout: AsyncRead + Unpin + Send + 'static,
+    {
+        let mut stdout = BufReader::new(stdout);
+        let _clear_response_handlers = util::defer({
+            let response_handlers = response_handlers.clone();
+            move || {
+                response_handlers.lock().take();
+            }
+        });
+
+        let mut buffer = Vec::new();
+        loop {
+            let message = transport::read_message(&mut stdout, &mut buffer).await?;
+            log::trace!("incoming message: {message}");
+
+            match serde_json::from_str::<Message>(message) {
+                Ok(Message::Event(EventMessage { event, body, .. })) => {
+                    let mut event_handlers = event_handlers.lock();
+                    if let Some(handler) = event_handlers.get_mut(event.as_str()) {
+                        handler(body.unwrap_or(Value::Null), cx.clone());
+                    } else {
+                        log::info!("debug adapter {name} sent unhandled event {event}");
+                    }
+                }
+                Ok(Message::Request(RequestMessage {
+                    seq,
+                    command,
+                    arguments,
+                })) => {
+                    let mut request_handlers = request_handlers.lock();
+                    if let Some(handler) = request_handlers.get_mut(command.as_str()) {
+                        handler(seq, arguments.unwrap_or(Value::Null), cx.clone());
+                    } else {
+                        drop(request_handlers);
+                        log::info!("debug adapter {name} sent unhandled request {command}");
+                        let response = Self::response_message(
+                            next_seq.fetch_add(1, SeqCst),
+                            seq,
+                            command,
+                            Err(anyhow!("unsupported request")),
+                        );
+                        outbound_tx.try_send(response).ok();
+                    }
+                }
+                Ok(Message::Response(ResponseMessage {
+                    request_seq,
+                    success,
+                    command,
+                    message,
+                    body,
+                    ..
+                })) => {
+                    let handler = response_handlers
+                        .lock()
+                        .as_mut()
+                        .and_then(|handlers| handlers.remove(&request_seq));
+                    if let Some(handler) = handler {
+                        if success {
+                            handler(Ok(body.unwrap_or(Value::Null)));
+                        } else {
+                            handler(Err(anyhow!(
+                                message.unwrap_or_else(|| format!("{command} request failed"))
+                            )));
+                        }
+                    }
+                }
+                Err(error) => {
+                    log::warn!("failed to deserialize DAP message: {error}\n{message}");
+                }
+            }
+
+            // Don't starve the main thread when receiving lots of messages at once.
+            smol::future::yield_now().await;
+        }
+    }
+
+    async fn handle_stderr<Stderr>(stderr: Stderr, name: DebugAdapterName) -> Result<()>
+    where
+        Stderr: AsyncRead + Unpin + Send + 'static,
+    {
+        let mut stderr = BufReader::new(stderr);
+        let mut buffer = String::new();
+
+        loop {
+            buffer.clear();
+            if stderr.read_line(&mut buffer).await? == 0 {
+                return Ok(());
+            }
+            log::debug!("debug adapter {name} stderr: {}", buffer.trim_end());
+        }
+    }
+
+    async fn handle_output<Stdin>(
+        stdin: Stdin,
+        outbound_rx: channel::Receiver<String>,
+        response_handlers: Arc<Mutex<Option<HashMap<u64, ResponseHandler>>>>,
+    ) -> Result<()>
+    where
+        Stdin: AsyncWrite + Unpin + Send + 'static,
+    {
+        let mut stdin = BufWriter::new(stdin);
+        let _clear_response_handlers = util::defer({
+            let response_handlers = response_handlers.clone();
+            move || {
+                response_handlers.lock().take();
+            }
+        });
+        let mut content_len_buffer = Vec::new();
+        while let Ok(message) = outbound_rx.recv().await {
+            log::trace!("outgoing message: {message}");
+
+            content_len_buffer.clear();
+            write!(content_len_buffer, "{}", message.len()).unwrap();
+            stdin.write_all(CONTENT_LEN_HEADER.as_bytes()).await?;
+            stdin.write_all(&content_len_buffer).await?;
+            stdin.write_all("\r\n\r\n".as_bytes()).await?;
+            stdin.write_all(message.as_bytes()).await?;
+            stdin.flush().await?;
+        }
+        Ok(())
+    }
+
+    fn response_message(
+        seq: u64,
+        request_seq: u64,
+        command: String,
+        result: Result<Value>,
+    ) -> String {
+        let response = match result {
+            Ok(body) => ResponseMessage {
+                seq,
+                request_seq,
+                success: true,
+                command,
+                message: None,
+                body: Some(body).filter(|body| !body.is_null()),
+            },
+            Err(error) => ResponseMessage {
+                seq,
+                request_seq,
+                success: false,
+                command,
+                message: Some(error.to_string()),
+                body: None,
+            },
+        };
+        serde_json::to_string(&Message::Response(response)).unwrap()
+    }
+
+    pub fn id(&self) -> DebugAdapterClientId {
+        self.id
+    }
+
+    pub fn name(&self) -> DebugAdapterName {
+        self.name.clone()
+    }
+
+    pub fn binary(&self) -> &DebugAdapterBinary {
+        &self.binary
+    }
+
+    /// Sends a request to the debug adapter.
+    ///
+    /// [DAP Specification](https://microsoft.github.io/debug-adapter-protocol/specification#Base_Protocol_Request)
+    pub fn request<R: 'static + requests::Request>(
+        &self,
+        arguments: R::Arguments,
+    ) -> impl 'static + Future<Output = Result<R::Response>> {
+        let seq = self.next_seq.fetch_add(1, SeqCst);
+        let (tx, rx) = oneshot::channel();
+        let handle_response = self
+            .response_handlers
+            .lock()
+            .as_mut()
+            .ok_or_else(|| anyhow!("debug adapter shut down"))
+            .map(|handlers| {
+                handlers.insert(
+                    seq,
+                    Box::new(move |result| {
+                        _ = tx.send(result);
+                    }),
+                );
+            });
+
+        let send = serde_json::to_value(arguments)
+            .context("failed to serialize request arguments")
+            .and_then(|arguments| {
+                let message = serde_json::to_string(&Message::Request(RequestMessage {
+                    seq,
+                    command: R::COMMAND.to_string(),
+                    arguments: Some(arguments).filter(|arguments| !arguments.is_null()),
+                }))?;
+                self.outbound_tx
+                    .try_send(message)
+                    .context("failed to write to debug adapter's stdin")
+            });
+
+        let mut timeout = self.executor.timer(DAP_REQUEST_TIMEOUT).fuse();
+        async move {
+            handle_response?;
+            send?;
+
+            let command = R::COMMAND;
+            select! {
+                response = rx.fuse() => {
+                    let body = response.map_err(|_| anyhow!("debug adapter shut down"))??;
+                    // Some adapters send an empty body for requests without a response body.
+                    let body = match body {
+                        Value::Object(map) if map.is_empty() => {
+                            serde_json::from_value(Value::Object(map)).or_else(|_| serde_json::from_value(Value::Null))
+                        }
+                        body => serde_json::from_value(body),
+                    };
+                    body.with_context(|| format!("failed to deserialize {command} response"))
+                }
+
+                _ = timeout => {
+                    log::error!("Cancelled DAP request {command:?} seq {seq} which took over {DAP_REQUEST_TIMEOUT:?}");
+                    anyhow::bail!("DAP request timeout");
+                }
+            }
+        }
+    }
+
+    /// Registers a handler for events sent by the debug adapter.
+    ///
+    /// [DAP Specification](https://microsoft.github.io/debug-adapter-protocol/specification#Base_Protocol_Event)
+    #[must_use]
+    pub fn on_event<E, F>(&self, mut f: F) -> Subscription
+    where
+        E: 'static + events::Event,
+        F: 'static + Send + FnMut(E::Body, AsyncApp),
+    {
+        let prev_handler = self.event_handlers.lock().insert(
+            E::EVENT,
+            Box::new(move |body, cx| {
+                if let Some(body) = serde_json::from_value(body).log_err() {
+                    f(body, cx);
+                }
+            }),
+        );
+        assert!(
+            prev_handler.is_none(),
+            "registered multiple handlers for the same DAP event"
+        );
+        Subscription::Event {
+            event: E::EVENT,
+            event_handlers: Some(self.event_handlers.clone()),
+        }
+    }
+
+    /// Registers a handler for reverse requests sent by the debug adapter, e.g. `runInTerminal`.
+    ///
+    /// [DAP Specification](https://microsoft.github.io/debug-adapter-protocol/specification#Reverse_Requests)
+    #[must_use]
+    pub fn on_request<R, F, Fut>(&self, mut f: F) -> Subscription
+    where
+        R: 'static + requests::Request,
+        F: 'static + Send + FnMut(R::Arguments, AsyncApp) -> Fut,
+        Fut: 'static + Future<Output = Result<R::Response>>,
+    {
+        let next_seq = self.next_seq.clone();
+        let outbound_tx = self.outbound_tx.clone();
+        let prev_handler = self.request_handlers.lock().insert(
+            R::COMMAND,
+            Box::new(move |request_seq, arguments, cx| {
+                let response = serde_json::from_value(arguments)
+                    .context("failed to deserialize request arguments")
+                    .map(|arguments| f(arguments, cx.clone()));
+                let next_seq = next_seq.clone();
+                let outbound_tx = outbound_tx.clone();
+                cx.foreground_executor()
+                    .spawn(async move {
+                        let result = match response {
+                            Ok(response) => response.await.and_then(|body| {
+                                serde_json::to_value(body)
+                                    .context("failed to serialize response body")
+                            }),
+                            Err(error) => Err(error),
+                        };
+                        let response = Self::response_message(
+                            next_seq.fetch_add(1, SeqCst),
+                            request_seq,
+                            R::COMMAND.to_string(),
+                            result,
+                        );
+                        outbound_tx.try_send(response).ok();
+                    })
+                    .detach();
+            }),
+        );
+        assert!(
+            prev_handler.is_none(),
+            "registered multiple handlers for the same DAP request"
+        );
+        Subscription::Request {
+            command: R::COMMAND,
+            request_handlers: Some(self.request_handlers.clone()),
+        }
+    }
+
+    /// Removes a reverse request handler registered via [`Self::on_request`].
+    pub fn remove_request_handler<R: requests::Request>(&self) {
+        self.request_handlers.lock().remove(R::COMMAND);
+    }
+
+    /// Removes an event handler registered via [`Self::on_event`].
+    pub fn remove_event_handler<E: events::Event>(&self) {
+        self.event_handlers.lock().remove(E::EVENT);
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    fn send_event<E: events::Event>(&self, body: E::Body) -> Result<()> {
+        let body = serde_json::to_value(body)?;
+        let message = serde_json::to_string(&Message::Event(EventMessage {
+            seq: self.next_seq.fetch_add(1, SeqCst),
+            event: E::EVENT.to_string(),
+            body: Some(body).filter(|body| !body.is_null()),
+        }))?;
+        self.outbound_tx.try_send(message)?;
+        Ok(())
+    }
+
+    /// Sends a `disconnect` request to the debug adapter and prepares the [`DebugAdapterClient`] to be dropped.
+    pub fn shutdown(
+        &self,
+        terminate_debuggee: bool,
+    ) -> Option<impl 'static + Send + Future<Output = Option<()>>> {
+        let tasks = self.io_tasks.lock().take()?;
+        let disconnect = self.request::<Disconnect>(DisconnectArguments {
+            restart: Some(false),
+            terminate_debuggee: Some(terminate_debuggee),
+            suspend_debuggee: None,
+        });
+        let outbound_tx = self.outbound_tx.clone();
+        let response_handlers = self.response_handlers.clone();
+        let adapter = self.adapter.clone();
+        let name = self.name.clone();
+        let mut timer = self.executor.timer(ADAPTER_SHUTDOWN_TIMEOUT).fuse();
+        Some(
+            async move {
+                log::debug!("debug adapter shutdown started");
+
+                select! {
+                    result = disconnect.fuse() => {
+                        result.log_err();
+                    }
+
+                    _ = timer => {
+                        log::info!("timeout waiting for debug adapter {name} to disconnect");
+                    },
+                }
+
+                outbound_tx.close();
+                response_handlers.lock().take();
+                adapter.lock().take().map(|mut child| child.kill());
+                log::debug!("debug adapter shutdown finished");
+
+                drop(tasks);
+                anyhow::Ok(())
+            }
+            .log_err(),
+        )
+    }
+}
+
+impl Drop for DebugAdapterClient {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown(true) {
+            self.executor.spawn(shutdown).detach();
+        }
+    }
+}
+
+impl fmt::Debug for DebugAdapterClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugAdapterClient")
+            .field("id", &self.id.0)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Subscription {
+    /// Detaching a subscription handle prevents it from unsubscribing on drop.
+    pub fn detach(&mut self) {
+        match self {
+            Subscription::Event { event_handlers, .. } => *event_handlers = None,
+            Subscription::Request {
+                request_handlers, ..
+            } => *request_handlers = None,
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        match self {
+            Subscription::Event {
+                event,
+                event_handlers,
+            } => {
+                if let Some(handlers) = event_handlers {
+                    handlers.lock().remove(event);
+                }
+            }
+            Subscription::Request {
+                command,
+                request_handlers,
+            } => {
+                if let Some(handlers) = request_handlers {
+                    handlers.lock().remove(command);
+                }
+            }
+        }
+    }
+}
+
+/// Mock debug adapter for use in tests.
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Clone)]
+pub struct FakeDebugAdapter {
+    pub adapter: Arc<DebugAdapterClient>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl FakeDebugAdapter {
+    /// Construct a fake debug adapter, connected to the returned client.
+    pub fn new(
+        id: DebugAdapterClientId,
+        name: &str,
+        capabilities: crate::Capabilities,
+        cx: AsyncApp,
+    ) -> (DebugAdapterClient, FakeDebugAdapter) {
+        let (stdin_writer, stdin_reader) = async_pipe::pipe();
+        let (stdout_writer, stdout_reader) = async_pipe::pipe();
+        let binary = DebugAdapterBinary {
+            command: format!("path/to/{name}").into(),
+            arguments: Vec::new(),
+            env: None,
+            cwd: None,
+        };
+
+        let client = DebugAdapterClient::new_internal(
+            id,
+            name.into(),
+            binary.clone(),
+            stdin_writer,
+            stdout_reader,
+            None::<async_pipe::PipeReader>,
+            None,
+            cx.clone(),
+        );
+        let fake = FakeDebugAdapter {
+            adapter: Arc::new(DebugAdapterClient::new_internal(
+                id,
+                name.into(),
+                binary,
+                stdout_writer,
+                stdin_reader,
+                None::<async_pipe::PipeReader>,
+                None,
+                cx,
+            )),
+        };
+        fake.handle_request::<requests::Initialize, _, _>(move |_, _| {
+            let capabilities = capabilities.clone();
+            async move { Ok(capabilities) }
+        });
+        fake.handle_request::<requests::Disconnect, _, _>(|_, _| async move { Ok(()) });
+
+        (client, fake)
+    }
+
+    /// Registers a handler for a specific kind of request. Removes any existing handler for specified request type.
+    pub fn handle_request<R, F, Fut>(
+        &self,
+        mut handler: F,
+    ) -> futures::channel::mpsc::UnboundedReceiver<()>
+    where
+        R: 'static + requests::Request,
+        F: 'static + Send + FnMut(R::Arguments, AsyncApp) -> Fut,
+        Fut: 'static + Send + Future<Output = Result<R::Response>>,
+    {
+        let (responded_tx, responded_rx) = futures::channel::mpsc::unbounded();
+        self.adapter.remove_request_handler::<R>();
+        self.adapter
+            .on_request::<R, _, _>(move |arguments, cx| {
+                let result = handler(arguments, cx.clone());
+                let responded_tx = responded_tx.clone();
+                let executor = cx.background_executor().clone();
+                async move {
+                    executor.simulate_random_delay().await;
+                    let result = result.await;
+                    responded_tx.unbounded_send(()).ok();
+                    result
+                }
+            })
+            .detach();
+        responded_rx
+    }
+
+    /// Sends an event to the connected client.
+    pub fn send_event<E: events::Event>(&self, body: E::Body) {
+        self.adapter.send_event::<E>(body).log_err();
+    }
+
+    /// See [`DebugAdapterClient::request`].
+    pub async fn request<R: 'static + requests::Request>(
+        &self,
+        arguments: R::Arguments,
+    ) -> Result<R::Response> {
+        self.adapter.executor.start_waiting();
+        self.adapter.request::<R>(arguments).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        requests::{StackTrace, Threads},
+        Capabilities, StackTraceArguments, StoppedEvent, StoppedEventReason, Thread,
+        ThreadsResponse,
+    };
+    use gpui::TestAppContext;
+
+    #[ctor::ctor]
+    fn init_logger() {
+        if std::env::var("RUST_LOG").is_ok() {
+            env_logger::init();
+        }
+    }
+
+    #[gpui::test]
+    async fn test_fake(cx: &mut TestAppContext) {
+        let (client, fake) = FakeDebugAdapter::new(
+            DebugAdapterClientId(0),
+            "the-adapter",
+            Capabilities {
+                supports_configuration_done_request: Some(true),
+                ..Default::default()
+            },
+            cx.to_async(),
+        );
+
+        let capabilities = client
+            .request::<requests::Initialize>(crate::InitializeRequestArguments {
+                client_id: Some("zed".to_string()),
+                client_name: Some("Zed".to_string()),
+                adapter_id: "the-adapter".to_string(),
+                locale: None,
+                lines_start_at1: Some(true),
+                columns_start_at1: Some(true),
+                path_format: None,
+                supports_variable_type: None,
+                supports_variable_paging: None,
+                supports_run_in_terminal_request: None,
+                supports_memory_references: None,
+                supports_progress_reporting: None,
+                supports_invalidated_event: None,
+                supports_memory_event: None,
+                supports_args_can_be_interpreted_by_shell: None,
+                supports_start_debugging_request: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(capabilities.supports_configuration_done_request, Some(true));
+
+        fake.handle_request::<Threads, _, _>(|_, _| async move {
+            Ok(ThreadsResponse {
+                threads: vec![Thread {
+                    id: 1,
+                    name: "main".to_string(),
+                }],
+            })
+        });
+        let threads = client.request::<Threads>(()).await.unwrap();
+        assert_eq!(threads.threads.len(), 1);
+        assert_eq!(threads.threads[0].name, "main");
+
+        // Requests the adapter doesn't handle are reported as errors.
+        let error = client
+            .request::<StackTrace>(StackTraceArguments {
+                thread_id: 1,
+                start_frame: None,
+                levels: None,
+                format: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "unsupported request");
+
+        let (stopped_tx, stopped_rx) = channel::unbounded();
+        client
+            .on_event::<events::Stopped, _>(move |event, _| {
+                stopped_tx.try_send(event).unwrap();
+            })
+            .detach();
+        fake.send_event::<events::Stopped>(StoppedEvent {
+            reason: StoppedEventReason::Breakpoint,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: None,
+        });
+        let stopped = stopped_rx.recv().await.unwrap();
+        assert_eq!(stopped.reason, StoppedEventReason::Breakpoint);
+        assert_eq!(stopped.thread_id, Some(1));
+
+        client.shutdown(false).unwrap().await;
+    }
+}
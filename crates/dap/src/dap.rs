@@ -0,0 +1,86 @@
+mod client;
+pub mod transport;
+
+pub use client::*;
+pub use dap_types::*;
+
+use collections::HashMap;
+use gpui::SharedString;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::PathBuf};
+
+/// Identifies a running debug adapter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct DebugAdapterClientId(pub usize);
+
+impl fmt::Display for DebugAdapterClientId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A name of a debug adapter, as reported to the adapter in the `initialize` request.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct DebugAdapterName(pub SharedString);
+
+impl fmt::Display for DebugAdapterName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl AsRef<str> for DebugAdapterName {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<'a> From<&'a str> for DebugAdapterName {
+    fn from(str: &'a str) -> DebugAdapterName {
+        DebugAdapterName(str.to_string().into())
+    }
+}
+
+/// Represents a launchable debug adapter, communicating over stdio.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DebugAdapterBinary {
+    /// The program to run.
+    pub command: PathBuf,
+    /// The arguments to pass to the program.
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    /// Environment variables to set for the adapter process.
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
+    /// The working directory of the adapter process.
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+}
+
+/// Whether a debug session starts the debuggee itself or attaches to an existing process.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DebugRequestType {
+    #[default]
+    Launch,
+    Attach,
+}
+
+/// Describes how to start a debug session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DebugAdapterConfig {
+    /// Human readable name of the configuration, shown in the debugger panel.
+    pub label: String,
+    /// The identifier sent to the adapter as `adapterID`, e.g. `lldb` or `debugpy`.
+    pub adapter: String,
+    /// How to run the debug adapter.
+    pub binary: DebugAdapterBinary,
+    /// Whether to send a `launch` or an `attach` request.
+    #[serde(default)]
+    pub request: DebugRequestType,
+    /// Adapter-specific arguments for the `launch` or `attach` request.
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
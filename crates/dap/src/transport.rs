@@ -0,0 +1,148 @@
+//! Message framing for the Debug Adapter Protocol.
+//!
+//! [DAP Specification](https://microsoft.github.io/debug-adapter-protocol/overview#base-protocol)
+
+use anyhow::{anyhow, Context as _, Result};
+use futures::{AsyncBufReadExt as _, AsyncRead, AsyncReadExt as _};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use smol::io::BufReader;
+
+pub(crate) const CONTENT_LEN_HEADER: &str = "Content-Length: ";
+const HEADER_DELIMITER: &[u8; 4] = b"\r\n\r\n";
+
+/// A single message exchanged between the client and a debug adapter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Message {
+    Request(RequestMessage),
+    Response(ResponseMessage),
+    Event(EventMessage),
+}
+
+/// A request, sent either by the client or by the adapter (a "reverse request").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestMessage {
+    pub seq: u64,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+}
+
+/// A response to a previously sent request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseMessage {
+    pub seq: u64,
+    pub request_seq: u64,
+    pub success: bool,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// An event, sent by the adapter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventMessage {
+    pub seq: u64,
+    pub event: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+async fn read_headers<Input>(reader: &mut BufReader<Input>, buffer: &mut Vec<u8>) -> Result<()>
+where
+    Input: AsyncRead + Unpin + Send + 'static,
+{
+    loop {
+        if buffer.len() >= HEADER_DELIMITER.len()
+            && buffer[(buffer.len() - HEADER_DELIMITER.len())..] == HEADER_DELIMITER[..]
+        {
+            return Ok(());
+        }
+
+        if reader.read_until(b'\n', buffer).await? == 0 {
+            return Err(anyhow!("cannot read DAP message headers"));
+        }
+    }
+}
+
+/// Reads the next message payload into `buffer`, returning it as a string slice.
+pub(crate) async fn read_message<'a, Input>(
+    reader: &mut BufReader<Input>,
+    buffer: &'a mut Vec<u8>,
+) -> Result<&'a str>
+where
+    Input: AsyncRead + Unpin + Send + 'static,
+{
+    buffer.clear();
+    read_headers(reader, buffer).await?;
+
+    let headers = std::str::from_utf8(&buffer[..])?;
+    let message_len = headers
+        .split('\n')
+        .find(|line| line.starts_with(CONTENT_LEN_HEADER))
+        .and_then(|line| line.strip_prefix(CONTENT_LEN_HEADER))
+        .ok_or_else(|| anyhow!("invalid DAP message header {headers:?}"))?
+        .trim_end()
+        .parse()?;
+
+    buffer.resize(message_len, 0);
+    reader.read_exact(buffer).await?;
+    std::str::from_utf8(buffer).context("DAP message is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[gpui::test]
+    async fn test_read_message() {
+        let mut buffer = Vec::new();
+        let mut reader = BufReader::new(
+            b"Content-Length: 13\r\n\r\n{\"seq\":1}\r\n\r\nContent-Length: 2\r\n\r\n{}" as &[u8],
+        );
+        assert_eq!(
+            read_message(&mut reader, &mut buffer).await.unwrap(),
+            "{\"seq\":1}\r\n\r\n"
+        );
+        assert_eq!(read_message(&mut reader, &mut buffer).await.unwrap(), "{}");
+        assert!(read_message(&mut reader, &mut buffer).await.is_err());
+    }
+
+    #[test]
+    fn test_message_serialization() {
+        let request = Message::Request(RequestMessage {
+            seq: 1,
+            command: "threads".into(),
+            arguments: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({"type": "request", "seq": 1, "command": "threads"})
+        );
+
+        let response: Message = serde_json::from_value(json!({
+            "type": "response",
+            "seq": 2,
+            "request_seq": 1,
+            "success": false,
+            "command": "threads",
+            "message": "not stopped",
+        }))
+        .unwrap();
+        assert_eq!(
+            response,
+            Message::Response(ResponseMessage {
+                seq: 2,
+                request_seq: 1,
+                success: false,
+                command: "threads".into(),
+                message: Some("not stopped".into()),
+                body: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use dap::OutputEventCategory;
+use editor::Editor;
+use gpui::{point, Entity, FocusHandle, Focusable, ScrollHandle, Subscription};
+use project::debugger::session::{Session, SessionEvent};
+use ui::prelude::*;
+
+struct ConsoleEntry {
+    text: SharedString,
+    color: Color,
+}
+
+/// Shows the output of the debuggee and evaluates expressions typed into the REPL.
+pub struct Console {
+    session: Option<Entity<Session>>,
+    frame_id: Option<u64>,
+    entries: Vec<ConsoleEntry>,
+    // The number of output events of the session that were already added to `entries`.
+    output_ix: usize,
+    query_bar: Entity<Editor>,
+    scroll_handle: ScrollHandle,
+    _session_subscription: Option<Subscription>,
+}
+
+impl Console {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let query_bar = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Evaluate an expression", cx);
+            editor
+        });
+        Self {
+            session: None,
+            frame_id: None,
+            entries: Vec::new(),
+            output_ix: 0,
+            query_bar,
+            scroll_handle: ScrollHandle::new(),
+            _session_subscription: None,
+        }
+    }
+
+    /// Switches to another session. Previous output is kept, so it can still be read after the session ends.
+    pub fn set_session(&mut self, session: Option<Entity<Session>>, cx: &mut Context<Self>) {
+        self._session_subscription = session.as_ref().map(|session| {
+            cx.subscribe(session, |this, _, event, cx| {
+                if *event == SessionEvent::Output {
+                    this.sync_output(cx);
+                }
+            })
+        });
+        if session.is_some() {
+            self.entries.clear();
+        }
+        self.session = session;
+        self.frame_id = None;
+        self.output_ix = 0;
+        self.sync_output(cx);
+    }
+
+    /// Sets the stack frame that expressions are evaluated in.
+    pub fn set_frame_id(&mut self, frame_id: Option<u64>) {
+        self.frame_id = frame_id;
+    }
+
+    fn sync_output(&mut self, cx: &mut Context<Self>) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let output = session.read(cx).output();
+        for event in output.iter().skip(self.output_ix) {
+            let color = match event.category {
+                Some(OutputEventCategory::Telemetry) => continue,
+                Some(OutputEventCategory::Stderr) => Color::Error,
+                Some(OutputEventCategory::Console) | Some(OutputEventCategory::Important) => {
+                    Color::Muted
+                }
+                _ => Color::Default,
+            };
+            self.entries.push(ConsoleEntry {
+                text: event.output.trim_end().to_string().into(),
+                color,
+            });
+        }
+        self.output_ix = output.len();
+        self.scroll_to_bottom();
+        cx.notify();
+    }
+
+    fn scroll_to_bottom(&self) {
+        // The offset is clamped to the content size when the output is laid out.
+        self.scroll_handle.set_offset(point(px(0.), Pixels::MIN));
+    }
+
+    fn evaluate(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let expression = self.query_bar.read(cx).text(cx);
+        if expression.trim().is_empty() {
+            return;
+        }
+        self.query_bar
+            .update(cx, |query_bar, cx| query_bar.clear(window, cx));
+        self.entries.push(ConsoleEntry {
+            text: format!("> {expression}").into(),
+            color: Color::Muted,
+        });
+        self.scroll_to_bottom();
+        cx.notify();
+
+        let frame_id = self.frame_id;
+        let evaluate = session.update(cx, |session, cx| session.evaluate(expression, frame_id, cx));
+        cx.spawn(|this, mut cx| async move {
+            let entry = match evaluate.await {
+                Ok(response) => ConsoleEntry {
+                    text: response.result.into(),
+                    color: Color::Default,
+                },
+                Err(error) => ConsoleEntry {
+                    text: error.to_string().into(),
+                    color: Color::Error,
+                },
+            };
+            this.update(&mut cx, |this, cx| {
+                this.entries.push(entry);
+                this.scroll_to_bottom();
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl Focusable for Console {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.query_bar.focus_handle(cx)
+    }
+}
+
+impl Render for Console {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("DebugConsole")
+            .size_full()
+            .on_action(cx.listener(Self::evaluate))
+            .child(
+                v_flex()
+                    .id("debugger-console-output")
+                    .flex_1()
+                    .px_2()
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .children(self.entries.iter().map(|entry| {
+                        Label::new(entry.text.clone())
+                            .size(LabelSize::Small)
+                            .color(entry.color)
+                            .buffer_font(cx)
+                    })),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(self.query_bar.clone()),
+            )
+    }
+}
@@ -0,0 +1,739 @@
+use crate::{
+    console::Console,
+    debugger_settings::{DebugPanelDockPosition, DebuggerSettings},
+    variable_list::VariableList,
+    Continue, Pause, StepInto, StepOut, StepOver, Stop, ToggleFocus,
+};
+use anyhow::{anyhow, Context as _, Result};
+use dap::{DebugAdapterConfig, StackFrame};
+use db::kvp::KEY_VALUE_STORE;
+use editor::Editor;
+use fs::Fs;
+use gpui::{
+    Action, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task,
+    WeakEntity,
+};
+use language::{Bias, Point};
+use project::{
+    debugger::{
+        dap_store::{DapStore, DapStoreEvent},
+        session::{Session, SessionEvent, ThreadStatus},
+    },
+    Project,
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::{path::PathBuf, sync::Arc};
+use ui::{prelude::*, ListItem, Tooltip};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    OpenOptions, OpenVisible, Workspace,
+};
+
+const DEBUG_PANEL_KEY: &str = "DebugPanel";
+
+#[derive(Serialize, Deserialize)]
+struct SerializedDebugPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+/// Row highlight for the line of the selected stack frame.
+enum DebugStackFrameLine {}
+
+pub struct DebugPanel {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    session: Option<Entity<Session>>,
+    selected_frame: Option<u64>,
+    highlighted_editor: Option<WeakEntity<Editor>>,
+    variable_list: Entity<VariableList>,
+    console: Entity<Console>,
+    pending_serialization: Task<Option<()>>,
+    _session_subscription: Option<Subscription>,
+    _dap_store_subscription: Subscription,
+}
+
+impl DebugPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(DEBUG_PANEL_KEY) })
+            .await
+            .context("loading debug panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedDebugPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            let panel = Self::new(workspace, window, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    panel.height = serialized_panel.height.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace_handle = cx.entity().downgrade();
+        cx.new(|cx| {
+            let dap_store = project.read(cx).dap_store().clone();
+            let dap_store_subscription =
+                cx.subscribe_in(&dap_store, window, Self::on_dap_store_event);
+            Self {
+                workspace: workspace_handle,
+                project,
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                height: None,
+                session: None,
+                selected_frame: None,
+                highlighted_editor: None,
+                variable_list: cx.new(|_| VariableList::new()),
+                console: cx.new(|cx| Console::new(window, cx)),
+                pending_serialization: Task::ready(None),
+                _session_subscription: None,
+                _dap_store_subscription: dap_store_subscription,
+            }
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let height = self.height;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        DEBUG_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedDebugPanel { width, height })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn dap_store(&self, cx: &App) -> Entity<DapStore> {
+        self.project.read(cx).dap_store().clone()
+    }
+
+    fn on_dap_store_event(
+        &mut self,
+        dap_store: &Entity<DapStore>,
+        event: &DapStoreEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            DapStoreEvent::SessionStarted(session_id) => {
+                let session = dap_store.read(cx).session_by_id(*session_id);
+                self.set_session(session, window, cx);
+            }
+            DapStoreEvent::SessionShutdown(session_id) => {
+                let is_current = self
+                    .session
+                    .as_ref()
+                    .map_or(false, |session| session.read(cx).id() == *session_id);
+                if is_current {
+                    let session = dap_store.read(cx).sessions().last().cloned();
+                    self.set_session(session, window, cx);
+                }
+            }
+        }
+    }
+
+    fn set_session(
+        &mut self,
+        session: Option<Entity<Session>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.clear_stack_frame(cx);
+        self._session_subscription = session
+            .as_ref()
+            .map(|session| cx.subscribe_in(session, window, Self::on_session_event));
+        self.console
+            .update(cx, |console, cx| console.set_session(session.clone(), cx));
+        self.session = session;
+        self.select_top_stack_frame(window, cx);
+        cx.notify();
+    }
+
+    fn on_session_event(
+        &mut self,
+        session: &Entity<Session>,
+        event: &SessionEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            SessionEvent::StackFrames(thread_id) => {
+                if session.read(cx).active_thread() == Some(*thread_id) {
+                    self.select_top_stack_frame(window, cx);
+                }
+            }
+            SessionEvent::Continued | SessionEvent::Terminated => self.clear_stack_frame(cx),
+            SessionEvent::Stopped(_) | SessionEvent::Threads | SessionEvent::Output => {}
+        }
+        cx.notify();
+    }
+
+    fn select_top_stack_frame(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let top_frame = self.session.as_ref().and_then(|session| {
+            let session = session.read(cx);
+            let thread = session.thread(session.active_thread()?)?;
+            thread.stack_frames.first().cloned()
+        });
+        if let Some(frame) = top_frame {
+            self.select_stack_frame(frame, window, cx);
+        }
+    }
+
+    fn select_thread(&mut self, thread_id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let fetch_stack_frames = session.update(cx, |session, cx| {
+            session.set_active_thread(thread_id, cx);
+            let thread = session.thread(thread_id)?;
+            (thread.status == ThreadStatus::Stopped && thread.stack_frames.is_empty())
+                .then(|| session.fetch_stack_frames(thread_id, cx))
+        });
+        match fetch_stack_frames {
+            Some(task) => task.detach_and_log_err(cx),
+            None => {
+                self.clear_stack_frame(cx);
+                self.select_top_stack_frame(window, cx);
+            }
+        }
+        cx.notify();
+    }
+
+    /// Shows the variables of the given frame and highlights its line in an editor.
+    fn select_stack_frame(
+        &mut self,
+        frame: StackFrame,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        self.selected_frame = Some(frame.id);
+        self.console
+            .update(cx, |console, _| console.set_frame_id(Some(frame.id)));
+        self.variable_list.update(cx, |variable_list, cx| {
+            variable_list.load_frame(session, frame.id, cx)
+        });
+        cx.notify();
+
+        let Some(path) = frame.source.and_then(|source| source.path) else {
+            self.clear_stack_frame_highlight(cx);
+            return;
+        };
+        let Some(open_task) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_abs_path(
+                    PathBuf::from(path),
+                    OpenOptions {
+                        visible: Some(OpenVisible::None),
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                )
+            })
+            .log_err()
+        else {
+            return;
+        };
+        // Adapters report one-based lines and columns.
+        let row = frame.line.saturating_sub(1) as u32;
+        let column = frame.column.saturating_sub(1) as u32;
+        cx.spawn_in(window, |this, mut cx| async move {
+            let editor = open_task
+                .await?
+                .downcast::<Editor>()
+                .ok_or_else(|| anyhow!("stack frame source is not a text file"))?;
+            this.update_in(&mut cx, |this, window, cx| {
+                this.clear_stack_frame_highlight(cx);
+                editor.update(cx, |editor, cx| {
+                    editor.go_to_singleton_buffer_point(Point::new(row, column), window, cx);
+                    let snapshot = editor.buffer().read(cx).snapshot(cx);
+                    let start_point = snapshot.clip_point(Point::new(row, 0), Bias::Left);
+                    // Force non-empty range to ensure the line is highlighted.
+                    let mut end_point =
+                        snapshot.clip_point(start_point + Point::new(0, 1), Bias::Left);
+                    if start_point == end_point {
+                        end_point = snapshot.clip_point(start_point + Point::new(1, 0), Bias::Left);
+                    }
+                    editor.highlight_rows::<DebugStackFrameLine>(
+                        snapshot.anchor_before(start_point)..snapshot.anchor_after(end_point),
+                        cx.theme().colors().editor_highlighted_line_background,
+                        true,
+                        cx,
+                    );
+                });
+                this.highlighted_editor = Some(editor.downgrade());
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn clear_stack_frame(&mut self, cx: &mut Context<Self>) {
+        self.selected_frame = None;
+        self.console
+            .update(cx, |console, _| console.set_frame_id(None));
+        self.variable_list
+            .update(cx, |variable_list, cx| variable_list.clear(cx));
+        self.clear_stack_frame_highlight(cx);
+    }
+
+    fn clear_stack_frame_highlight(&mut self, cx: &mut Context<Self>) {
+        if let Some(editor) = self
+            .highlighted_editor
+            .take()
+            .and_then(|editor| editor.upgrade())
+        {
+            editor.update(cx, |editor, cx| {
+                editor.clear_row_highlights::<DebugStackFrameLine>();
+                cx.notify();
+            });
+        }
+    }
+
+    fn is_stopped(&self, cx: &App) -> bool {
+        self.session.as_ref().map_or(false, |session| {
+            let session = session.read(cx);
+            session
+                .active_thread()
+                .and_then(|thread_id| session.thread(thread_id))
+                .map_or(false, |thread| thread.status == ThreadStatus::Stopped)
+        })
+    }
+
+    /// Starts the first debug configuration from the settings.
+    pub fn start(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(config) = DebuggerSettings::get_global(cx)
+            .configurations
+            .first()
+            .cloned()
+        else {
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    workspace.show_error(
+                        &anyhow!("no debug configurations found in the `debugger.configurations` setting"),
+                        cx,
+                    )
+                })
+                .ok();
+            return;
+        };
+        self.start_configuration(config, window, cx);
+    }
+
+    fn start_configuration(
+        &mut self,
+        config: DebugAdapterConfig,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let start = self
+            .dap_store(cx)
+            .update(cx, |dap_store, cx| dap_store.new_session(config, cx));
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, |_, mut cx| async move {
+            if let Err(error) = start.await {
+                workspace
+                    .update(&mut cx, |workspace, cx| workspace.show_error(&error, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn update_session(
+        &mut self,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut Session, &mut Context<Session>) -> Task<Result<()>>,
+    ) {
+        if let Some(session) = self.session.clone() {
+            session.update(cx, f).detach_and_log_err(cx);
+        }
+    }
+
+    /// Resumes the stopped thread, or starts debugging if no session is running.
+    pub fn continue_thread(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.session.is_some() {
+            self.update_session(cx, Session::continue_thread);
+        } else {
+            self.start(window, cx);
+        }
+    }
+
+    pub fn pause(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.update_session(cx, Session::pause);
+    }
+
+    pub fn step_over(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.update_session(cx, Session::step_over);
+    }
+
+    pub fn step_in(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.update_session(cx, Session::step_in);
+    }
+
+    pub fn step_out(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.update_session(cx, Session::step_out);
+    }
+
+    pub fn stop(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(session) = self.session.as_ref() else {
+            return;
+        };
+        let session_id = session.read(cx).id();
+        self.dap_store(cx)
+            .update(cx, |dap_store, cx| {
+                dap_store.shutdown_session(session_id, cx)
+            })
+            .detach();
+    }
+
+    fn render_toolbar_button(
+        &self,
+        id: &'static str,
+        icon: IconName,
+        title: &'static str,
+        action: &dyn Action,
+        disabled: bool,
+        handler: fn(&mut Self, &mut Window, &mut Context<Self>),
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        IconButton::new(id, icon)
+            .icon_size(IconSize::Small)
+            .disabled(disabled)
+            .tooltip(Tooltip::for_action_title(title, action))
+            .on_click(cx.listener(move |this, _, window, cx| handler(this, window, cx)))
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_session = self.session.is_some();
+        let is_stopped = self.is_stopped(cx);
+        let continue_title = if has_session { "Continue" } else { "Start" };
+        let can_start = self.dap_store(cx).read(cx).can_start_sessions();
+
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(self.render_toolbar_button(
+                "debug-continue",
+                IconName::DebugContinue,
+                continue_title,
+                &Continue,
+                if has_session { !is_stopped } else { !can_start },
+                Self::continue_thread,
+                cx,
+            ))
+            .child(self.render_toolbar_button(
+                "debug-pause",
+                IconName::DebugPause,
+                "Pause",
+                &Pause,
+                !has_session || is_stopped,
+                Self::pause,
+                cx,
+            ))
+            .child(self.render_toolbar_button(
+                "debug-step-over",
+                IconName::DebugStepOver,
+                "Step Over",
+                &StepOver,
+                !is_stopped,
+                Self::step_over,
+                cx,
+            ))
+            .child(self.render_toolbar_button(
+                "debug-step-into",
+                IconName::DebugStepInto,
+                "Step Into",
+                &StepInto,
+                !is_stopped,
+                Self::step_in,
+                cx,
+            ))
+            .child(self.render_toolbar_button(
+                "debug-step-out",
+                IconName::DebugStepOut,
+                "Step Out",
+                &StepOut,
+                !is_stopped,
+                Self::step_out,
+                cx,
+            ))
+            .child(self.render_toolbar_button(
+                "debug-stop",
+                IconName::Stop,
+                "Stop",
+                &Stop,
+                !has_session,
+                Self::stop,
+                cx,
+            ))
+            .when_some(self.session.as_ref(), |this, session| {
+                this.child(
+                    Label::new(session.read(cx).config().label.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+    }
+
+    fn render_section(&self, title: &'static str, content: impl IntoElement) -> Div {
+        v_flex()
+            .flex_1()
+            .min_w(px(0.))
+            .child(
+                div().px_2().py_1().child(
+                    Label::new(title)
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                ),
+            )
+            .child(content)
+    }
+
+    fn render_session(&self, session: &Entity<Session>, cx: &mut Context<Self>) -> AnyElement {
+        let session = session.read(cx);
+        let active_thread = session.active_thread();
+        let threads = session
+            .threads()
+            .map(|thread| (thread.thread.id, thread.thread.name.clone(), thread.status))
+            .collect::<Vec<_>>();
+        let stack_frames = active_thread
+            .and_then(|thread_id| session.thread(thread_id))
+            .map(|thread| thread.stack_frames.clone())
+            .unwrap_or_default();
+
+        let threads =
+            v_flex().children(threads.into_iter().map(|(thread_id, name, status)| {
+                let status = match status {
+                    ThreadStatus::Running => "Running",
+                    ThreadStatus::Stopped => "Stopped",
+                    ThreadStatus::Exited => "Exited",
+                };
+                ListItem::new(("debug-thread", thread_id))
+                    .toggle_state(active_thread == Some(thread_id))
+                    .child(Label::new(name).size(LabelSize::Small))
+                    .end_slot(
+                        Label::new(status)
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select_thread(thread_id, window, cx)
+                    }))
+            }));
+
+        let stack_frames =
+            v_flex().children(stack_frames.into_iter().enumerate().map(|(ix, frame)| {
+                let location = frame
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.name.clone().or_else(|| source.path.clone()))
+                    .map(|file| format!("{file}:{}", frame.line));
+                ListItem::new(("debug-stack-frame", ix))
+                    .toggle_state(self.selected_frame == Some(frame.id))
+                    .child(Label::new(frame.name.clone()).size(LabelSize::Small))
+                    .when_some(location, |this, location| {
+                        this.end_slot(
+                            Label::new(location)
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select_stack_frame(frame.clone(), window, cx)
+                    }))
+            }));
+
+        h_flex()
+            .flex_1()
+            .items_start()
+            .size_full()
+            .child(
+                v_flex()
+                    .id("debug-threads-and-frames")
+                    .flex_1()
+                    .h_full()
+                    .overflow_y_scroll()
+                    .child(self.render_section("Threads", threads))
+                    .child(self.render_section("Call Stack", stack_frames)),
+            )
+            .child(
+                self.render_section("Variables", self.variable_list.clone())
+                    .h_full(),
+            )
+            .child(
+                self.render_section("Console", self.console.clone())
+                    .h_full(),
+            )
+            .into_any_element()
+    }
+
+    fn render_configurations(&self, cx: &mut Context<Self>) -> AnyElement {
+        if !self.dap_store(cx).read(cx).can_start_sessions() {
+            return div()
+                .p_2()
+                .child(
+                    Label::new("Debugging isn't supported in remote projects yet.")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element();
+        }
+
+        let configurations = DebuggerSettings::get_global(cx).configurations.clone();
+        if configurations.is_empty() {
+            return div()
+                .p_2()
+                .child(
+                    Label::new("Add debug configurations to the `debugger.configurations` setting to start debugging.")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element();
+        }
+
+        v_flex()
+            .children(configurations.into_iter().enumerate().map(|(ix, config)| {
+                ListItem::new(("debug-configuration", ix))
+                    .start_slot(Icon::new(IconName::DebugContinue).size(IconSize::Small))
+                    .child(Label::new(config.label.clone()).size(LabelSize::Small))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.start_configuration(config.clone(), window, cx)
+                    }))
+            }))
+            .into_any_element()
+    }
+}
+
+impl Panel for DebugPanel {
+    fn persistent_name() -> &'static str {
+        "Debug Panel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match DebuggerSettings::get_global(cx).dock {
+            DebugPanelDockPosition::Left => DockPosition::Left,
+            DebugPanelDockPosition::Bottom => DockPosition::Bottom,
+            DebugPanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<DebuggerSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left => DebugPanelDockPosition::Left,
+                    DockPosition::Bottom => DebugPanelDockPosition::Bottom,
+                    DockPosition::Right => DebugPanelDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, window: &Window, cx: &App) -> Pixels {
+        let settings = DebuggerSettings::get_global(cx);
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or(settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or(settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        DebuggerSettings::get_global(cx)
+            .button
+            .then_some(IconName::Debug)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Debug Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        9
+    }
+}
+
+impl Focusable for DebugPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for DebugPanel {}
+
+impl Render for DebugPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match self.session.clone() {
+            Some(session) => self.render_session(&session, cx),
+            None => self.render_configurations(cx),
+        };
+
+        v_flex()
+            .key_context("DebugPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_toolbar(cx))
+            .child(content)
+    }
+}
@@ -0,0 +1,59 @@
+use dap::DebugAdapterConfig;
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DebugPanelDockPosition {
+    Left,
+    Bottom,
+    Right,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct DebuggerSettings {
+    pub button: bool,
+    pub dock: DebugPanelDockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+    pub configurations: Vec<DebugAdapterConfig>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct DebuggerSettingsContent {
+    /// Whether to show the debug panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the debug panel.
+    ///
+    /// Default: bottom
+    pub dock: Option<DebugPanelDockPosition>,
+    /// Default width of the debug panel when docked to the left or right.
+    ///
+    /// Default: 360
+    pub default_width: Option<f32>,
+    /// Default height of the debug panel when docked to the bottom.
+    ///
+    /// Default: 320
+    pub default_height: Option<f32>,
+    /// The debug configurations that can be started from the debug panel.
+    ///
+    /// Default: []
+    pub configurations: Option<Vec<DebugAdapterConfig>>,
+}
+
+impl Settings for DebuggerSettings {
+    const KEY: Option<&'static str> = Some("debugger");
+
+    type FileContent = DebuggerSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::App,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
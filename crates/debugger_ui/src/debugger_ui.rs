@@ -0,0 +1,53 @@
+mod console;
+pub mod debugger_panel;
+pub mod debugger_settings;
+mod variable_list;
+
+use debugger_panel::DebugPanel;
+use debugger_settings::DebuggerSettings;
+use gpui::{actions, Action, App, Context, Window};
+use settings::Settings;
+use workspace::Workspace;
+
+actions!(
+    debugger,
+    [
+        Start,
+        Continue,
+        Pause,
+        StepOver,
+        StepInto,
+        StepOut,
+        Stop,
+        ToggleFocus
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    DebuggerSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<DebugPanel>(window, cx);
+        });
+        register_panel_action::<Start>(workspace, DebugPanel::start);
+        register_panel_action::<Continue>(workspace, DebugPanel::continue_thread);
+        register_panel_action::<Pause>(workspace, DebugPanel::pause);
+        register_panel_action::<StepOver>(workspace, DebugPanel::step_over);
+        register_panel_action::<StepInto>(workspace, DebugPanel::step_in);
+        register_panel_action::<StepOut>(workspace, DebugPanel::step_out);
+        register_panel_action::<Stop>(workspace, DebugPanel::stop);
+    })
+    .detach();
+}
+
+fn register_panel_action<A: Action>(
+    workspace: &mut Workspace,
+    handler: fn(&mut DebugPanel, &mut Window, &mut Context<DebugPanel>),
+) {
+    workspace.register_action(move |workspace, _: &A, window, cx| {
+        if let Some(panel) = workspace.panel::<DebugPanel>(cx) {
+            panel.update(cx, |panel, cx| handler(panel, window, cx));
+        }
+    });
+}
@@ -0,0 +1,173 @@
+use collections::{HashMap, HashSet};
+use dap::{Scope, Variable};
+use gpui::{Entity, Task};
+use project::debugger::session::Session;
+use ui::{prelude::*, ListItem};
+use util::ResultExt as _;
+
+/// Nested variables are only rendered up to this depth, as adapters may report reference cycles.
+const MAX_VARIABLE_DEPTH: usize = 16;
+
+/// The scopes and variables of the selected stack frame.
+pub struct VariableList {
+    session: Option<Entity<Session>>,
+    scopes: Vec<Scope>,
+    variables: HashMap<u64, Vec<Variable>>,
+    expanded: HashSet<u64>,
+    // Variable references are only valid while the debuggee is stopped,
+    // so responses to requests from a previous stop are ignored.
+    epoch: usize,
+    _load_scopes: Task<Option<()>>,
+}
+
+impl VariableList {
+    pub fn new() -> Self {
+        Self {
+            session: None,
+            scopes: Vec::new(),
+            variables: HashMap::default(),
+            expanded: HashSet::default(),
+            epoch: 0,
+            _load_scopes: Task::ready(None),
+        }
+    }
+
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.session = None;
+        self.scopes.clear();
+        self.variables.clear();
+        self.expanded.clear();
+        self.epoch += 1;
+        self._load_scopes = Task::ready(None);
+        cx.notify();
+    }
+
+    pub fn load_frame(&mut self, session: Entity<Session>, frame_id: u64, cx: &mut Context<Self>) {
+        self.clear(cx);
+        let scopes = session.read(cx).scopes(frame_id, cx);
+        self.session = Some(session);
+        let epoch = self.epoch;
+        self._load_scopes = cx.spawn(|this, mut cx| async move {
+            let scopes = scopes.await.log_err()?;
+            this.update(&mut cx, |this, cx| {
+                if this.epoch != epoch {
+                    return;
+                }
+                // Expand the innermost scope, unless its variables are costly to retrieve.
+                if let Some(scope) = scopes.first().filter(|scope| !scope.expensive) {
+                    this.expanded.insert(scope.variables_reference);
+                    this.fetch_variables(scope.variables_reference, cx);
+                }
+                this.scopes = scopes;
+                cx.notify();
+            })
+            .ok()
+        });
+    }
+
+    fn fetch_variables(&mut self, variables_reference: u64, cx: &mut Context<Self>) {
+        if self.variables.contains_key(&variables_reference) {
+            return;
+        }
+        let Some(session) = self.session.as_ref() else {
+            return;
+        };
+        let variables = session.read(cx).variables(variables_reference, cx);
+        let epoch = self.epoch;
+        cx.spawn(|this, mut cx| async move {
+            let variables = variables.await?;
+            this.update(&mut cx, |this, cx| {
+                if this.epoch == epoch {
+                    this.variables.insert(variables_reference, variables);
+                    cx.notify();
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn toggle(&mut self, variables_reference: u64, cx: &mut Context<Self>) {
+        if !self.expanded.remove(&variables_reference) {
+            self.expanded.insert(variables_reference);
+            self.fetch_variables(variables_reference, cx);
+        }
+        cx.notify();
+    }
+
+    fn render_variables(
+        &self,
+        variables_reference: u64,
+        depth: usize,
+        items: &mut Vec<AnyElement>,
+        cx: &mut Context<Self>,
+    ) {
+        if depth > MAX_VARIABLE_DEPTH {
+            return;
+        }
+        let Some(variables) = self.variables.get(&variables_reference) else {
+            return;
+        };
+        for (ix, variable) in variables.iter().enumerate() {
+            let child_reference = variable.variables_reference;
+            let is_expanded = child_reference > 0 && self.expanded.contains(&child_reference);
+            items.push(
+                ListItem::new(ElementId::Name(
+                    format!("variable-{variables_reference}-{ix}").into(),
+                ))
+                .indent_level(depth)
+                .toggle((child_reference > 0).then_some(is_expanded))
+                .on_toggle(cx.listener(move |this, _, _, cx| this.toggle(child_reference, cx)))
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(variable.name.clone()).size(LabelSize::Small))
+                        .child(
+                            Label::new(variable.value.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .single_line(),
+                        )
+                        .when_some(variable.type_.clone(), |this, type_| {
+                            this.child(
+                                Label::new(type_)
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Disabled),
+                            )
+                        }),
+                )
+                .into_any_element(),
+            );
+            if is_expanded {
+                self.render_variables(child_reference, depth + 1, items, cx);
+            }
+        }
+    }
+}
+
+impl Render for VariableList {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut items = Vec::new();
+        for scope in &self.scopes {
+            let variables_reference = scope.variables_reference;
+            let is_expanded = self.expanded.contains(&variables_reference);
+            items.push(
+                ListItem::new(ElementId::Name(
+                    format!("scope-{variables_reference}").into(),
+                ))
+                .toggle(is_expanded)
+                .on_toggle(cx.listener(move |this, _, _, cx| this.toggle(variables_reference, cx)))
+                .child(Label::new(scope.name.clone()).size(LabelSize::Small))
+                .into_any_element(),
+            );
+            if is_expanded {
+                self.render_variables(variables_reference, 1, &mut items, cx);
+            }
+        }
+
+        v_flex()
+            .id("debugger-variables")
+            .size_full()
+            .overflow_y_scroll()
+            .children(items)
+    }
+}
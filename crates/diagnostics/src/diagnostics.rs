@@ -40,15 +40,23 @@ use ui::{h_flex, prelude::*, Icon, IconName, Label};
 use util::ResultExt;
 use workspace::{
     item::{BreadcrumbText, Item, ItemEvent, ItemHandle, TabContentParams},
+    quickfix::{QuickfixEntry, QuickfixPosition},
     searchable::SearchableItemHandle,
     ItemNavHistory, ToolbarItemLocation, Workspace,
 };
 
-actions!(diagnostics, [Deploy, ToggleWarnings]);
+actions!(diagnostics, [Deploy, ToggleWarnings, SendToQuickfixList]);
 
 struct IncludeWarnings(bool);
 impl Global for IncludeWarnings {}
 
+fn include_warnings(cx: &App) -> bool {
+    match cx.try_global::<IncludeWarnings>() {
+        Some(include_warnings) => include_warnings.0,
+        None => ProjectSettings::get_global(cx).diagnostics.include_warnings,
+    }
+}
+
 pub fn init(cx: &mut App) {
     cx.observe_new(ProjectDiagnosticsEditor::register).detach();
 }
@@ -148,6 +156,7 @@ impl ProjectDiagnosticsEditor {
         _: &mut Context<Workspace>,
     ) {
         workspace.register_action(Self::deploy);
+        workspace.register_action(Self::send_to_quickfix_list);
     }
 
     fn new_with_context(
@@ -313,10 +322,7 @@ impl ProjectDiagnosticsEditor {
         } else {
             let workspace_handle = cx.entity().downgrade();
 
-            let include_warnings = match cx.try_global::<IncludeWarnings>() {
-                Some(include_warnings) => include_warnings.0,
-                None => ProjectSettings::get_global(cx).diagnostics.include_warnings,
-            };
+            let include_warnings = include_warnings(cx);
 
             let diagnostics = cx.new(|cx| {
                 ProjectDiagnosticsEditor::new(
@@ -331,6 +337,61 @@ impl ProjectDiagnosticsEditor {
         }
     }
 
+    /// Replaces the quickfix list with the primary diagnostics of the project,
+    /// including warnings if the diagnostics view shows them.
+    fn send_to_quickfix_list(
+        workspace: &mut Workspace,
+        _: &SendToQuickfixList,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        let max_severity = if include_warnings(cx) {
+            DiagnosticSeverity::WARNING
+        } else {
+            DiagnosticSeverity::ERROR
+        };
+        let paths = project
+            .read(cx)
+            .diagnostic_summaries(false, cx)
+            .map(|(path, _, _)| path)
+            .collect::<BTreeSet<_>>();
+        cx.spawn_in(window, |workspace, mut cx| async move {
+            let mut entries = Vec::new();
+            for path in paths {
+                let Some(buffer) = project
+                    .update(&mut cx, |project, cx| project.open_buffer(path.clone(), cx))?
+                    .await
+                    .log_err()
+                else {
+                    continue;
+                };
+                let snapshot = buffer.read_with(&cx, |buffer, _| buffer.snapshot())?;
+                for (_, group) in snapshot.diagnostic_groups(None) {
+                    let entry = &group.entries[group.primary_ix];
+                    if entry.diagnostic.severity > max_severity {
+                        continue;
+                    }
+                    entries.push(QuickfixEntry {
+                        path: path.clone(),
+                        position: QuickfixPosition::Anchored {
+                            buffer: buffer.clone(),
+                            range: entry.range.clone(),
+                        },
+                        message: entry.diagnostic.message.clone().into(),
+                        severity: Some(entry.diagnostic.severity),
+                    });
+                }
+            }
+            workspace.update(&mut cx, |workspace, cx| {
+                workspace.quickfix_list().update(cx, |quickfix_list, cx| {
+                    quickfix_list.set_entries("Diagnostics", entries, true, cx)
+                });
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn toggle_warnings(&mut self, _: &ToggleWarnings, window: &mut Window, cx: &mut Context<Self>) {
         self.include_warnings = !self.include_warnings;
         cx.set_global(IncludeWarnings(self.include_warnings));
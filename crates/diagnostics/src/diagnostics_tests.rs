@@ -719,6 +719,140 @@ async fn test_diagnostics_multiple_servers(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_send_to_quickfix_list(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/test"),
+        json!({
+            "a.rs": "fn ü() { x }\n",
+            "b.rs": "fn b() {\n    let y = 1;\n}\n",
+        }),
+    )
+    .await;
+
+    let language_server_id = LanguageServerId(0);
+    let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let workspace = window.root(cx).unwrap();
+
+    let diagnostic = |message: &str, severity, group_id| Diagnostic {
+        message: message.to_string(),
+        severity,
+        is_primary: true,
+        is_disk_based: true,
+        group_id,
+        ..Default::default()
+    };
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_diagnostic_entries(
+                language_server_id,
+                PathBuf::from(path!("/test/a.rs")),
+                None,
+                vec![DiagnosticEntry {
+                    range: Unclipped(PointUtf16::new(0, 9))..Unclipped(PointUtf16::new(0, 10)),
+                    diagnostic: diagnostic(
+                        "cannot find value `x` in this scope",
+                        DiagnosticSeverity::ERROR,
+                        0,
+                    ),
+                }],
+                cx,
+            )
+            .unwrap();
+        lsp_store
+            .update_diagnostic_entries(
+                language_server_id,
+                PathBuf::from(path!("/test/b.rs")),
+                None,
+                vec![
+                    DiagnosticEntry {
+                        range: Unclipped(PointUtf16::new(1, 8))..Unclipped(PointUtf16::new(1, 9)),
+                        diagnostic: diagnostic(
+                            "unused variable: `y`",
+                            DiagnosticSeverity::WARNING,
+                            1,
+                        ),
+                    },
+                    DiagnosticEntry {
+                        range: Unclipped(PointUtf16::new(0, 3))..Unclipped(PointUtf16::new(0, 4)),
+                        diagnostic: diagnostic(
+                            "function `b` is never used",
+                            DiagnosticSeverity::HINT,
+                            2,
+                        ),
+                    },
+                ],
+                cx,
+            )
+            .unwrap();
+    });
+
+    let quickfix_entries = |cx: &mut VisualTestContext| {
+        workspace.update_in(cx, |workspace, window, cx| {
+            ProjectDiagnosticsEditor::send_to_quickfix_list(
+                workspace,
+                &SendToQuickfixList,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .quickfix_list()
+                .read(cx)
+                .entries()
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.path.path.to_string_lossy().to_string(),
+                        entry.position.start(cx),
+                        entry.message.to_string(),
+                        entry.severity,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Primary diagnostics up to warnings are sent, with their columns in characters.
+    assert_eq!(
+        quickfix_entries(cx),
+        [
+            (
+                "a.rs".to_string(),
+                (0, 9),
+                "cannot find value `x` in this scope".to_string(),
+                Some(DiagnosticSeverity::ERROR)
+            ),
+            (
+                "b.rs".to_string(),
+                (1, 8),
+                "unused variable: `y`".to_string(),
+                Some(DiagnosticSeverity::WARNING)
+            ),
+        ]
+    );
+
+    // Warnings are left out when the diagnostics view hides them.
+    cx.update(|_, cx| cx.set_global(IncludeWarnings(false)));
+    assert_eq!(
+        quickfix_entries(cx),
+        [(
+            "a.rs".to_string(),
+            (0, 9),
+            "cannot find value `x` in this scope".to_string(),
+            Some(DiagnosticSeverity::ERROR)
+        )]
+    );
+}
+
 #[gpui::test(iterations = 20)]
 async fn test_random_diagnostics(cx: &mut TestAppContext, mut rng: StdRng) {
     init_test(cx);
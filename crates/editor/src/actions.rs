@@ -309,6 +309,8 @@ actions!(
         GoToDiagnostic,
         GoToHunk,
         GoToPreviousHunk,
+        GoToConflict,
+        GoToPreviousConflict,
         GoToImplementation,
         GoToImplementationSplit,
         GoToPreviousDiagnostic,
@@ -413,6 +415,7 @@ actions!(
         Tab,
         Backtab,
         ToggleAutoSignatureHelp,
+        ToggleBreakpoint,
         ToggleGitBlameInline,
         ToggleIndentGuides,
         ToggleInlayHints,
@@ -424,6 +427,7 @@ actions!(
         ToggleRelativeLineNumbers,
         ToggleSelectionMenu,
         ToggleSoftWrap,
+        ToggleSplitDiff,
         ToggleTabBar,
         Transpose,
         Undo,
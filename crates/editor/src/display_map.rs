@@ -82,6 +82,7 @@ pub trait ToDisplayPoint {
 
 type TextHighlights = TreeMap<TypeId, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>;
 type InlayHighlights = TreeMap<TypeId, TreeMap<InlayId, (HighlightStyle, InlayHighlight)>>;
+type SemanticHighlights = TreeMap<BufferId, Arc<Vec<(Range<Anchor>, HighlightStyle)>>>;
 
 /// Decides how text in a [`MultiBuffer`] should be displayed in a buffer, handling inlay hints,
 /// folding, hard tabs, soft wrapping, custom blocks (like diagnostics), and highlighting.
@@ -105,6 +106,8 @@ pub struct DisplayMap {
     text_highlights: TextHighlights,
     /// Regions of inlays that should be highlighted.
     inlay_highlights: InlayHighlights,
+    /// Regions of text that language servers classified with semantic tokens, per buffer.
+    semantic_highlights: SemanticHighlights,
     /// A container for explicitly foldable ranges, which supersede indentation based fold range suggestions.
     crease_map: CreaseMap,
     pub(crate) fold_placeholder: FoldPlaceholder,
@@ -148,6 +151,7 @@ impl DisplayMap {
             fold_placeholder,
             text_highlights: Default::default(),
             inlay_highlights: Default::default(),
+            semantic_highlights: Default::default(),
             clip_at_line_ends: false,
             masked: false,
         }
@@ -175,6 +179,7 @@ impl DisplayMap {
             crease_snapshot: self.crease_map.snapshot(),
             text_highlights: self.text_highlights.clone(),
             inlay_highlights: self.inlay_highlights.clone(),
+            semantic_highlights: self.semantic_highlights.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
             masked: self.masked,
             fold_placeholder: self.fold_placeholder.clone(),
@@ -491,6 +496,22 @@ impl DisplayMap {
         cleared
     }
 
+    /// Replaces the semantic highlights of a buffer, which are layered below text highlights.
+    ///
+    /// The ranges must be ordered by their start and must not overlap.
+    pub(crate) fn set_semantic_highlights(
+        &mut self,
+        buffer_id: BufferId,
+        highlights: Vec<(Range<Anchor>, HighlightStyle)>,
+    ) {
+        self.semantic_highlights
+            .insert(buffer_id, Arc::new(highlights));
+    }
+
+    pub(crate) fn clear_semantic_highlights(&mut self, buffer_id: BufferId) -> bool {
+        self.semantic_highlights.remove(&buffer_id).is_some()
+    }
+
     pub fn set_font(&self, font: Font, font_size: Pixels, cx: &mut Context<Self>) -> bool {
         self.wrap_map
             .update(cx, |map, cx| map.set_font_with_size(font, font_size, cx))
@@ -553,6 +574,7 @@ impl DisplayMap {
 pub(crate) struct Highlights<'a> {
     pub text_highlights: Option<&'a TextHighlights>,
     pub inlay_highlights: Option<&'a InlayHighlights>,
+    pub semantic_highlights: Option<&'a SemanticHighlights>,
     pub styles: HighlightStyles,
 }
 
@@ -687,6 +709,7 @@ pub struct DisplaySnapshot {
     block_snapshot: BlockSnapshot,
     text_highlights: TextHighlights,
     inlay_highlights: InlayHighlights,
+    semantic_highlights: SemanticHighlights,
     clip_at_line_ends: bool,
     masked: bool,
     pub(crate) fold_placeholder: FoldPlaceholder,
@@ -869,6 +892,7 @@ impl DisplaySnapshot {
             Highlights {
                 text_highlights: Some(&self.text_highlights),
                 inlay_highlights: Some(&self.inlay_highlights),
+                semantic_highlights: Some(&self.semantic_highlights),
                 styles: highlight_styles,
             },
         )
@@ -2564,6 +2588,79 @@ pub mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_chunks_with_semantic_highlights(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| init_test(cx, |_| {}));
+
+        let theme = SyntaxTheme::new_test(vec![]);
+        let (text, ranges) = marked_text_ranges("let «abc» = «def»;", false);
+        let buffer = cx.new(|cx| Buffer::local(text, cx));
+        let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let buffer_snapshot = buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx));
+
+        let map = cx.new(|cx| {
+            DisplayMap::new(
+                buffer,
+                font("Courier"),
+                px(16.0),
+                None,
+                1,
+                1,
+                FoldPlaceholder::test(),
+                cx,
+            )
+        });
+
+        enum MyType {}
+
+        let style = |color| HighlightStyle {
+            color: Some(color),
+            ..Default::default()
+        };
+        let anchor_range = |range: Range<usize>| {
+            buffer_snapshot.anchor_after(range.start)..buffer_snapshot.anchor_before(range.end)
+        };
+        map.update(cx, |map, _| {
+            map.set_semantic_highlights(
+                buffer_id,
+                vec![
+                    (anchor_range(ranges[0].clone()), style(Hsla::green())),
+                    (anchor_range(ranges[1].clone()), style(Hsla::red())),
+                ],
+            );
+            // Text highlights are layered on top of the semantic ones.
+            map.highlight_text(
+                TypeId::of::<MyType>(),
+                vec![anchor_range(5..11)],
+                style(Hsla::blue()),
+            );
+        });
+        assert_eq!(
+            cx.update(|cx| chunks(DisplayRow(0)..DisplayRow(1), &map, &theme, cx)),
+            [
+                ("let ".to_string(), None, None),
+                ("a".to_string(), None, Some(Hsla::green())),
+                ("bc = d".to_string(), None, Some(Hsla::blue())),
+                ("ef".to_string(), None, Some(Hsla::red())),
+                (";".to_string(), None, None),
+            ]
+        );
+
+        map.update(cx, |map, _| {
+            assert!(map.clear_semantic_highlights(buffer_id));
+            assert!(!map.clear_semantic_highlights(buffer_id));
+        });
+        assert_eq!(
+            cx.update(|cx| chunks(DisplayRow(0)..DisplayRow(1), &map, &theme, cx)),
+            [
+                ("let a".to_string(), None, None),
+                ("bc = d".to_string(), None, Some(Hsla::blue())),
+                ("ef;".to_string(), None, None),
+            ]
+        );
+    }
+
     #[gpui::test]
     fn test_clip_point(cx: &mut gpui::App) {
         init_test(cx, |_| {});
@@ -21,6 +21,7 @@ pub mod display_map;
 mod editor_settings;
 mod editor_settings_controls;
 mod element;
+mod folding_ranges;
 mod git;
 mod highlight_matching_bracket;
 mod hover_links;
@@ -35,9 +36,13 @@ mod mouse_context_menu;
 pub mod movement;
 mod persistence;
 mod proposed_changes_editor;
+pub mod quickfix;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
+mod split_diff;
+mod sticky_scroll;
 pub mod tasks;
 
 #[cfg(test)]
@@ -106,7 +111,8 @@ use language::{
     point_from_lsp, text_diff_with_options, AutoindentMode, BracketMatch, BracketPair, Buffer,
     Capability, CharKind, CodeLabel, CursorShape, Diagnostic, DiffOptions, EditPredictionsMode,
     EditPreview, HighlightedText, IndentKind, IndentSize, Language, OffsetRangeExt, Point,
-    Selection, SelectionGoal, TextObject, TransactionId, TreeSitterOptions, WordsQuery,
+    Selection, SelectionGoal, TextObject, TransactionId, TreeSitterOptions, UndoTreeDistance,
+    WordsQuery,
 };
 use language::{point_to_lsp, BufferRow, CharClassifier, Runnable, RunnableRange};
 use linked_editing_ranges::refresh_linked_ranges;
@@ -116,7 +122,9 @@ pub use proposed_changes_editor::{
     ProposedChangeLocation, ProposedChangesEditor, ProposedChangesEditorToolbar,
 };
 use smallvec::smallvec;
+pub use split_diff::SplitDiff;
 use std::iter::Peekable;
+pub use sticky_scroll::StickyScope;
 use task::{ResolvedTask, TaskTemplate, TaskVariables};
 
 use hover_links::{find_file, HoverLink, HoveredLinkState, InlayHighlight};
@@ -137,11 +145,12 @@ use multi_buffer::{
     MultiOrSingleBufferOffsetRange, ToOffsetUtf16,
 };
 use project::{
+    git::GitEvent,
     lsp_store::{CompletionDocumentation, FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
     project_settings::{GitGutterSetting, ProjectSettings},
-    CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, InlayHint,
-    Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectTransaction,
-    TaskSourceKind,
+    CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, DocumentLink,
+    InlayHint, Location, LocationLink, PrepareRenameResponse, Project, ProjectItem,
+    ProjectTransaction, TaskSourceKind,
 };
 use rand::prelude::*;
 use rpc::{proto::*, ErrorExt};
@@ -287,6 +296,7 @@ pub fn init(cx: &mut App) {
             workspace.register_action(Editor::new_file_vertical);
             workspace.register_action(Editor::new_file_horizontal);
             workspace.register_action(Editor::cancel_language_server_work);
+            quickfix::register_actions(workspace);
         },
     )
     .detach();
@@ -631,6 +641,7 @@ pub struct Editor {
     autoclose_regions: Vec<AutocloseRegion>,
     snippet_stack: InvalidationStack<SnippetState>,
     select_larger_syntax_node_stack: Vec<Box<[Selection<usize>]>>,
+    selection_ranges_task: Option<Task<()>>,
     ime_transaction: Option<TransactionId>,
     active_diagnostics: Option<ActiveDiagnosticGroup>,
     show_inline_diagnostics: bool,
@@ -658,6 +669,7 @@ pub struct Editor {
     show_git_diff_gutter: Option<bool>,
     show_code_actions: Option<bool>,
     show_runnables: Option<bool>,
+    show_breakpoints: Option<bool>,
     show_wrap_guides: Option<bool>,
     show_indent_guides: Option<bool>,
     placeholder_text: Option<Arc<str>>,
@@ -681,6 +693,12 @@ pub struct Editor {
     document_highlights_task: Option<Task<()>>,
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
+    semantic_tokens_tasks: HashMap<BufferId, Task<()>>,
+    semantic_tokens_buffers: HashSet<BufferId>,
+    folding_ranges_tasks: HashMap<BufferId, Task<()>>,
+    folding_range_creases: HashMap<BufferId, Vec<CreaseId>>,
+    conflicts_tasks: HashMap<BufferId, Task<()>>,
+    conflicts: HashMap<BufferId, git::conflicts::BufferConflicts>,
     pending_rename: Option<RenameState>,
     searchable: bool,
     cursor_shape: CursorShape,
@@ -788,6 +806,7 @@ pub struct EditorSnapshot {
     show_git_diff_gutter: Option<bool>,
     show_code_actions: Option<bool>,
     show_runnables: Option<bool>,
+    show_breakpoints: Option<bool>,
     git_blame_gutter_max_author_length: Option<usize>,
     pub display_snapshot: DisplaySnapshot,
     pub placeholder_text: Option<Arc<str>>,
@@ -1241,6 +1260,12 @@ impl Editor {
                             editor
                                 .refresh_inlay_hints(InlayHintRefreshReason::RefreshRequested, cx);
                         }
+                        project::Event::RefreshSemanticTokens => {
+                            semantic_tokens::refresh_semantic_tokens(editor, None, None, cx);
+                        }
+                        project::Event::LanguageServerAdded(..) => {
+                            folding_ranges::refresh_folding_ranges(editor, None, None, cx);
+                        }
                         project::Event::SnippetEdit(id, snippet_edits) => {
                             if let Some(buffer) = editor.buffer.read(cx).buffer(*id) {
                                 let focus_handle = editor.focus_handle(cx);
@@ -1279,6 +1304,18 @@ impl Editor {
                         },
                     ));
                 }
+                let breakpoint_store = project.read(cx).breakpoint_store().clone();
+                project_subscriptions.push(cx.observe(&breakpoint_store, |_, _, cx| cx.notify()));
+                let git_store = project.read(cx).git_store().clone();
+                project_subscriptions.push(cx.subscribe(
+                    &git_store,
+                    |editor, _, event: &GitEvent, cx| match event {
+                        GitEvent::FileSystemUpdated | GitEvent::GitStateUpdated => {
+                            git::conflicts::refresh_conflicts(editor, None, None, cx);
+                        }
+                        _ => {}
+                    },
+                ));
             }
         }
 
@@ -1333,6 +1370,7 @@ impl Editor {
             autoclose_regions: Default::default(),
             snippet_stack: Default::default(),
             select_larger_syntax_node_stack: Vec::new(),
+            selection_ranges_task: None,
             ime_transaction: Default::default(),
             active_diagnostics: None,
             show_inline_diagnostics: ProjectSettings::get_global(cx).diagnostics.inline.enabled,
@@ -1355,6 +1393,7 @@ impl Editor {
             show_git_diff_gutter: None,
             show_code_actions: None,
             show_runnables: None,
+            show_breakpoints: None,
             show_wrap_guides: None,
             show_indent_guides,
             placeholder_text: None,
@@ -1459,6 +1498,12 @@ impl Editor {
             ],
             tasks_update_task: None,
             linked_edit_ranges: Default::default(),
+            semantic_tokens_tasks: HashMap::default(),
+            semantic_tokens_buffers: HashSet::default(),
+            folding_ranges_tasks: HashMap::default(),
+            folding_range_creases: HashMap::default(),
+            conflicts_tasks: HashMap::default(),
+            conflicts: HashMap::default(),
             in_project_search: false,
             previous_search_ranges: None,
             breadcrumb_header: None,
@@ -1479,6 +1524,9 @@ impl Editor {
         this.end_selection(window, cx);
         this.scroll_manager.show_scrollbar(window, cx);
         jsx_tag_auto_close::refresh_enabled_in_any_buffer(&mut this, &buffer, cx);
+        semantic_tokens::refresh_semantic_tokens(&mut this, None, None, cx);
+        folding_ranges::refresh_folding_ranges(&mut this, None, None, cx);
+        git::conflicts::refresh_conflicts(&mut this, None, None, cx);
 
         if mode == EditorMode::Full {
             let should_auto_hide_scrollbars = cx.should_auto_hide_scrollbars();
@@ -1763,6 +1811,7 @@ impl Editor {
             show_git_diff_gutter: self.show_git_diff_gutter,
             show_code_actions: self.show_code_actions,
             show_runnables: self.show_runnables,
+            show_breakpoints: self.show_breakpoints,
             git_blame_gutter_max_author_length,
             display_snapshot: self.display_map.update(cx, |map, cx| map.snapshot(cx)),
             scroll_anchor: self.scroll_manager.anchor(),
@@ -6046,6 +6095,97 @@ impl Editor {
             }))
     }
 
+    fn render_breakpoint(&self, row: DisplayRow, cx: &mut Context<Self>) -> IconButton {
+        IconButton::new(
+            ("breakpoint_indicator", row.0 as usize),
+            ui::IconName::DebugBreakpoint,
+        )
+        .shape(ui::IconButtonShape::Square)
+        .icon_size(IconSize::XSmall)
+        .icon_color(Color::Error)
+        .tooltip(Tooltip::for_action_title(
+            "Toggle Breakpoint",
+            &ToggleBreakpoint,
+        ))
+        .on_click(cx.listener(move |editor, _e, window, cx| {
+            window.focus(&editor.focus_handle(cx));
+            let position = editor
+                .snapshot(window, cx)
+                .display_point_to_anchor(DisplayPoint::new(row, 0), Bias::Left);
+            editor.toggle_breakpoint_at(position, cx);
+        }))
+    }
+
+    /// Returns the display rows within `range` that contain a breakpoint.
+    pub(crate) fn breakpoint_rows(
+        &self,
+        range: Range<DisplayRow>,
+        snapshot: &EditorSnapshot,
+        cx: &App,
+    ) -> HashSet<DisplayRow> {
+        let mut rows = HashSet::default();
+        let Some(project) = self.project.as_ref() else {
+            return rows;
+        };
+        let breakpoint_store = project.read(cx).breakpoint_store().read(cx);
+        let multi_buffer = self.buffer.read(cx);
+        let start = snapshot
+            .display_snapshot
+            .display_point_to_point(DisplayPoint::new(range.start, 0), Bias::Left);
+        let end = snapshot
+            .display_snapshot
+            .display_point_to_point(DisplayPoint::new(range.end, 0), Bias::Right);
+        for (buffer_snapshot, buffer_range, excerpt_id) in
+            snapshot.buffer_snapshot.range_to_buffer_ranges(start..end)
+        {
+            let Some(buffer) = multi_buffer.buffer(buffer_snapshot.remote_id()) else {
+                continue;
+            };
+            let buffer_range = buffer_snapshot.anchor_before(buffer_range.start)
+                ..buffer_snapshot.anchor_after(buffer_range.end);
+            for (anchor, _) in
+                breakpoint_store.breakpoints(&buffer, Some(buffer_range), buffer_snapshot, cx)
+            {
+                let Some(anchor) = snapshot
+                    .buffer_snapshot
+                    .anchor_in_excerpt(excerpt_id, *anchor)
+                else {
+                    continue;
+                };
+                let row = anchor.to_display_point(snapshot).row();
+                if range.contains(&row) {
+                    rows.insert(row);
+                }
+            }
+        }
+        rows
+    }
+
+    pub fn toggle_breakpoint(
+        &mut self,
+        _: &ToggleBreakpoint,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let head = self.selections.newest_anchor().head();
+        self.toggle_breakpoint_at(head, cx);
+    }
+
+    fn toggle_breakpoint_at(&mut self, position: Anchor, cx: &mut Context<Self>) {
+        let Some(project) = self.project.as_ref() else {
+            return;
+        };
+        let Some((buffer, text_anchor)) =
+            self.buffer.read(cx).text_anchor_for_position(position, cx)
+        else {
+            return;
+        };
+        let breakpoint_store = project.read(cx).breakpoint_store().clone();
+        breakpoint_store.update(cx, |store, cx| {
+            store.toggle_breakpoint(buffer, text_anchor, cx);
+        });
+    }
+
     pub fn context_menu_visible(&self) -> bool {
         !self.edit_prediction_preview_is_active()
             && self
@@ -9097,6 +9237,60 @@ impl Editor {
         }
     }
 
+    /// Restores the given state of the undo tree of a singleton buffer, even if
+    /// it's on a branch that was left by undoing and then editing.
+    pub fn travel_to_transaction(
+        &mut self,
+        transaction_id: Option<TransactionId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.travel_undo_tree(
+            |buffer, cx| buffer.travel_to_transaction(transaction_id, cx),
+            window,
+            cx,
+        );
+    }
+
+    pub fn undo_tree_earlier(
+        &mut self,
+        distance: UndoTreeDistance,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.travel_undo_tree(|buffer, cx| buffer.earlier(distance, cx), window, cx);
+    }
+
+    pub fn undo_tree_later(
+        &mut self,
+        distance: UndoTreeDistance,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.travel_undo_tree(|buffer, cx| buffer.later(distance, cx), window, cx);
+    }
+
+    fn travel_undo_tree(
+        &mut self,
+        travel: impl FnOnce(&mut Buffer, &mut Context<Buffer>) -> Option<TransactionId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+
+        if let Some(transaction_id) = buffer.update(cx, travel) {
+            self.request_autoscroll(Autoscroll::fit(), cx);
+            self.unmark_text(window, cx);
+            self.refresh_inline_completion(true, false, window, cx);
+            cx.emit(EditorEvent::Edited { transaction_id });
+        }
+    }
+
     pub fn finalize_last_transaction(&mut self, cx: &mut Context<Self>) {
         self.buffer
             .update(cx, |buffer, cx| buffer.finalize_last_transaction(cx));
@@ -11187,10 +11381,36 @@ impl Editor {
         _: &SelectLargerSyntaxNode,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        let old_selections = self.selections.all::<usize>(cx).into_boxed_slice();
+        if let Some(selection_ranges) = self.lsp_selection_ranges(&old_selections, cx) {
+            self.selection_ranges_task = Some(cx.spawn_in(window, |editor, mut cx| async move {
+                let selection_ranges = selection_ranges.await;
+                editor
+                    .update_in(&mut cx, |editor, window, cx| {
+                        // Discard the ranges if the selections changed while they were requested.
+                        if *editor.selections.all::<usize>(cx) == *old_selections {
+                            editor.expand_selections(old_selections, selection_ranges, window, cx);
+                        }
+                    })
+                    .ok();
+            }));
+        } else {
+            self.expand_selections(old_selections, HashMap::default(), window, cx);
+        }
+    }
+
+    /// Expands each selection to the innermost of its language server selection ranges that
+    /// contains it, falling back to the enclosing syntax node.
+    fn expand_selections(
+        &mut self,
+        old_selections: Box<[Selection<usize>]>,
+        selection_ranges: HashMap<usize, Vec<Range<Anchor>>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
         let buffer = self.buffer.read(cx).snapshot(cx);
-        let old_selections = self.selections.all::<usize>(cx).into_boxed_slice();
 
         let mut stack = mem::take(&mut self.select_larger_syntax_node_stack);
         let mut selected_larger_node = false;
@@ -11198,32 +11418,48 @@ impl Editor {
             .iter()
             .map(|selection| {
                 let old_range = selection.start..selection.end;
-                let mut new_range = old_range.clone();
-                let mut new_node = None;
-                while let Some((node, containing_range)) = buffer.syntax_ancestor(new_range.clone())
-                {
-                    new_node = Some(node);
-                    new_range = match containing_range {
-                        MultiOrSingleBufferOffsetRange::Single(_) => break,
-                        MultiOrSingleBufferOffsetRange::Multi(range) => range,
-                    };
-                    if !display_map.intersects_fold(new_range.start)
-                        && !display_map.intersects_fold(new_range.end)
+                let lsp_range = selection_ranges
+                    .get(&selection.id)
+                    .into_iter()
+                    .flatten()
+                    .map(|range| range.start.to_offset(&buffer)..range.end.to_offset(&buffer))
+                    .find(|range| {
+                        range.start <= old_range.start
+                            && range.end >= old_range.end
+                            && *range != old_range
+                            && !display_map.intersects_fold(range.start)
+                            && !display_map.intersects_fold(range.end)
+                    });
+                let new_range = lsp_range.unwrap_or_else(|| {
+                    let mut new_range = old_range.clone();
+                    let mut new_node = None;
+                    while let Some((node, containing_range)) =
+                        buffer.syntax_ancestor(new_range.clone())
                     {
-                        break;
+                        new_node = Some(node);
+                        new_range = match containing_range {
+                            MultiOrSingleBufferOffsetRange::Single(_) => break,
+                            MultiOrSingleBufferOffsetRange::Multi(range) => range,
+                        };
+                        if !display_map.intersects_fold(new_range.start)
+                            && !display_map.intersects_fold(new_range.end)
+                        {
+                            break;
+                        }
                     }
-                }
 
-                if let Some(node) = new_node {
-                    // Log the ancestor, to support using this action as a way to explore TreeSitter
-                    // nodes. Parent and grandparent are also logged because this operation will not
-                    // visit nodes that have the same range as their parent.
-                    log::info!("Node: {node:?}");
-                    let parent = node.parent();
-                    log::info!("Parent: {parent:?}");
-                    let grandparent = parent.and_then(|x| x.parent());
-                    log::info!("Grandparent: {grandparent:?}");
-                }
+                    if let Some(node) = new_node {
+                        // Log the ancestor, to support using this action as a way to explore TreeSitter
+                        // nodes. Parent and grandparent are also logged because this operation will not
+                        // visit nodes that have the same range as their parent.
+                        log::info!("Node: {node:?}");
+                        let parent = node.parent();
+                        log::info!("Parent: {parent:?}");
+                        let grandparent = parent.and_then(|x| x.parent());
+                        log::info!("Grandparent: {grandparent:?}");
+                    }
+                    new_range
+                });
 
                 selected_larger_node |= new_range != old_range;
                 Selection {
@@ -11245,6 +11481,93 @@ impl Editor {
         self.select_larger_syntax_node_stack = stack;
     }
 
+    /// Requests the selection ranges around each selection from language servers, keyed by
+    /// selection id. Returns `None` if no language server of the selected buffers provides them.
+    fn lsp_selection_ranges(
+        &self,
+        selections: &[Selection<usize>],
+        cx: &mut Context<Self>,
+    ) -> Option<Task<HashMap<usize, Vec<Range<Anchor>>>>> {
+        let provider = self.semantics_provider.clone()?;
+        let mut positions_by_buffer = HashMap::<BufferId, (Entity<Buffer>, Vec<_>)>::default();
+        {
+            let multi_buffer = self.buffer.read(cx);
+            let snapshot = multi_buffer.snapshot(cx);
+            for selection in selections {
+                let Some(excerpt) = snapshot.excerpt_containing(selection.range()) else {
+                    continue;
+                };
+                let Some(buffer) = multi_buffer.buffer(excerpt.buffer_id()) else {
+                    continue;
+                };
+                let position = snapshot.anchor_before(selection.head()).text_anchor;
+                positions_by_buffer
+                    .entry(excerpt.buffer_id())
+                    .or_insert_with(|| (buffer, Vec::new()))
+                    .1
+                    .push((selection.id, excerpt.id(), position));
+            }
+        }
+
+        let mut requests = Vec::new();
+        for (buffer, positions) in positions_by_buffer.into_values() {
+            if !provider.supports_selection_ranges(&buffer, cx) {
+                continue;
+            }
+            let Some(request) = provider.selection_ranges(
+                &buffer,
+                positions.iter().map(|(_, _, position)| *position).collect(),
+                cx,
+            ) else {
+                continue;
+            };
+            requests.push((positions, request));
+        }
+        if requests.is_empty() {
+            return None;
+        }
+
+        Some(cx.spawn(|editor, mut cx| async move {
+            let mut ranges_by_selection = HashMap::default();
+            for (positions, request) in requests {
+                let Some(selection_ranges) = request.await.log_err() else {
+                    continue;
+                };
+                let Ok(snapshot) =
+                    editor.update(&mut cx, |editor, cx| editor.buffer.read(cx).snapshot(cx))
+                else {
+                    break;
+                };
+                for ((selection_id, excerpt_id, _), ranges) in
+                    positions.into_iter().zip(selection_ranges)
+                {
+                    let (Some(buffer), Some(context)) = (
+                        snapshot.buffer_for_excerpt(excerpt_id),
+                        snapshot.context_range_for_excerpt(excerpt_id),
+                    ) else {
+                        continue;
+                    };
+                    let ranges = ranges
+                        .into_iter()
+                        // Ranges that extend past the excerpt can't be selected.
+                        .take_while(|range| {
+                            range.start.cmp(&context.start, buffer).is_ge()
+                                && range.end.cmp(&context.end, buffer).is_le()
+                        })
+                        .filter_map(|range| {
+                            Some(
+                                snapshot.anchor_in_excerpt(excerpt_id, range.start)?
+                                    ..snapshot.anchor_in_excerpt(excerpt_id, range.end)?,
+                            )
+                        })
+                        .collect();
+                    ranges_by_selection.insert(selection_id, ranges);
+                }
+            }
+            ranges_by_selection
+        }))
+    }
+
     pub fn select_smaller_syntax_node(
         &mut self,
         _: &SelectSmallerSyntaxNode,
@@ -13925,6 +14248,58 @@ impl Editor {
         self.do_stage_or_unstage_and_next(false, window, cx);
     }
 
+    pub fn stage_selected_lines(
+        &mut self,
+        _: &::git::StageSelectedLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(true, cx);
+    }
+
+    pub fn unstage_selected_lines(
+        &mut self,
+        _: &::git::UnstageSelectedLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(false, cx);
+    }
+
+    /// Stages or unstages only the selected lines of the hunks under the
+    /// selections, rather than the whole hunks.
+    pub fn stage_or_unstage_selected_lines(&mut self, stage: bool, cx: &mut Context<Self>) {
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(|this, mut cx| async move {
+            task.await?;
+            this.update(&mut cx, |this, cx| {
+                let snapshot = this.buffer.read(cx).snapshot(cx);
+                let mut rows_by_buffer = HashMap::<BufferId, Vec<Range<u32>>>::default();
+                for range in &ranges {
+                    for (buffer, range, _) in snapshot.range_to_buffer_ranges(range.clone()) {
+                        let start = buffer.offset_to_point(range.start);
+                        let end = buffer.offset_to_point(range.end);
+                        // A selection ending at the start of a line doesn't include that line.
+                        let end_row = if end.column == 0 && end.row > start.row {
+                            end.row
+                        } else {
+                            end.row + 1
+                        };
+                        rows_by_buffer
+                            .entry(buffer.remote_id())
+                            .or_default()
+                            .push(start.row..end_row);
+                    }
+                }
+                for (buffer_id, rows) in rows_by_buffer {
+                    this.do_stage_or_unstage_lines(stage, buffer_id, &rows, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn stage_or_unstage_diff_hunks(
         &mut self,
         stage: bool,
@@ -14058,6 +14433,23 @@ impl Editor {
         None
     }
 
+    fn do_stage_or_unstage_lines(
+        &self,
+        stage: bool,
+        buffer_id: BufferId,
+        rows: &[Range<u32>],
+        cx: &mut App,
+    ) -> Option<()> {
+        let project = self.project.as_ref()?;
+        let buffer = project.read(cx).buffer_for_id(buffer_id, cx)?;
+        let diff = self.buffer.read(cx).diff_for(buffer_id)?;
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        diff.update(cx, |diff, cx| {
+            diff.stage_or_unstage_lines(stage, rows, &buffer_snapshot, cx)
+        });
+        Some(())
+    }
+
     pub fn expand_selected_diff_hunks(&mut self, cx: &mut Context<Self>) {
         let ranges: Vec<_> = self.selections.disjoint.iter().map(|s| s.range()).collect();
         self.buffer
@@ -14510,6 +14902,11 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn set_show_breakpoints(&mut self, show_breakpoints: bool, cx: &mut Context<Self>) {
+        self.show_breakpoints = Some(show_breakpoints);
+        cx.notify();
+    }
+
     pub fn set_masked(&mut self, masked: bool, cx: &mut Context<Self>) {
         if self.display_map.read(cx).masked != masked {
             self.display_map.update(cx, |map, _| map.masked = masked);
@@ -15573,6 +15970,24 @@ impl Editor {
                     self.update_visible_inline_completion(window, cx);
                 }
                 if let Some(buffer) = buffer_edited {
+                    semantic_tokens::refresh_semantic_tokens(
+                        self,
+                        Some(buffer.clone()),
+                        Some(semantic_tokens::UPDATE_DEBOUNCE),
+                        cx,
+                    );
+                    folding_ranges::refresh_folding_ranges(
+                        self,
+                        Some(buffer.clone()),
+                        Some(folding_ranges::UPDATE_DEBOUNCE),
+                        cx,
+                    );
+                    git::conflicts::refresh_conflicts(
+                        self,
+                        Some(buffer.clone()),
+                        Some(git::conflicts::UPDATE_DEBOUNCE),
+                        cx,
+                    );
                     let buffer_id = buffer.read(cx).remote_id();
                     if !self.registered_buffers.contains_key(&buffer_id) {
                         if let Some(project) = self.project.as_ref() {
@@ -15649,12 +16064,18 @@ impl Editor {
                     excerpts: excerpts.clone(),
                 });
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                semantic_tokens::refresh_semantic_tokens(self, Some(buffer.clone()), None, cx);
+                folding_ranges::refresh_folding_ranges(self, Some(buffer.clone()), None, cx);
+                git::conflicts::refresh_conflicts(self, Some(buffer.clone()), None, cx);
             }
             multi_buffer::Event::ExcerptsRemoved { ids } => {
                 self.refresh_inlay_hints(InlayHintRefreshReason::ExcerptsRemoved(ids.clone()), cx);
                 let buffer = self.buffer.read(cx);
                 self.registered_buffers
                     .retain(|buffer_id, _| buffer.buffer(*buffer_id).is_some());
+                semantic_tokens::remove_stale_semantic_tokens(self, cx);
+                folding_ranges::remove_stale_folding_ranges(self, cx);
+                git::conflicts::remove_stale_conflicts(self, cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
                 cx.emit(EditorEvent::ExcerptsRemoved { ids: ids.clone() })
             }
@@ -15689,7 +16110,10 @@ impl Editor {
                 cx.notify();
             }
             multi_buffer::Event::DirtyChanged => cx.emit(EditorEvent::DirtyChanged),
-            multi_buffer::Event::Saved => cx.emit(EditorEvent::Saved),
+            multi_buffer::Event::Saved => {
+                self.write_undo_history_to_db(cx);
+                cx.emit(EditorEvent::Saved);
+            }
             multi_buffer::Event::FileHandleChanged
             | multi_buffer::Event::Reloaded
             | multi_buffer::Event::BufferDiffChanged => cx.emit(EditorEvent::TitleChanged),
@@ -15725,6 +16149,8 @@ impl Editor {
             )),
             cx,
         );
+        semantic_tokens::refresh_semantic_tokens(self, None, None, cx);
+        folding_ranges::refresh_folding_ranges(self, None, None, cx);
 
         let old_cursor_shape = self.cursor_shape;
 
@@ -16499,6 +16925,68 @@ impl Editor {
             }));
         });
     }
+
+    fn read_undo_history_from_db(&mut self, cx: &mut Context<Editor>) {
+        if self.mode != EditorMode::Full || !EditorSettings::get_global(cx).persistent_undo {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let Some(abs_path) = buffer
+            .read(cx)
+            .file()
+            .and_then(|file| Some(file.as_local()?.abs_path(cx)))
+        else {
+            return;
+        };
+
+        let snapshot = buffer.read(cx).snapshot();
+        cx.spawn(|_, mut cx| async move {
+            let history = cx
+                .background_spawn({
+                    let snapshot = snapshot.clone();
+                    async move { DB.get_undo_history(abs_path, snapshot.as_rope().chunks()) }
+                })
+                .await?;
+            let Some(history) = history else {
+                return Ok(());
+            };
+
+            buffer.update(&mut cx, |buffer, _| {
+                if buffer.version() == *snapshot.version() {
+                    buffer.restore_undo_history(&history);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Stores the undo history of the saved buffer, which is what it gets
+    /// validated against when it's restored.
+    fn write_undo_history_to_db(&self, cx: &mut Context<Editor>) {
+        if self.mode != EditorMode::Full || !EditorSettings::get_global(cx).persistent_undo {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let buffer = buffer.read(cx);
+        let Some(abs_path) = buffer
+            .file()
+            .and_then(|file| Some(file.as_local()?.abs_path(cx)))
+        else {
+            return;
+        };
+
+        let undo_history = buffer.undo_history_snapshot();
+        let snapshot = buffer.snapshot();
+        cx.background_spawn(async move {
+            DB.save_undo_history(abs_path, snapshot.as_rope().chunks(), undo_history.build())
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
 }
 
 fn insert_extra_newline_brackets(
@@ -16952,6 +17440,30 @@ pub trait SemanticsProvider {
         new_name: String,
         cx: &mut App,
     ) -> Option<Task<Result<ProjectTransaction>>>;
+
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
+
+    /// Returns, for each position, the ranges to expand a selection at that position to, from the
+    /// innermost to the outermost.
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>>;
+
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<DocumentLink>>>>;
+
+    fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>>;
 }
 
 pub trait CompletionProvider {
@@ -17412,6 +17924,44 @@ impl SemanticsProvider for Entity<Project> {
             project.perform_rename(buffer.clone(), position, new_name, cx)
         }))
     }
+
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.update(cx, |this, cx| {
+            buffer.update(cx, |buffer, cx| {
+                this.any_language_server_supports_selection_ranges(buffer, cx)
+            })
+        })
+    }
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        Some(self.update(cx, |project, cx| {
+            project.selection_ranges(buffer, positions, cx)
+        }))
+    }
+
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<DocumentLink>>>> {
+        Some(self.update(cx, |project, cx| project.document_links(buffer.clone(), cx)))
+    }
+
+    fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>> {
+        Some(self.update(cx, |project, cx| {
+            project.resolve_document_link(link, buffer, cx)
+        }))
+    }
 }
 
 fn inlay_hint_settings(
@@ -17624,6 +18174,8 @@ impl EditorSnapshot {
 
         let show_runnables = self.show_runnables.unwrap_or(gutter_settings.runnables);
 
+        let show_breakpoints = self.show_breakpoints.unwrap_or(gutter_settings.breakpoints);
+
         let git_blame_entries_width =
             self.git_blame_gutter_max_author_length
                 .map(|max_author_length| {
@@ -17642,7 +18194,7 @@ impl EditorSnapshot {
                 });
 
         let mut left_padding = git_blame_entries_width.unwrap_or(Pixels::ZERO);
-        left_padding += if show_code_actions || show_runnables {
+        left_padding += if show_code_actions || show_runnables || show_breakpoints {
             em_width * 3.0
         } else if show_git_gutter && show_line_numbers {
             em_width * 2.0
@@ -5747,6 +5747,96 @@ async fn test_select_previous_with_single_selection(cx: &mut TestAppContext) {
     cx.assert_editor_state("«abcˇ»\n«ˇabc» «abcˇ»\ndef«abcˇ»\n«abcˇ»");
 }
 
+#[gpui::test]
+async fn test_sticky_scopes(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::LANGUAGE.to_string()),
+        )
+        .with_outline_query(
+            r#"
+            (struct_item
+                "struct" @context
+                name: (_) @name) @item
+            (impl_item
+                "impl" @context
+                type: (_) @name) @item
+            (function_item
+                "fn" @context
+                name: (_) @name) @item
+            (mod_item
+                "mod" @context
+                name: (_) @name) @item
+            "#,
+        )
+        .unwrap(),
+    );
+
+    let text = r#"
+        mod outer {
+            struct Foo;
+
+            impl Foo {
+                fn bar() {
+                    let a = 1;
+                    let b = 2;
+                    let c = 3;
+                }
+            }
+        }
+    "#
+    .unindent();
+
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(language, cx));
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| build_editor(buffer, window, cx));
+
+    editor
+        .condition::<crate::EditorEvent>(cx, |editor, cx| !editor.buffer.read(cx).is_parsing(cx))
+        .await;
+
+    editor.update_in(cx, |editor, window, cx| {
+        let snapshot = editor.snapshot(window, cx);
+        let sticky_scopes = |top_row, max_depth| {
+            snapshot
+                .sticky_scopes(DisplayRow(top_row), max_depth, None)
+                .into_iter()
+                .map(|scope| (scope.item.text, scope.display_row.0, scope.end_row.0))
+                .collect::<Vec<_>>()
+        };
+
+        // Scopes whose first row is still visible aren't pinned.
+        assert!(sticky_scopes(0, 5).is_empty());
+        assert_eq!(sticky_scopes(1, 5), vec![("mod outer".to_string(), 0, 10)]);
+        assert_eq!(
+            sticky_scopes(4, 5),
+            vec![
+                ("mod outer".to_string(), 0, 10),
+                ("impl Foo".to_string(), 3, 9),
+                ("fn bar".to_string(), 4, 8),
+            ]
+        );
+        assert_eq!(
+            sticky_scopes(4, 2),
+            vec![
+                ("mod outer".to_string(), 0, 10),
+                ("impl Foo".to_string(), 3, 9)
+            ]
+        );
+        // Nested scopes that end above the row they'd cover aren't pinned.
+        assert_eq!(
+            sticky_scopes(8, 5),
+            vec![
+                ("mod outer".to_string(), 0, 10),
+                ("impl Foo".to_string(), 3, 9)
+            ]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_select_larger_smaller_syntax_node(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -5935,6 +6025,97 @@ async fn test_select_larger_smaller_syntax_node(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_syntax_node_with_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state(indoc! {"
+        fn a() {
+            let b = cˇ + 1;
+        }
+        fn d() {
+            eˇ();
+        }
+    "});
+
+    let range = |start: (u32, u32), end: (u32, u32)| {
+        lsp::Range::new(
+            lsp::Position::new(start.0, start.1),
+            lsp::Position::new(end.0, end.1),
+        )
+    };
+    cx.lsp
+        .handle_request::<lsp::request::SelectionRangeRequest, _, _>(move |params, _| async move {
+            Ok(Some(
+                params
+                    .positions
+                    .into_iter()
+                    .map(|position| match position.line {
+                        1 => lsp::SelectionRange {
+                            range: range((1, 12), (1, 13)),
+                            parent: Some(Box::new(lsp::SelectionRange {
+                                range: range((1, 12), (1, 17)),
+                                parent: None,
+                            })),
+                        },
+                        _ => lsp::SelectionRange {
+                            range: range((4, 4), (4, 7)),
+                            parent: None,
+                        },
+                    })
+                    .collect(),
+            ))
+        });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx)
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            let b = «cˇ» + 1;
+        }
+        fn d() {
+            «e()ˇ»;
+        }
+    "});
+
+    // Selections without a larger language server range fall back to syntax nodes.
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx)
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            let b = «c + 1ˇ»;
+        }
+        fn d() {
+            «e();ˇ»
+        }
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            let b = «cˇ» + 1;
+        }
+        fn d() {
+            «e()ˇ»;
+        }
+    "});
+}
+
 #[gpui::test]
 async fn test_fold_function_bodies(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
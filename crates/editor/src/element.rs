@@ -5,8 +5,8 @@ use crate::{
         Block, BlockContext, BlockStyle, DisplaySnapshot, HighlightedChunk, ToDisplayPoint,
     },
     editor_settings::{
-        CurrentLineHighlight, DoubleClickInMultibuffer, MultiCursorModifier, ScrollBeyondLastLine,
-        ScrollbarDiagnostics, ShowScrollbar,
+        CurrentLineHighlight, DoubleClickInMultibuffer, MinimapSide, MultiCursorModifier,
+        ScrollBeyondLastLine, ScrollbarDiagnostics, ShowMinimap, ShowScrollbar,
     },
     git::blame::GitBlame,
     hover_popover::{
@@ -15,7 +15,7 @@ use crate::{
     inlay_hint_settings,
     items::BufferSearchHighlights,
     mouse_context_menu::{self, MenuPosition, MouseContextMenu},
-    scroll::{axis_pair, scroll_amount::ScrollAmount, AxisPair},
+    scroll::{axis_pair, scroll_amount::ScrollAmount, Autoscroll, AxisPair},
     BlockId, ChunkReplacement, CursorShape, CustomBlockId, DisplayDiffHunk, DisplayPoint,
     DisplayRow, DocumentHighlightRead, DocumentHighlightWrite, EditDisplayMode, Editor, EditorMode,
     EditorSettings, EditorSnapshot, EditorStyle, FocusedBlock, GoToHunk, GoToPreviousHunk,
@@ -39,7 +39,8 @@ use gpui::{
     GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Keystroke, Length,
     ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
     ParentElement, Pixels, ScrollDelta, ScrollWheelEvent, ShapedLine, SharedString, Size,
-    StatefulInteractiveElement, Style, Styled, Subscription, TextRun, TextStyleRefinement, Window,
+    StatefulInteractiveElement, Style, Styled, StyledText, Subscription, TextRun,
+    TextStyleRefinement, Window,
 };
 use inline_completion::Direction;
 use itertools::Itertools;
@@ -332,6 +333,8 @@ impl EditorElement {
         register_action(editor, window, Editor::go_to_prev_diagnostic);
         register_action(editor, window, Editor::go_to_next_hunk);
         register_action(editor, window, Editor::go_to_prev_hunk);
+        register_action(editor, window, Editor::go_to_conflict);
+        register_action(editor, window, Editor::go_to_previous_conflict);
         register_action(editor, window, |editor, action, window, cx| {
             editor
                 .go_to_definition(action, window, cx)
@@ -388,6 +391,7 @@ impl EditorElement {
         register_action(editor, window, Editor::unfold_at);
         register_action(editor, window, Editor::fold_selected_ranges);
         register_action(editor, window, Editor::set_mark);
+        register_action(editor, window, Editor::toggle_breakpoint);
         register_action(editor, window, Editor::swap_selection_ends);
         register_action(editor, window, Editor::show_completions);
         register_action(editor, window, Editor::show_word_completions);
@@ -419,6 +423,12 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
         register_action(editor, window, Editor::unstage_and_next);
+        register_action(editor, window, Editor::stage_selected_lines);
+        register_action(editor, window, Editor::unstage_selected_lines);
+        register_action(editor, window, Editor::accept_ours);
+        register_action(editor, window, Editor::accept_theirs);
+        register_action(editor, window, Editor::accept_both);
+        register_action(editor, window, Editor::mark_conflicts_resolved);
         register_action(editor, window, Editor::expand_all_diff_hunks);
 
         register_action(editor, window, |editor, action, window, cx| {
@@ -1482,6 +1492,220 @@ impl EditorElement {
         axis_pair(horizontal_scrollbar, vertical_scrollbar)
     }
 
+    /// The width of the minimap, or `None` if it shouldn't be shown in an
+    /// editor of the given bounds.
+    fn minimap_width(
+        &self,
+        snapshot: &EditorSnapshot,
+        bounds: Bounds<Pixels>,
+        gutter_dimensions: GutterDimensions,
+        em_advance: Pixels,
+        line_height: Pixels,
+        cx: &App,
+    ) -> Option<Pixels> {
+        if snapshot.mode != EditorMode::Full || !self.editor.read(cx).show_scrollbars {
+            return None;
+        }
+
+        let settings = EditorSettings::get_global(cx).minimap;
+        match settings.show {
+            ShowMinimap::Never => return None,
+            ShowMinimap::Auto => {
+                let row_count = snapshot.max_point().row().next_row().as_f32();
+                if row_count <= bounds.size.height / line_height {
+                    return None;
+                }
+            }
+            ShowMinimap::Always => {}
+        }
+
+        let width = px(settings.width);
+        let remaining_columns =
+            (bounds.size.width - gutter_dimensions.width - width - self.style.scrollbar_width)
+                / em_advance;
+        (width > Pixels::ZERO && remaining_columns >= settings.min_editor_columns as f32)
+            .then_some(width)
+    }
+
+    fn layout_minimap(
+        &self,
+        bounds: Bounds<Pixels>,
+        snapshot: &EditorSnapshot,
+        scroll_top: f32,
+        height_in_lines: f32,
+        max_scroll_top: f32,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> MinimapLayout {
+        let row_height = MinimapLayout::ROW_HEIGHT;
+        let column_width = MinimapLayout::COLUMN_WIDTH;
+        let row_count = snapshot.max_point().row().next_row();
+        let minimap_rows = bounds.size.height / row_height;
+        let first_row =
+            minimap_first_row(scroll_top, max_scroll_top, row_count.as_f32(), minimap_rows);
+        let start_row = DisplayRow(first_row as u32);
+        let end_row = DisplayRow(cmp::min(
+            (first_row + minimap_rows).ceil() as u32,
+            row_count.0,
+        ));
+
+        let row_top = |row: f32| bounds.top() + (row - first_row) * row_height;
+        let rows_bounds = |rows: Range<DisplayRow>| {
+            Bounds::from_corners(
+                point(bounds.left(), row_top(rows.start.as_f32())),
+                point(
+                    bounds.right(),
+                    row_top(cmp::max(rows.end, rows.start.next_row()).as_f32()),
+                ),
+            )
+        };
+        let max_columns = (bounds.size.width / column_width) as u32;
+        let run_bounds = |columns: Range<u32>, top: Pixels| {
+            (columns.start < max_columns).then(|| {
+                let end = cmp::min(columns.end, max_columns);
+                Bounds::new(
+                    point(bounds.left() + columns.start as f32 * column_width, top),
+                    size((end - columns.start) as f32 * column_width, row_height),
+                )
+            })
+        };
+
+        let mut text = Vec::new();
+        let mut row = start_row.0;
+        let mut column = 0;
+        for chunk in snapshot.highlighted_chunks(start_row..end_row, true, &self.style) {
+            let color = chunk
+                .style
+                .and_then(|style| style.color)
+                .unwrap_or(self.style.text.color)
+                .opacity(MinimapLayout::TEXT_OPACITY);
+            for (ix, line) in chunk.text.split('\n').enumerate() {
+                if ix > 0 {
+                    row += 1;
+                    column = 0;
+                }
+                let top = row_top(row as f32);
+                let mut run_start = None;
+                for ch in line.chars() {
+                    if ch.is_whitespace() {
+                        if let Some(start) = run_start.take() {
+                            text.extend(run_bounds(start..column, top).map(|b| fill(b, color)));
+                        }
+                    } else if run_start.is_none() {
+                        run_start = Some(column);
+                    }
+                    column += 1;
+                }
+                if let Some(start) = run_start {
+                    text.extend(run_bounds(start..column, top).map(|b| fill(b, color)));
+                }
+            }
+        }
+
+        let start_anchor = if start_row == Default::default() {
+            Anchor::min()
+        } else {
+            snapshot
+                .buffer_snapshot
+                .anchor_before(DisplayPoint::new(start_row, 0).to_offset(snapshot, Bias::Left))
+        };
+        let end_anchor = if end_row > snapshot.max_point().row() {
+            Anchor::max()
+        } else {
+            snapshot
+                .buffer_snapshot
+                .anchor_before(DisplayPoint::new(end_row, 0).to_offset(snapshot, Bias::Right))
+        };
+
+        let mut highlights = self
+            .editor
+            .read(cx)
+            .background_highlights_in_range(
+                start_anchor..end_anchor,
+                &snapshot.display_snapshot,
+                cx.theme().colors(),
+            )
+            .into_iter()
+            .map(|(range, color)| {
+                fill(
+                    rows_bounds(range.start.row()..range.end.row().next_row()),
+                    color,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let player = cx.theme().players().local();
+        let selections = self.editor.update(cx, |editor, cx| {
+            editor
+                .selections
+                .disjoint_in_range::<Point>(start_anchor..end_anchor, cx)
+        });
+        for selection in selections {
+            let start = selection.start.to_display_point(&snapshot.display_snapshot);
+            let end = selection.end.to_display_point(&snapshot.display_snapshot);
+            let color = if selection.is_empty() {
+                player.cursor.opacity(MinimapLayout::TEXT_OPACITY)
+            } else {
+                player.selection
+            };
+            highlights.push(fill(rows_bounds(start.row()..end.row().next_row()), color));
+        }
+
+        let colors = cx.theme().colors();
+        let folded_buffers = self.editor.read(cx).folded_buffers(cx);
+        let diff_hunks = snapshot
+            .display_diff_hunks_for_rows(start_row..end_row, folded_buffers)
+            .map(|hunk| {
+                let (rows, color) = match hunk {
+                    DisplayDiffHunk::Folded { display_row } => (
+                        display_row..display_row.next_row(),
+                        colors.version_control_modified,
+                    ),
+                    DisplayDiffHunk::Unfolded {
+                        display_row_range,
+                        status,
+                        ..
+                    } => (
+                        display_row_range,
+                        match status.kind {
+                            DiffHunkStatusKind::Added => colors.version_control_added,
+                            DiffHunkStatusKind::Modified => colors.version_control_modified,
+                            DiffHunkStatusKind::Deleted => colors.version_control_deleted,
+                        },
+                    ),
+                };
+                let mut hunk_bounds = rows_bounds(rows);
+                hunk_bounds.size.width = MinimapLayout::DIFF_HUNK_WIDTH;
+                fill(hunk_bounds, color)
+            })
+            .collect();
+
+        let thumb_bounds = Bounds::new(
+            point(bounds.left(), row_top(scroll_top)),
+            size(bounds.size.width, height_in_lines * row_height),
+        );
+
+        // How far the thumb moves within the minimap when the editor scrolls
+        // by one row, which is less than a row whenever the minimap scrolls too.
+        let scroll_unit_size = if row_count.as_f32() > minimap_rows && max_scroll_top > 0. {
+            row_height * (1. - (row_count.as_f32() - minimap_rows) / max_scroll_top).max(0.1)
+        } else {
+            row_height
+        };
+
+        MinimapLayout {
+            hitbox: window.insert_hitbox(bounds, false),
+            first_row,
+            height_in_lines,
+            max_scroll_top,
+            scroll_unit_size,
+            thumb_bounds,
+            text,
+            highlights,
+            diff_hunks,
+        }
+    }
+
     fn prepaint_crease_toggles(
         &self,
         crease_toggles: &mut [Option<AnyElement>],
@@ -2029,6 +2253,7 @@ impl EditorElement {
         gutter_hitbox: &Hitbox,
         display_hunks: &[(DisplayDiffHunk, Option<Hitbox>)],
         snapshot: &EditorSnapshot,
+        breakpoint_rows: &HashSet<DisplayRow>,
         window: &mut Window,
         cx: &mut App,
     ) -> Vec<AnyElement> {
@@ -2095,6 +2320,9 @@ impl EditorElement {
                     {
                         return None;
                     }
+                    if breakpoint_rows.contains(&display_row) {
+                        return None;
+                    }
                     let button = editor.render_run_indicator(
                         &self.style,
                         Some(display_row) == active_task_indicator_row,
@@ -2195,6 +2423,39 @@ impl EditorElement {
         elements
     }
 
+    fn layout_breakpoints(
+        &self,
+        line_height: Pixels,
+        breakpoint_rows: &HashSet<DisplayRow>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        display_hunks: &[(DisplayDiffHunk, Option<Hitbox>)],
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        self.editor.update(cx, |editor, cx| {
+            breakpoint_rows
+                .iter()
+                .sorted()
+                .map(|display_row| {
+                    let button = editor.render_breakpoint(*display_row, cx);
+                    prepaint_gutter_button(
+                        button,
+                        *display_row,
+                        line_height,
+                        gutter_dimensions,
+                        scroll_pixel_position,
+                        gutter_hitbox,
+                        display_hunks,
+                        window,
+                        cx,
+                    )
+                })
+                .collect_vec()
+        })
+    }
+
     fn layout_code_actions_indicator(
         &self,
         line_height: Pixels,
@@ -3198,6 +3459,95 @@ impl EditorElement {
         header
     }
 
+    fn layout_sticky_scopes(
+        &self,
+        snapshot: &EditorSnapshot,
+        scroll_top: f32,
+        top_rows: u32,
+        hitbox: &Hitbox,
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        line_height: Pixels,
+        em_advance: Pixels,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        let settings = EditorSettings::get_global(cx).sticky_scroll;
+        if !settings.enabled || snapshot.mode != EditorMode::Full {
+            return Vec::new();
+        }
+
+        let scopes = snapshot.sticky_scopes(
+            DisplayRow(scroll_top as u32 + top_rows),
+            settings.max_depth,
+            Some(cx.theme().syntax()),
+        );
+        let scope_count = scopes.len();
+        scopes
+            .into_iter()
+            .enumerate()
+            .filter_map(|(ix, scope)| {
+                let pinned_row = top_rows as usize + ix;
+                // Push the header up once the end of its scope scrolls past it, hiding it
+                // rather than sliding it under the rows pinned above the scopes.
+                let scope_bottom = (scope.end_row.next_row().as_f32() - scroll_top) * line_height;
+                let y = (pinned_row as f32 * line_height).min(scope_bottom - line_height);
+                if top_rows > 0 && y < top_rows as f32 * line_height {
+                    return None;
+                }
+
+                let column = scope
+                    .item
+                    .range
+                    .start
+                    .to_display_point(&snapshot.display_snapshot)
+                    .column();
+                let indent = content_origin.x - hitbox.origin.x + column as f32 * em_advance
+                    - scroll_pixel_position.x;
+
+                let editor = self.editor.clone();
+                let position = scope.item.range.start;
+                let mut header = h_flex()
+                    .id(("sticky-scope", ix))
+                    .w(hitbox.size.width)
+                    .h(line_height)
+                    .pl(indent.max(Pixels::ZERO))
+                    .overflow_hidden()
+                    .bg(self.style.background)
+                    .when(ix + 1 == scope_count, |header| {
+                        header
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                    })
+                    .occlude()
+                    .cursor_pointer()
+                    .child(
+                        StyledText::new(scope.item.text)
+                            .with_default_highlights(&self.style.text, scope.item.highlight_ranges),
+                    )
+                    .on_click(move |_, window, cx| {
+                        editor.update(cx, |editor, cx| {
+                            // Keep the clicked item right below the scopes enclosing it.
+                            editor.change_selections(
+                                Some(Autoscroll::top_relative(pinned_row)),
+                                window,
+                                cx,
+                                |selections| selections.select_anchor_ranges([position..position]),
+                            );
+                        });
+                    })
+                    .into_any_element();
+
+                let size = size(
+                    AvailableSpace::Definite(hitbox.size.width),
+                    AvailableSpace::Definite(line_height),
+                );
+                header.prepaint_as_root(hitbox.origin + point(Pixels::ZERO, y), size, window, cx);
+                Some(header)
+            })
+            .collect()
+    }
+
     fn layout_cursor_popovers(
         &self,
         line_height: Pixels,
@@ -4503,6 +4853,10 @@ impl EditorElement {
                 test_indicator.paint(window, cx);
             }
 
+            for breakpoint in layout.breakpoints.iter_mut() {
+                breakpoint.paint(window, cx);
+            }
+
             if let Some(indicator) = layout.code_actions_indicator.as_mut() {
                 indicator.paint(window, cx);
             }
@@ -5045,6 +5399,114 @@ impl EditorElement {
         }
     }
 
+    fn paint_minimap(&mut self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
+        let Some(minimap) = layout.minimap.take() else {
+            return;
+        };
+        let hitbox = minimap.hitbox.clone();
+
+        window.paint_layer(hitbox.bounds, |window| {
+            window.with_content_mask(
+                Some(ContentMask {
+                    bounds: hitbox.bounds,
+                }),
+                |window| {
+                    window.paint_quad(fill(hitbox.bounds, self.style.background));
+                    for quad in minimap
+                        .highlights
+                        .into_iter()
+                        .chain(minimap.text)
+                        .chain(minimap.diff_hunks)
+                    {
+                        window.paint_quad(quad);
+                    }
+                    window.paint_quad(quad(
+                        minimap.thumb_bounds,
+                        Corners::default(),
+                        cx.theme().colors().scrollbar_thumb_background,
+                        Edges::default(),
+                        cx.theme().colors().scrollbar_thumb_border,
+                    ));
+                },
+            );
+        });
+
+        window.set_cursor_style(CursorStyle::Arrow, &hitbox);
+
+        let max_scroll_top = minimap.max_scroll_top;
+        let scroll_unit_size = minimap.scroll_unit_size;
+        window.on_mouse_event({
+            let editor = self.editor.clone();
+            let mut mouse_position = window.mouse_position();
+            move |event: &MouseMoveEvent, phase, window, cx| {
+                if phase == DispatchPhase::Capture {
+                    return;
+                }
+
+                editor.update(cx, |editor, cx| {
+                    if editor.scroll_manager.is_dragging_minimap() {
+                        if event.pressed_button == Some(MouseButton::Left) {
+                            let mut position = editor.scroll_position(cx);
+                            position.y += (event.position.y - mouse_position.y) / scroll_unit_size;
+                            position.y = position.y.clamp(0., max_scroll_top);
+                            editor.set_scroll_position(position, window, cx);
+                            cx.stop_propagation();
+                        } else {
+                            editor.scroll_manager.set_is_dragging_minimap(false, cx);
+                        }
+                    }
+                    mouse_position = event.position;
+                })
+            }
+        });
+
+        if self.editor.read(cx).scroll_manager.is_dragging_minimap() {
+            window.on_mouse_event({
+                let editor = self.editor.clone();
+                move |_: &MouseUpEvent, phase, _, cx| {
+                    if phase == DispatchPhase::Capture {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_is_dragging_minimap(false, cx);
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        } else {
+            let first_row = minimap.first_row;
+            let height_in_lines = minimap.height_in_lines;
+            let thumb_bounds = minimap.thumb_bounds;
+            window.on_mouse_event({
+                let editor = self.editor.clone();
+                move |event: &MouseDownEvent, phase, window, cx| {
+                    if phase == DispatchPhase::Capture
+                        || event.button != MouseButton::Left
+                        || !hitbox.is_hovered(window)
+                    {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_is_dragging_minimap(true, cx);
+
+                        // Clicking outside of the visible region centers it on the clicked row.
+                        if !thumb_bounds.contains(&event.position) {
+                            let row = first_row
+                                + (event.position.y - hitbox.top()) / MinimapLayout::ROW_HEIGHT;
+                            let mut position = editor.scroll_position(cx);
+                            position.y = (row - height_in_lines / 2.).clamp(0., max_scroll_top);
+                            editor.set_scroll_position(position, window, cx);
+                        }
+
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        }
+    }
+
     fn collect_fast_scrollbar_markers(
         &self,
         layout: &EditorLayout,
@@ -5375,6 +5837,10 @@ impl EditorElement {
             let position_map = layout.position_map.clone();
             let editor = self.editor.clone();
             let hitbox = layout.hitbox.clone();
+            let minimap_hitbox = layout
+                .minimap
+                .as_ref()
+                .map(|minimap| minimap.hitbox.clone());
             let mut delta = ScrollDelta::default();
 
             // Set a minimum scroll_sensitivity of 0.01 to make sure the user doesn't
@@ -5382,7 +5848,11 @@ impl EditorElement {
             let scroll_sensitivity = EditorSettings::get_global(cx).scroll_sensitivity.max(0.01);
 
             move |event: &ScrollWheelEvent, phase, window, cx| {
-                if phase == DispatchPhase::Bubble && hitbox.is_hovered(window) {
+                let is_hovered = hitbox.is_hovered(window)
+                    || minimap_hitbox
+                        .as_ref()
+                        .map_or(false, |minimap_hitbox| minimap_hitbox.is_hovered(window));
+                if phase == DispatchPhase::Bubble && is_hovered {
                     delta = delta.coalesce(event.delta);
                     editor.update(cx, |editor, cx| {
                         let position_map: &PositionMap = &position_map;
@@ -6617,6 +7087,28 @@ impl Element for EditorElement {
                             cx,
                         )
                         .unwrap_or_default();
+
+                    // The minimap takes its space from the editor's bounds, and everything
+                    // else is laid out in what remains.
+                    let element_bounds = bounds;
+                    let minimap_width = self.minimap_width(
+                        &snapshot,
+                        bounds,
+                        gutter_dimensions,
+                        em_advance,
+                        line_height,
+                        cx,
+                    );
+                    let (bounds, minimap_bounds) = match minimap_width {
+                        Some(minimap_width) => {
+                            let side = EditorSettings::get_global(cx).minimap.side;
+                            let (text_bounds, minimap_bounds) =
+                                split_minimap_bounds(bounds, minimap_width, side);
+                            (text_bounds, Some(minimap_bounds))
+                        }
+                        None => (bounds, None),
+                    };
+
                     let text_width = bounds.size.width - gutter_dimensions.width;
 
                     let editor_width =
@@ -7019,7 +7511,7 @@ impl Element for EditorElement {
                             self.editor.update(cx, |editor, cx| {
                                 editor.resize_blocks(resized_blocks, autoscroll_request, cx)
                             });
-                            return self.prepaint(None, bounds, &mut (), window, cx);
+                            return self.prepaint(None, element_bounds, &mut (), window, cx);
                         }
                     };
 
@@ -7086,6 +7578,26 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let sticky_scopes = window.with_element_namespace("sticky_scopes", |window| {
+                        self.layout_sticky_scopes(
+                            &snapshot,
+                            scroll_position.y,
+                            // Scopes are pinned below the sticky buffer header of multibuffers.
+                            if sticky_buffer_header.is_some() {
+                                FILE_HEADER_HEIGHT
+                            } else {
+                                0
+                            },
+                            &hitbox,
+                            content_origin,
+                            scroll_pixel_position,
+                            line_height,
+                            em_advance,
+                            window,
+                            cx,
+                        )
+                    });
+
                     let crease_trailers =
                         window.with_element_namespace("crease_trailers", |window| {
                             self.prepaint_crease_trailers(
@@ -7259,8 +7771,31 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let minimap = minimap_bounds.map(|minimap_bounds| {
+                        self.layout_minimap(
+                            minimap_bounds,
+                            &snapshot,
+                            scroll_position.y,
+                            height_in_lines,
+                            max_scroll_top,
+                            window,
+                            cx,
+                        )
+                    });
+
                     let gutter_settings = EditorSettings::get_global(cx).gutter;
 
+                    let show_breakpoints = snapshot
+                        .show_breakpoints
+                        .unwrap_or(gutter_settings.breakpoints);
+                    let breakpoint_rows = if show_breakpoints {
+                        self.editor
+                            .read(cx)
+                            .breakpoint_rows(start_row..end_row, &snapshot, cx)
+                    } else {
+                        HashSet::default()
+                    };
+
                     let mut code_actions_indicator = None;
                     if let Some(newest_selection_head) = newest_selection_head {
                         let newest_selection_point =
@@ -7309,7 +7844,13 @@ impl Element for EditorElement {
                                             )
                                             .is_some_and(|row_info| row_info.expand_info.is_some());
 
-                                        if !has_test_indicator && !has_expand_indicator {
+                                        let has_breakpoint =
+                                            breakpoint_rows.contains(&newest_selection_head.row());
+
+                                        if !has_test_indicator
+                                            && !has_expand_indicator
+                                            && !has_breakpoint
+                                        {
                                             code_actions_indicator = self
                                                 .layout_code_actions_indicator(
                                                     line_height,
@@ -7348,6 +7889,7 @@ impl Element for EditorElement {
                             &gutter_hitbox,
                             &display_hunks,
                             &snapshot,
+                            &breakpoint_rows,
                             window,
                             cx,
                         )
@@ -7355,6 +7897,17 @@ impl Element for EditorElement {
                         Vec::new()
                     };
 
+                    let breakpoints = self.layout_breakpoints(
+                        line_height,
+                        &breakpoint_rows,
+                        scroll_pixel_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &display_hunks,
+                        window,
+                        cx,
+                    );
+
                     self.layout_signature_help(
                         &hitbox,
                         content_origin,
@@ -7486,6 +8039,7 @@ impl Element for EditorElement {
                         display_hunks,
                         content_origin,
                         scrollbars_layout,
+                        minimap,
                         active_rows,
                         highlighted_rows,
                         highlighted_ranges,
@@ -7504,12 +8058,14 @@ impl Element for EditorElement {
                         diff_hunk_controls,
                         mouse_context_menu,
                         test_indicators,
+                        breakpoints,
                         code_actions_indicator,
                         crease_toggles,
                         crease_trailers,
                         tab_invisible,
                         space_invisible,
                         sticky_buffer_header,
+                        sticky_scopes,
                         expand_toggles,
                     }
                 })
@@ -7577,7 +8133,14 @@ impl Element for EditorElement {
                         }
                     });
 
+                    window.with_element_namespace("sticky_scopes", |window| {
+                        for sticky_scope in &mut layout.sticky_scopes {
+                            sticky_scope.paint(window, cx);
+                        }
+                    });
+
                     self.paint_scrollbars(layout, window, cx);
+                    self.paint_minimap(layout, window, cx);
                     self.paint_inline_completion_popover(layout, window, cx);
                     self.paint_mouse_context_menu(layout, window, cx);
                 });
@@ -7596,6 +8159,49 @@ pub(super) fn gutter_bounds(
     }
 }
 
+fn split_minimap_bounds(
+    editor_bounds: Bounds<Pixels>,
+    minimap_width: Pixels,
+    side: MinimapSide,
+) -> (Bounds<Pixels>, Bounds<Pixels>) {
+    let text_size = size(
+        editor_bounds.size.width - minimap_width,
+        editor_bounds.size.height,
+    );
+    let minimap_size = size(minimap_width, editor_bounds.size.height);
+    match side {
+        MinimapSide::Left => (
+            Bounds::new(
+                editor_bounds.origin + point(minimap_width, Pixels::ZERO),
+                text_size,
+            ),
+            Bounds::new(editor_bounds.origin, minimap_size),
+        ),
+        MinimapSide::Right => (
+            Bounds::new(editor_bounds.origin, text_size),
+            Bounds::new(
+                editor_bounds.origin + point(text_size.width, Pixels::ZERO),
+                minimap_size,
+            ),
+        ),
+    }
+}
+
+/// The fractional display row shown at the top of the minimap. Buffers with
+/// more rows than the minimap fits scroll it in proportion to the editor, so
+/// both reach the end of the buffer together.
+fn minimap_first_row(
+    scroll_top: f32,
+    max_scroll_top: f32,
+    row_count: f32,
+    minimap_rows: f32,
+) -> f32 {
+    if row_count <= minimap_rows || max_scroll_top <= 0. {
+        return 0.;
+    }
+    (scroll_top / max_scroll_top).clamp(0., 1.) * (row_count - minimap_rows)
+}
+
 struct ScrollbarRangeData {
     scrollbar_bounds: Bounds<Pixels>,
     scroll_range: Bounds<Pixels>,
@@ -7662,6 +8268,7 @@ pub struct EditorLayout {
     gutter_hitbox: Hitbox,
     content_origin: gpui::Point<Pixels>,
     scrollbars_layout: AxisPair<Option<ScrollbarLayout>>,
+    minimap: Option<MinimapLayout>,
     mode: EditorMode,
     wrap_guides: SmallVec<[(Pixels, bool); 2]>,
     indent_guides: Option<Vec<IndentGuideLayout>>,
@@ -7683,6 +8290,7 @@ pub struct EditorLayout {
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     code_actions_indicator: Option<AnyElement>,
     test_indicators: Vec<AnyElement>,
+    breakpoints: Vec<AnyElement>,
     crease_toggles: Vec<Option<AnyElement>>,
     expand_toggles: Vec<Option<(AnyElement, gpui::Point<Pixels>)>>,
     diff_hunk_controls: Vec<AnyElement>,
@@ -7692,6 +8300,7 @@ pub struct EditorLayout {
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
     sticky_buffer_header: Option<AnyElement>,
+    sticky_scopes: Vec<AnyElement>,
 }
 
 impl EditorLayout {
@@ -7835,6 +8444,27 @@ impl ScrollbarLayout {
     }
 }
 
+struct MinimapLayout {
+    hitbox: Hitbox,
+    /// The fractional display row at the top of the minimap.
+    first_row: f32,
+    height_in_lines: f32,
+    max_scroll_top: f32,
+    scroll_unit_size: Pixels,
+    /// The region of the minimap that is visible in the editor.
+    thumb_bounds: Bounds<Pixels>,
+    text: Vec<PaintQuad>,
+    highlights: Vec<PaintQuad>,
+    diff_hunks: Vec<PaintQuad>,
+}
+
+impl MinimapLayout {
+    const ROW_HEIGHT: Pixels = px(2.0);
+    const COLUMN_WIDTH: Pixels = px(1.0);
+    const DIFF_HUNK_WIDTH: Pixels = px(3.0);
+    const TEXT_OPACITY: f32 = 0.6;
+}
+
 struct CreaseTrailerLayout {
     element: AnyElement,
     bounds: Bounds<Pixels>,
@@ -8304,12 +8934,14 @@ mod tests {
     use super::*;
     use crate::{
         display_map::{BlockPlacement, BlockProperties},
+        editor_settings::MinimapContent,
         editor_tests::{init_test, update_test_language_settings},
         Editor, MultiBuffer,
     };
     use gpui::{TestAppContext, VisualTestContext};
     use language::language_settings;
     use log::info;
+    use settings::SettingsStore;
     use std::num::NonZeroU32;
     use util::test::sample_text;
 
@@ -8530,6 +9162,61 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_minimap_layout(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.minimap = Some(MinimapContent {
+                        show: Some(ShowMinimap::Always),
+                        side: Some(MinimapSide::Right),
+                        width: Some(100.),
+                        min_editor_columns: Some(20),
+                    });
+                });
+            });
+        });
+
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple(&sample_text(100, 6, 'a'), cx);
+            Editor::new(EditorMode::Full, buffer, None, window, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|_, cx| editor.read(cx).style().unwrap().clone());
+
+        let (_, state) = cx.draw(point(px(0.), px(0.)), size(px(800.), px(400.)), |_, _| {
+            EditorElement::new(&editor, style.clone())
+        });
+        let minimap = state.minimap.as_ref().expect("minimap should be shown");
+        assert_eq!(
+            minimap.hitbox.bounds,
+            Bounds::new(point(px(700.), px(0.)), size(px(100.), px(400.)))
+        );
+        assert_eq!(state.hitbox.bounds.size.width, px(700.));
+        assert_eq!(minimap.first_row, 0.);
+        assert!(!minimap.text.is_empty());
+
+        // Editors too narrow to fit the minimum number of columns besides it don't show the minimap.
+        let (_, state) = cx.draw(point(px(0.), px(0.)), size(px(200.), px(400.)), |_, _| {
+            EditorElement::new(&editor, style.clone())
+        });
+        assert!(state.minimap.is_none());
+        assert_eq!(state.hitbox.bounds.size.width, px(200.));
+    }
+
+    #[test]
+    fn test_minimap_first_row() {
+        // Buffers that fit in the minimap never scroll it.
+        assert_eq!(minimap_first_row(10., 40., 50., 100.), 0.);
+        // Otherwise the minimap scrolls in proportion to the editor.
+        assert_eq!(minimap_first_row(0., 400., 500., 100.), 0.);
+        assert_eq!(minimap_first_row(200., 400., 500., 100.), 200.);
+        assert_eq!(minimap_first_row(400., 400., 500., 100.), 400.);
+        assert_eq!(minimap_first_row(600., 400., 500., 100.), 400.);
+    }
+
     #[gpui::test]
     fn test_all_invisibles_drawing(cx: &mut TestAppContext) {
         const TAB_SIZE: u32 = 4;
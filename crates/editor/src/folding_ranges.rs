@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use collections::HashSet;
+use gpui::{Context, Entity};
+use language::{language_settings::language_settings, Buffer};
+use text::{BufferId, ToPoint as _};
+use util::ResultExt;
+
+use crate::{display_map::Crease, Editor, EditorMode};
+
+pub(super) const UPDATE_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Queries the folding ranges of the given buffer, or of all buffers in the editor, and replaces
+/// the buffer's creases with them once they arrive.
+pub(super) fn refresh_folding_ranges(
+    editor: &mut Editor,
+    for_buffer: Option<Entity<Buffer>>,
+    debounce: Option<Duration>,
+    cx: &mut Context<Editor>,
+) {
+    if editor.mode != EditorMode::Full {
+        return;
+    }
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+
+    let buffers = match for_buffer {
+        Some(buffer) => vec![buffer],
+        None => editor.buffer.read(cx).all_buffers().into_iter().collect(),
+    };
+    for buffer in buffers {
+        let buffer_id = buffer.read(cx).remote_id();
+        if !lsp_folding_ranges_enabled(&buffer, cx) {
+            editor.folding_ranges_tasks.remove(&buffer_id);
+            remove_folding_range_creases(editor, buffer_id, cx);
+            continue;
+        }
+
+        let project = project.downgrade();
+        let task = cx.spawn(|editor, mut cx| async move {
+            if let Some(debounce) = debounce {
+                cx.background_executor().timer(debounce).await;
+            }
+
+            let Some(ranges_task) = project
+                .update(&mut cx, |project, cx| {
+                    project.folding_ranges(buffer.clone(), cx)
+                })
+                .ok()
+            else {
+                return;
+            };
+            let Some(ranges) = ranges_task.await.log_err() else {
+                return;
+            };
+
+            editor
+                .update(&mut cx, |editor, cx| {
+                    let buffer_snapshot = buffer.read(cx).snapshot();
+                    let multi_buffer = editor.buffer.read(cx);
+                    let excerpts = multi_buffer.excerpts_for_buffer(buffer_id, cx);
+                    let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+                    let placeholder = editor.display_map.read(cx).fold_placeholder.clone();
+
+                    let mut creases = Vec::new();
+                    for (excerpt_id, excerpt_range) in excerpts {
+                        let context_start = excerpt_range.context.start.to_point(&buffer_snapshot);
+                        let context_end = excerpt_range.context.end.to_point(&buffer_snapshot);
+                        // Only the outermost range starting on each row can be toggled.
+                        let mut folded_rows = HashSet::default();
+                        for range in &ranges {
+                            let start = range.range.start.to_point(&buffer_snapshot);
+                            let end = range.range.end.to_point(&buffer_snapshot);
+                            if start < context_start
+                                || end > context_end
+                                || start >= end
+                                || !folded_rows.insert(start.row)
+                            {
+                                continue;
+                            }
+                            let (Some(start), Some(end)) = (
+                                multi_buffer_snapshot
+                                    .anchor_in_excerpt(excerpt_id, range.range.start),
+                                multi_buffer_snapshot
+                                    .anchor_in_excerpt(excerpt_id, range.range.end),
+                            ) else {
+                                continue;
+                            };
+                            creases.push(Crease::simple(start..end, placeholder.clone()));
+                        }
+                    }
+
+                    remove_folding_range_creases(editor, buffer_id, cx);
+                    let crease_ids = editor.insert_creases(creases, cx);
+                    editor.folding_range_creases.insert(buffer_id, crease_ids);
+                    cx.notify();
+                })
+                .ok();
+        });
+        editor.folding_ranges_tasks.insert(buffer_id, task);
+    }
+}
+
+/// Drops the folding ranges of buffers that are no longer part of the editor.
+pub(super) fn remove_stale_folding_ranges(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let multi_buffer = editor.buffer.read(cx);
+    let stale_buffers = editor
+        .folding_range_creases
+        .keys()
+        .copied()
+        .filter(|buffer_id| multi_buffer.buffer(*buffer_id).is_none())
+        .collect::<Vec<_>>();
+    for buffer_id in stale_buffers {
+        editor.folding_ranges_tasks.remove(&buffer_id);
+        remove_folding_range_creases(editor, buffer_id, cx);
+    }
+}
+
+fn lsp_folding_ranges_enabled(buffer: &Entity<Buffer>, cx: &Context<Editor>) -> bool {
+    let buffer = buffer.read(cx);
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .lsp_folding_ranges
+}
+
+fn remove_folding_range_creases(
+    editor: &mut Editor,
+    buffer_id: BufferId,
+    cx: &mut Context<Editor>,
+) {
+    if let Some(crease_ids) = editor.folding_range_creases.remove(&buffer_id) {
+        editor.remove_creases(crease_ids, cx);
+        cx.notify();
+    }
+}
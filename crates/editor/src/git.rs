@@ -1 +1,2 @@
 pub mod blame;
+pub mod conflicts;
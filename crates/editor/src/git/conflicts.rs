@@ -0,0 +1,798 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use collections::HashSet;
+use git::{status::FileStatus, AcceptBoth, AcceptOurs, AcceptTheirs, MarkResolved};
+use gpui::{Entity, WeakEntity};
+use language::Buffer;
+use multi_buffer::{Anchor, ToPoint as _};
+use text::{BufferId, BufferSnapshot, ToOffset as _};
+use ui::{prelude::*, Tooltip};
+
+use crate::{
+    actions::{GoToConflict, GoToPreviousConflict},
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    scroll::Autoscroll,
+    Direction, Editor, EditorMode,
+};
+
+pub(crate) const UPDATE_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// A region of a buffer delimited by git's conflict markers:
+///
+/// ```text
+/// <<<<<<< ours
+/// our lines
+/// ||||||| base
+/// the common ancestor's lines, with the diff3 conflict style
+/// =======
+/// their lines
+/// >>>>>>> theirs
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictRegion {
+    /// The whole region, from the start of the `<<<<<<<` line to the end of the `>>>>>>>` line.
+    pub range: Range<text::Anchor>,
+    pub ours: Range<text::Anchor>,
+    pub base: Option<Range<text::Anchor>>,
+    pub theirs: Range<text::Anchor>,
+    /// The name following the `<<<<<<<` marker, usually the branch being merged into.
+    pub ours_name: SharedString,
+    /// The name following the `>>>>>>>` marker, usually the branch being merged.
+    pub theirs_name: SharedString,
+}
+
+/// Which side of a conflict to keep when resolving it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictResolution {
+    Ours,
+    Theirs,
+    Both,
+}
+
+/// The conflicts found in one of the editor's buffers, and the blocks showing their buttons.
+#[derive(Default)]
+pub(crate) struct BufferConflicts {
+    regions: Vec<ConflictRegion>,
+    block_ids: HashSet<CustomBlockId>,
+}
+
+struct ConflictOursHighlight;
+struct ConflictBaseHighlight;
+struct ConflictTheirsHighlight;
+
+/// Parses the conflict markers of a buffer into the regions they delimit, ignoring unterminated
+/// regions.
+pub fn parse_conflicts(snapshot: &BufferSnapshot) -> Vec<ConflictRegion> {
+    struct PendingConflict {
+        start: usize,
+        ours_name: SharedString,
+        ours_start: usize,
+        ours_end: Option<usize>,
+        base_start: Option<usize>,
+        base_end: Option<usize>,
+        theirs_start: Option<usize>,
+    }
+
+    fn marker_name<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+        let name = line.strip_prefix(marker)?;
+        (name.is_empty() || name.starts_with(char::is_whitespace)).then(|| name.trim())
+    }
+
+    let anchor_range =
+        |range: Range<usize>| snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+
+    let mut regions = Vec::new();
+    let mut pending: Option<PendingConflict> = None;
+    let mut offset = 0;
+    for line in snapshot.text().split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        if let Some(name) = marker_name(line, "<<<<<<<") {
+            pending = Some(PendingConflict {
+                start: line_start,
+                ours_name: name.to_string().into(),
+                ours_start: offset,
+                ours_end: None,
+                base_start: None,
+                base_end: None,
+                theirs_start: None,
+            });
+            continue;
+        }
+
+        let Some(conflict) = pending.as_mut() else {
+            continue;
+        };
+        if conflict.theirs_start.is_none() {
+            if marker_name(line, "|||||||").is_some() && conflict.base_start.is_none() {
+                conflict.ours_end = Some(line_start);
+                conflict.base_start = Some(offset);
+            } else if line.trim_end() == "=======" {
+                if conflict.base_start.is_some() {
+                    conflict.base_end = Some(line_start);
+                } else {
+                    conflict.ours_end = Some(line_start);
+                }
+                conflict.theirs_start = Some(offset);
+            }
+        } else if let Some(name) = marker_name(line, ">>>>>>>") {
+            let Some(conflict) = pending.take() else {
+                continue;
+            };
+            let (Some(ours_end), Some(theirs_start)) = (conflict.ours_end, conflict.theirs_start)
+            else {
+                continue;
+            };
+            regions.push(ConflictRegion {
+                range: anchor_range(conflict.start..offset),
+                ours: anchor_range(conflict.ours_start..ours_end),
+                base: conflict
+                    .base_start
+                    .zip(conflict.base_end)
+                    .map(|(start, end)| anchor_range(start..end)),
+                theirs: anchor_range(theirs_start..line_start),
+                ours_name: conflict.ours_name,
+                theirs_name: name.to_string().into(),
+            });
+        }
+    }
+    regions
+}
+
+/// Parses the conflicts of the given buffer, or of all buffers in the editor, if git reports them
+/// as conflicted, and shows them once parsed.
+pub(crate) fn refresh_conflicts(
+    editor: &mut Editor,
+    for_buffer: Option<Entity<Buffer>>,
+    debounce: Option<Duration>,
+    cx: &mut Context<Editor>,
+) {
+    if editor.mode != EditorMode::Full {
+        return;
+    }
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+
+    let buffers = match for_buffer {
+        Some(buffer) => vec![buffer],
+        None => editor.buffer.read(cx).all_buffers().into_iter().collect(),
+    };
+    for buffer in buffers {
+        let buffer_id = buffer.read(cx).remote_id();
+        let is_conflicted = project
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .status_for_buffer_id(buffer_id, cx)
+            .is_some_and(FileStatus::is_conflicted);
+        if !is_conflicted {
+            editor.conflicts_tasks.remove(&buffer_id);
+            if editor.conflicts.contains_key(&buffer_id) {
+                set_conflicts(editor, buffer_id, Vec::new(), cx);
+            }
+            continue;
+        }
+
+        let task = cx.spawn(|editor, mut cx| async move {
+            if let Some(debounce) = debounce {
+                cx.background_executor().timer(debounce).await;
+            }
+
+            let Ok(snapshot) = buffer.read_with(&cx, |buffer, _| buffer.text_snapshot()) else {
+                return;
+            };
+            let regions = cx
+                .background_executor()
+                .spawn(async move { parse_conflicts(&snapshot) })
+                .await;
+            editor
+                .update(&mut cx, |editor, cx| {
+                    set_conflicts(editor, buffer_id, regions, cx)
+                })
+                .ok();
+        });
+        editor.conflicts_tasks.insert(buffer_id, task);
+    }
+}
+
+pub(crate) fn remove_stale_conflicts(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let multi_buffer = editor.buffer.read(cx);
+    let stale_buffers = editor
+        .conflicts
+        .keys()
+        .copied()
+        .filter(|buffer_id| multi_buffer.buffer(*buffer_id).is_none())
+        .collect::<Vec<_>>();
+    for buffer_id in stale_buffers {
+        editor.conflicts_tasks.remove(&buffer_id);
+        set_conflicts(editor, buffer_id, Vec::new(), cx);
+    }
+}
+
+fn set_conflicts(
+    editor: &mut Editor,
+    buffer_id: BufferId,
+    regions: Vec<ConflictRegion>,
+    cx: &mut Context<Editor>,
+) {
+    if let Some(previous) = editor.conflicts.remove(&buffer_id) {
+        editor.remove_blocks(previous.block_ids, None, cx);
+    }
+
+    if !regions.is_empty() {
+        let editor_handle = cx.entity().downgrade();
+        let blocks = regions
+            .iter()
+            .flat_map(|region| {
+                ranges_in_excerpts(editor, buffer_id, &region.range, cx)
+                    .into_iter()
+                    .map(|range| BlockProperties {
+                        placement: BlockPlacement::Above(range.start),
+                        height: 1,
+                        style: BlockStyle::Fixed,
+                        render: Arc::new({
+                            let editor = editor_handle.clone();
+                            let region = region.clone();
+                            move |cx: &mut BlockContext| {
+                                render_conflict_buttons(&editor, buffer_id, &region, cx)
+                            }
+                        }),
+                        priority: 0,
+                    })
+            })
+            .collect::<Vec<_>>();
+        let block_ids = editor.insert_blocks(blocks, None, cx).into_iter().collect();
+        editor
+            .conflicts
+            .insert(buffer_id, BufferConflicts { regions, block_ids });
+    }
+
+    highlight_conflicts(editor, cx);
+}
+
+/// Maps a range of a buffer into each of the editor's excerpts that shows its start.
+fn ranges_in_excerpts(
+    editor: &Editor,
+    buffer_id: BufferId,
+    range: &Range<text::Anchor>,
+    cx: &App,
+) -> Vec<Range<Anchor>> {
+    let multi_buffer = editor.buffer.read(cx);
+    let Some(buffer) = multi_buffer.buffer(buffer_id) else {
+        return Vec::new();
+    };
+    let buffer_snapshot = buffer.read(cx).text_snapshot();
+    let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+    let start = range.start.to_offset(&buffer_snapshot);
+    multi_buffer
+        .excerpts_for_buffer(buffer_id, cx)
+        .into_iter()
+        .filter(|(_, excerpt_range)| {
+            excerpt_range.context.start.to_offset(&buffer_snapshot) <= start
+                && start <= excerpt_range.context.end.to_offset(&buffer_snapshot)
+        })
+        .filter_map(|(excerpt_id, _)| {
+            Some(
+                multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, range.start)?
+                    ..multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, range.end)?,
+            )
+        })
+        .collect()
+}
+
+fn highlight_conflicts(editor: &mut Editor, cx: &mut Context<Editor>) {
+    editor.clear_row_highlights::<ConflictOursHighlight>();
+    editor.clear_row_highlights::<ConflictBaseHighlight>();
+    editor.clear_row_highlights::<ConflictTheirsHighlight>();
+
+    let status = cx.theme().status();
+    let ours_color = status.info_background;
+    let base_color = status.conflict_background;
+    let theirs_color = status.success_background;
+
+    let regions = editor
+        .conflicts
+        .iter()
+        .flat_map(|(buffer_id, conflicts)| {
+            conflicts
+                .regions
+                .iter()
+                .map(|region| (*buffer_id, region.clone()))
+        })
+        .collect::<Vec<_>>();
+    for (buffer_id, region) in regions {
+        // The marker lines delimiting each side are highlighted along with it, except for the
+        // separators, as ranges ending at the start of a line don't highlight that line.
+        let ours = region.range.start..region.ours.end;
+        for range in ranges_in_excerpts(editor, buffer_id, &ours, cx) {
+            editor.highlight_rows::<ConflictOursHighlight>(range, ours_color, false, cx);
+        }
+        if let Some(base) = &region.base {
+            for range in ranges_in_excerpts(editor, buffer_id, base, cx) {
+                editor.highlight_rows::<ConflictBaseHighlight>(range, base_color, false, cx);
+            }
+        }
+        let theirs = region.theirs.start..region.range.end;
+        for range in ranges_in_excerpts(editor, buffer_id, &theirs, cx) {
+            editor.highlight_rows::<ConflictTheirsHighlight>(range, theirs_color, false, cx);
+        }
+    }
+    cx.notify();
+}
+
+fn render_conflict_buttons(
+    editor: &WeakEntity<Editor>,
+    buffer_id: BufferId,
+    region: &ConflictRegion,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let button = |label: String, resolution: ConflictResolution| {
+        let editor = editor.clone();
+        let region = region.clone();
+        Button::new(
+            SharedString::from(format!("conflict-{:?}", resolution)),
+            label,
+        )
+        .label_size(LabelSize::Small)
+        .on_click(move |_, _, cx| {
+            editor
+                .update(cx, |editor, cx| {
+                    editor.resolve_conflict(buffer_id, &region, resolution, cx)
+                })
+                .ok();
+        })
+    };
+    let ours = if region.ours_name.is_empty() {
+        "Use Ours".to_string()
+    } else {
+        format!("Use Ours ({})", region.ours_name)
+    };
+    let theirs = if region.theirs_name.is_empty() {
+        "Use Theirs".to_string()
+    } else {
+        format!("Use Theirs ({})", region.theirs_name)
+    };
+
+    h_flex()
+        .id(cx.block_id)
+        .h(cx.line_height)
+        .pl(cx.anchor_x)
+        .gap_1()
+        .child(button(ours, ConflictResolution::Ours))
+        .child(button(theirs, ConflictResolution::Theirs))
+        .child(
+            button("Use Both".to_string(), ConflictResolution::Both)
+                .tooltip(Tooltip::text("Keep our lines followed by theirs")),
+        )
+        .into_any_element()
+}
+
+impl Editor {
+    /// Replaces a conflict region with the lines of the side it's resolved to.
+    pub fn resolve_conflict(
+        &mut self,
+        buffer_id: BufferId,
+        region: &ConflictRegion,
+        resolution: ConflictResolution,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) else {
+            return;
+        };
+        buffer.update(cx, |buffer, cx| {
+            let text = {
+                let side = |range: &Range<text::Anchor>| {
+                    buffer.text_for_range(range.clone()).collect::<String>()
+                };
+                match resolution {
+                    ConflictResolution::Ours => side(&region.ours),
+                    ConflictResolution::Theirs => side(&region.theirs),
+                    ConflictResolution::Both => side(&region.ours) + &side(&region.theirs),
+                }
+            };
+            buffer.edit([(region.range.clone(), text)], None, cx);
+        });
+
+        let remaining = self
+            .conflicts
+            .get(&buffer_id)
+            .map(|conflicts| {
+                conflicts
+                    .regions
+                    .iter()
+                    .filter(|other| *other != region)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        set_conflicts(self, buffer_id, remaining, cx);
+    }
+
+    pub fn accept_ours(&mut self, _: &AcceptOurs, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_conflict_at_cursor(ConflictResolution::Ours, cx);
+    }
+
+    pub fn accept_theirs(&mut self, _: &AcceptTheirs, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_conflict_at_cursor(ConflictResolution::Theirs, cx);
+    }
+
+    pub fn accept_both(&mut self, _: &AcceptBoth, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_conflict_at_cursor(ConflictResolution::Both, cx);
+    }
+
+    fn resolve_conflict_at_cursor(
+        &mut self,
+        resolution: ConflictResolution,
+        cx: &mut Context<Self>,
+    ) {
+        let head = self.selections.newest::<usize>(cx).head();
+        let Some((buffer, offset)) = self.buffer.read(cx).point_to_buffer_offset(head, cx) else {
+            return;
+        };
+        let buffer_id = buffer.read(cx).remote_id();
+        let buffer_snapshot = buffer.read(cx).text_snapshot();
+        let Some(region) = self.conflicts.get(&buffer_id).and_then(|conflicts| {
+            conflicts
+                .regions
+                .iter()
+                .find(|region| {
+                    region.range.start.to_offset(&buffer_snapshot) <= offset
+                        && offset <= region.range.end.to_offset(&buffer_snapshot)
+                })
+                .cloned()
+        }) else {
+            return;
+        };
+        self.resolve_conflict(buffer_id, &region, resolution, cx);
+    }
+
+    pub fn go_to_conflict(
+        &mut self,
+        _: &GoToConflict,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_conflict_in_direction(Direction::Next, window, cx);
+    }
+
+    pub fn go_to_previous_conflict(
+        &mut self,
+        _: &GoToPreviousConflict,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_conflict_in_direction(Direction::Prev, window, cx);
+    }
+
+    fn go_to_conflict_in_direction(
+        &mut self,
+        direction: Direction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut starts = self
+            .conflicts
+            .iter()
+            .flat_map(|(buffer_id, conflicts)| {
+                conflicts
+                    .regions
+                    .iter()
+                    .flat_map(|region| ranges_in_excerpts(self, *buffer_id, &region.range, cx))
+            })
+            .map(|range| range.start.to_point(&snapshot))
+            .collect::<Vec<_>>();
+        starts.sort();
+
+        let cursor = self.selections.newest::<text::Point>(cx).head();
+        let destination = match direction {
+            Direction::Next => starts
+                .iter()
+                .find(|start| **start > cursor)
+                .or(starts.first()),
+            Direction::Prev => starts
+                .iter()
+                .rev()
+                .find(|start| **start < cursor)
+                .or(starts.last()),
+        };
+        if let Some(destination) = destination.copied() {
+            self.unfold_ranges(&[destination..destination], false, false, cx);
+            self.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                s.select_ranges([destination..destination]);
+            });
+        }
+    }
+
+    /// Saves and stages the editor's conflicted files, marking their conflicts as resolved.
+    pub fn mark_conflicts_resolved(
+        &mut self,
+        _: &MarkResolved,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let git_store = project.read(cx).git_store().clone();
+        let buffers = self
+            .buffer
+            .read(cx)
+            .all_buffers()
+            .into_iter()
+            .filter(|buffer| {
+                git_store
+                    .read(cx)
+                    .status_for_buffer_id(buffer.read(cx).remote_id(), cx)
+                    .is_some_and(FileStatus::is_conflicted)
+            })
+            .collect::<Vec<_>>();
+        if buffers.is_empty() {
+            return;
+        }
+
+        let dirty_buffers = buffers
+            .iter()
+            .filter(|buffer| buffer.read(cx).is_dirty())
+            .cloned()
+            .collect::<HashSet<_>>();
+        let save = project.update(cx, |project, cx| project.save_buffers(dirty_buffers, cx));
+        cx.spawn(|_, mut cx| async move {
+            save.await?;
+            let stage_tasks = cx.update(|cx| {
+                buffers
+                    .iter()
+                    .filter_map(|buffer| {
+                        let (repository, path) = git_store
+                            .read(cx)
+                            .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)?;
+                        Some(repository.update(cx, |repository, cx| {
+                            repository.stage_entries(vec![path], cx)
+                        }))
+                    })
+                    .collect::<Vec<_>>()
+            })?;
+            for task in stage_tasks {
+                task.await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor_tests::init_test;
+    use fs::Fs as _;
+    use git::{
+        repository::RepoPath,
+        status::{StatusCode, UnmergedStatus, UnmergedStatusCode},
+    };
+    use gpui::{TestAppContext, VisualTestContext};
+    use multi_buffer::MultiBuffer;
+    use pretty_assertions::assert_eq;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use std::path::Path;
+    use util::path;
+
+    const CONFLICTED_TEXT: &str = "one\n\
+        <<<<<<< HEAD\n\
+        two\n\
+        =======\n\
+        deux\n\
+        >>>>>>> feature\n\
+        three\n\
+        <<<<<<< HEAD\n\
+        four\n\
+        =======\n\
+        quatre\n\
+        >>>>>>> feature\n";
+
+    fn parse(text: &str) -> (BufferSnapshot, Vec<ConflictRegion>) {
+        let buffer = text::Buffer::new(0, BufferId::new(1).unwrap(), text.to_string());
+        let snapshot = buffer.snapshot();
+        let regions = parse_conflicts(&snapshot);
+        (snapshot, regions)
+    }
+
+    fn text_for(snapshot: &BufferSnapshot, range: &Range<text::Anchor>) -> String {
+        snapshot.text_for_range(range.clone()).collect()
+    }
+
+    #[test]
+    fn test_parse_conflicts() {
+        let (snapshot, regions) = parse(
+            "one\n\
+             <<<<<<< HEAD\n\
+             two\n\
+             =======\n\
+             deux\n\
+             zwei\n\
+             >>>>>>> feature\n\
+             three\n\
+             <<<<<<< ours\n\
+             four\n\
+             ||||||| base\n\
+             4\n\
+             =======\n\
+             >>>>>>> theirs\n",
+        );
+        assert_eq!(regions.len(), 2);
+
+        assert_eq!(
+            text_for(&snapshot, &regions[0].range),
+            "<<<<<<< HEAD\ntwo\n=======\ndeux\nzwei\n>>>>>>> feature\n"
+        );
+        assert_eq!(text_for(&snapshot, &regions[0].ours), "two\n");
+        assert_eq!(regions[0].base, None);
+        assert_eq!(text_for(&snapshot, &regions[0].theirs), "deux\nzwei\n");
+        assert_eq!(regions[0].ours_name.as_ref(), "HEAD");
+        assert_eq!(regions[0].theirs_name.as_ref(), "feature");
+
+        assert_eq!(text_for(&snapshot, &regions[1].ours), "four\n");
+        assert_eq!(
+            regions[1]
+                .base
+                .as_ref()
+                .map(|base| text_for(&snapshot, base)),
+            Some("4\n".to_string())
+        );
+        assert_eq!(text_for(&snapshot, &regions[1].theirs), "");
+    }
+
+    #[test]
+    fn test_parse_conflicts_ignores_unterminated_markers() {
+        let (_, regions) = parse("<<<<<<< HEAD\none\n=======\ntwo\n");
+        assert_eq!(regions, Vec::new());
+
+        let (_, regions) = parse("<<<<<<<< not a marker\none\n=======\ntwo\n>>>>>>> theirs\n");
+        assert_eq!(regions, Vec::new());
+    }
+
+    #[gpui::test]
+    async fn test_accept_conflict_sides(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let (_fs, editor, cx) = conflicted_editor(cx).await;
+
+        move_cursor_to(&editor, "two", cx);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.accept_ours(&AcceptOurs, window, cx)
+        });
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            "one\ntwo\nthree\n<<<<<<< HEAD\nfour\n=======\nquatre\n>>>>>>> feature\n"
+        );
+        assert_eq!(conflict_count(&editor, cx), 1);
+
+        // Only the conflict under the cursor is resolved.
+        move_cursor_to(&editor, "one", cx);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.accept_theirs(&AcceptTheirs, window, cx)
+        });
+        assert_eq!(conflict_count(&editor, cx), 1);
+
+        move_cursor_to(&editor, "quatre", cx);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.accept_theirs(&AcceptTheirs, window, cx)
+        });
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            "one\ntwo\nthree\nquatre\n"
+        );
+        assert_eq!(conflict_count(&editor, cx), 0);
+
+        // Both sides are kept in order, ours first.
+        editor.update_in(cx, |editor, window, cx| {
+            editor.set_text(CONFLICTED_TEXT, window, cx)
+        });
+        cx.executor().advance_clock(UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(conflict_count(&editor, cx), 2);
+        move_cursor_to(&editor, "four", cx);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.accept_both(&AcceptBoth, window, cx)
+        });
+        assert_eq!(
+            editor.update(cx, |editor, cx| editor.text(cx)),
+            "one\n<<<<<<< HEAD\ntwo\n=======\ndeux\n>>>>>>> feature\nthree\nfour\nquatre\n"
+        );
+        assert_eq!(conflict_count(&editor, cx), 1);
+    }
+
+    #[gpui::test]
+    async fn test_mark_conflicts_resolved_stages_files(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let (fs, editor, cx) = conflicted_editor(cx).await;
+
+        for side in ["two", "four"] {
+            move_cursor_to(&editor, side, cx);
+            editor.update_in(cx, |editor, window, cx| {
+                editor.accept_ours(&AcceptOurs, window, cx)
+            });
+        }
+        editor.update_in(cx, |editor, window, cx| {
+            editor.mark_conflicts_resolved(&MarkResolved, window, cx)
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(path!("/project/file.txt").as_ref()).await.unwrap(),
+            "one\ntwo\nthree\nfour\n"
+        );
+        let mut status = None;
+        fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+            status = state.statuses.get(&RepoPath::from("file.txt")).copied();
+        });
+        assert_eq!(status, Some(StatusCode::Modified.index()));
+        editor.update(cx, |editor, cx| {
+            assert!(!editor.buffer().read(cx).is_dirty(cx));
+            assert!(editor.conflicts.is_empty());
+        });
+    }
+
+    async fn conflicted_editor(
+        cx: &mut TestAppContext,
+    ) -> (Arc<FakeFs>, Entity<Editor>, &mut VisualTestContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.txt": CONFLICTED_TEXT,
+            }),
+        )
+        .await;
+        fs.set_status_for_repo_via_git_operation(
+            path!("/project/.git").as_ref(),
+            &[(
+                Path::new("file.txt"),
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }
+                .into(),
+            )],
+        );
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/file.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let (editor, cx) = cx.add_window_view(|window, cx| {
+            Editor::new(EditorMode::Full, multi_buffer, Some(project), window, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(conflict_count(&editor, cx), 2);
+        (fs, editor, cx)
+    }
+
+    fn move_cursor_to(editor: &Entity<Editor>, line: &str, cx: &mut VisualTestContext) {
+        editor.update_in(cx, |editor, window, cx| {
+            let offset = editor
+                .text(cx)
+                .find(&format!("{line}\n"))
+                .expect("line not found");
+            editor.change_selections(None, window, cx, |s| s.select_ranges([offset..offset]));
+        });
+    }
+
+    fn conflict_count(editor: &Entity<Editor>, cx: &mut VisualTestContext) -> usize {
+        editor.update(cx, |editor, _| {
+            editor
+                .conflicts
+                .values()
+                .map(|conflicts| conflicts.regions.len())
+                .sum()
+        })
+    }
+}
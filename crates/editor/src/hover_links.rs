@@ -4,6 +4,7 @@ use crate::{
     scroll::ScrollAmount,
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToTypeDefinition, GotoDefinitionKind, InlayId, Navigated, PointForPosition, SelectPhase,
+    SemanticsProvider,
 };
 use gpui::{px, App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window};
 use language::{Bias, ToOffset};
@@ -14,7 +15,7 @@ use project::{
     ResolveState, ResolvedPath,
 };
 use settings::Settings;
-use std::ops::Range;
+use std::{ops::Range, rc::Rc};
 use theme::ActiveTheme as _;
 use util::{maybe, ResultExt, TryFutureExt as _};
 
@@ -533,7 +534,24 @@ pub fn show_link_definition(
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, buffer_position, cx.clone()) {
+                    if let Some((link_range, link)) = find_document_link(
+                        &buffer,
+                        buffer_position,
+                        provider.clone(),
+                        project.clone(),
+                        &mut cx,
+                    )
+                    .await
+                    {
+                        let range = maybe!({
+                            let start = snapshot.anchor_in_excerpt(excerpt_id, link_range.start)?;
+                            let end = snapshot.anchor_in_excerpt(excerpt_id, link_range.end)?;
+                            Some(RangeInEditor::Text(start..end))
+                        });
+                        Some((range, vec![link]))
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, buffer_position, cx.clone())
+                    {
                         this.update(&mut cx, |_, _| {
                             let range = maybe!({
                                 let start =
@@ -652,6 +670,52 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Finds the document link reported by the language server at the given position, resolving its
+/// target if needed. Links to local files are opened in the workspace and others as URLs.
+pub(crate) async fn find_document_link(
+    buffer: &Entity<language::Buffer>,
+    position: text::Anchor,
+    provider: Option<Rc<dyn SemanticsProvider>>,
+    project: Option<Entity<Project>>,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let provider = provider?;
+    let links = cx
+        .update(|_, cx| provider.document_links(buffer, cx))
+        .ok()??
+        .await
+        .log_err()?;
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot()).ok()?;
+    let link = links.into_iter().find(|link| {
+        link.range.start.cmp(&position, &snapshot).is_le()
+            && link.range.end.cmp(&position, &snapshot).is_ge()
+    })?;
+
+    let range = link.range.clone();
+    let link = if link.target.is_some() {
+        link
+    } else {
+        cx.update(|_, cx| provider.resolve_document_link(link, buffer.clone(), cx))
+            .ok()??
+            .await
+            .log_err()?
+    };
+    let target = link.target?;
+    let url = lsp::Url::parse(&target).ok()?;
+    if url.scheme() != "file" {
+        return Some((range, HoverLink::Url(target)));
+    }
+
+    let path = url.to_file_path().ok()?;
+    let resolved_path = project?
+        .update(cx, |project, cx| {
+            project.resolve_abs_path(&path.to_string_lossy(), cx)
+        })
+        .ok()?
+        .await?;
+    Some((range, HoverLink::File(resolved_path)))
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -1445,6 +1509,62 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            // See ˇthe crate docs.
+        "});
+        let link_range = cx.lsp_range(indoc! {"
+            // See the «crate docs».
+        "});
+
+        let mut link_requests = cx.handle_request::<lsp::request::DocumentLinkRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: link_range,
+                    target: None,
+                    tooltip: None,
+                    data: Some(serde_json::json!("crate-docs")),
+                }]))
+            },
+        );
+        let mut resolve_requests =
+            cx.handle_request::<lsp::request::DocumentLinkResolve, _, _>(|_, link, _| async move {
+                assert_eq!(link.data, Some(serde_json::json!("crate-docs")));
+                Ok(lsp::DocumentLink {
+                    target: Some("https://docs.rs/editor".parse().unwrap()),
+                    ..link
+                })
+            });
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            // See the crate dˇocs.
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        link_requests.next().await;
+        resolve_requests.next().await;
+        cx.background_executor.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            // See the «crate docsˇ».
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/editor".into()));
+    }
+
     #[gpui::test]
     async fn test_urls_at_beginning_of_buffer(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
@@ -1137,6 +1137,7 @@ impl SerializableItem for Editor {
                                     let mut editor =
                                         Editor::for_buffer(buffer, Some(project), window, cx);
 
+                                    editor.read_undo_history_from_db(cx);
                                     editor.read_selections_from_db(
                                         item_id,
                                         workspace_id,
@@ -1272,7 +1273,9 @@ impl ProjectItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        Self::for_buffer(buffer, Some(project), window, cx)
+        let mut editor = Self::for_buffer(buffer, Some(project), window, cx);
+        editor.read_undo_history_from_db(cx);
+        editor
     }
 }
 
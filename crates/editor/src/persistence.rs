@@ -3,6 +3,10 @@ use db::sqlez::bindable::{Bind, Column, StaticColumnCount};
 use db::sqlez::statement::Statement;
 use fs::MTime;
 use itertools::Itertools as _;
+use language::{UndoHistory, UndoHistoryTransaction};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::ops::Range;
 use std::path::PathBuf;
 
 use db::sqlez_macros::sql;
@@ -74,6 +78,46 @@ impl Column for SerializedEditor {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct SerializedUndoHistory {
+    undo_stack: Vec<SerializedUndoTransaction>,
+    redo_stack: Vec<SerializedUndoTransaction>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedUndoTransaction {
+    edits: Vec<(Range<usize>, String)>,
+    inverse_edits: Vec<(Range<usize>, String)>,
+}
+
+impl From<UndoHistoryTransaction> for SerializedUndoTransaction {
+    fn from(transaction: UndoHistoryTransaction) -> Self {
+        Self {
+            edits: transaction.edits,
+            inverse_edits: transaction.inverse_edits,
+        }
+    }
+}
+
+impl From<SerializedUndoTransaction> for UndoHistoryTransaction {
+    fn from(transaction: SerializedUndoTransaction) -> Self {
+        Self {
+            edits: transaction.edits,
+            inverse_edits: transaction.inverse_edits,
+        }
+    }
+}
+
+/// Hashes the contents an undo history was recorded against, so that it's
+/// only restored when the file hasn't changed since.
+fn undo_history_content_hash<'a>(chunks: impl IntoIterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    for chunk in chunks {
+        hasher.update(chunk.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
 define_connection!(
     // Current schema shape using pseudo-rust syntax:
     // editors(
@@ -88,6 +132,12 @@ define_connection!(
     //   mtime_seconds: Option<i64>,
     //   mtime_nanos: Option<i32>,
     // )
+    //
+    // editor_undo_histories(
+    //   path: PathBuf,
+    //   content_hash: String,
+    //   history: String,
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> = &[
         sql! (
             CREATE TABLE editors(
@@ -147,6 +197,14 @@ define_connection!(
                 ON DELETE CASCADE
             ) STRICT;
         ),
+        sql! (
+            CREATE TABLE editor_undo_histories (
+                path BLOB NOT NULL,
+                content_hash TEXT NOT NULL,
+                history TEXT NOT NULL,
+                PRIMARY KEY(path)
+            ) STRICT;
+        ),
     ];
 );
 
@@ -268,6 +326,72 @@ VALUES {placeholders};
         Ok(())
     }
 
+    query! {
+        fn get_undo_history_with_hash(path: PathBuf, content_hash: String) -> Result<Option<String>> {
+            SELECT history
+            FROM editor_undo_histories
+            WHERE path = ?1 AND content_hash = ?2
+        }
+    }
+
+    query! {
+        async fn save_undo_history_with_hash(path: PathBuf, content_hash: String, history: String) -> Result<()> {
+            INSERT INTO editor_undo_histories
+                (path, content_hash, history)
+            VALUES
+                (?1, ?2, ?3)
+            ON CONFLICT DO UPDATE SET
+                content_hash = ?2,
+                history = ?3
+        }
+    }
+
+    query! {
+        pub async fn delete_undo_history(path: PathBuf) -> Result<()> {
+            DELETE FROM editor_undo_histories
+            WHERE path = ?
+        }
+    }
+
+    /// Returns the undo history stored for the file at `path`, if it was
+    /// recorded against the given contents.
+    pub fn get_undo_history<'a>(
+        &self,
+        path: PathBuf,
+        contents: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Option<UndoHistory>> {
+        let content_hash = undo_history_content_hash(contents);
+        let Some(history) = self.get_undo_history_with_hash(path, content_hash)? else {
+            return Ok(None);
+        };
+        let history: SerializedUndoHistory = serde_json::from_str(&history)?;
+        Ok(Some(UndoHistory {
+            undo_stack: history.undo_stack.into_iter().map(Into::into).collect(),
+            redo_stack: history.redo_stack.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    /// Stores the undo history of the file at `path`, replacing any history
+    /// previously stored for it.
+    pub async fn save_undo_history<'a>(
+        &self,
+        path: PathBuf,
+        contents: impl IntoIterator<Item = &'a str>,
+        history: UndoHistory,
+    ) -> Result<()> {
+        if history.is_empty() {
+            return self.delete_undo_history(path).await;
+        }
+
+        let content_hash = undo_history_content_hash(contents);
+        let history = serde_json::to_string(&SerializedUndoHistory {
+            undo_stack: history.undo_stack.into_iter().map(Into::into).collect(),
+            redo_stack: history.redo_stack.into_iter().map(Into::into).collect(),
+        })?;
+        self.save_undo_history_with_hash(path, content_hash, history)
+            .await
+    }
+
     pub async fn delete_unloaded_items(
         &self,
         workspace: WorkspaceId,
@@ -374,4 +498,30 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+    #[gpui::test]
+    async fn test_save_and_get_undo_history() {
+        let path = PathBuf::from("/undo/testing.txt");
+        let history = UndoHistory {
+            undo_stack: vec![UndoHistoryTransaction {
+                edits: vec![(1..1, "bc".to_string())],
+                inverse_edits: vec![(1..3, String::new())],
+            }],
+            redo_stack: Vec::new(),
+        };
+
+        DB.save_undo_history(path.clone(), ["a", "bcd"], history.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_undo_history(path.clone(), ["abcd"]).unwrap(),
+            Some(history)
+        );
+        // The history doesn't apply to different contents.
+        assert_eq!(DB.get_undo_history(path.clone(), ["abc"]).unwrap(), None);
+
+        DB.save_undo_history(path.clone(), ["abcd"], UndoHistory::default())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_undo_history(path, ["abcd"]).unwrap(), None);
+    }
 }
@@ -1,4 +1,6 @@
-use crate::{ApplyAllDiffHunks, Editor, EditorEvent, SemanticsProvider};
+use crate::{
+    ApplyAllDiffHunks, Editor, EditorEvent, SemanticsProvider, SplitDiff, ToggleSplitDiff,
+};
 use buffer_diff::BufferDiff;
 use collections::HashSet;
 use futures::{channel::mpsc, future::join_all};
@@ -18,6 +20,7 @@ use workspace::{
 pub struct ProposedChangesEditor {
     editor: Entity<Editor>,
     multibuffer: Entity<MultiBuffer>,
+    split_diff: Option<Entity<SplitDiff>>,
     title: SharedString,
     buffer_entries: Vec<BufferEntry>,
     _recalculate_diffs_task: Task<Option<()>>,
@@ -74,6 +77,7 @@ impl ProposedChangesEditor {
                 editor
             }),
             multibuffer,
+            split_diff: None,
             title: title.into(),
             buffer_entries: Vec::new(),
             recalculate_diffs_tx,
@@ -135,6 +139,22 @@ impl ProposedChangesEditor {
         })
     }
 
+    fn toggle_split_diff(
+        &mut self,
+        _: &ToggleSplitDiff,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.split_diff.take() {
+            Some(split_diff) => split_diff.update(cx, |split_diff, cx| split_diff.unsplit(cx)),
+            None => {
+                let editor = self.editor.clone();
+                self.split_diff = Some(cx.new(|cx| SplitDiff::new(editor, window, cx)));
+            }
+        }
+        cx.notify();
+    }
+
     pub fn set_title(&mut self, title: SharedString, cx: &mut Context<Self>) {
         self.title = title;
         cx.notify();
@@ -262,11 +282,15 @@ impl ProposedChangesEditor {
 }
 
 impl Render for ProposedChangesEditor {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .size_full()
             .key_context("ProposedChangesEditor")
-            .child(self.editor.clone())
+            .on_action(cx.listener(Self::toggle_split_diff))
+            .map(|this| match &self.split_diff {
+                Some(split_diff) => this.child(split_diff.clone()),
+                None => this.child(self.editor.clone()),
+            })
     }
 }
 
@@ -513,4 +537,41 @@ impl SemanticsProvider for BranchBufferSemanticsProvider {
     ) -> Option<Task<gpui::Result<project::ProjectTransaction>>> {
         None
     }
+
+    fn supports_selection_ranges(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        if let Some(buffer) = self.to_base(&buffer, &[], cx) {
+            self.0.supports_selection_ranges(&buffer, cx)
+        } else {
+            false
+        }
+    }
+
+    fn selection_ranges(
+        &self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<text::Anchor>,
+        cx: &mut App,
+    ) -> Option<Task<gpui::Result<Vec<Vec<Range<text::Anchor>>>>>> {
+        let buffer = self.to_base(&buffer, &positions, cx)?;
+        self.0.selection_ranges(&buffer, positions, cx)
+    }
+
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<gpui::Result<Vec<project::DocumentLink>>>> {
+        let buffer = self.to_base(&buffer, &[], cx)?;
+        self.0.document_links(&buffer, cx)
+    }
+
+    fn resolve_document_link(
+        &self,
+        link: project::DocumentLink,
+        buffer: Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<gpui::Result<project::DocumentLink>>> {
+        let buffer = self.to_base(&buffer, &[], cx)?;
+        self.0.resolve_document_link(link, buffer, cx)
+    }
 }
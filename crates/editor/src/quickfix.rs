@@ -0,0 +1,89 @@
+use anyhow::Result;
+use gpui::{Context, Task, Window};
+use language::ToPoint as _;
+use workspace::{
+    quickfix::{First, Last, Next, Previous, QuickfixPosition},
+    Workspace,
+};
+
+use crate::{Bias, Editor};
+
+pub(crate) fn register_actions(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &First, window, cx| {
+        open_quickfix_entry(workspace, 0, window, cx).detach_and_log_err(cx);
+    });
+    workspace.register_action(|workspace, _: &Last, window, cx| {
+        let entry_count = workspace.quickfix_list().read(cx).entries().len();
+        if let Some(ix) = entry_count.checked_sub(1) {
+            open_quickfix_entry(workspace, ix, window, cx).detach_and_log_err(cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &Next, window, cx| {
+        if let Some(ix) = workspace.quickfix_list().read(cx).next_index(1) {
+            open_quickfix_entry(workspace, ix, window, cx).detach_and_log_err(cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &Previous, window, cx| {
+        if let Some(ix) = workspace.quickfix_list().read(cx).previous_index(1) {
+            open_quickfix_entry(workspace, ix, window, cx).detach_and_log_err(cx);
+        }
+    });
+}
+
+/// Selects the quickfix entry at `ix` and opens an editor with its range
+/// selected, anchoring the printed positions of the entries in its file. The
+/// task resolves once the editor is ready.
+pub fn open_quickfix_entry(
+    workspace: &mut Workspace,
+    ix: usize,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> Task<Result<()>> {
+    let Some(entry) = workspace
+        .quickfix_list()
+        .update(cx, |quickfix_list, cx| quickfix_list.select(ix, cx))
+    else {
+        return Task::ready(Ok(()));
+    };
+    let open_item =
+        workspace.open_path_preview(entry.path.clone(), None, true, true, true, window, cx);
+    cx.spawn_in(window, |workspace, mut cx| async move {
+        let item = open_item.await?;
+        let Some(editor) = item.downcast::<Editor>() else {
+            return Ok(());
+        };
+        let Some(buffer) = editor.update(&mut cx, |editor, cx| {
+            editor.buffer().read(cx).as_singleton()
+        })?
+        else {
+            return Ok(());
+        };
+        let position = workspace.update(&mut cx, |workspace, cx| {
+            workspace.quickfix_list().update(cx, |quickfix_list, cx| {
+                quickfix_list.anchor_entries(&entry.path, &buffer, cx);
+                quickfix_list
+                    .entries()
+                    .get(ix)
+                    .map(|entry| entry.position.clone())
+            })
+        })?;
+        let Some(QuickfixPosition::Anchored {
+            buffer: entry_buffer,
+            range,
+        }) = position
+        else {
+            return Ok(());
+        };
+        editor.update_in(&mut cx, |editor, window, cx| {
+            let entry_buffer = entry_buffer.read(cx);
+            let start = range.start.to_point(entry_buffer);
+            let end = range.end.to_point(entry_buffer);
+            // The entry's buffer is only a different one if its file was
+            // reopened since the entry was anchored.
+            let snapshot = buffer.read(cx).snapshot();
+            let range =
+                snapshot.clip_point(start, Bias::Left)..snapshot.clip_point(end, Bias::Left);
+            editor.go_to_singleton_buffer_range(range, window, cx);
+        })
+    })
+}
@@ -0,0 +1,159 @@
+use std::{cmp, ops::Range, time::Duration};
+
+use gpui::{Context, Entity, HighlightStyle};
+use language::{language_settings::language_settings, Buffer};
+use multi_buffer::Anchor;
+use text::{BufferId, ToOffset as _};
+use theme::ActiveTheme as _;
+use util::ResultExt;
+
+use crate::{Editor, EditorMode};
+
+pub(super) const UPDATE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Queries the semantic tokens of the given buffer, or of all buffers in the editor, and
+/// highlights them according to the theme once they arrive.
+pub(super) fn refresh_semantic_tokens(
+    editor: &mut Editor,
+    for_buffer: Option<Entity<Buffer>>,
+    debounce: Option<Duration>,
+    cx: &mut Context<Editor>,
+) {
+    if editor.mode != EditorMode::Full {
+        return;
+    }
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+
+    let buffers = match for_buffer {
+        Some(buffer) => vec![buffer],
+        None => editor.buffer.read(cx).all_buffers().into_iter().collect(),
+    };
+    for buffer in buffers {
+        let buffer_id = buffer.read(cx).remote_id();
+        if !semantic_tokens_enabled(&buffer, cx) {
+            editor.semantic_tokens_tasks.remove(&buffer_id);
+            clear_semantic_highlights(editor, buffer_id, cx);
+            continue;
+        }
+
+        let project = project.downgrade();
+        let task = cx.spawn(|editor, mut cx| async move {
+            if let Some(debounce) = debounce {
+                cx.background_executor().timer(debounce).await;
+            }
+
+            let Some(tokens_task) = project
+                .update(&mut cx, |project, cx| {
+                    project.semantic_tokens(buffer.clone(), cx)
+                })
+                .ok()
+            else {
+                return;
+            };
+            let Some(tokens) = tokens_task.await.log_err() else {
+                return;
+            };
+
+            editor
+                .update(&mut cx, |editor, cx| {
+                    let buffer_snapshot = buffer.read(cx).snapshot();
+                    let multi_buffer = editor.buffer.read(cx);
+                    let excerpts = multi_buffer.excerpts_for_buffer(buffer_id, cx);
+                    let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+                    let syntax_theme = cx.theme().syntax();
+
+                    let mut highlights = Vec::new();
+                    for (excerpt_id, excerpt_range) in excerpts {
+                        let context_start = excerpt_range.context.start.to_offset(&buffer_snapshot);
+                        let context_end = excerpt_range.context.end.to_offset(&buffer_snapshot);
+                        for token in &tokens.tokens {
+                            let start = cmp::max(
+                                token.range.start.to_offset(&buffer_snapshot),
+                                context_start,
+                            );
+                            let end =
+                                cmp::min(token.range.end.to_offset(&buffer_snapshot), context_end);
+                            if start >= end {
+                                continue;
+                            }
+                            let Some(token_type) = tokens.token_type(token) else {
+                                continue;
+                            };
+                            let Some(style) = syntax_theme
+                                .semantic_token_style(token_type, tokens.token_modifiers(token))
+                            else {
+                                continue;
+                            };
+                            let (Some(start), Some(end)) = (
+                                multi_buffer_snapshot.anchor_in_excerpt(
+                                    excerpt_id,
+                                    buffer_snapshot.anchor_after(start),
+                                ),
+                                multi_buffer_snapshot.anchor_in_excerpt(
+                                    excerpt_id,
+                                    buffer_snapshot.anchor_before(end),
+                                ),
+                            ) else {
+                                continue;
+                            };
+                            highlights.push((start..end, style));
+                        }
+                    }
+
+                    set_semantic_highlights(editor, buffer_id, highlights, cx);
+                })
+                .ok();
+        });
+        editor.semantic_tokens_tasks.insert(buffer_id, task);
+    }
+}
+
+/// Drops the semantic highlights of buffers that are no longer part of the editor.
+pub(super) fn remove_stale_semantic_tokens(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let multi_buffer = editor.buffer.read(cx);
+    let stale_buffers = editor
+        .semantic_tokens_buffers
+        .iter()
+        .copied()
+        .filter(|buffer_id| multi_buffer.buffer(*buffer_id).is_none())
+        .collect::<Vec<_>>();
+    for buffer_id in stale_buffers {
+        editor.semantic_tokens_tasks.remove(&buffer_id);
+        clear_semantic_highlights(editor, buffer_id, cx);
+    }
+}
+
+fn semantic_tokens_enabled(buffer: &Entity<Buffer>, cx: &Context<Editor>) -> bool {
+    let buffer = buffer.read(cx);
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .semantic_tokens
+}
+
+fn set_semantic_highlights(
+    editor: &mut Editor,
+    buffer_id: BufferId,
+    highlights: Vec<(Range<Anchor>, HighlightStyle)>,
+    cx: &mut Context<Editor>,
+) {
+    editor.display_map.update(cx, |display_map, _| {
+        display_map.set_semantic_highlights(buffer_id, highlights)
+    });
+    editor.semantic_tokens_buffers.insert(buffer_id);
+    cx.notify();
+}
+
+fn clear_semantic_highlights(editor: &mut Editor, buffer_id: BufferId, cx: &mut Context<Editor>) {
+    editor.semantic_tokens_buffers.remove(&buffer_id);
+    let cleared = editor.display_map.update(cx, |display_map, _| {
+        display_map.clear_semantic_highlights(buffer_id)
+    });
+    if cleared {
+        cx.notify();
+    }
+}
@@ -0,0 +1,668 @@
+use crate::{
+    display_map::{BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    Anchor, Editor, EditorEvent,
+};
+use collections::{HashMap, HashSet};
+use gpui::{
+    App, Entity, EntityId, EventEmitter, FocusHandle, Focusable, Render, Subscription, Task,
+};
+use language::{Buffer, Capability};
+use multi_buffer::{ExcerptId, ExcerptRange, MultiBuffer, MultiBufferSnapshot};
+use std::{ops::Range, sync::Arc, time::Duration};
+use text::{AnchorRangeExt as _, Bias, BufferId, OffsetRangeExt as _, Point};
+use ui::{prelude::*, Divider, DividerColor};
+
+const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Shows the base text of each diff in an editor's multibuffer to the left of
+/// that editor, with the two scrolled together.
+///
+/// While split, the editor's hunks are collapsed, and padding is inserted on
+/// whichever side of each hunk has fewer lines so that unchanged lines stay
+/// aligned.
+pub struct SplitDiff {
+    editor: Entity<Editor>,
+    base_editor: Entity<Editor>,
+    base_multibuffer: Entity<MultiBuffer>,
+    /// The buffer holding each diff's base text, along with the id of the
+    /// base text snapshot it was created from.
+    base_buffers: HashMap<BufferId, (BufferId, Entity<Buffer>)>,
+    excerpts: HashMap<ExcerptId, SplitExcerpt>,
+    hunks_were_expanded: bool,
+    update_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+/// What the split shows alongside one of the editor's excerpts.
+struct SplitExcerpt {
+    layout: ExcerptLayout,
+    base_excerpt_id: ExcerptId,
+    editor_padding: Vec<CustomBlockId>,
+    base_editor_padding: Vec<CustomBlockId>,
+    added_rows: Vec<Range<Anchor>>,
+    deleted_rows: Vec<Range<Anchor>>,
+}
+
+/// The rows an excerpt shows on each side and the hunks within them. An
+/// excerpt's padding is only redone when these change.
+#[derive(PartialEq)]
+struct ExcerptLayout {
+    base_buffer_id: EntityId,
+    context: Range<Point>,
+    base_context: Range<Point>,
+    /// The rows of each hunk, and the range of the base text it replaces.
+    hunks: Vec<(Range<u32>, Range<usize>)>,
+}
+
+/// Row highlights for the lines a hunk removes or adds.
+enum SplitDiffHunkRows {}
+
+impl SplitDiff {
+    pub fn new(editor: Entity<Editor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let multibuffer = editor.read(cx).buffer().clone();
+        let hunks_were_expanded = multibuffer.read(cx).all_diff_hunks_expanded();
+        multibuffer.update(cx, |multibuffer, cx| {
+            multibuffer.set_all_diff_hunks_collapsed(cx)
+        });
+
+        let base_multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadOnly));
+        let base_editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(base_multibuffer.clone(), None, window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_read_only(true);
+            editor
+        });
+
+        let subscriptions = vec![
+            cx.subscribe_in(&multibuffer, window, |this, _, event, window, cx| {
+                if matches!(
+                    event,
+                    multi_buffer::Event::Edited { .. }
+                        | multi_buffer::Event::ExcerptsAdded { .. }
+                        | multi_buffer::Event::ExcerptsRemoved { .. }
+                        | multi_buffer::Event::ExcerptsExpanded { .. }
+                        | multi_buffer::Event::DiffHunksToggled
+                        | multi_buffer::Event::BufferDiffChanged
+                ) {
+                    this.schedule_update(window, cx);
+                }
+            }),
+            cx.subscribe_in(&editor, window, |this, _, event, window, cx| {
+                if let EditorEvent::ScrollPositionChanged { .. } = event {
+                    Self::sync_scroll_position(&this.editor, &this.base_editor, window, cx);
+                }
+            }),
+            cx.subscribe_in(&base_editor, window, |this, _, event, window, cx| {
+                if let EditorEvent::ScrollPositionChanged { .. } = event {
+                    Self::sync_scroll_position(&this.base_editor, &this.editor, window, cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            editor,
+            base_editor,
+            base_multibuffer,
+            base_buffers: HashMap::default(),
+            excerpts: HashMap::default(),
+            hunks_were_expanded,
+            update_task: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.update(window, cx);
+        this
+    }
+
+    pub fn editor(&self) -> &Entity<Editor> {
+        &self.editor
+    }
+
+    /// Removes everything the split added to the editor, and expands its hunks
+    /// again if they were before.
+    pub fn unsplit(&mut self, cx: &mut App) {
+        let padding = self
+            .excerpts
+            .drain()
+            .flat_map(|(_, excerpt)| excerpt.editor_padding)
+            .collect();
+        self.editor.update(cx, |editor, cx| {
+            editor.remove_blocks(padding, None, cx);
+            editor.clear_row_highlights::<SplitDiffHunkRows>();
+            if self.hunks_were_expanded {
+                editor.set_expand_all_diff_hunks(cx);
+            }
+        });
+    }
+
+    fn schedule_update(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_task = cx.spawn_in(window, |this, mut cx| async move {
+            cx.background_executor().timer(UPDATE_DEBOUNCE).await;
+            this.update_in(&mut cx, |this, window, cx| this.update(window, cx))
+                .ok();
+        });
+    }
+
+    fn sync_scroll_position(
+        source: &Entity<Editor>,
+        target: &Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let position = source.update(cx, |editor, cx| editor.scroll_position(cx));
+        target.update(cx, |editor, cx| {
+            let target_position = editor.scroll_position(cx);
+            if target_position.y != position.y {
+                editor.set_scroll_position(gpui::point(target_position.x, position.y), window, cx);
+            }
+        });
+    }
+
+    /// Updates the base editor's excerpts to mirror the editor's, along with
+    /// the padding and highlights of both. Only the excerpts whose rows or
+    /// hunks changed since the last update are redone.
+    fn update(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let multibuffer = self.editor.read(cx).buffer().clone();
+        let snapshot = multibuffer.read(cx).snapshot(cx);
+
+        let mut previous_excerpts = std::mem::take(&mut self.excerpts);
+        let mut excerpts = HashMap::default();
+        let mut changed_excerpts = Vec::new();
+        let mut stale_base_excerpts = Vec::new();
+        let mut stale_editor_padding = HashSet::default();
+        let mut stale_base_editor_padding = HashSet::default();
+        let mut prev_base_excerpt_id = ExcerptId::min();
+        for (excerpt_id, buffer, range) in snapshot.excerpts() {
+            let buffer_id = buffer.remote_id();
+            let diff = multibuffer
+                .read(cx)
+                .diff_for(buffer_id)
+                .map(|diff| diff.read(cx).snapshot(cx));
+            let base_buffer = match &diff {
+                Some(diff) => {
+                    let base_text = diff.base_text();
+                    match self.base_buffers.get(&buffer_id) {
+                        Some((base_text_id, base_buffer))
+                            if *base_text_id == base_text.remote_id() =>
+                        {
+                            base_buffer.clone()
+                        }
+                        _ => {
+                            let base_buffer = cx.new(|cx| {
+                                let mut buffer = Buffer::local(base_text.text(), cx);
+                                buffer.set_language(base_text.language().cloned(), cx);
+                                buffer.set_capability(Capability::ReadOnly, cx);
+                                buffer
+                            });
+                            self.base_buffers
+                                .insert(buffer_id, (base_text.remote_id(), base_buffer.clone()));
+                            base_buffer
+                        }
+                    }
+                }
+                // Without a diff, both sides show the same text.
+                None => match multibuffer.read(cx).buffer(buffer_id) {
+                    Some(buffer) => buffer,
+                    None => continue,
+                },
+            };
+
+            let context = range.context.to_point(buffer);
+            let layout = match &diff {
+                Some(diff) => {
+                    let base_snapshot = base_buffer.read(cx).text_snapshot();
+                    let start = diff.base_text_row_for_buffer_row(context.start.row, buffer);
+                    let end = if context.end.column == 0 {
+                        diff.base_text_row_for_buffer_row(context.end.row, buffer)
+                    } else {
+                        diff.base_text_row_for_buffer_row(context.end.row + 1, buffer)
+                    };
+                    let range =
+                        buffer.anchor_before(context.start)..buffer.anchor_after(context.end);
+                    ExcerptLayout {
+                        base_buffer_id: base_buffer.entity_id(),
+                        base_context: base_snapshot.clip_point(Point::new(start, 0), Bias::Left)
+                            ..base_snapshot.clip_point(Point::new(end, 0), Bias::Left),
+                        hunks: diff
+                            .hunks_intersecting_range(range, buffer)
+                            .map(|hunk| {
+                                (
+                                    hunk.range.start.row..hunk.range.end.row,
+                                    hunk.diff_base_byte_range,
+                                )
+                            })
+                            .collect(),
+                        context,
+                    }
+                }
+                None => ExcerptLayout {
+                    base_buffer_id: base_buffer.entity_id(),
+                    base_context: context.clone(),
+                    hunks: Vec::new(),
+                    context,
+                },
+            };
+
+            match previous_excerpts.remove(&excerpt_id) {
+                Some(previous) if previous.layout == layout => {
+                    prev_base_excerpt_id = previous.base_excerpt_id;
+                    excerpts.insert(excerpt_id, previous);
+                }
+                previous => {
+                    if let Some(previous) = previous {
+                        stale_base_excerpts.push(previous.base_excerpt_id);
+                        stale_editor_padding.extend(previous.editor_padding);
+                        stale_base_editor_padding.extend(previous.base_editor_padding);
+                    }
+                    let base_excerpt_id =
+                        self.base_multibuffer.update(cx, |base_multibuffer, cx| {
+                            base_multibuffer
+                                .insert_excerpts_after(
+                                    prev_base_excerpt_id,
+                                    base_buffer.clone(),
+                                    [ExcerptRange {
+                                        context: layout.base_context.clone(),
+                                        primary: None,
+                                    }],
+                                    cx,
+                                )
+                                .pop()
+                        });
+                    let Some(base_excerpt_id) = base_excerpt_id else {
+                        continue;
+                    };
+                    prev_base_excerpt_id = base_excerpt_id;
+                    excerpts.insert(
+                        excerpt_id,
+                        SplitExcerpt {
+                            layout,
+                            base_excerpt_id,
+                            editor_padding: Vec::new(),
+                            base_editor_padding: Vec::new(),
+                            added_rows: Vec::new(),
+                            deleted_rows: Vec::new(),
+                        },
+                    );
+                    changed_excerpts.push((excerpt_id, buffer.clone(), base_buffer));
+                }
+            }
+        }
+        for (_, removed) in previous_excerpts {
+            stale_base_excerpts.push(removed.base_excerpt_id);
+            stale_editor_padding.extend(removed.editor_padding);
+            stale_base_editor_padding.extend(removed.base_editor_padding);
+        }
+        if changed_excerpts.is_empty() && stale_base_excerpts.is_empty() {
+            self.excerpts = excerpts;
+            return;
+        }
+
+        self.editor.update(cx, |editor, cx| {
+            editor.remove_blocks(stale_editor_padding, None, cx);
+        });
+        self.base_editor.update(cx, |editor, cx| {
+            editor.remove_blocks(stale_base_editor_padding, None, cx);
+        });
+        self.base_multibuffer.update(cx, |base_multibuffer, cx| {
+            base_multibuffer.remove_excerpts(stale_base_excerpts, cx);
+        });
+        let buffer_ids = snapshot
+            .excerpts()
+            .map(|(_, buffer, _)| buffer.remote_id())
+            .collect::<HashSet<_>>();
+        self.base_buffers
+            .retain(|buffer_id, _| buffer_ids.contains(buffer_id));
+
+        let base_snapshot = self.base_multibuffer.read(cx).snapshot(cx);
+        for (excerpt_id, buffer, base_buffer) in changed_excerpts {
+            let Some(excerpt) = excerpts.get_mut(&excerpt_id) else {
+                continue;
+            };
+            let base_excerpt_id = excerpt.base_excerpt_id;
+            let base_buffer = base_buffer.read(cx).text_snapshot();
+            let mut editor_padding = Vec::new();
+            let mut base_editor_padding = Vec::new();
+            for (rows, diff_base_byte_range) in &excerpt.layout.hunks {
+                let base_range = diff_base_byte_range.to_point(&base_buffer);
+                let mut deleted_row_count = base_range.end.row - base_range.start.row;
+                if base_range.end.column > 0 {
+                    deleted_row_count += 1;
+                }
+                let added_row_count = rows.end - rows.start;
+
+                if added_row_count > 0 {
+                    excerpt.added_rows.extend(hunk_rows(
+                        &snapshot,
+                        excerpt_id,
+                        &buffer,
+                        rows.start,
+                        added_row_count,
+                    ));
+                }
+                if deleted_row_count > added_row_count {
+                    editor_padding.extend(padding_block(
+                        &snapshot,
+                        excerpt_id,
+                        &buffer,
+                        rows.start,
+                        added_row_count,
+                        deleted_row_count - added_row_count,
+                    ));
+                }
+                if deleted_row_count > 0 {
+                    excerpt.deleted_rows.extend(hunk_rows(
+                        &base_snapshot,
+                        base_excerpt_id,
+                        &base_buffer,
+                        base_range.start.row,
+                        deleted_row_count,
+                    ));
+                }
+                if added_row_count > deleted_row_count {
+                    base_editor_padding.extend(padding_block(
+                        &base_snapshot,
+                        base_excerpt_id,
+                        &base_buffer,
+                        base_range.start.row,
+                        deleted_row_count,
+                        added_row_count - deleted_row_count,
+                    ));
+                }
+            }
+            excerpt.editor_padding = self.editor.update(cx, |editor, cx| {
+                editor.insert_blocks(editor_padding, None, cx)
+            });
+            excerpt.base_editor_padding = self.base_editor.update(cx, |editor, cx| {
+                editor.insert_blocks(base_editor_padding, None, cx)
+            });
+        }
+
+        let colors = cx.theme().colors();
+        let added_color = colors.version_control_added.opacity(0.12);
+        let deleted_color = colors.version_control_deleted.opacity(0.12);
+        self.editor.update(cx, |editor, cx| {
+            editor.clear_row_highlights::<SplitDiffHunkRows>();
+            for excerpt in excerpts.values() {
+                for rows in &excerpt.added_rows {
+                    editor.highlight_rows::<SplitDiffHunkRows>(
+                        rows.clone(),
+                        added_color,
+                        false,
+                        cx,
+                    );
+                }
+            }
+        });
+        self.base_editor.update(cx, |editor, cx| {
+            editor.clear_row_highlights::<SplitDiffHunkRows>();
+            for excerpt in excerpts.values() {
+                for rows in &excerpt.deleted_rows {
+                    editor.highlight_rows::<SplitDiffHunkRows>(
+                        rows.clone(),
+                        deleted_color,
+                        false,
+                        cx,
+                    );
+                }
+            }
+        });
+        self.excerpts = excerpts;
+        Self::sync_scroll_position(&self.editor, &self.base_editor, window, cx);
+        cx.notify();
+    }
+}
+
+/// Returns the range from the first to the last of a hunk's rows within an
+/// excerpt.
+fn hunk_rows(
+    snapshot: &MultiBufferSnapshot,
+    excerpt_id: ExcerptId,
+    buffer: &text::BufferSnapshot,
+    start_row: u32,
+    row_count: u32,
+) -> Option<Range<Anchor>> {
+    let start = buffer.anchor_before(Point::new(start_row, 0));
+    let end = buffer.clip_point(Point::new(start_row + row_count - 1, 0), Bias::Left);
+    let end = buffer.anchor_before(end);
+    Some(
+        snapshot.anchor_in_excerpt(excerpt_id, start)?
+            ..snapshot.anchor_in_excerpt(excerpt_id, end)?,
+    )
+}
+
+/// Pads the side of a hunk with fewer rows. The padding goes after the rows
+/// that side has, or before the row following the hunk if it has none.
+fn padding_block(
+    snapshot: &MultiBufferSnapshot,
+    excerpt_id: ExcerptId,
+    buffer: &text::BufferSnapshot,
+    start_row: u32,
+    row_count: u32,
+    height: u32,
+) -> Option<BlockProperties<Anchor>> {
+    let placement = if row_count > 0 {
+        let last_row = buffer.clip_point(Point::new(start_row + row_count - 1, 0), Bias::Left);
+        let anchor = buffer.anchor_before(last_row);
+        BlockPlacement::Below(snapshot.anchor_in_excerpt(excerpt_id, anchor)?)
+    } else if start_row <= buffer.max_point().row {
+        let anchor = buffer.anchor_before(Point::new(start_row, 0));
+        BlockPlacement::Above(snapshot.anchor_in_excerpt(excerpt_id, anchor)?)
+    } else {
+        let anchor = buffer.anchor_after(buffer.max_point());
+        BlockPlacement::Below(snapshot.anchor_in_excerpt(excerpt_id, anchor)?)
+    };
+    Some(BlockProperties {
+        placement,
+        height,
+        style: BlockStyle::Fixed,
+        render: Arc::new(|cx| {
+            div()
+                .size_full()
+                .bg(cx
+                    .theme()
+                    .colors()
+                    .editor_active_line_background
+                    .opacity(0.5))
+                .into_any_element()
+        }),
+        priority: 0,
+    })
+}
+
+impl EventEmitter<EditorEvent> for SplitDiff {}
+
+impl Focusable for SplitDiff {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for SplitDiff {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .size_full()
+            .items_start()
+            .child(div().flex_1().h_full().child(self.base_editor.clone()))
+            .child(Divider::vertical().color(DividerColor::Border))
+            .child(div().flex_1().h_full().child(self.editor.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{display_map::ToDisplayPoint as _, editor_tests::init_test};
+    use buffer_diff::BufferDiff;
+    use gpui::{TestAppContext, VisualTestContext};
+
+    #[gpui::test]
+    async fn test_padding_aligns_unchanged_lines(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let (_, editor, split, cx) = split_diff(
+            &[(
+                "one\ntwo\nthree\nfour\nfive\n",
+                "one\nTWO\nfour\nsix\nseven\nfive\n",
+            )],
+            cx,
+        )
+        .await;
+        let base_editor = split.update(cx, |split, _| split.base_editor.clone());
+
+        // The modified hunk is padded below `TWO` in the editor, and the
+        // inserted lines are padded in the base editor.
+        for line in ["one", "four", "five"] {
+            assert_eq!(
+                display_row(&editor, line, cx),
+                display_row(&base_editor, line, cx),
+                "{line} isn't aligned"
+            );
+        }
+        assert_eq!(
+            display_row(&editor, "four", cx) - display_row(&editor, "TWO", cx),
+            2
+        );
+        assert_eq!(
+            display_row(&base_editor, "five", cx) - display_row(&base_editor, "four", cx),
+            3
+        );
+    }
+
+    #[gpui::test]
+    async fn test_scroll_positions_are_synchronized(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let text = (0..100)
+            .map(|row| format!("line {row}\n"))
+            .collect::<String>();
+        let (_, editor, split, cx) = split_diff(&[(text.as_str(), text.as_str())], cx).await;
+        let base_editor = split.update(cx, |split, _| split.base_editor.clone());
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.set_scroll_position(gpui::point(0., 20.), window, cx)
+        });
+        base_editor.update(cx, |base_editor, cx| {
+            assert_eq!(base_editor.scroll_position(cx).y, 20.);
+        });
+
+        base_editor.update_in(cx, |base_editor, window, cx| {
+            base_editor.set_scroll_position(gpui::point(0., 35.), window, cx)
+        });
+        editor.update(cx, |editor, cx| {
+            assert_eq!(editor.scroll_position(cx).y, 35.);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_edits_only_redo_the_edited_excerpt(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let (buffers, _, split, cx) = split_diff(
+            &[("a\nb\nc\n", "a\nB\nc\n"), ("d\ne\nf\n", "d\nE\nF\nf\n")],
+            cx,
+        )
+        .await;
+        let padding = |split: &Entity<SplitDiff>, cx: &mut VisualTestContext| {
+            split.update(cx, |split, _| {
+                let mut padding = split
+                    .excerpts
+                    .iter()
+                    .map(|(excerpt_id, excerpt)| {
+                        (
+                            *excerpt_id,
+                            excerpt.base_excerpt_id,
+                            excerpt.base_editor_padding.clone(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                padding.sort_by_key(|(excerpt_id, _, _)| *excerpt_id);
+                padding
+            })
+        };
+        let before = padding(&split, cx);
+        assert_eq!(before.len(), 2);
+        assert_eq!(before[1].2.len(), 1);
+
+        buffers[1].update(cx, |buffer, cx| buffer.edit([(0..0, "new\n")], None, cx));
+        cx.executor().advance_clock(UPDATE_DEBOUNCE);
+        cx.run_until_parked();
+
+        let after = padding(&split, cx);
+        assert_eq!(after[0], before[0]);
+        assert_eq!(after[1].0, before[1].0);
+        assert_ne!(after[1].1, before[1].1);
+    }
+
+    #[gpui::test]
+    async fn test_unsplit_restores_expanded_hunks(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let (_, editor, split, cx) = split_diff(&[("one\ntwo\n", "one\n")], cx).await;
+        editor.update(cx, |editor, cx| {
+            assert!(!editor.buffer().read(cx).all_diff_hunks_expanded());
+            let split = split.read(cx);
+            assert_eq!(split.excerpts.len(), 1);
+            assert!(split
+                .excerpts
+                .values()
+                .all(|excerpt| excerpt.editor_padding.len() == 1));
+        });
+
+        split.update(cx, |split, cx| split.unsplit(cx));
+        editor.update(cx, |editor, cx| {
+            assert!(editor.buffer().read(cx).all_diff_hunks_expanded());
+            assert_eq!(editor.highlighted_rows::<SplitDiffHunkRows>().count(), 0);
+        });
+        split.update(cx, |split, _| assert!(split.excerpts.is_empty()));
+    }
+
+    /// Opens an editor with an excerpt for each of the given base and buffer
+    /// texts, with the buffers' hunks expanded, and splits it.
+    async fn split_diff<'a>(
+        texts: &[(&str, &str)],
+        cx: &'a mut TestAppContext,
+    ) -> (
+        Vec<Entity<Buffer>>,
+        Entity<Editor>,
+        Entity<SplitDiff>,
+        &'a mut VisualTestContext,
+    ) {
+        let mut buffers = Vec::new();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(Capability::ReadWrite);
+            for (base, text) in texts {
+                let buffer = cx.new(|cx| Buffer::local(*text, cx));
+                let diff = cx.new(|cx| BufferDiff::new_with_base_text(base, &buffer, cx));
+                let max_point = buffer.read(cx).max_point();
+                multibuffer.push_excerpts(
+                    buffer.clone(),
+                    [ExcerptRange {
+                        context: Point::zero()..max_point,
+                        primary: None,
+                    }],
+                    cx,
+                );
+                multibuffer.add_diff(diff, cx);
+                buffers.push(buffer);
+            }
+            multibuffer
+        });
+        let (editor, cx) = cx.add_window_view(|window, cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, None, window, cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor
+        });
+        let split = cx.new_window_entity(|window, cx| SplitDiff::new(editor.clone(), window, cx));
+        cx.run_until_parked();
+        (buffers, editor, split, cx)
+    }
+
+    /// Returns the display row of the given line in an editor.
+    fn display_row(editor: &Entity<Editor>, line: &str, cx: &mut VisualTestContext) -> u32 {
+        editor.update_in(cx, |editor, window, cx| {
+            let snapshot = editor.snapshot(window, cx);
+            let text = snapshot.buffer_snapshot.text();
+            let offset = text
+                .match_indices(&format!("{line}\n"))
+                .map(|(offset, _)| offset)
+                .find(|offset| *offset == 0 || text.as_bytes()[offset - 1] == b'\n')
+                .expect("line not found");
+            offset.to_display_point(&snapshot.display_snapshot).row().0
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use language::OutlineItem;
+use multi_buffer::Anchor;
+use theme::SyntaxTheme;
+
+use crate::{display_map::ToDisplayPoint, DisplayPoint, DisplayRow, EditorSnapshot};
+
+/// An outline item enclosing the top of the viewport, whose first row has
+/// been scrolled out of view.
+#[derive(Clone, Debug)]
+pub struct StickyScope {
+    /// The row the item starts on.
+    pub display_row: DisplayRow,
+    /// The last row of the item.
+    pub end_row: DisplayRow,
+    pub item: OutlineItem<Anchor>,
+}
+
+impl EditorSnapshot {
+    /// Returns the outline items that enclose `top_row`, outermost first, at
+    /// most `max_depth` of them. Each pinned scope covers a row of the
+    /// viewport, so the scope at each depth must still extend past the first
+    /// row visible below the scopes pinned above it.
+    pub fn sticky_scopes(
+        &self,
+        top_row: DisplayRow,
+        max_depth: usize,
+        theme: Option<&SyntaxTheme>,
+    ) -> Vec<StickyScope> {
+        let mut scopes = Vec::new();
+        if max_depth == 0 || top_row > self.max_point().row() {
+            return scopes;
+        }
+
+        // Looking up the end of the top row includes the items starting on
+        // it, which are hidden once any scope is pinned above them.
+        let position = DisplayPoint::new(top_row, self.display_snapshot.line_len(top_row))
+            .to_point(&self.display_snapshot);
+        let Some((_, items)) = self.buffer_snapshot.symbols_containing(position, theme) else {
+            return scopes;
+        };
+        for item in items.into_iter().take(max_depth) {
+            let row = DisplayRow(top_row.0 + scopes.len() as u32);
+            let display_row = item
+                .range
+                .start
+                .to_display_point(&self.display_snapshot)
+                .row();
+            let end_row = item
+                .range
+                .end
+                .to_display_point(&self.display_snapshot)
+                .row();
+            if display_row >= row || end_row < row {
+                break;
+            }
+
+            scopes.push(StickyScope {
+                display_row,
+                end_row,
+                item,
+            });
+        }
+        scopes
+    }
+}
@@ -35,14 +35,31 @@ actions!(
         ToggleStaged,
         StageAndNext,
         UnstageAndNext,
+        StageSelectedLines,
+        UnstageSelectedLines,
+        // per-conflict
+        AcceptOurs,
+        AcceptTheirs,
+        AcceptBoth,
         // per-file
         StageFile,
         UnstageFile,
+        FileHistory,
+        MarkResolved,
+        OpenMergeEditor,
+        // per-commit
+        CherryPick,
+        Revert,
+        InteractiveRebase,
         // repo-wide
         StageAll,
         UnstageAll,
         RestoreTrackedFiles,
         TrashUntrackedFiles,
+        StashAll,
+        StashAllIncludingUntracked,
+        StashPop,
+        ViewHistory,
         Uncommit,
         Push,
         ForcePush,
@@ -52,6 +69,11 @@ actions!(
         ExpandCommitEditor,
         GenerateCommitMessage,
         Init,
+        ContinueOperation,
+        AbortOperation,
+        UpdateSubmodules,
+        SwitchWorktree,
+        OpenPullRequest,
     ]
 );
 
@@ -15,6 +15,59 @@ pub struct PullRequest {
     pub url: Url,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PullRequestState {
+    Open,
+    Draft,
+    Merged,
+    Closed,
+}
+
+/// A pull request opened from a branch, as reported by the hosting provider's API.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BranchPullRequest {
+    pub number: u32,
+    pub title: SharedString,
+    pub url: Url,
+    pub state: PullRequestState,
+    /// The commit at the tip of the pull request, which its checks ran against.
+    pub head_sha: SharedString,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CheckStatus {
+    Pending,
+    Success,
+    Failure,
+    /// The check finished without passing or failing, e.g. because it was skipped.
+    Neutral,
+}
+
+/// A CI check or status reported for a commit.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StatusCheck {
+    pub name: SharedString,
+    pub status: CheckStatus,
+    pub url: Option<Url>,
+}
+
+impl StatusCheck {
+    /// Combines the statuses of a commit's checks into one, where any failure
+    /// fails the commit and any pending check keeps it pending.
+    pub fn summarize(checks: &[StatusCheck]) -> Option<CheckStatus> {
+        let mut summary = None;
+        for check in checks {
+            summary = Some(match (summary, check.status) {
+                (_, CheckStatus::Failure) | (Some(CheckStatus::Failure), _) => CheckStatus::Failure,
+                (_, CheckStatus::Pending) | (Some(CheckStatus::Pending), _) => CheckStatus::Pending,
+                (_, CheckStatus::Success) | (Some(CheckStatus::Success), _) => CheckStatus::Success,
+                _ => CheckStatus::Neutral,
+            });
+        }
+        summary
+    }
+}
+
 pub struct BuildCommitPermalinkParams<'a> {
     pub sha: &'a str,
 }
@@ -86,6 +139,40 @@ pub trait GitHostingProvider {
     ) -> Result<Option<Url>> {
         Ok(None)
     }
+
+    /// Returns whether this provider can look up the pull requests of a branch.
+    fn supports_pull_requests(&self) -> bool {
+        false
+    }
+
+    /// Returns the pull requests opened from the given branch, most recent first.
+    async fn pull_requests_for_branch(
+        &self,
+        _remote: &ParsedGitRemote,
+        _branch: &str,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<BranchPullRequest>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the checks reported for the tip of the given pull request.
+    async fn pull_request_checks(
+        &self,
+        _remote: &ParsedGitRemote,
+        _pull_request: &BranchPullRequest,
+        _http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<StatusCheck>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns a URL to a page for opening a pull request from the given branch.
+    fn build_create_pull_request_url(
+        &self,
+        _remote: &ParsedGitRemote,
+        _branch: &str,
+    ) -> Option<Url> {
+        None
+    }
 }
 
 #[derive(Default, Deref, DerefMut)]
@@ -1,4 +1,4 @@
-use crate::status::FileStatus;
+use crate::status::{FileStatus, StatusCode};
 use crate::SHORT_SHA_LENGTH;
 use crate::{blame::Blame, status::GitStatus};
 use anyhow::{anyhow, Context, Result};
@@ -51,6 +51,16 @@ impl Branch {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points to.
+    pub sha: SharedString,
+    /// The tag's message if it is annotated, and its commit's subject otherwise.
+    pub subject: SharedString,
+    pub timestamp: Option<i64>,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Upstream {
     pub ref_name: SharedString,
@@ -63,6 +73,14 @@ impl Upstream {
             .strip_prefix("refs/remotes/")
             .and_then(|stripped| stripped.split("/").next())
     }
+
+    /// Returns the name of the branch on the remote, which may contain slashes.
+    pub fn branch_name(&self) -> Option<&str> {
+        self.ref_name
+            .strip_prefix("refs/remotes/")
+            .and_then(|stripped| stripped.split_once('/'))
+            .map(|(_, branch)| branch)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -134,11 +152,225 @@ impl CommitDetails {
     }
 }
 
+/// Which part of the history [`GitRepository::log`] lists.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogOptions {
+    /// The number of commits to skip, for paging through the history.
+    pub skip: usize,
+    /// The maximum number of commits to list.
+    pub limit: usize,
+    /// Only list the commits that changed this path.
+    pub path: Option<RepoPath>,
+    /// Keep listing the history of `path` past the commits that renamed it.
+    pub follow_renames: bool,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LogEntry {
+    pub sha: SharedString,
+    pub parents: Vec<SharedString>,
+    pub subject: SharedString,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// This is a unix timestamp
+    pub commit_timestamp: i64,
+}
+
+impl LogEntry {
+    pub fn short_sha(&self) -> SharedString {
+        self.sha[..SHORT_SHA_LENGTH.min(self.sha.len())]
+            .to_string()
+            .into()
+    }
+}
+
+/// The files changed by a commit, with their contents before and after it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitDiff {
+    pub files: Vec<CommitFile>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitFile {
+    pub path: RepoPath,
+    /// The contents of the file in the commit's parent, or `None` if the commit added it.
+    pub old_text: Option<String>,
+    /// The contents of the file in the commit, or `None` if the commit deleted it.
+    pub new_text: Option<String>,
+}
+
+/// The files that differ between the working tree and a revision.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RevisionDiff {
+    /// The sha of the commit the working tree was compared against.
+    pub base: SharedString,
+    pub entries: Vec<RevisionDiffEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevisionDiffEntry {
+    pub path: RepoPath,
+    /// Whether the file was added, deleted, or modified since the revision.
+    pub status: StatusCode,
+}
+
+/// The versions of a conflicted file recorded in the index's merge stages.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictVersions {
+    /// The contents of the file in the merge base (stage 1), or `None` if it did not exist there.
+    pub base: Option<String>,
+    /// The contents of the file on our side (stage 2), or `None` if we deleted it.
+    pub ours: Option<String>,
+    /// The contents of the file on their side (stage 3), or `None` if they deleted it.
+    pub theirs: Option<String>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StashEntry {
+    /// The position of the entry in the stash, where 0 is the most recently stashed.
+    pub index: usize,
+    pub sha: SharedString,
+    pub message: SharedString,
+    /// This is a unix timestamp
+    pub commit_timestamp: i64,
+}
+
+impl StashEntry {
+    /// The name git uses to refer to this entry, e.g. `stash@{0}`.
+    pub fn ref_name(&self) -> String {
+        stash_ref(self.index)
+    }
+}
+
+fn stash_ref(index: usize) -> String {
+    format!("stash@{{{index}}}")
+}
+
+/// An operation that applies commits one at a time, and stops partway for the
+/// user to resolve any that don't apply cleanly.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SequencerOperation {
+    CherryPick,
+    Revert,
+    Rebase,
+}
+
+impl SequencerOperation {
+    /// The git subcommand that runs the operation.
+    pub fn command(self) -> &'static str {
+        match self {
+            SequencerOperation::CherryPick => "cherry-pick",
+            SequencerOperation::Revert => "revert",
+            SequencerOperation::Rebase => "rebase",
+        }
+    }
+}
+
+/// What an interactive rebase does with a commit.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [RebaseAction; 5] = [
+        RebaseAction::Pick,
+        RebaseAction::Reword,
+        RebaseAction::Squash,
+        RebaseAction::Fixup,
+        RebaseAction::Drop,
+    ];
+
+    /// The command for the action in a rebase todo list.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+}
+
+/// A line of an interactive rebase's todo list.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The new message of a reworded commit.
+    pub message: Option<String>,
+}
+
+/// Formats the todo list of an interactive rebase, along with the environment
+/// variables its commands read.
+///
+/// Reworded commits are picked and then amended with their new message, so that
+/// git never has to open an editor.
+fn rebase_todo_script(todo: &[RebaseTodoEntry]) -> (String, Vec<(String, String)>) {
+    let mut script = String::new();
+    let mut env = Vec::new();
+    for entry in todo {
+        match (entry.action, &entry.message) {
+            (RebaseAction::Reword, Some(message)) => {
+                let variable = format!("ZED_REWORD_MESSAGE_{}", env.len());
+                script.push_str(&format!("pick {} {}\n", entry.sha, entry.subject));
+                script.push_str(&format!(
+                    "exec git commit --amend --only --allow-empty --message \"${variable}\"\n"
+                ));
+                env.push((variable, message.clone()));
+            }
+            (action, _) => {
+                script.push_str(&format!(
+                    "{} {} {}\n",
+                    action.as_str(),
+                    entry.sha,
+                    entry.subject
+                ));
+            }
+        }
+    }
+    (script, env)
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Remote {
     pub name: SharedString,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    /// The commit checked out in the submodule, or the one the superproject records
+    /// for it if it isn't initialized.
+    pub sha: SharedString,
+    pub status: SubmoduleStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmoduleStatus {
+    Uninitialized,
+    UpToDate,
+    /// The submodule has a different commit checked out than the superproject records.
+    OutOfDate,
+    Conflicted,
+}
+
+/// A working tree checked out from a repository.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GitWorktree {
+    pub path: PathBuf,
+    pub sha: SharedString,
+    /// The checked out branch, if HEAD isn't detached.
+    pub branch: Option<SharedString>,
+    pub is_main: bool,
+}
+
 pub enum ResetMode {
     // reset the branch pointer, leave index and worktree unchanged
     // (this will make it look like things that were committed are now
@@ -163,6 +395,25 @@ pub trait GitRepository: Send + Sync {
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath, cx: AsyncApp) -> BoxFuture<Option<String>>;
 
+    /// Returns the contents of an entry in the given revision, or None if the revision does not exist or has no entry for the given path.
+    ///
+    /// Also returns `None` for symlinks.
+    fn load_revision_text(
+        &self,
+        revision: String,
+        path: RepoPath,
+        cx: AsyncApp,
+    ) -> BoxFuture<Option<String>>;
+
+    /// Returns the base, ours and theirs versions of a conflicted path from the index's merge stages.
+    ///
+    /// Symlinks are treated as missing on every side.
+    fn load_conflict_versions(
+        &self,
+        path: RepoPath,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<ConflictVersions>>;
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -187,6 +438,43 @@ pub trait GitRepository: Send + Sync {
     fn change_branch(&self, _: String, _: AsyncApp) -> BoxFuture<Result<()>>;
     fn create_branch(&self, _: String, _: AsyncApp) -> BoxFuture<Result<()>>;
 
+    /// Deletes a local branch. Unless `force` is set, this fails if the branch
+    /// has commits that aren't merged into its upstream or HEAD.
+    fn delete_branch(&self, name: String, force: bool, cx: AsyncApp) -> BoxFuture<Result<()>>;
+
+    fn rename_branch(&self, name: String, new_name: String, cx: AsyncApp) -> BoxFuture<Result<()>>;
+
+    /// Makes the branch track `upstream` (such as `origin/main`), or track nothing if `None`.
+    fn set_upstream_branch(
+        &self,
+        name: String,
+        upstream: Option<String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Returns the repository's tags, most recently created first.
+    fn tags(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<Tag>>>;
+
+    /// Tags `target` (or HEAD, if `None`), annotating the tag if a message is given.
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>>;
+
+    fn delete_tag(&self, name: String, cx: AsyncApp) -> BoxFuture<Result<()>>;
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        ask_pass: AskPassSession,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
     fn reset(
         &self,
         commit: String,
@@ -203,6 +491,13 @@ pub trait GitRepository: Send + Sync {
 
     fn show(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDetails>>;
 
+    /// Lists the commits reachable from HEAD, newest first and in topological order, so that no
+    /// commit is listed before all of its children.
+    fn log(&self, options: LogOptions, cx: AsyncApp) -> BoxFuture<Result<Vec<LogEntry>>>;
+
+    /// Loads the contents of the files changed by a commit, before and after it.
+    fn load_commit_diff(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>>;
+
     fn blame(
         &self,
         path: RepoPath,
@@ -281,16 +576,151 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<Result<Vec<Remote>>>;
 
+    /// Returns the submodules registered in the repository.
+    fn submodules(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<Submodule>>>;
+
+    /// Initializes the given submodules (or all of them, if none are given), and
+    /// checks out the commits the repository records for them.
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        askpass: AskPassSession,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
+    /// Returns the working trees checked out from the repository, starting with the main one.
+    fn worktrees(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<GitWorktree>>>;
+
+    /// Checks out `branch` into a new linked working tree at `path`, first creating
+    /// the branch from HEAD if `create_branch` is set.
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        branch: String,
+        create_branch: bool,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>>;
+
     /// returns a list of remote branches that contain HEAD
     fn check_for_pushed_commit(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<SharedString>>>;
 
     /// Run git diff
     fn diff(&self, diff: DiffType, cx: AsyncApp) -> BoxFuture<Result<String>>;
+
+    /// Lists the files that differ between the working tree and the merge base
+    /// of the given revision and HEAD, which for a branch is what merging the
+    /// working tree into it would change.
+    ///
+    /// Untracked files are not included.
+    fn diff_since(&self, revision: String, cx: AsyncApp) -> BoxFuture<Result<RevisionDiff>>;
+
+    /// Stashes the changes in the worktree and index, optionally limited to the given paths.
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        paths: Vec<RepoPath>,
+        include_untracked: bool,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Returns the stash entries, most recent first.
+    fn stash_list(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<StashEntry>>>;
+
+    /// Returns the changes recorded in a stash entry as a patch.
+    fn stash_show(&self, index: usize, cx: AsyncApp) -> BoxFuture<Result<String>>;
+
+    /// Applies the stash entry at `index` to the worktree, keeping it in the stash.
+    ///
+    /// Fails without doing anything if that entry isn't the commit `sha` anymore.
+    fn stash_apply(
+        &self,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Applies the stash entry at `index` to the worktree and removes it from the stash.
+    ///
+    /// Fails without doing anything if that entry isn't the commit `sha` anymore.
+    fn stash_pop(
+        &self,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Removes the stash entry at `index` without applying it.
+    ///
+    /// Fails without doing anything if that entry isn't the commit `sha` anymore.
+    fn stash_drop(
+        &self,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>>;
+
+    /// Applies the changes introduced by the given commits on top of HEAD, in order.
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
+    /// Creates commits that undo the changes introduced by the given commits, in order.
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
+    /// Returns the todo list an interactive rebase onto `onto` starts with, oldest commit first.
+    ///
+    /// If `onto` is `None`, the list covers every commit up to the root.
+    fn rebase_todo(
+        &self,
+        onto: Option<String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<Vec<RebaseTodoEntry>>>;
+
+    /// Runs an interactive rebase onto `onto` (or from the root, if `None`) with the given todo list.
+    fn rebase(
+        &self,
+        onto: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
+    /// Returns the cherry-pick, revert or rebase that has stopped partway through, if any.
+    fn sequencer_operation(&self, cx: AsyncApp) -> BoxFuture<Result<Option<SequencerOperation>>>;
+
+    /// Resumes the stopped operation, once its conflicts have been resolved and staged.
+    fn continue_operation(
+        &self,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
+
+    /// Abandons the stopped operation, returning the repository to where it started.
+    fn abort_operation(
+        &self,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>>;
 }
 
 pub enum DiffType {
     HeadToIndex,
     HeadToWorktree,
+    /// The changes in the working tree since the given revision.
+    RevisionToWorktree(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
@@ -325,6 +755,132 @@ impl RealGitRepository {
             .context("failed to read git work directory")
             .map(Path::to_path_buf)
     }
+
+    fn stash_command(
+        &self,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<String>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .envs(env)
+                .arg("stash")
+                .args(args)
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to run git stash:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        })
+        .boxed()
+    }
+
+    /// Runs a stash subcommand on the entry at `index`, after checking that it's still the
+    /// commit `sha`. Entries shift whenever something is stashed or dropped, so an index
+    /// alone could refer to a different entry than the one the user picked.
+    fn stash_entry_command(
+        &self,
+        subcommand: &'static str,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let working_directory = working_directory?;
+            let stash_ref = stash_ref(index);
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args(["rev-parse", "--verify", "--quiet", &stash_ref])
+                .output()
+                .await?;
+            if !output.status.success() || String::from_utf8_lossy(&output.stdout).trim() != sha {
+                return Err(anyhow!(
+                    "{stash_ref} is no longer the stash entry {sha}, refresh the stash list and try again"
+                ));
+            }
+
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .envs(env)
+                .args(["stash", subcommand, &stash_ref])
+                .output()
+                .await?;
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to run git stash:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+            Ok(())
+        })
+        .boxed()
+    }
+
+    /// Runs a command that may stop for conflicts, reporting git's explanation if it does.
+    fn sequencer_command(
+        &self,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .envs(env)
+                // Keep the default messages of new commits rather than waiting on an editor.
+                .env("GIT_EDITOR", "true")
+                .args(args)
+                .stdin(Stdio::null())
+                .output()
+                .await?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            if !output.status.success() {
+                // Conflicts are listed on stdout, and how to proceed is explained on stderr.
+                return Err(anyhow!("{}\n{}", stdout.trim(), stderr.trim()));
+            }
+            Ok(RemoteCommandOutput { stdout, stderr })
+        })
+        .boxed()
+    }
+
+    /// Runs a command that updates branches or tags, failing with git's explanation if it refuses.
+    fn ref_command(&self, args: Vec<String>, cx: AsyncApp) -> BoxFuture<Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .args(args)
+                .stdin(Stdio::null())
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "{}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            Ok(())
+        })
+        .boxed()
+    }
 }
 
 // https://git-scm.com/book/en/v2/Git-Internals-Git-Objects
@@ -372,6 +928,84 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn log(&self, options: LogOptions, cx: AsyncApp) -> BoxFuture<Result<Vec<LogEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let mut args = vec![
+                "log".to_string(),
+                "--topo-order".to_string(),
+                "--format=%H%x00%P%x00%an%x00%ae%x00%ct%x00%s".to_string(),
+                format!("--skip={}", options.skip),
+                format!("--max-count={}", options.limit),
+            ];
+            if let Some(path) = options.path {
+                if options.follow_renames {
+                    args.push("--follow".to_string());
+                } else {
+                    // Rewrite the parents to the commits that touched the path, so that the
+                    // history still forms a graph.
+                    args.push("--parents".to_string());
+                }
+                args.push("--".to_string());
+                args.push(path.to_string_lossy().into_owned());
+            }
+
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .args(args)
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to run git log:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+            parse_log_output(&String::from_utf8_lossy(&output.stdout))
+        })
+        .boxed()
+    }
+
+    fn load_commit_diff(&self, commit: String, cx: AsyncApp) -> BoxFuture<Result<CommitDiff>> {
+        let repo = self.repository.clone();
+        cx.background_spawn(async move {
+            let repo = repo.lock();
+            let commit = repo.revparse_single(&commit)?.peel_to_commit()?;
+            let new_tree = commit.tree()?;
+            let old_tree = if commit.parent_count() > 0 {
+                Some(commit.parent(0)?.tree()?)
+            } else {
+                None
+            };
+            let mut diff = repo.diff_tree_to_tree(old_tree.as_ref(), Some(&new_tree), None)?;
+            diff.find_similar(None)?;
+
+            let blob_text = |id: git2::Oid| {
+                if id.is_zero() {
+                    return None;
+                }
+                let blob = repo.find_blob(id).ok()?;
+                Some(String::from_utf8_lossy(blob.content()).into_owned())
+            };
+
+            let mut files = Vec::new();
+            for delta in diff.deltas() {
+                let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+                    continue;
+                };
+                files.push(CommitFile {
+                    path: path.into(),
+                    old_text: blob_text(delta.old_file().id()),
+                    new_text: blob_text(delta.new_file().id()),
+                });
+            }
+            Ok(CommitDiff { files })
+        })
+        .boxed()
+    }
+
     fn reset(
         &self,
         commit: String,
@@ -477,6 +1111,68 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn load_revision_text(
+        &self,
+        revision: String,
+        path: RepoPath,
+        cx: AsyncApp,
+    ) -> BoxFuture<Option<String>> {
+        let repo = self.repository.clone();
+        cx.background_spawn(async move {
+            let repo = repo.lock();
+            let tree = repo
+                .revparse_single(&revision)
+                .ok()?
+                .peel_to_tree()
+                .log_err()?;
+            let entry = tree.get_path(&path).ok()?;
+            if entry.filemode() == i32::from(git2::FileMode::Link) {
+                return None;
+            }
+            let content = repo.find_blob(entry.id()).log_err()?.content().to_owned();
+            let content = String::from_utf8(content).log_err()?;
+            Some(content)
+        })
+        .boxed()
+    }
+
+    fn load_conflict_versions(
+        &self,
+        path: RepoPath,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<ConflictVersions>> {
+        let repo = self.repository.clone();
+        cx.background_spawn(async move {
+            const STAGE_BASE: i32 = 1;
+            const STAGE_OURS: i32 = 2;
+            const STAGE_THEIRS: i32 = 3;
+
+            let repo = repo.lock();
+            let index = repo.index()?;
+            check_path_to_repo_path_errors(&path)?;
+
+            let stage_text = |stage| -> Result<Option<String>> {
+                let oid = match index.get_path(&path, stage) {
+                    Some(entry) if entry.mode != GIT_MODE_SYMLINK => entry.id,
+                    _ => return Ok(None),
+                };
+                let content = repo.find_blob(oid)?.content().to_owned();
+                Ok(Some(String::from_utf8(content)?))
+            };
+
+            let versions = ConflictVersions {
+                base: stage_text(STAGE_BASE)?,
+                ours: stage_text(STAGE_OURS)?,
+                theirs: stage_text(STAGE_THEIRS)?,
+            };
+            if versions.ours.is_none() && versions.theirs.is_none() {
+                return Err(anyhow!("{} is not conflicted", path.display()));
+            }
+            Ok(versions)
+        })
+        .boxed()
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -674,6 +1370,124 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn delete_branch(&self, name: String, force: bool, cx: AsyncApp) -> BoxFuture<Result<()>> {
+        let flag = if force { "-D" } else { "-d" };
+        self.ref_command(vec!["branch".into(), flag.into(), "--".into(), name], cx)
+    }
+
+    fn rename_branch(&self, name: String, new_name: String, cx: AsyncApp) -> BoxFuture<Result<()>> {
+        self.ref_command(
+            vec!["branch".into(), "-m".into(), "--".into(), name, new_name],
+            cx,
+        )
+    }
+
+    fn set_upstream_branch(
+        &self,
+        name: String,
+        upstream: Option<String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let args = match upstream {
+            Some(upstream) => vec![
+                "branch".into(),
+                format!("--set-upstream-to={upstream}"),
+                "--".into(),
+                name,
+            ],
+            None => vec![
+                "branch".into(),
+                "--unset-upstream".into(),
+                "--".into(),
+                name,
+            ],
+        };
+        self.ref_command(args, cx)
+    }
+
+    fn tags(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<Tag>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let fields = [
+                "%(refname)",
+                "%(objectname)",
+                "%(*objectname)",
+                "%(creatordate:unix)",
+                "%(contents:subject)",
+            ]
+            .join("%00");
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .args(["for-each-ref", "refs/tags", "--sort=-creatordate"])
+                .arg(format!("--format={fields}"))
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to get git tags:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+
+            parse_tag_input(&String::from_utf8_lossy(&output.stdout))
+        })
+        .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        target: Option<String>,
+        message: Option<String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["tag".to_string()];
+        if let Some(message) = message {
+            args.extend(["-a".into(), "-m".into(), message]);
+        }
+        args.extend(["--".into(), name]);
+        args.extend(target);
+        self.ref_command(args, cx)
+    }
+
+    fn delete_tag(&self, name: String, cx: AsyncApp) -> BoxFuture<Result<()>> {
+        self.ref_command(vec!["tag".into(), "-d".into(), "--".into(), name], cx)
+    }
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        ask_pass: AskPassSession,
+        env: HashMap<String, String>,
+        // git push must be started on the main thread, see `push`.
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env)
+                .env("GIT_ASKPASS", ask_pass.script_path())
+                .env("SSH_ASKPASS", ask_pass.script_path())
+                .env("SSH_ASKPASS_REQUIRE", "force")
+                .env("GIT_HTTP_USER_AGENT", "Zed")
+                .current_dir(&working_directory?)
+                .args(["push"])
+                .arg(remote_name)
+                .arg(format!("refs/tags/{name}"))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+            let git_process = command.spawn()?;
+
+            run_remote_command(ask_pass, git_process).await
+        }
+        .boxed()
+    }
+
     fn blame(
         &self,
         path: RepoPath,
@@ -703,9 +1517,10 @@ impl GitRepository for RealGitRepository {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
         cx.background_spawn(async move {
-            let args = match diff {
+            let args = match &diff {
                 DiffType::HeadToIndex => Some("--staged"),
                 DiffType::HeadToWorktree => None,
+                DiffType::RevisionToWorktree(revision) => Some(revision.as_str()),
             };
 
             let output = new_smol_command(&git_binary_path)
@@ -726,6 +1541,44 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn diff_since(&self, revision: String, cx: AsyncApp) -> BoxFuture<Result<RevisionDiff>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let working_directory = working_directory?;
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args(["merge-base", &revision, "HEAD"])
+                .output()
+                .await?;
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to find the merge base of {revision} and HEAD:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+            let base = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args(["diff", "--name-status", "--no-renames", "-z", &base, "--"])
+                .output()
+                .await?;
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to run git diff:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+
+            Ok(RevisionDiff {
+                base: base.into(),
+                entries: parse_name_status(&String::from_utf8_lossy(&output.stdout)),
+            })
+        })
+        .boxed()
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -934,38 +1787,128 @@ impl GitRepository for RealGitRepository {
                 if output.status.success() {
                     let remote_name = String::from_utf8_lossy(&output.stdout);
 
-                    return Ok(vec![Remote {
-                        name: remote_name.trim().to_string().into(),
-                    }]);
-                }
-            }
+                    return Ok(vec![Remote {
+                        name: remote_name.trim().to_string().into(),
+                    }]);
+                }
+            }
+
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory)
+                .args(["remote"])
+                .output()
+                .await?;
+
+            if output.status.success() {
+                let remote_names = String::from_utf8_lossy(&output.stdout)
+                    .split('\n')
+                    .filter(|name| !name.is_empty())
+                    .map(|name| Remote {
+                        name: name.trim().to_string().into(),
+                    })
+                    .collect();
+
+                return Ok(remote_names);
+            } else {
+                return Err(anyhow!(
+                    "Failed to get remotes:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+        })
+        .boxed()
+    }
+
+    fn submodules(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<Submodule>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .args(["submodule", "status"])
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to get git submodules:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+
+            parse_submodule_status(&String::from_utf8_lossy(&output.stdout))
+        })
+        .boxed()
+    }
+
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassSession,
+        env: HashMap<String, String>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        async move {
+            let mut command = new_smol_command("git");
+            command
+                .envs(env)
+                .env("GIT_ASKPASS", ask_pass.script_path())
+                .env("SSH_ASKPASS", ask_pass.script_path())
+                .env("SSH_ASKPASS_REQUIRE", "force")
+                .current_dir(&working_directory?)
+                .args(["submodule", "update", "--init", "--recursive", "--"])
+                .args(paths.iter().map(|path| path.as_ref()))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+            let git_process = command.spawn()?;
+
+            run_remote_command(ask_pass, git_process).await
+        }
+        .boxed()
+    }
 
+    fn worktrees(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<GitWorktree>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
             let output = new_smol_command(&git_binary_path)
-                .current_dir(&working_directory)
-                .args(["remote"])
+                .current_dir(&working_directory?)
+                .args(["worktree", "list", "--porcelain"])
                 .output()
                 .await?;
 
-            if output.status.success() {
-                let remote_names = String::from_utf8_lossy(&output.stdout)
-                    .split('\n')
-                    .filter(|name| !name.is_empty())
-                    .map(|name| Remote {
-                        name: name.trim().to_string().into(),
-                    })
-                    .collect();
-
-                return Ok(remote_names);
-            } else {
+            if !output.status.success() {
                 return Err(anyhow!(
-                    "Failed to get remotes:\n{}",
+                    "Failed to get git worktrees:\n{}",
                     String::from_utf8_lossy(&output.stderr)
                 ));
             }
+
+            Ok(parse_worktree_list(&String::from_utf8_lossy(
+                &output.stdout,
+            )))
         })
         .boxed()
     }
 
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        branch: String,
+        create_branch: bool,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let path = path.to_string_lossy().to_string();
+        let args = if create_branch {
+            vec!["worktree".into(), "add".into(), "-b".into(), branch, path]
+        } else {
+            vec!["worktree".into(), "add".into(), path, branch]
+        };
+        self.ref_command(args, cx)
+    }
+
     fn check_for_pushed_commit(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<SharedString>>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -1022,6 +1965,216 @@ impl GitRepository for RealGitRepository {
         })
         .boxed()
     }
+
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        paths: Vec<RepoPath>,
+        include_untracked: bool,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let mut args = vec!["push".to_string()];
+        if include_untracked {
+            args.push("--include-untracked".to_string());
+        }
+        if let Some(message) = message {
+            args.push("--message".to_string());
+            args.push(message);
+        }
+        if !paths.is_empty() {
+            args.push("--".to_string());
+            args.extend(paths.iter().map(|path| path.to_string_lossy().into_owned()));
+        }
+        let command = self.stash_command(args, env, cx);
+        async move { command.await.map(|_| ()) }.boxed()
+    }
+
+    fn stash_list(&self, cx: AsyncApp) -> BoxFuture<Result<Vec<StashEntry>>> {
+        let args = vec![
+            "list".to_string(),
+            "--format=%gd%x00%H%x00%ct%x00%gs".to_string(),
+        ];
+        let command = self.stash_command(args, HashMap::default(), cx);
+        async move { parse_stash_list(&command.await?) }.boxed()
+    }
+
+    fn stash_show(&self, index: usize, cx: AsyncApp) -> BoxFuture<Result<String>> {
+        let args = vec!["show".to_string(), "--patch".to_string(), stash_ref(index)];
+        self.stash_command(args, HashMap::default(), cx)
+    }
+
+    fn stash_apply(
+        &self,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        self.stash_entry_command("apply", index, sha, env, cx)
+    }
+
+    fn stash_pop(
+        &self,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        self.stash_entry_command("pop", index, sha, env, cx)
+    }
+
+    fn stash_drop(
+        &self,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        self.stash_entry_command("drop", index, sha, env, cx)
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut args = vec!["cherry-pick".to_string()];
+        args.extend(commits);
+        self.sequencer_command(args, env, cx)
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut args = vec!["revert".to_string(), "--no-edit".to_string()];
+        args.extend(commits);
+        self.sequencer_command(args, env, cx)
+    }
+
+    fn rebase_todo(
+        &self,
+        onto: Option<String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<Vec<RebaseTodoEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        cx.background_spawn(async move {
+            let range = match onto {
+                Some(onto) => format!("{onto}..HEAD"),
+                None => "HEAD".to_string(),
+            };
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .args([
+                    "log",
+                    "--reverse",
+                    "--topo-order",
+                    "--no-merges",
+                    "--format=%H%x00%s",
+                ])
+                .arg(range)
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Failed to list commits to rebase:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let (sha, subject) = line.split_once('\0')?;
+                    Some(RebaseTodoEntry {
+                        action: RebaseAction::Pick,
+                        sha: sha.to_string().into(),
+                        subject: subject.to_string().into(),
+                        message: None,
+                    })
+                })
+                .collect())
+        })
+        .boxed()
+    }
+
+    fn rebase(
+        &self,
+        onto: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        mut env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let (script, variables) = rebase_todo_script(&todo);
+        env.extend(variables);
+        env.insert("ZED_REBASE_TODO".to_string(), script);
+        // Git runs the sequence editor through the shell, passing it the path of the todo list.
+        env.insert(
+            "GIT_SEQUENCE_EDITOR".to_string(),
+            "printf '%s' \"$ZED_REBASE_TODO\" >".to_string(),
+        );
+
+        let mut args = vec!["rebase".to_string(), "--interactive".to_string()];
+        args.push(onto.unwrap_or_else(|| "--root".to_string()));
+        self.sequencer_command(args, env, cx)
+    }
+
+    fn sequencer_operation(&self, cx: AsyncApp) -> BoxFuture<Result<Option<SequencerOperation>>> {
+        let git_dir = self.path();
+        cx.background_spawn(async move { Ok(stopped_sequencer_operation(&git_dir)) })
+            .boxed()
+    }
+
+    fn continue_operation(
+        &self,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let Some(operation) = stopped_sequencer_operation(&self.path()) else {
+            return async { Err(anyhow!("No cherry-pick, revert or rebase is in progress")) }
+                .boxed();
+        };
+        let args = vec![operation.command().to_string(), "--continue".to_string()];
+        self.sequencer_command(args, env, cx)
+    }
+
+    fn abort_operation(
+        &self,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let Some(operation) = stopped_sequencer_operation(&self.path()) else {
+            return async { Err(anyhow!("No cherry-pick, revert or rebase is in progress")) }
+                .boxed();
+        };
+        let args = vec![operation.command().to_string(), "--abort".to_string()];
+        self.sequencer_command(args, env, cx)
+    }
+}
+
+/// Finds the operation that has stopped partway from the state git leaves in the `.git` directory.
+fn stopped_sequencer_operation(git_dir: &Path) -> Option<SequencerOperation> {
+    if git_dir.join("rebase-merge").is_dir() || git_dir.join("rebase-apply").is_dir() {
+        return Some(SequencerOperation::Rebase);
+    }
+    if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        return Some(SequencerOperation::CherryPick);
+    }
+    if git_dir.join("REVERT_HEAD").exists() {
+        return Some(SequencerOperation::Revert);
+    }
+    // Once a conflicting commit of a sequence is committed, only the remaining steps are left.
+    let todo = std::fs::read_to_string(git_dir.join("sequencer").join("todo")).ok()?;
+    match todo.split_whitespace().next()? {
+        "pick" | "p" => Some(SequencerOperation::CherryPick),
+        "revert" => Some(SequencerOperation::Revert),
+        _ => None,
+    }
 }
 
 async fn run_remote_command(
@@ -1071,6 +2224,24 @@ pub struct FakeGitRepositoryState {
     pub statuses: HashMap<RepoPath, FileStatus>,
     pub current_branch_name: Option<String>,
     pub branches: HashSet<String>,
+    /// The remote branch each local branch tracks, such as `origin/main`.
+    pub upstreams: HashMap<String, String>,
+    pub tags: Vec<Tag>,
+    /// The tags that were pushed, along with the remote they were pushed to.
+    pub pushed_tags: Vec<(String, String)>,
+    pub submodules: Vec<Submodule>,
+    pub worktrees: Vec<GitWorktree>,
+    pub stash_entries: Vec<StashEntry>,
+    pub commit_history: Vec<LogEntry>,
+    pub commit_diffs: HashMap<String, CommitDiff>,
+    pub conflict_versions: HashMap<RepoPath, ConflictVersions>,
+    pub revision_contents: HashMap<String, HashMap<RepoPath, String>>,
+    pub revision_diffs: HashMap<String, RevisionDiff>,
+    pub sequencer_operation: Option<SequencerOperation>,
+    /// The cherry-pick, revert and rebase commands that were run, in order.
+    pub sequencer_commands: Vec<String>,
+    /// The todo list of the most recent interactive rebase.
+    pub rebase_todo: Vec<RebaseTodoEntry>,
     pub simulated_index_write_error_message: Option<String>,
 }
 
@@ -1078,6 +2249,70 @@ impl FakeGitRepository {
     pub fn open(state: Arc<Mutex<FakeGitRepositoryState>>) -> Arc<dyn GitRepository> {
         Arc::new(FakeGitRepository { state })
     }
+
+    /// Records a cherry-pick, revert or rebase. Any conflicted files make it stop partway, as if
+    /// applying one of its commits had conflicted.
+    fn start_sequencer_operation(
+        &self,
+        operation: SequencerOperation,
+        args: Vec<String>,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut state = self.state.lock();
+        if let Some(operation) = state.sequencer_operation {
+            let result = Err(anyhow!("A {} is already in progress", operation.command()));
+            return async { result }.boxed();
+        }
+        state.sequencer_commands.push(args.join(" "));
+        if !state.conflict_versions.is_empty() {
+            state.sequencer_operation = Some(operation);
+        }
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async {
+            Ok(RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        }
+        .boxed()
+    }
+
+    /// Continues or aborts the stopped operation.
+    fn finish_sequencer_operation(&self, abort: bool) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut state = self.state.lock();
+        let Some(operation) = state.sequencer_operation else {
+            return async { Err(anyhow!("No cherry-pick, revert or rebase is in progress")) }
+                .boxed();
+        };
+        if abort {
+            state.conflict_versions.clear();
+            state.statuses.retain(|_, status| !status.is_conflicted());
+        } else if !state.conflict_versions.is_empty() {
+            let result = Err(anyhow!(
+                "Resolve and stage all conflicts before continuing the {}",
+                operation.command()
+            ));
+            return async { result }.boxed();
+        }
+        let flag = if abort { "--abort" } else { "--continue" };
+        state
+            .sequencer_commands
+            .push(format!("{} {flag}", operation.command()));
+        state.sequencer_operation = None;
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async {
+            Ok(RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        }
+        .boxed()
+    }
 }
 
 impl FakeGitRepositoryState {
@@ -1091,9 +2326,33 @@ impl FakeGitRepositoryState {
             statuses: Default::default(),
             current_branch_name: Default::default(),
             branches: Default::default(),
+            upstreams: Default::default(),
+            tags: Default::default(),
+            pushed_tags: Default::default(),
+            submodules: Default::default(),
+            worktrees: Default::default(),
+            stash_entries: Default::default(),
+            commit_history: Default::default(),
+            commit_diffs: Default::default(),
+            conflict_versions: Default::default(),
+            revision_contents: Default::default(),
+            revision_diffs: Default::default(),
+            sequencer_operation: Default::default(),
+            sequencer_commands: Default::default(),
+            rebase_todo: Default::default(),
             simulated_index_write_error_message: None,
         }
     }
+
+    fn check_stash_entry(&self, index: usize, sha: &str) -> Result<()> {
+        match self.stash_entries.get(index) {
+            Some(entry) if entry.sha.as_ref() == sha => Ok(()),
+            _ => Err(anyhow!(
+                "{} is no longer the stash entry {sha}, refresh the stash list and try again",
+                stash_ref(index)
+            )),
+        }
+    }
 }
 
 impl GitRepository for FakeGitRepository {
@@ -1111,6 +2370,37 @@ impl GitRepository for FakeGitRepository {
         async { content }.boxed()
     }
 
+    fn load_revision_text(
+        &self,
+        revision: String,
+        path: RepoPath,
+        _: AsyncApp,
+    ) -> BoxFuture<Option<String>> {
+        let content = self
+            .state
+            .lock()
+            .revision_contents
+            .get(&revision)
+            .and_then(|contents| contents.get(&path))
+            .cloned();
+        async { content }.boxed()
+    }
+
+    fn load_conflict_versions(
+        &self,
+        path: RepoPath,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<ConflictVersions>> {
+        let result = self
+            .state
+            .lock()
+            .conflict_versions
+            .get(&path)
+            .cloned()
+            .with_context(|| format!("{} is not conflicted", path.display()));
+        async { result }.boxed()
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -1146,8 +2436,56 @@ impl GitRepository for FakeGitRepository {
         vec![]
     }
 
-    fn show(&self, _: String, _: AsyncApp) -> BoxFuture<Result<CommitDetails>> {
-        unimplemented!()
+    fn show(&self, commit: String, _: AsyncApp) -> BoxFuture<Result<CommitDetails>> {
+        let result = self
+            .state
+            .lock()
+            .commit_history
+            .iter()
+            .find(|entry| entry.sha.as_ref() == commit)
+            .map(|entry| CommitDetails {
+                sha: entry.sha.clone(),
+                message: entry.subject.clone(),
+                commit_timestamp: entry.commit_timestamp,
+                committer_email: entry.author_email.clone(),
+                committer_name: entry.author_name.clone(),
+            })
+            .with_context(|| format!("no commit {commit}"));
+        async { result }.boxed()
+    }
+
+    fn log(&self, options: LogOptions, _: AsyncApp) -> BoxFuture<Result<Vec<LogEntry>>> {
+        let state = self.state.lock();
+        // A commit changed the path if its diff in `commit_diffs` includes it.
+        let entries = state
+            .commit_history
+            .iter()
+            .filter(|entry| {
+                options.path.as_ref().map_or(true, |path| {
+                    state
+                        .commit_diffs
+                        .get(entry.sha.as_ref())
+                        .map_or(false, |diff| {
+                            diff.files.iter().any(|file| file.path == *path)
+                        })
+                })
+            })
+            .skip(options.skip)
+            .take(options.limit)
+            .cloned()
+            .collect();
+        async { Ok(entries) }.boxed()
+    }
+
+    fn load_commit_diff(&self, commit: String, _: AsyncApp) -> BoxFuture<Result<CommitDiff>> {
+        let result = self
+            .state
+            .lock()
+            .commit_diffs
+            .get(&commit)
+            .cloned()
+            .with_context(|| format!("no diff for commit {commit}"));
+        async { result }.boxed()
     }
 
     fn reset(&self, _: String, _: ResetMode, _: HashMap<String, String>) -> BoxFuture<Result<()>> {
@@ -1206,7 +2544,13 @@ impl GitRepository for FakeGitRepository {
                 is_head: Some(branch_name) == current_branch.as_ref(),
                 name: branch_name.into(),
                 most_recent_commit: None,
-                upstream: None,
+                upstream: state.upstreams.get(branch_name).map(|upstream| Upstream {
+                    ref_name: format!("refs/remotes/{upstream}").into(),
+                    tracking: UpstreamTracking::Tracked(UpstreamTrackingStatus {
+                        ahead: 0,
+                        behind: 0,
+                    }),
+                }),
             })
             .collect());
 
@@ -1233,6 +2577,128 @@ impl GitRepository for FakeGitRepository {
         async { Ok(()) }.boxed()
     }
 
+    fn delete_branch(&self, name: String, _force: bool, _: AsyncApp) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        let result = if state.current_branch_name.as_ref() == Some(&name) {
+            Err(anyhow!("cannot delete the checked out branch {name}"))
+        } else if state.branches.remove(&name) {
+            state
+                .event_emitter
+                .try_send(state.path.clone())
+                .expect("Dropped repo change event");
+            Ok(())
+        } else {
+            Err(anyhow!("branch {name} not found"))
+        };
+        async { result }.boxed()
+    }
+
+    fn rename_branch(&self, name: String, new_name: String, _: AsyncApp) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        let result = if state.branches.remove(&name) {
+            if state.current_branch_name.as_ref() == Some(&name) {
+                state.current_branch_name = Some(new_name.clone());
+            }
+            state.branches.insert(new_name);
+            state
+                .event_emitter
+                .try_send(state.path.clone())
+                .expect("Dropped repo change event");
+            Ok(())
+        } else {
+            Err(anyhow!("branch {name} not found"))
+        };
+        async { result }.boxed()
+    }
+
+    fn set_upstream_branch(
+        &self,
+        name: String,
+        upstream: Option<String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        if !state.branches.contains(&name) {
+            let result = Err(anyhow!("branch {name} not found"));
+            return async { result }.boxed();
+        }
+        match upstream {
+            Some(upstream) => state.upstreams.insert(name, upstream),
+            None => state.upstreams.remove(&name),
+        };
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async { Ok(()) }.boxed()
+    }
+
+    fn tags(&self, _: AsyncApp) -> BoxFuture<Result<Vec<Tag>>> {
+        let tags = self.state.lock().tags.clone();
+        async { Ok(tags) }.boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        _target: Option<String>,
+        message: Option<String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        state.tags.insert(
+            0,
+            Tag {
+                name: name.into(),
+                sha: SharedString::default(),
+                subject: message.unwrap_or_default().into(),
+                timestamp: None,
+            },
+        );
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async { Ok(()) }.boxed()
+    }
+
+    fn delete_tag(&self, name: String, _: AsyncApp) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        let len = state.tags.len();
+        state.tags.retain(|tag| tag.name != name);
+        let result = if state.tags.len() < len {
+            state
+                .event_emitter
+                .try_send(state.path.clone())
+                .expect("Dropped repo change event");
+            Ok(())
+        } else {
+            Err(anyhow!("tag {name} not found"))
+        };
+        async { result }.boxed()
+    }
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        _ask_pass: AskPassSession,
+        _env: HashMap<String, String>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut state = self.state.lock();
+        let result = if state.tags.iter().any(|tag| tag.name == name) {
+            state.pushed_tags.push((name, remote_name));
+            Ok(RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        } else {
+            Err(anyhow!("tag {name} not found"))
+        };
+        async { result }.boxed()
+    }
+
     fn blame(
         &self,
         path: RepoPath,
@@ -1250,11 +2716,20 @@ impl GitRepository for FakeGitRepository {
 
     fn stage_paths(
         &self,
-        _paths: Vec<RepoPath>,
+        paths: Vec<RepoPath>,
         _env: HashMap<String, String>,
         _cx: AsyncApp,
     ) -> BoxFuture<Result<()>> {
-        unimplemented!()
+        let mut state = self.state.lock();
+        for path in paths {
+            state.conflict_versions.remove(&path);
+            state.statuses.insert(path, StatusCode::Modified.index());
+        }
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async { Ok(()) }.boxed()
     }
 
     fn unstage_paths(
@@ -1316,6 +2791,74 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn submodules(&self, _: AsyncApp) -> BoxFuture<Result<Vec<Submodule>>> {
+        let submodules = self.state.lock().submodules.clone();
+        async { Ok(submodules) }.boxed()
+    }
+
+    fn update_submodules(
+        &self,
+        paths: Vec<RepoPath>,
+        _ask_pass: AskPassSession,
+        _env: HashMap<String, String>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut state = self.state.lock();
+        if let Some(path) = paths.iter().find(|path| {
+            !state
+                .submodules
+                .iter()
+                .any(|submodule| submodule.path == **path)
+        }) {
+            let result = Err(anyhow!("{} is not a submodule", path.display()));
+            return async { result }.boxed();
+        }
+        for submodule in &mut state.submodules {
+            // Like git, leave submodules with conflicts for the user to resolve.
+            if (paths.is_empty() || paths.contains(&submodule.path))
+                && submodule.status != SubmoduleStatus::Conflicted
+            {
+                submodule.status = SubmoduleStatus::UpToDate;
+            }
+        }
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async {
+            Ok(RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        }
+        .boxed()
+    }
+
+    fn worktrees(&self, _: AsyncApp) -> BoxFuture<Result<Vec<GitWorktree>>> {
+        let worktrees = self.state.lock().worktrees.clone();
+        async { Ok(worktrees) }.boxed()
+    }
+
+    fn create_worktree(
+        &self,
+        path: PathBuf,
+        branch: String,
+        create_branch: bool,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        if create_branch {
+            state.branches.insert(branch.clone());
+        }
+        state.worktrees.push(GitWorktree {
+            path,
+            sha: SharedString::default(),
+            branch: Some(branch.into()),
+            is_main: false,
+        });
+        async { Ok(()) }.boxed()
+    }
+
     fn check_for_pushed_commit(&self, _cx: AsyncApp) -> BoxFuture<Result<Vec<SharedString>>> {
         unimplemented!()
     }
@@ -1323,6 +2866,193 @@ impl GitRepository for FakeGitRepository {
     fn diff(&self, _diff: DiffType, _cx: AsyncApp) -> BoxFuture<Result<String>> {
         unimplemented!()
     }
+
+    fn diff_since(&self, revision: String, _: AsyncApp) -> BoxFuture<Result<RevisionDiff>> {
+        let result = self
+            .state
+            .lock()
+            .revision_diffs
+            .get(&revision)
+            .cloned()
+            .with_context(|| format!("unknown revision {revision}"));
+        async { result }.boxed()
+    }
+
+    fn stash_push(
+        &self,
+        message: Option<String>,
+        _paths: Vec<RepoPath>,
+        _include_untracked: bool,
+        _env: HashMap<String, String>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        let branch = state
+            .current_branch_name
+            .as_deref()
+            .unwrap_or("(no branch)");
+        let message = message.unwrap_or_else(|| "WIP".to_string());
+        let entry = StashEntry {
+            index: 0,
+            sha: format!("{:040x}", state.stash_entries.len()).into(),
+            message: format!("On {branch}: {message}").into(),
+            commit_timestamp: 0,
+        };
+        state.stash_entries.insert(0, entry);
+        for (index, entry) in state.stash_entries.iter_mut().enumerate() {
+            entry.index = index;
+        }
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async { Ok(()) }.boxed()
+    }
+
+    fn stash_list(&self, _cx: AsyncApp) -> BoxFuture<Result<Vec<StashEntry>>> {
+        let entries = self.state.lock().stash_entries.clone();
+        async { Ok(entries) }.boxed()
+    }
+
+    fn stash_show(&self, index: usize, _cx: AsyncApp) -> BoxFuture<Result<String>> {
+        let result = self
+            .state
+            .lock()
+            .stash_entries
+            .get(index)
+            .map(|_| String::new())
+            .with_context(|| format!("no stash entry at index {index}"));
+        async { result }.boxed()
+    }
+
+    fn stash_apply(
+        &self,
+        index: usize,
+        sha: String,
+        _env: HashMap<String, String>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let result = self.state.lock().check_stash_entry(index, &sha);
+        async { result }.boxed()
+    }
+
+    fn stash_pop(
+        &self,
+        index: usize,
+        sha: String,
+        env: HashMap<String, String>,
+        cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        self.stash_drop(index, sha, env, cx)
+    }
+
+    fn stash_drop(
+        &self,
+        index: usize,
+        sha: String,
+        _env: HashMap<String, String>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<Result<()>> {
+        let mut state = self.state.lock();
+        if let Err(error) = state.check_stash_entry(index, &sha) {
+            return async { Err(error) }.boxed();
+        }
+        state.stash_entries.remove(index);
+        for (index, entry) in state.stash_entries.iter_mut().enumerate() {
+            entry.index = index;
+        }
+        state
+            .event_emitter
+            .try_send(state.path.clone())
+            .expect("Dropped repo change event");
+        async { Ok(()) }.boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        _: HashMap<String, String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut args = vec!["cherry-pick".to_string()];
+        args.extend(commits);
+        self.start_sequencer_operation(SequencerOperation::CherryPick, args)
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        _: HashMap<String, String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let mut args = vec!["revert".to_string()];
+        args.extend(commits);
+        self.start_sequencer_operation(SequencerOperation::Revert, args)
+    }
+
+    fn rebase_todo(
+        &self,
+        onto: Option<String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<Vec<RebaseTodoEntry>>> {
+        let mut todo = self
+            .state
+            .lock()
+            .commit_history
+            .iter()
+            .take_while(|entry| Some(entry.sha.as_ref()) != onto.as_deref())
+            .map(|entry| RebaseTodoEntry {
+                action: RebaseAction::Pick,
+                sha: entry.sha.clone(),
+                subject: entry.subject.clone(),
+                message: None,
+            })
+            .collect::<Vec<_>>();
+        todo.reverse();
+        async { Ok(todo) }.boxed()
+    }
+
+    fn rebase(
+        &self,
+        onto: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        _: HashMap<String, String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        let args = vec![
+            "rebase".to_string(),
+            "--interactive".to_string(),
+            onto.unwrap_or_else(|| "--root".to_string()),
+        ];
+        {
+            let mut state = self.state.lock();
+            if state.sequencer_operation.is_none() {
+                state.rebase_todo = todo;
+            }
+        }
+        self.start_sequencer_operation(SequencerOperation::Rebase, args)
+    }
+
+    fn sequencer_operation(&self, _: AsyncApp) -> BoxFuture<Result<Option<SequencerOperation>>> {
+        let operation = self.state.lock().sequencer_operation;
+        async move { Ok(operation) }.boxed()
+    }
+
+    fn continue_operation(
+        &self,
+        _: HashMap<String, String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        self.finish_sequencer_operation(false)
+    }
+
+    fn abort_operation(
+        &self,
+        _: HashMap<String, String>,
+        _: AsyncApp,
+    ) -> BoxFuture<Result<RemoteCommandOutput>> {
+        self.finish_sequencer_operation(true)
+    }
 }
 
 fn check_path_to_repo_path_errors(relative_file_path: &Path) -> Result<()> {
@@ -1444,6 +3174,25 @@ impl MapSeekTarget<RepoPath> for RepoPathDescendants<'_> {
     }
 }
 
+/// Parses the output of `git diff --name-status --no-renames -z`.
+fn parse_name_status(output: &str) -> Vec<RevisionDiffEntry> {
+    let mut fields = output.split('\0');
+    let mut entries = Vec::new();
+    while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+        let status = match status {
+            "A" => StatusCode::Added,
+            "D" => StatusCode::Deleted,
+            "T" => StatusCode::TypeChanged,
+            _ => StatusCode::Modified,
+        };
+        entries.push(RevisionDiffEntry {
+            path: RepoPath::from_str(path),
+            status,
+        });
+    }
+    entries
+}
+
 fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     let mut branches = Vec::new();
     for line in input.split('\n') {
@@ -1493,6 +3242,174 @@ fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     Ok(branches)
 }
 
+fn parse_tag_input(input: &str) -> Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let name: SharedString = fields
+            .next()
+            .context("no refname")?
+            .strip_prefix("refs/tags/")
+            .context("unexpected format for refname")?
+            .to_string()
+            .into();
+        let object_sha = fields.next().context("no objectname")?;
+        // Annotated tags are objects of their own, which point at the commit.
+        let peeled_sha = fields.next().context("no *objectname")?;
+        let sha = if peeled_sha.is_empty() {
+            object_sha
+        } else {
+            peeled_sha
+        };
+        let timestamp = fields.next().context("no creatordate")?.parse::<i64>().ok();
+        let subject: SharedString = fields
+            .next()
+            .context("no contents:subject")?
+            .to_string()
+            .into();
+
+        tags.push(Tag {
+            name,
+            sha: sha.to_string().into(),
+            subject,
+            timestamp,
+        });
+    }
+
+    Ok(tags)
+}
+
+fn parse_submodule_status(input: &str) -> Result<Vec<Submodule>> {
+    let mut submodules = Vec::new();
+    for line in input.lines() {
+        if line.is_empty() {
+            continue;
+        }
+        // Each line is a status character, the commit, the path, and (for
+        // initialized submodules) a description of the commit in parentheses.
+        let status = match line.chars().next() {
+            Some('-') => SubmoduleStatus::Uninitialized,
+            Some('+') => SubmoduleStatus::OutOfDate,
+            Some('U') => SubmoduleStatus::Conflicted,
+            _ => SubmoduleStatus::UpToDate,
+        };
+        let (sha, path) = line[1..]
+            .split_once(' ')
+            .with_context(|| format!("unexpected submodule status: {line}"))?;
+        let path = match path
+            .strip_suffix(')')
+            .and_then(|path| path.rsplit_once(" ("))
+        {
+            Some((path, _description)) => path,
+            None => path,
+        };
+        submodules.push(Submodule {
+            path: RepoPath::from_str(path),
+            sha: sha.to_string().into(),
+            status,
+        });
+    }
+    Ok(submodules)
+}
+
+fn parse_worktree_list(input: &str) -> Vec<GitWorktree> {
+    let mut worktrees = Vec::new();
+    // Worktrees are separated by blank lines, and the main one is listed first.
+    for block in input.split("\n\n") {
+        let mut path = None;
+        let mut sha = SharedString::default();
+        let mut branch = None;
+        for line in block.lines() {
+            if let Some(worktree_path) = line.strip_prefix("worktree ") {
+                path = Some(PathBuf::from(worktree_path));
+            } else if let Some(head) = line.strip_prefix("HEAD ") {
+                sha = head.to_string().into();
+            } else if let Some(ref_name) = line.strip_prefix("branch ") {
+                let name = ref_name.strip_prefix("refs/heads/").unwrap_or(ref_name);
+                branch = Some(name.to_string().into());
+            }
+        }
+        if let Some(path) = path {
+            worktrees.push(GitWorktree {
+                path,
+                sha,
+                branch,
+                is_main: worktrees.is_empty(),
+            });
+        }
+    }
+    worktrees
+}
+
+fn parse_log_output(input: &str) -> Result<Vec<LogEntry>> {
+    let mut entries = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let sha: SharedString = fields.next().context("no commit hash")?.to_string().into();
+        let parents = fields
+            .next()
+            .context("no parent hashes")?
+            .split_whitespace()
+            .map(|parent| parent.to_string().into())
+            .collect();
+        let author_name: SharedString = fields.next().context("no author name")?.to_string().into();
+        let author_email: SharedString =
+            fields.next().context("no author email")?.to_string().into();
+        let commit_timestamp = fields.next().context("no committer date")?.parse::<i64>()?;
+        let subject: SharedString = fields.next().context("no subject")?.to_string().into();
+
+        entries.push(LogEntry {
+            sha,
+            parents,
+            subject,
+            author_name,
+            author_email,
+            commit_timestamp,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn parse_stash_list(input: &str) -> Result<Vec<StashEntry>> {
+    let mut entries = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let index = fields
+            .next()
+            .context("no reflog selector")?
+            .strip_prefix("stash@{")
+            .and_then(|selector| selector.strip_suffix('}'))
+            .context("unexpected format for reflog selector")?
+            .parse::<usize>()?;
+        let sha: SharedString = fields.next().context("no commit hash")?.to_string().into();
+        let commit_timestamp = fields.next().context("no committer date")?.parse::<i64>()?;
+        let message: SharedString = fields
+            .next()
+            .context("no reflog subject")?
+            .to_string()
+            .into();
+
+        entries.push(StashEntry {
+            index,
+            sha,
+            message,
+            commit_timestamp,
+        });
+    }
+
+    Ok(entries)
+}
+
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
     if upstream_track == "" {
         return Ok(UpstreamTracking::Tracked(UpstreamTrackingStatus {
@@ -1552,3 +3469,333 @@ fn test_branches_parsing() {
         }]
     )
 }
+
+#[test]
+fn test_stash_list_parsing() {
+    let input = "stash@{0}\x00d3c9b1e2f1e9c2a1e8b7e6f5d4c3b2a1f0e9d8c7\x001733187470\x00On main: tweak settings\nstash@{1}\x00060964da10574cd9bf06463a53bf6e0769c5c45e\x001733180000\x00WIP on main: 060964d generated protobuf\n";
+    assert_eq!(
+        parse_stash_list(&input).unwrap(),
+        vec![
+            StashEntry {
+                index: 0,
+                sha: "d3c9b1e2f1e9c2a1e8b7e6f5d4c3b2a1f0e9d8c7".into(),
+                message: "On main: tweak settings".into(),
+                commit_timestamp: 1733187470,
+            },
+            StashEntry {
+                index: 1,
+                sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                message: "WIP on main: 060964d generated protobuf".into(),
+                commit_timestamp: 1733180000,
+            },
+        ]
+    )
+}
+
+#[test]
+fn test_log_parsing() {
+    let input = "d3c9b1e2f1e9c2a1e8b7e6f5d4c3b2a1f0e9d8c7\x00060964da10574cd9bf06463a53bf6e0769c5c45e 1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d\x00Jane Doe\x00jane@example.com\x001733187470\x00Merge branch 'fix'\n060964da10574cd9bf06463a53bf6e0769c5c45e\x00\x00John Doe\x00john@example.com\x001733180000\x00generated protobuf\n";
+    assert_eq!(
+        parse_log_output(&input).unwrap(),
+        vec![
+            LogEntry {
+                sha: "d3c9b1e2f1e9c2a1e8b7e6f5d4c3b2a1f0e9d8c7".into(),
+                parents: vec![
+                    "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    "1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d".into(),
+                ],
+                subject: "Merge branch 'fix'".into(),
+                author_name: "Jane Doe".into(),
+                author_email: "jane@example.com".into(),
+                commit_timestamp: 1733187470,
+            },
+            LogEntry {
+                sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                parents: vec![],
+                subject: "generated protobuf".into(),
+                author_name: "John Doe".into(),
+                author_email: "john@example.com".into(),
+                commit_timestamp: 1733180000,
+            },
+        ]
+    )
+}
+
+#[test]
+fn test_rebase_todo_script() {
+    let entry = |action, sha: &str, subject: &str, message: Option<&str>| RebaseTodoEntry {
+        action,
+        sha: sha.to_string().into(),
+        subject: subject.to_string().into(),
+        message: message.map(str::to_string),
+    };
+    let (script, env) = rebase_todo_script(&[
+        entry(RebaseAction::Pick, "aaa", "first", None),
+        entry(
+            RebaseAction::Reword,
+            "bbb",
+            "second",
+            Some("Second, reworded"),
+        ),
+        entry(RebaseAction::Fixup, "ccc", "fix second", None),
+        entry(RebaseAction::Drop, "ddd", "wip", None),
+        entry(
+            RebaseAction::Reword,
+            "eee",
+            "fifth",
+            Some("Fifth\n\nWith a body"),
+        ),
+    ]);
+    assert_eq!(
+        script,
+        "pick aaa first\n\
+         pick bbb second\n\
+         exec git commit --amend --only --allow-empty --message \"$ZED_REWORD_MESSAGE_0\"\n\
+         fixup ccc fix second\n\
+         drop ddd wip\n\
+         pick eee fifth\n\
+         exec git commit --amend --only --allow-empty --message \"$ZED_REWORD_MESSAGE_1\"\n"
+    );
+    assert_eq!(
+        env,
+        vec![
+            (
+                "ZED_REWORD_MESSAGE_0".to_string(),
+                "Second, reworded".to_string()
+            ),
+            (
+                "ZED_REWORD_MESSAGE_1".to_string(),
+                "Fifth\n\nWith a body".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_name_status() {
+    let entries = parse_name_status("M\0src/lib.rs\0A\0new file.txt\0D\0old.txt\0T\0link\0");
+    assert_eq!(
+        entries,
+        vec![
+            RevisionDiffEntry {
+                path: RepoPath::from_str("src/lib.rs"),
+                status: StatusCode::Modified,
+            },
+            RevisionDiffEntry {
+                path: RepoPath::from_str("new file.txt"),
+                status: StatusCode::Added,
+            },
+            RevisionDiffEntry {
+                path: RepoPath::from_str("old.txt"),
+                status: StatusCode::Deleted,
+            },
+            RevisionDiffEntry {
+                path: RepoPath::from_str("link"),
+                status: StatusCode::TypeChanged,
+            },
+        ]
+    );
+    assert_eq!(parse_name_status(""), vec![]);
+}
+
+#[test]
+fn test_parse_tag_input() {
+    let input = "refs/tags/v1.0\0aaaa\0\01700000000\0Bump version\n\
+                 refs/tags/v0.9\0bbbb\0cccc\01600000000\0Release 0.9\n";
+    assert_eq!(
+        parse_tag_input(input).unwrap(),
+        vec![
+            Tag {
+                name: "v1.0".into(),
+                sha: "aaaa".into(),
+                subject: "Bump version".into(),
+                timestamp: Some(1700000000),
+            },
+            Tag {
+                name: "v0.9".into(),
+                sha: "cccc".into(),
+                subject: "Release 0.9".into(),
+                timestamp: Some(1600000000),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_submodule_status() {
+    let input = " 1111111111111111111111111111111111111111 vendor/lib (v1.2.0)\n\
+                 -2222222222222222222222222222222222222222 docs/theme\n\
+                 +3333333333333333333333333333333333333333 tools/my tool (heads/main)\n";
+    assert_eq!(
+        parse_submodule_status(input).unwrap(),
+        vec![
+            Submodule {
+                path: RepoPath::from_str("vendor/lib"),
+                sha: "1111111111111111111111111111111111111111".into(),
+                status: SubmoduleStatus::UpToDate,
+            },
+            Submodule {
+                path: RepoPath::from_str("docs/theme"),
+                sha: "2222222222222222222222222222222222222222".into(),
+                status: SubmoduleStatus::Uninitialized,
+            },
+            Submodule {
+                path: RepoPath::from_str("tools/my tool"),
+                sha: "3333333333333333333333333333333333333333".into(),
+                status: SubmoduleStatus::OutOfDate,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_worktree_list() {
+    let input = "worktree /home/me/project\n\
+                 HEAD aaaa\n\
+                 branch refs/heads/main\n\
+                 \n\
+                 worktree /home/me/project-fix\n\
+                 HEAD bbbb\n\
+                 detached\n\
+                 \n";
+    assert_eq!(
+        parse_worktree_list(input),
+        vec![
+            GitWorktree {
+                path: PathBuf::from("/home/me/project"),
+                sha: "aaaa".into(),
+                branch: Some("main".into()),
+                is_main: true,
+            },
+            GitWorktree {
+                path: PathBuf::from("/home/me/project-fix"),
+                sha: "bbbb".into(),
+                branch: None,
+                is_main: false,
+            },
+        ]
+    );
+}
+
+#[gpui::test]
+async fn test_load_conflict_versions(cx: &mut gpui::TestAppContext) {
+    let dir = util::test::TempTree::new(serde_json::json!({
+        "file.txt": "base\n",
+        "deleted.txt": "base\n",
+    }));
+    let repo = git2::Repository::init(dir.path()).unwrap();
+    let signature = git2::Signature::now("test", "test@zed.dev").unwrap();
+    let commit = |files: &[(&str, Option<&str>)], parents: &[&git2::Commit<'_>]| {
+        let mut index = repo.index().unwrap();
+        for (path, text) in files {
+            match text {
+                Some(text) => {
+                    std::fs::write(dir.path().join(path), text).unwrap();
+                    index.add_path(Path::new(path)).unwrap();
+                }
+                None => {
+                    std::fs::remove_file(dir.path().join(path)).unwrap();
+                    index.remove_path(Path::new(path)).unwrap();
+                }
+            }
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let oid = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "commit",
+                &tree,
+                parents,
+            )
+            .unwrap();
+        repo.find_commit(oid).unwrap()
+    };
+
+    // Cherry-pick a change made on top of the base onto a conflicting one, where one file is
+    // modified on our side and deleted on theirs.
+    let base = commit(
+        &[
+            ("file.txt", Some("base\n")),
+            ("deleted.txt", Some("base\n")),
+        ],
+        &[],
+    );
+    let theirs = commit(
+        &[("file.txt", Some("theirs\n")), ("deleted.txt", None)],
+        &[&base],
+    );
+    repo.reset(base.as_object(), git2::ResetType::Hard, None)
+        .unwrap();
+    commit(
+        &[
+            ("file.txt", Some("ours\n")),
+            ("deleted.txt", Some("ours\n")),
+        ],
+        &[&base],
+    );
+    repo.cherrypick(&theirs, None).unwrap();
+    assert!(repo.index().unwrap().has_conflicts());
+
+    let repo = RealGitRepository::new(git2::Repository::open(dir.path()).unwrap(), None);
+    let versions = repo
+        .load_conflict_versions("file.txt".into(), cx.to_async())
+        .await
+        .unwrap();
+    assert_eq!(
+        versions,
+        ConflictVersions {
+            base: Some("base\n".into()),
+            ours: Some("ours\n".into()),
+            theirs: Some("theirs\n".into()),
+        }
+    );
+
+    // The deleted side has no stage in the index.
+    let versions = repo
+        .load_conflict_versions("deleted.txt".into(), cx.to_async())
+        .await
+        .unwrap();
+    assert_eq!(
+        versions,
+        ConflictVersions {
+            base: Some("base\n".into()),
+            ours: Some("ours\n".into()),
+            theirs: None,
+        }
+    );
+
+    let error = repo
+        .load_conflict_versions("other.txt".into(), cx.to_async())
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "other.txt is not conflicted");
+}
+
+#[gpui::test]
+async fn test_fake_load_conflict_versions(cx: &mut gpui::TestAppContext) {
+    let (event_emitter, _) = smol::channel::unbounded();
+    let mut state = FakeGitRepositoryState::new(PathBuf::from("/repo/.git"), event_emitter);
+    let versions = ConflictVersions {
+        base: None,
+        ours: Some("ours\n".into()),
+        theirs: Some("theirs\n".into()),
+    };
+    state
+        .conflict_versions
+        .insert("file.txt".into(), versions.clone());
+    let repo = FakeGitRepository::open(Arc::new(Mutex::new(state)));
+
+    assert_eq!(
+        repo.load_conflict_versions("file.txt".into(), cx.to_async())
+            .await
+            .unwrap(),
+        versions
+    );
+    assert!(repo
+        .load_conflict_versions("other.txt".into(), cx.to_async())
+        .await
+        .is_err());
+}
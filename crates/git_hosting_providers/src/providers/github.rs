@@ -7,12 +7,12 @@ use futures::AsyncReadExt;
 use gpui::SharedString;
 use http_client::{AsyncBody, HttpClient, HttpRequestExt, Request};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 use url::Url;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    PullRequest, RemoteUrl,
+    BranchPullRequest, BuildCommitPermalinkParams, BuildPermalinkParams, CheckStatus,
+    GitHostingProvider, ParsedGitRemote, PullRequest, PullRequestState, RemoteUrl, StatusCheck,
 };
 
 use crate::get_host_from_git_remote_url;
@@ -45,6 +45,36 @@ struct User {
     pub avatar_url: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct PullRequestDetails {
+    number: u32,
+    title: String,
+    html_url: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    merged_at: Option<String>,
+    head: PullRequestHead,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestHead {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRuns {
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRun {
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+}
+
 pub struct Github {
     name: String,
     base_url: Url,
@@ -77,19 +107,19 @@ impl Github {
         })
     }
 
-    async fn fetch_github_commit_author(
-        &self,
-        repo_owner: &str,
-        repo: &str,
-        commit: &str,
-        client: &Arc<dyn HttpClient>,
-    ) -> Result<Option<User>> {
+    fn api_url(&self, path: &str) -> Result<Url> {
         let Some(host) = self.base_url.host_str() else {
             bail!("failed to get host from github base url");
         };
-        let url = format!("https://api.{host}/repos/{repo_owner}/{repo}/commits/{commit}");
+        Ok(Url::parse(&format!("https://api.{host}/{path}"))?)
+    }
 
-        let mut request = Request::get(&url)
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        url: &Url,
+        client: &Arc<dyn HttpClient>,
+    ) -> Result<T> {
+        let mut request = Request::get(url.as_str())
             .header("Content-Type", "application/json")
             .follow_redirects(http_client::RedirectPolicy::FollowAll);
 
@@ -100,7 +130,7 @@ impl Github {
         let mut response = client
             .send(request.body(AsyncBody::default())?)
             .await
-            .with_context(|| format!("error fetching GitHub commit details at {:?}", url))?;
+            .with_context(|| format!("error fetching {url}"))?;
 
         let mut body = Vec::new();
         response.body_mut().read_to_end(&mut body).await?;
@@ -114,10 +144,21 @@ impl Github {
         }
 
         let body_str = std::str::from_utf8(&body)?;
+        Ok(serde_json::from_str::<T>(body_str)?)
+    }
 
-        serde_json::from_str::<CommitDetails>(body_str)
+    async fn fetch_github_commit_author(
+        &self,
+        repo_owner: &str,
+        repo: &str,
+        commit: &str,
+        client: &Arc<dyn HttpClient>,
+    ) -> Result<Option<User>> {
+        let url = self.api_url(&format!("repos/{repo_owner}/{repo}/commits/{commit}"))?;
+        self.fetch::<CommitDetails>(&url, client)
+            .await
             .map(|commit| commit.author)
-            .context("failed to deserialize GitHub commit details")
+            .context("failed to fetch GitHub commit details")
     }
 }
 
@@ -230,10 +271,94 @@ impl GitHostingProvider for Github {
             .transpose()?;
         Ok(avatar_url)
     }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    async fn pull_requests_for_branch(
+        &self,
+        remote: &ParsedGitRemote,
+        branch: &str,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<BranchPullRequest>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let mut url = self.api_url(&format!("repos/{owner}/{repo}/pulls"))?;
+        url.query_pairs_mut()
+            .append_pair("head", &format!("{owner}:{branch}"))
+            .append_pair("state", "all");
+        let pull_requests = self
+            .fetch::<Vec<PullRequestDetails>>(&url, &http_client)
+            .await
+            .context("failed to fetch GitHub pull requests")?;
+
+        pull_requests
+            .into_iter()
+            .map(|pull_request| {
+                let state = match pull_request.state.as_str() {
+                    _ if pull_request.merged_at.is_some() => PullRequestState::Merged,
+                    "open" if pull_request.draft => PullRequestState::Draft,
+                    "open" => PullRequestState::Open,
+                    _ => PullRequestState::Closed,
+                };
+                Ok(BranchPullRequest {
+                    number: pull_request.number,
+                    title: pull_request.title.into(),
+                    url: Url::parse(&pull_request.html_url)?,
+                    state,
+                    head_sha: pull_request.head.sha.into(),
+                })
+            })
+            .collect()
+    }
+
+    async fn pull_request_checks(
+        &self,
+        remote: &ParsedGitRemote,
+        pull_request: &BranchPullRequest,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<StatusCheck>> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let sha = &pull_request.head_sha;
+        let url = self.api_url(&format!("repos/{owner}/{repo}/commits/{sha}/check-runs"))?;
+        let check_runs = self
+            .fetch::<CheckRuns>(&url, &http_client)
+            .await
+            .context("failed to fetch GitHub check runs")?;
+
+        Ok(check_runs
+            .check_runs
+            .into_iter()
+            .map(|check_run| {
+                let status = match (check_run.status.as_str(), check_run.conclusion.as_deref()) {
+                    ("completed", Some("success")) => CheckStatus::Success,
+                    ("completed", Some("neutral" | "skipped")) => CheckStatus::Neutral,
+                    ("completed", _) => CheckStatus::Failure,
+                    _ => CheckStatus::Pending,
+                };
+                StatusCheck {
+                    name: check_run.name.into(),
+                    status,
+                    url: check_run.html_url.and_then(|url| Url::parse(&url).ok()),
+                }
+            })
+            .collect())
+    }
+
+    fn build_create_pull_request_url(&self, remote: &ParsedGitRemote, branch: &str) -> Option<Url> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let mut url = self
+            .base_url()
+            .join(&format!("{owner}/{repo}/compare/{branch}"))
+            .ok()?;
+        url.set_query(Some("expand=1"));
+        Some(url)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use http_client::{FakeHttpClient, Response};
     use indoc::indoc;
     use pretty_assertions::assert_eq;
 
@@ -465,4 +590,99 @@ mod tests {
         };
         assert_eq!(github.extract_pull_request(&remote, &message), None);
     }
+
+    #[test]
+    fn test_pull_requests_and_checks_for_branch() {
+        let http_client = FakeHttpClient::create(|request| async move {
+            let body = match request.uri().to_string().as_str() {
+                "https://api.github.com/repos/zed-industries/zed/pulls?head=zed-industries%3Afix-panel&state=all" => {
+                    r#"[
+                        {
+                            "number": 10687,
+                            "title": "Fix panel",
+                            "html_url": "https://github.com/zed-industries/zed/pull/10687",
+                            "state": "open",
+                            "draft": false,
+                            "merged_at": null,
+                            "head": { "sha": "abc123" }
+                        },
+                        {
+                            "number": 10500,
+                            "title": "Fix panel (first try)",
+                            "html_url": "https://github.com/zed-industries/zed/pull/10500",
+                            "state": "closed",
+                            "merged_at": "2024-04-01T00:00:00Z",
+                            "head": { "sha": "def456" }
+                        }
+                    ]"#
+                }
+                "https://api.github.com/repos/zed-industries/zed/commits/abc123/check-runs" => {
+                    r#"{
+                        "check_runs": [
+                            { "name": "tests", "status": "completed", "conclusion": "success", "html_url": null },
+                            { "name": "clippy", "status": "completed", "conclusion": "failure", "html_url": null },
+                            { "name": "bundle", "status": "in_progress", "conclusion": null, "html_url": null }
+                        ]
+                    }"#
+                }
+                _ => {
+                    return Ok(Response::builder()
+                        .status(404)
+                        .body(Default::default())
+                        .unwrap())
+                }
+            };
+            Ok(Response::builder().status(200).body(body.into()).unwrap())
+        });
+
+        let github = Github::new();
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let pull_requests = futures::executor::block_on(github.pull_requests_for_branch(
+            &remote,
+            "fix-panel",
+            http_client.clone(),
+        ))
+        .unwrap();
+        assert_eq!(
+            pull_requests
+                .iter()
+                .map(|pull_request| (pull_request.number, pull_request.state))
+                .collect::<Vec<_>>(),
+            vec![
+                (10687, PullRequestState::Open),
+                (10500, PullRequestState::Merged)
+            ]
+        );
+
+        let checks = futures::executor::block_on(github.pull_request_checks(
+            &remote,
+            &pull_requests[0],
+            http_client,
+        ))
+        .unwrap();
+        assert_eq!(
+            checks
+                .iter()
+                .map(|check| (check.name.as_ref(), check.status))
+                .collect::<Vec<_>>(),
+            vec![
+                ("tests", CheckStatus::Success),
+                ("clippy", CheckStatus::Failure),
+                ("bundle", CheckStatus::Pending)
+            ]
+        );
+        assert_eq!(StatusCheck::summarize(&checks), Some(CheckStatus::Failure));
+
+        assert_eq!(
+            github
+                .build_create_pull_request_url(&remote, "fix-panel")
+                .unwrap()
+                .as_str(),
+            "https://github.com/zed-industries/zed/compare/fix-panel?expand=1"
+        );
+    }
 }
@@ -1,15 +1,38 @@
 use std::str::FromStr;
+use std::sync::Arc;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use futures::AsyncReadExt;
+use http_client::{AsyncBody, HttpClient, HttpRequestExt, Request};
+use serde::{de::DeserializeOwned, Deserialize};
 use url::Url;
 
 use git::{
-    BuildCommitPermalinkParams, BuildPermalinkParams, GitHostingProvider, ParsedGitRemote,
-    RemoteUrl,
+    BranchPullRequest, BuildCommitPermalinkParams, BuildPermalinkParams, CheckStatus,
+    GitHostingProvider, ParsedGitRemote, PullRequestState, RemoteUrl, StatusCheck,
 };
 
 use crate::get_host_from_git_remote_url;
 
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: u32,
+    title: String,
+    web_url: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    name: String,
+    status: String,
+    target_url: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct Gitlab {
     name: String,
@@ -42,8 +65,53 @@ impl Gitlab {
             base_url: Url::parse(&format!("https://{}", host))?,
         })
     }
+
+    /// Returns the API URL of the project at `remote`, followed by `path`.
+    fn project_api_url(&self, remote: &ParsedGitRemote, path: &[&str]) -> Result<Url> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let mut url = self.base_url.join("api/v4/projects")?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("invalid GitLab base url"))?
+            .push(&format!("{owner}/{repo}"))
+            .extend(path);
+        Ok(url)
+    }
+
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        url: &Url,
+        client: &Arc<dyn HttpClient>,
+    ) -> Result<T> {
+        let mut request = Request::get(url.as_str())
+            .header("Content-Type", "application/json")
+            .follow_redirects(http_client::RedirectPolicy::FollowAll);
+
+        if let Ok(gitlab_token) = std::env::var("GITLAB_TOKEN") {
+            request = request.header("PRIVATE-TOKEN", gitlab_token);
+        }
+
+        let mut response = client
+            .send(request.body(AsyncBody::default())?)
+            .await
+            .with_context(|| format!("error fetching {url}"))?;
+
+        let mut body = Vec::new();
+        response.body_mut().read_to_end(&mut body).await?;
+
+        if response.status().is_client_error() {
+            let text = String::from_utf8_lossy(body.as_slice());
+            bail!(
+                "status error {}, response: {text:?}",
+                response.status().as_u16()
+            );
+        }
+
+        let body_str = std::str::from_utf8(&body)?;
+        Ok(serde_json::from_str::<T>(body_str)?)
+    }
 }
 
+#[async_trait]
 impl GitHostingProvider for Gitlab {
     fn name(&self) -> String {
         self.name.clone()
@@ -118,10 +186,89 @@ impl GitHostingProvider for Gitlab {
         );
         permalink
     }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    async fn pull_requests_for_branch(
+        &self,
+        remote: &ParsedGitRemote,
+        branch: &str,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<BranchPullRequest>> {
+        let mut url = self.project_api_url(remote, &["merge_requests"])?;
+        url.query_pairs_mut().append_pair("source_branch", branch);
+        let merge_requests = self
+            .fetch::<Vec<MergeRequest>>(&url, &http_client)
+            .await
+            .context("failed to fetch GitLab merge requests")?;
+
+        merge_requests
+            .into_iter()
+            .map(|merge_request| {
+                let state = match merge_request.state.as_str() {
+                    "opened" if merge_request.draft => PullRequestState::Draft,
+                    "opened" => PullRequestState::Open,
+                    "merged" => PullRequestState::Merged,
+                    _ => PullRequestState::Closed,
+                };
+                Ok(BranchPullRequest {
+                    number: merge_request.iid,
+                    title: merge_request.title.into(),
+                    url: Url::parse(&merge_request.web_url)?,
+                    state,
+                    head_sha: merge_request.sha.into(),
+                })
+            })
+            .collect()
+    }
+
+    async fn pull_request_checks(
+        &self,
+        remote: &ParsedGitRemote,
+        pull_request: &BranchPullRequest,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Result<Vec<StatusCheck>> {
+        let url = self.project_api_url(
+            remote,
+            &["repository", "commits", &*pull_request.head_sha, "statuses"],
+        )?;
+        let statuses = self
+            .fetch::<Vec<CommitStatus>>(&url, &http_client)
+            .await
+            .context("failed to fetch GitLab commit statuses")?;
+
+        Ok(statuses
+            .into_iter()
+            .map(|status| StatusCheck {
+                name: status.name.into(),
+                status: match status.status.as_str() {
+                    "success" => CheckStatus::Success,
+                    "failed" | "canceled" => CheckStatus::Failure,
+                    "skipped" | "manual" => CheckStatus::Neutral,
+                    _ => CheckStatus::Pending,
+                },
+                url: status.target_url.and_then(|url| Url::parse(&url).ok()),
+            })
+            .collect())
+    }
+
+    fn build_create_pull_request_url(&self, remote: &ParsedGitRemote, branch: &str) -> Option<Url> {
+        let ParsedGitRemote { owner, repo } = remote;
+        let mut url = self
+            .base_url()
+            .join(&format!("{owner}/{repo}/-/merge_requests/new"))
+            .ok()?;
+        url.query_pairs_mut()
+            .append_pair("merge_request[source_branch]", branch);
+        Some(url)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use http_client::{FakeHttpClient, Response};
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -293,4 +440,82 @@ mod tests {
         let expected_url = "https://gitlab-instance.big-co.com/zed-industries/zed/-/blob/b2efec9824c45fcc90c9a7eb107a50d1772a60aa/crates/zed/src/main.rs";
         assert_eq!(permalink.to_string(), expected_url.to_string())
     }
+
+    #[test]
+    fn test_merge_requests_and_statuses_for_branch() {
+        let http_client = FakeHttpClient::create(|request| async move {
+            let body = match request.uri().to_string().as_str() {
+                "https://gitlab.com/api/v4/projects/zed-industries%2Fzed/merge_requests?source_branch=fix-panel" => {
+                    r#"[
+                        {
+                            "iid": 12,
+                            "title": "Draft: Fix panel",
+                            "web_url": "https://gitlab.com/zed-industries/zed/-/merge_requests/12",
+                            "state": "opened",
+                            "draft": true,
+                            "sha": "abc123"
+                        }
+                    ]"#
+                }
+                "https://gitlab.com/api/v4/projects/zed-industries%2Fzed/repository/commits/abc123/statuses" => {
+                    r#"[
+                        { "name": "test", "status": "success", "target_url": "https://gitlab.com/zed-industries/zed/-/jobs/1" },
+                        { "name": "deploy", "status": "manual", "target_url": null }
+                    ]"#
+                }
+                _ => {
+                    return Ok(Response::builder()
+                        .status(404)
+                        .body(Default::default())
+                        .unwrap())
+                }
+            };
+            Ok(Response::builder().status(200).body(body.into()).unwrap())
+        });
+
+        let gitlab = Gitlab::new();
+        let remote = ParsedGitRemote {
+            owner: "zed-industries".into(),
+            repo: "zed".into(),
+        };
+
+        let merge_requests = futures::executor::block_on(gitlab.pull_requests_for_branch(
+            &remote,
+            "fix-panel",
+            http_client.clone(),
+        ))
+        .unwrap();
+        assert_eq!(merge_requests.len(), 1);
+        assert_eq!(merge_requests[0].number, 12);
+        assert_eq!(merge_requests[0].state, PullRequestState::Draft);
+
+        let statuses = futures::executor::block_on(gitlab.pull_request_checks(
+            &remote,
+            &merge_requests[0],
+            http_client,
+        ))
+        .unwrap();
+        assert_eq!(
+            statuses
+                .iter()
+                .map(|status| (status.name.as_ref(), status.status))
+                .collect::<Vec<_>>(),
+            vec![
+                ("test", CheckStatus::Success),
+                ("deploy", CheckStatus::Neutral)
+            ]
+        );
+        assert_eq!(
+            StatusCheck::summarize(&statuses),
+            Some(CheckStatus::Success)
+        );
+
+        assert_eq!(
+            gitlab
+                .build_create_pull_request_url(&remote, "fix-panel")
+                .unwrap()
+                .as_str(),
+            "https://gitlab.com/zed-industries/zed/-/merge_requests/new?merge_request%5Bsource_branch%5D=fix-panel"
+        );
+    }
 }
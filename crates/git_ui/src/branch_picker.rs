@@ -1,22 +1,25 @@
 use anyhow::{anyhow, Context as _};
 use fuzzy::StringMatchCandidate;
 
-use git::repository::Branch;
+use crate::git_panel::GitPanel;
+use git::repository::{Branch, Tag};
 use gpui::{
-    rems, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, Modifiers, ModifiersChangedEvent, ParentElement, Render,
-    SharedString, Styled, Subscription, Task, Window,
+    actions, rems, Action, App, BackgroundExecutor, Context, DismissEvent, Entity, EventEmitter,
+    FocusHandle, Focusable, InteractiveElement, IntoElement, Modifiers, ModifiersChangedEvent,
+    ParentElement, PromptLevel, Render, SharedString, Styled, Subscription, Task, Window,
 };
 use picker::{Picker, PickerDelegate};
 use project::git::Repository;
 use std::sync::Arc;
 use time::OffsetDateTime;
 use time_format::format_local_timestamp;
-use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use ui::{prelude::*, HighlightedLabel, KeyBinding, ListItem, ListItemSpacing};
 use util::ResultExt;
 use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
 
+actions!(branch_picker, [Delete, Rename, SetUpstream, ToggleTags]);
+
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _, _| {
         workspace.register_action(open);
@@ -139,6 +142,55 @@ impl BranchList {
         self.picker
             .update(cx, |picker, _| picker.delegate.modifiers = ev.modifiers)
     }
+
+    fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.delete_selected(window, cx);
+        })
+    }
+
+    fn rename(&mut self, _: &Rename, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let Some(Entry::Branch { branch, .. }) = picker.delegate.selected_entry().cloned()
+            else {
+                return;
+            };
+            let name = branch.name.clone();
+            picker.delegate.mode = PickerMode::Rename(branch);
+            picker.set_query(name.to_string(), window, cx);
+            picker.refresh_placeholder(window, cx);
+            picker.refresh(window, cx);
+        })
+    }
+
+    fn set_upstream(&mut self, _: &SetUpstream, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let Some(Entry::Branch { branch, .. }) = picker.delegate.selected_entry().cloned()
+            else {
+                return;
+            };
+            picker.delegate.mode = PickerMode::SetUpstream(branch);
+            picker.set_query("", window, cx);
+            picker.refresh_placeholder(window, cx);
+            picker.refresh(window, cx);
+        })
+    }
+
+    fn toggle_tags(&mut self, _: &ToggleTags, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let delegate = &mut picker.delegate;
+            delegate.mode = match delegate.mode {
+                PickerMode::Tags => PickerMode::Branches,
+                _ => PickerMode::Tags,
+            };
+            delegate.selected_index = 0;
+            if matches!(delegate.mode, PickerMode::Tags) && delegate.all_tags.is_none() {
+                delegate.load_tags(window, cx);
+            }
+            picker.refresh_placeholder(window, cx);
+            picker.refresh(window, cx);
+        })
+    }
 }
 impl ModalView for BranchList {}
 impl EventEmitter<DismissEvent> for BranchList {}
@@ -152,8 +204,13 @@ impl Focusable for BranchList {
 impl Render for BranchList {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
+            .key_context("BranchPicker")
             .w(self.width)
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
+            .on_action(cx.listener(Self::delete))
+            .on_action(cx.listener(Self::rename))
+            .on_action(cx.listener(Self::set_upstream))
+            .on_action(cx.listener(Self::toggle_tags))
             .child(self.picker.clone())
             .on_mouse_down_out({
                 cx.listener(move |this, _, window, cx| {
@@ -166,17 +223,61 @@ impl Render for BranchList {
 }
 
 #[derive(Debug, Clone)]
-struct BranchEntry {
-    branch: Branch,
-    positions: Vec<usize>,
-    is_new: bool,
+enum PickerMode {
+    Branches,
+    Tags,
+    /// Picking a new name for the branch.
+    Rename(Branch),
+    /// Picking the upstream branch the branch should track.
+    SetUpstream(Branch),
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Branch {
+        branch: Branch,
+        positions: Vec<usize>,
+    },
+    NewBranch {
+        name: SharedString,
+    },
+    Tag {
+        tag: Tag,
+        positions: Vec<usize>,
+    },
+    NewTag {
+        name: SharedString,
+    },
+    RenameTo {
+        name: SharedString,
+    },
+    Track {
+        upstream: SharedString,
+        positions: Vec<usize>,
+    },
+    Untrack {
+        upstream: SharedString,
+    },
+}
+
+impl Entry {
+    fn name(&self) -> &SharedString {
+        match self {
+            Entry::Branch { branch, .. } => &branch.name,
+            Entry::Tag { tag, .. } => &tag.name,
+            Entry::NewBranch { name } | Entry::NewTag { name } | Entry::RenameTo { name } => name,
+            Entry::Track { upstream, .. } | Entry::Untrack { upstream } => upstream,
+        }
+    }
 }
 
 pub struct BranchListDelegate {
-    matches: Vec<BranchEntry>,
+    matches: Vec<Entry>,
     all_branches: Option<Vec<Branch>>,
+    all_tags: Option<Vec<Tag>>,
     repo: Option<Entity<Repository>>,
     style: BranchListStyle,
+    mode: PickerMode,
     selected_index: usize,
     last_query: String,
     modifiers: Modifiers,
@@ -188,13 +289,51 @@ impl BranchListDelegate {
             matches: vec![],
             repo,
             style,
+            mode: PickerMode::Branches,
             all_branches: None,
+            all_tags: None,
             selected_index: 0,
             last_query: Default::default(),
             modifiers: Default::default(),
         }
     }
 
+    fn selected_entry(&self) -> Option<&Entry> {
+        self.matches.get(self.selected_index)
+    }
+
+    fn set_matches(&mut self, matches: Vec<Entry>, query: String) {
+        self.matches = matches;
+        if self.matches.is_empty() {
+            self.selected_index = 0;
+        } else {
+            self.selected_index = core::cmp::min(self.selected_index, self.matches.len() - 1);
+        }
+        self.last_query = query;
+    }
+
+    fn current_branch_name(&self, cx: &App) -> Option<SharedString> {
+        self.repo
+            .as_ref()
+            .and_then(|repo| repo.read(cx).current_branch().map(|b| b.name.clone()))
+    }
+
+    fn load_tags(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let tags = repo.read(cx).tags();
+        cx.spawn_in(window, |picker, mut cx| async move {
+            let tags = tags.await??;
+            picker.update_in(&mut cx, |picker, window, cx| {
+                picker.delegate.all_tags = Some(tags);
+                picker.refresh(window, cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn create_branch(
         &self,
         new_branch_name: SharedString,
@@ -216,13 +355,303 @@ impl BranchListDelegate {
         });
         cx.emit(DismissEvent);
     }
+
+    fn change_branch(&self, branch: Branch, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if self
+            .current_branch_name(cx)
+            .is_some_and(|current_branch| current_branch == branch.name)
+        {
+            cx.emit(DismissEvent);
+            return;
+        }
+
+        cx.spawn_in(window, |picker, mut cx| async move {
+            let branch_change_task = picker.update(&mut cx, |this, cx| {
+                let repo = this
+                    .delegate
+                    .repo
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("No active repository"))?
+                    .clone();
+
+                let cx = cx.to_async();
+
+                anyhow::Ok(async move {
+                    cx.update(|cx| repo.read(cx).change_branch(branch.name.to_string()))?
+                        .await?
+                })
+            })??;
+
+            branch_change_task.await?;
+
+            picker.update(&mut cx, |_, cx| {
+                cx.emit(DismissEvent);
+
+                anyhow::Ok(())
+            })
+        })
+        .detach_and_prompt_err("Failed to change branch", window, cx, |_, _, _| None);
+    }
+
+    fn rename_branch(
+        &self,
+        new_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let (Some(repo), PickerMode::Rename(branch)) = (self.repo.as_ref(), &self.mode) else {
+            return;
+        };
+        let rename = repo
+            .read(cx)
+            .rename_branch(branch.name.to_string(), new_name.to_string());
+        cx.spawn(|_, _| async move { rename.await? })
+            .detach_and_prompt_err("Failed to rename branch", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+        cx.emit(DismissEvent);
+    }
+
+    fn set_upstream_branch(
+        &self,
+        upstream: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let (Some(repo), PickerMode::SetUpstream(branch)) = (self.repo.as_ref(), &self.mode) else {
+            return;
+        };
+        let set_upstream = repo.read(cx).set_upstream_branch(
+            branch.name.to_string(),
+            upstream.map(|upstream| upstream.to_string()),
+        );
+        cx.spawn(|_, _| async move { set_upstream.await? })
+            .detach_and_prompt_err("Failed to set upstream branch", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+        cx.emit(DismissEvent);
+    }
+
+    fn create_tag(&self, name: SharedString, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.as_ref() else {
+            return;
+        };
+        let create = repo.read(cx).create_tag(name.to_string(), None, None);
+        cx.spawn(|_, _| async move { create.await? })
+            .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+        cx.emit(DismissEvent);
+    }
+
+    fn push_tag(&self, name: SharedString, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(git_panel) = window
+            .root::<Workspace>()
+            .flatten()
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+        let answer = window.prompt(
+            PromptLevel::Info,
+            &format!("Push tag {name}?"),
+            None,
+            &["Push", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, |_, mut cx| async move {
+            if answer.await != Ok(0) {
+                return anyhow::Ok(());
+            }
+            git_panel.update_in(&mut cx, |git_panel, window, cx| {
+                git_panel.push_tag(name, window, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_selected(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        match self.selected_entry().cloned() {
+            Some(Entry::Branch { branch, .. }) => {
+                let name = branch.name;
+                let answer = window.prompt(
+                    PromptLevel::Warning,
+                    &format!("Delete branch {name}?"),
+                    None,
+                    &["Delete", "Cancel"],
+                    cx,
+                );
+                cx.spawn_in(window, |picker, mut cx| async move {
+                    if answer.await != Ok(0) {
+                        return Ok(());
+                    }
+                    let deleted = repo
+                        .update(&mut cx, |repo, _| {
+                            repo.delete_branch(name.to_string(), false)
+                        })?
+                        .await?;
+                    if let Err(error) = deleted {
+                        if !error.to_string().contains("not fully merged") {
+                            return Err(error);
+                        }
+                        let answer = cx.update(|window, cx| {
+                            window.prompt(
+                                PromptLevel::Warning,
+                                &format!("Branch {name} is not fully merged"),
+                                Some("Its commits that aren't merged anywhere else will be lost."),
+                                &["Delete Anyway", "Cancel"],
+                                cx,
+                            )
+                        })?;
+                        if answer.await != Ok(0) {
+                            return Ok(());
+                        }
+                        repo.update(&mut cx, |repo, _| {
+                            repo.delete_branch(name.to_string(), true)
+                        })?
+                        .await??;
+                    }
+                    picker
+                        .update_in(&mut cx, |picker, window, cx| {
+                            if let Some(branches) = picker.delegate.all_branches.as_mut() {
+                                branches.retain(|branch| branch.name != name);
+                            }
+                            picker.refresh(window, cx);
+                        })
+                        .ok();
+                    Ok(())
+                })
+                .detach_and_prompt_err(
+                    "Failed to delete branch",
+                    window,
+                    cx,
+                    |e, _, _| Some(e.to_string()),
+                );
+            }
+            Some(Entry::Tag { tag, .. }) => {
+                let name = tag.name;
+                let answer = window.prompt(
+                    PromptLevel::Warning,
+                    &format!("Delete tag {name}?"),
+                    None,
+                    &["Delete", "Cancel"],
+                    cx,
+                );
+                cx.spawn_in(window, |picker, mut cx| async move {
+                    if answer.await != Ok(0) {
+                        return Ok(());
+                    }
+                    repo.update(&mut cx, |repo, _| repo.delete_tag(name.to_string()))?
+                        .await??;
+                    picker
+                        .update_in(&mut cx, |picker, window, cx| {
+                            if let Some(tags) = picker.delegate.all_tags.as_mut() {
+                                tags.retain(|tag| tag.name != name);
+                            }
+                            picker.refresh(window, cx);
+                        })
+                        .ok();
+                    Ok(())
+                })
+                .detach_and_prompt_err(
+                    "Failed to delete tag",
+                    window,
+                    cx,
+                    |e, _, _| Some(e.to_string()),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn upstream_candidates(&self, branch: &Branch) -> Vec<SharedString> {
+        let mut candidates = Vec::<SharedString>::new();
+        let upstreams = self
+            .all_branches
+            .iter()
+            .flatten()
+            .filter_map(|branch| branch.upstream.as_ref());
+        // Suggest the branch's namesake on each remote first.
+        for upstream in upstreams.clone() {
+            if let Some(remote) = upstream.remote_name() {
+                let candidate = SharedString::from(format!("{remote}/{}", branch.name));
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        for upstream in upstreams {
+            if let Some(name) = upstream.ref_name.strip_prefix("refs/remotes/") {
+                let candidate = SharedString::from(name.to_string());
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates
+    }
+
+    fn footer_button(
+        id: &'static str,
+        label: &'static str,
+        action: &dyn Action,
+        window: &mut Window,
+        cx: &App,
+    ) -> Button {
+        let keybind = KeyBinding::for_action(action, window, cx);
+        let action = action.boxed_clone();
+        Button::new(id, label)
+            .label_size(LabelSize::Small)
+            .when_some(keybind, |this, keybind| this.key_binding(keybind))
+            .on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+    }
+}
+
+/// Fuzzy-matches `query` against `names`, returning the index and matched positions of each
+/// hit. All names match an empty query, in their original order.
+async fn match_names(
+    names: &[SharedString],
+    query: &str,
+    executor: BackgroundExecutor,
+) -> Vec<(usize, Vec<usize>)> {
+    if query.is_empty() {
+        return (0..names.len()).map(|ix| (ix, Vec::new())).collect();
+    }
+    let candidates = names
+        .iter()
+        .enumerate()
+        .map(|(ix, name)| StringMatchCandidate::new(ix, name))
+        .collect::<Vec<StringMatchCandidate>>();
+    fuzzy::match_strings(
+        &candidates,
+        query,
+        true,
+        10000,
+        &Default::default(),
+        executor,
+    )
+    .await
+    .into_iter()
+    .map(|candidate| (candidate.candidate_id, candidate.positions))
+    .collect()
 }
 
 impl PickerDelegate for BranchListDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Select branch...".into()
+        match &self.mode {
+            PickerMode::Branches => "Select branch...".into(),
+            PickerMode::Tags => "Select tag...".into(),
+            PickerMode::Rename(branch) => format!("Rename {} to...", branch.name).into(),
+            PickerMode::SetUpstream(branch) => {
+                format!("Select upstream of {}...", branch.name).into()
+            }
+        }
     }
 
     fn match_count(&self) -> usize {
@@ -248,131 +677,139 @@ impl PickerDelegate for BranchListDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Task<()> {
-        let Some(all_branches) = self.all_branches.clone() else {
-            return Task::ready(());
-        };
-
         const RECENT_BRANCHES_COUNT: usize = 10;
-        cx.spawn_in(window, move |picker, mut cx| async move {
-            let mut matches: Vec<BranchEntry> = if query.is_empty() {
-                all_branches
-                    .into_iter()
-                    .take(RECENT_BRANCHES_COUNT)
-                    .map(|branch| BranchEntry {
-                        branch,
-                        positions: Vec::new(),
-                        is_new: false,
-                    })
-                    .collect()
-            } else {
-                let candidates = all_branches
-                    .iter()
-                    .enumerate()
-                    .map(|(ix, command)| StringMatchCandidate::new(ix, &command.name.clone()))
-                    .collect::<Vec<StringMatchCandidate>>();
-                fuzzy::match_strings(
-                    &candidates,
-                    &query,
-                    true,
-                    10000,
-                    &Default::default(),
-                    cx.background_executor().clone(),
-                )
-                .await
-                .iter()
-                .cloned()
-                .map(|candidate| BranchEntry {
-                    branch: all_branches[candidate.candidate_id].clone(),
-                    positions: candidate.positions,
-                    is_new: false,
-                })
-                .collect()
-            };
-            picker
-                .update(&mut cx, |picker, _| {
+
+        let executor = cx.background_executor().clone();
+        match self.mode.clone() {
+            PickerMode::Branches => {
+                let Some(all_branches) = self.all_branches.clone() else {
+                    return Task::ready(());
+                };
+                cx.spawn_in(window, move |picker, mut cx| async move {
+                    let names = all_branches
+                        .iter()
+                        .map(|branch| branch.name.clone())
+                        .collect::<Vec<_>>();
+                    let limit = if query.is_empty() {
+                        RECENT_BRANCHES_COUNT
+                    } else {
+                        usize::MAX
+                    };
+                    let mut matches = match_names(&names, &query, executor)
+                        .await
+                        .into_iter()
+                        .take(limit)
+                        .map(|(ix, positions)| Entry::Branch {
+                            branch: all_branches[ix].clone(),
+                            positions,
+                        })
+                        .collect::<Vec<_>>();
                     #[allow(clippy::nonminimal_bool)]
                     if !query.is_empty()
-                        && !matches
-                            .first()
-                            .is_some_and(|entry| entry.branch.name == query)
+                        && !matches.first().is_some_and(|entry| entry.name() == &query)
                     {
-                        matches.push(BranchEntry {
-                            branch: Branch {
-                                name: query.clone().into(),
-                                is_head: false,
-                                upstream: None,
-                                most_recent_commit: None,
-                            },
-                            positions: Vec::new(),
-                            is_new: true,
+                        matches.push(Entry::NewBranch {
+                            name: query.clone().into(),
+                        });
+                    }
+                    picker
+                        .update(&mut cx, |picker, _| {
+                            picker.delegate.set_matches(matches, query)
                         })
+                        .log_err();
+                })
+            }
+            PickerMode::Tags => {
+                let Some(all_tags) = self.all_tags.clone() else {
+                    return Task::ready(());
+                };
+                cx.spawn_in(window, move |picker, mut cx| async move {
+                    let names = all_tags
+                        .iter()
+                        .map(|tag| tag.name.clone())
+                        .collect::<Vec<_>>();
+                    let mut matches = match_names(&names, &query, executor)
+                        .await
+                        .into_iter()
+                        .map(|(ix, positions)| Entry::Tag {
+                            tag: all_tags[ix].clone(),
+                            positions,
+                        })
+                        .collect::<Vec<_>>();
+                    if !query.is_empty() && !names.iter().any(|name| name == &query) {
+                        matches.push(Entry::NewTag {
+                            name: query.clone().into(),
+                        });
                     }
-                    let delegate = &mut picker.delegate;
-                    delegate.matches = matches;
-                    if delegate.matches.is_empty() {
-                        delegate.selected_index = 0;
-                    } else {
-                        delegate.selected_index =
-                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    picker
+                        .update(&mut cx, |picker, _| {
+                            picker.delegate.set_matches(matches, query)
+                        })
+                        .log_err();
+                })
+            }
+            PickerMode::Rename(branch) => {
+                let new_name = query.trim();
+                let matches = if new_name.is_empty() || new_name == branch.name.as_ref() {
+                    Vec::new()
+                } else {
+                    vec![Entry::RenameTo {
+                        name: new_name.to_string().into(),
+                    }]
+                };
+                self.set_matches(matches, query);
+                Task::ready(())
+            }
+            PickerMode::SetUpstream(branch) => {
+                let candidates = self.upstream_candidates(&branch);
+                cx.spawn_in(window, move |picker, mut cx| async move {
+                    let upstream = query.trim();
+                    let mut matches = match_names(&candidates, upstream, executor)
+                        .await
+                        .into_iter()
+                        .map(|(ix, positions)| Entry::Track {
+                            upstream: candidates[ix].clone(),
+                            positions,
+                        })
+                        .collect::<Vec<_>>();
+                    if !upstream.is_empty() && !candidates.iter().any(|name| name == upstream) {
+                        matches.push(Entry::Track {
+                            upstream: upstream.to_string().into(),
+                            positions: Vec::new(),
+                        });
+                    }
+                    if let Some(current) = branch
+                        .upstream
+                        .as_ref()
+                        .and_then(|upstream| upstream.ref_name.strip_prefix("refs/remotes/"))
+                    {
+                        matches.push(Entry::Untrack {
+                            upstream: current.to_string().into(),
+                        });
                     }
-                    delegate.last_query = query;
+                    picker
+                        .update(&mut cx, |picker, _| {
+                            picker.delegate.set_matches(matches, query)
+                        })
+                        .log_err();
                 })
-                .log_err();
-        })
+            }
+        }
     }
 
     fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
-        let Some(entry) = self.matches.get(self.selected_index()) else {
+        let Some(entry) = self.selected_entry().cloned() else {
             return;
         };
-        if entry.is_new {
-            self.create_branch(entry.branch.name.clone(), window, cx);
-            return;
+        match entry {
+            Entry::Branch { branch, .. } => self.change_branch(branch, window, cx),
+            Entry::NewBranch { name } => self.create_branch(name, window, cx),
+            Entry::Tag { tag, .. } => self.push_tag(tag.name, window, cx),
+            Entry::NewTag { name } => self.create_tag(name, window, cx),
+            Entry::RenameTo { name } => self.rename_branch(name, window, cx),
+            Entry::Track { upstream, .. } => self.set_upstream_branch(Some(upstream), window, cx),
+            Entry::Untrack { .. } => self.set_upstream_branch(None, window, cx),
         }
-
-        let current_branch = self.repo.as_ref().map(|repo| {
-            repo.update(cx, |repo, _| {
-                repo.current_branch().map(|branch| branch.name.clone())
-            })
-        });
-
-        if current_branch
-            .flatten()
-            .is_some_and(|current_branch| current_branch == entry.branch.name)
-        {
-            cx.emit(DismissEvent);
-            return;
-        }
-
-        cx.spawn_in(window, {
-            let branch = entry.branch.clone();
-            |picker, mut cx| async move {
-                let branch_change_task = picker.update(&mut cx, |this, cx| {
-                    let repo = this
-                        .delegate
-                        .repo
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("No active repository"))?
-                        .clone();
-
-                    let cx = cx.to_async();
-
-                    anyhow::Ok(async move {
-                        cx.update(|cx| repo.read(cx).change_branch(branch.name.to_string()))?
-                            .await?
-                    })
-                })??;
-
-                branch_change_task.await?;
-
-                picker.update(&mut cx, |_, cx| {
-                    cx.emit(DismissEvent);
-
-                    anyhow::Ok(())
-                })
-            }
-        })
-        .detach_and_prompt_err("Failed to change branch", window, cx, |_, _, _| None);
     }
 
     fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
@@ -383,6 +820,75 @@ impl PickerDelegate for BranchListDelegate {
         None
     }
 
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        if self.style != BranchListStyle::Modal {
+            return None;
+        }
+        let toggle_label = match self.mode {
+            PickerMode::Branches => "Tags",
+            PickerMode::Tags => "Branches",
+            PickerMode::Rename(_) | PickerMode::SetUpstream(_) => return None,
+        };
+        let selected_entry = self.selected_entry();
+        Some(
+            h_flex()
+                .w_full()
+                .h_8()
+                .p_2()
+                .justify_between()
+                .rounded_b_sm()
+                .bg(cx.theme().colors().ghost_element_selected)
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(Self::footer_button(
+                    "toggle-tags",
+                    toggle_label,
+                    &ToggleTags,
+                    window,
+                    cx,
+                ))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .when(
+                            matches!(selected_entry, Some(Entry::Branch { .. })),
+                            |this| {
+                                this.child(Self::footer_button(
+                                    "rename-branch",
+                                    "Rename",
+                                    &Rename,
+                                    window,
+                                    cx,
+                                ))
+                                .child(Self::footer_button(
+                                    "set-upstream",
+                                    "Set Upstream",
+                                    &SetUpstream,
+                                    window,
+                                    cx,
+                                ))
+                            },
+                        )
+                        .when(
+                            matches!(
+                                selected_entry,
+                                Some(Entry::Branch { .. } | Entry::Tag { .. })
+                            ),
+                            |this| {
+                                this.child(Self::footer_button(
+                                    "delete", "Delete", &Delete, window, cx,
+                                ))
+                            },
+                        ),
+                )
+                .into_any_element(),
+        )
+    }
+
     fn render_match(
         &self,
         ix: usize,
@@ -392,22 +898,86 @@ impl PickerDelegate for BranchListDelegate {
     ) -> Option<Self::ListItem> {
         let entry = &self.matches[ix];
 
-        let (commit_time, subject) = entry
-            .branch
-            .most_recent_commit
-            .as_ref()
-            .map(|commit| {
-                let subject = commit.subject.clone();
-                let commit_time = OffsetDateTime::from_unix_timestamp(commit.commit_timestamp)
-                    .unwrap_or_else(|_| OffsetDateTime::now_utc());
-                let formatted_time = format_local_timestamp(
-                    commit_time,
-                    OffsetDateTime::now_utc(),
-                    time_format::TimestampFormat::Relative,
-                );
-                (Some(formatted_time), Some(subject))
-            })
-            .unwrap_or_else(|| (None, None));
+        let (label, timestamp, description) = match entry {
+            Entry::Branch { branch, positions } => (
+                HighlightedLabel::new(branch.name.clone(), positions.clone())
+                    .truncate()
+                    .into_any_element(),
+                branch
+                    .most_recent_commit
+                    .as_ref()
+                    .map(|commit| commit.commit_timestamp),
+                branch
+                    .most_recent_commit
+                    .as_ref()
+                    .map(|commit| commit.subject.to_string())
+                    .unwrap_or_else(|| "no commits found".to_string()),
+            ),
+            Entry::NewBranch { name } => (
+                Label::new(format!("Create branch \"{}\"…", name))
+                    .single_line()
+                    .into_any_element(),
+                None,
+                match self.current_branch_name(cx) {
+                    Some(current_branch) => format!("based off {}", current_branch),
+                    None => "based off the current branch".to_string(),
+                },
+            ),
+            Entry::Tag { tag, positions } => (
+                HighlightedLabel::new(tag.name.clone(), positions.clone())
+                    .truncate()
+                    .into_any_element(),
+                tag.timestamp,
+                tag.subject.to_string(),
+            ),
+            Entry::NewTag { name } => (
+                Label::new(format!("Create tag \"{}\"…", name))
+                    .single_line()
+                    .into_any_element(),
+                None,
+                match self.current_branch_name(cx) {
+                    Some(current_branch) => format!("at the tip of {}", current_branch),
+                    None => "at the current commit".to_string(),
+                },
+            ),
+            Entry::RenameTo { name } => (
+                Label::new(format!("Rename to \"{}\"", name))
+                    .single_line()
+                    .into_any_element(),
+                None,
+                match &self.mode {
+                    PickerMode::Rename(branch) => format!("currently {}", branch.name),
+                    _ => String::new(),
+                },
+            ),
+            Entry::Track {
+                upstream,
+                positions,
+            } => (
+                HighlightedLabel::new(upstream.clone(), positions.clone())
+                    .truncate()
+                    .into_any_element(),
+                None,
+                format!("track {}", upstream),
+            ),
+            Entry::Untrack { upstream } => (
+                Label::new("Stop tracking upstream")
+                    .single_line()
+                    .into_any_element(),
+                None,
+                format!("currently {}", upstream),
+            ),
+        };
+
+        let timestamp = timestamp.map(|timestamp| {
+            let time = OffsetDateTime::from_unix_timestamp(timestamp)
+                .unwrap_or_else(|_| OffsetDateTime::now_utc());
+            format_local_timestamp(
+                time,
+                OffsetDateTime::now_utc(),
+                time_format::TimestampFormat::Relative,
+            )
+        });
 
         Some(
             ListItem::new(SharedString::from(format!("vcs-menu-{ix}")))
@@ -428,26 +998,10 @@ impl PickerDelegate for BranchListDelegate {
                                 .overflow_x_hidden()
                                 .gap_2()
                                 .justify_between()
-                                .child(div().flex_shrink().overflow_x_hidden().child(
-                                    if entry.is_new {
-                                        Label::new(format!(
-                                            "Create branch \"{}\"…",
-                                            entry.branch.name
-                                        ))
-                                        .single_line()
-                                        .into_any_element()
-                                    } else {
-                                        HighlightedLabel::new(
-                                            entry.branch.name.clone(),
-                                            entry.positions.clone(),
-                                        )
-                                        .truncate()
-                                        .into_any_element()
-                                    },
-                                ))
-                                .when_some(commit_time, |el, commit_time| {
+                                .child(div().flex_shrink().overflow_x_hidden().child(label))
+                                .when_some(timestamp, |el, timestamp| {
                                     el.child(
-                                        Label::new(commit_time)
+                                        Label::new(timestamp)
                                             .size(LabelSize::Small)
                                             .color(Color::Muted)
                                             .into_element(),
@@ -455,25 +1009,14 @@ impl PickerDelegate for BranchListDelegate {
                                 }),
                         )
                         .when(self.style == BranchListStyle::Modal, |el| {
-                            el.child(div().max_w_96().child({
-                                let message = if entry.is_new {
-                                    if let Some(current_branch) =
-                                        self.repo.as_ref().and_then(|repo| {
-                                            repo.read(cx).current_branch().map(|b| b.name.clone())
-                                        })
-                                    {
-                                        format!("based off {}", current_branch)
-                                    } else {
-                                        "based off the current branch".to_string()
-                                    }
-                                } else {
-                                    subject.unwrap_or("no commits found".into()).to_string()
-                                };
-                                Label::new(message)
-                                    .size(LabelSize::Small)
-                                    .truncate()
-                                    .color(Color::Muted)
-                            }))
+                            el.child(
+                                div().max_w_96().child(
+                                    Label::new(description)
+                                        .size(LabelSize::Small)
+                                        .truncate()
+                                        .color(Color::Muted),
+                                ),
+                            )
                         }),
                 ),
         )
@@ -0,0 +1,390 @@
+use anyhow::Result;
+use buffer_diff::BufferDiff;
+use editor::{actions::ToggleSplitDiff, Editor, EditorEvent, SplitDiff};
+use fs::MTime;
+use git::repository::{CommitDiff, LogEntry};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, Entity, EventEmitter, FocusHandle, Focusable,
+    Render, Task,
+};
+use language::{Anchor, Buffer, Capability, DiskState, LocalFile, OffsetRangeExt};
+use multi_buffer::{MultiBuffer, PathKey};
+use project::{git::Repository, Project, WorktreeId};
+use std::{
+    any::{Any, TypeId},
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use theme::ActiveTheme;
+use ui::{prelude::*, Tooltip};
+use workspace::{
+    item::{BreadcrumbText, Item, ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+    ItemNavHistory, ToolbarItemLocation, Workspace,
+};
+
+/// A read-only multibuffer showing the changes made by a single commit.
+pub struct CommitView {
+    commit: LogEntry,
+    editor: Entity<Editor>,
+    split_diff: Option<Entity<SplitDiff>>,
+    multibuffer: Entity<MultiBuffer>,
+    _task: Task<Result<()>>,
+}
+
+/// The file of a buffer holding a file's contents at a commit, so that it is
+/// displayed and highlighted like the file it came from.
+struct GitBlob {
+    path: Arc<Path>,
+    worktree_id: WorktreeId,
+    mtime: MTime,
+}
+
+const COMMIT_NAMESPACE: &'static str = "0";
+
+impl CommitView {
+    /// Opens the changes made by the commit, or activates an existing view of them.
+    pub fn open(
+        commit: LogEntry,
+        repository: Entity<Repository>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if let Some(existing) = workspace
+            .items_of_type::<Self>(cx)
+            .find(|view| view.read(cx).commit.sha == commit.sha)
+        {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let diff = repository.read(cx).load_commit_diff(commit.sha.to_string());
+        let project = workspace.project().clone();
+        cx.spawn_in(window, |workspace, mut cx| async move {
+            let diff = diff.await??;
+            workspace.update_in(&mut cx, |workspace, window, cx| {
+                let commit_view =
+                    cx.new(|cx| Self::new(commit, diff, repository, project, window, cx));
+                workspace.add_item_to_active_pane(Box::new(commit_view), None, true, window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to load commit", window, cx, |e, _, _| {
+            Some(format!("{e}"))
+        });
+    }
+
+    fn new(
+        commit: LogEntry,
+        diff: CommitDiff,
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadOnly));
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project.clone()), window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_read_only(true);
+            editor
+        });
+        cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        })
+        .detach();
+
+        let repository = repository.read(cx);
+        let worktree_id = repository.worktree_id;
+        let mtime = MTime::from_seconds_and_nanos(commit.commit_timestamp.max(0) as u64, 0);
+        let files = diff
+            .files
+            .into_iter()
+            .map(|file| {
+                let path = repository
+                    .repo_path_to_project_path(&file.path)
+                    .map(|project_path| project_path.path)
+                    .unwrap_or_else(|| file.path.0.clone());
+                let blob = GitBlob {
+                    path,
+                    worktree_id,
+                    mtime,
+                };
+                (blob, file.old_text, file.new_text)
+            })
+            .collect::<Vec<_>>();
+
+        let language_registry = project.read(cx).languages().clone();
+        let task = cx.spawn(|this, mut cx| async move {
+            for (blob, old_text, new_text) in files {
+                let language = language_registry
+                    .language_for_file_path(&blob.path)
+                    .await
+                    .ok();
+                let path_key = PathKey::namespaced(COMMIT_NAMESPACE, blob.path.clone());
+                let (buffer, diff, base_text_set) = this.update(&mut cx, |_, cx| {
+                    let buffer = cx.new(|cx| {
+                        let mut buffer = Buffer::local(new_text.unwrap_or_default(), cx);
+                        buffer.set_language(language.clone(), cx);
+                        buffer.file_updated(Arc::new(blob), cx);
+                        buffer.set_capability(Capability::ReadOnly, cx);
+                        buffer
+                    });
+                    let base_buffer = cx.new(|cx| {
+                        let mut buffer = Buffer::local(old_text.unwrap_or_default(), cx);
+                        buffer.set_language(language, cx);
+                        buffer
+                    });
+                    let snapshot = buffer.read(cx).text_snapshot();
+                    let diff = cx.new(|cx| BufferDiff::new(&snapshot, cx));
+                    let base_text_set =
+                        diff.update(cx, |diff, cx| diff.set_base_text(base_buffer, snapshot, cx));
+                    (buffer, diff, base_text_set)
+                })?;
+                base_text_set.await.ok();
+
+                this.update(&mut cx, |this, cx| {
+                    let snapshot = buffer.read(cx).snapshot();
+                    let hunk_ranges = diff
+                        .read(cx)
+                        .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx)
+                        .map(|hunk| hunk.buffer_range.to_point(&snapshot))
+                        .collect::<Vec<_>>();
+                    this.multibuffer.update(cx, |multibuffer, cx| {
+                        multibuffer.add_diff(diff, cx);
+                        multibuffer.set_excerpts_for_path(
+                            path_key,
+                            buffer,
+                            hunk_ranges,
+                            editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                            cx,
+                        );
+                    });
+                })?;
+            }
+            Ok(())
+        });
+
+        Self {
+            commit,
+            editor,
+            split_diff: None,
+            multibuffer,
+            _task: task,
+        }
+    }
+
+    fn toggle_split_diff(
+        &mut self,
+        _: &ToggleSplitDiff,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.split_diff.take() {
+            Some(split_diff) => split_diff.update(cx, |split_diff, cx| split_diff.unsplit(cx)),
+            None => {
+                let editor = self.editor.clone();
+                self.split_diff = Some(cx.new(|cx| SplitDiff::new(editor, window, cx)));
+            }
+        }
+        cx.notify();
+    }
+}
+
+impl language::File for GitBlob {
+    fn as_local(&self) -> Option<&dyn LocalFile> {
+        None
+    }
+
+    fn disk_state(&self) -> DiskState {
+        DiskState::Present { mtime: self.mtime }
+    }
+
+    fn path(&self) -> &Arc<Path> {
+        &self.path
+    }
+
+    fn full_path(&self, _: &App) -> PathBuf {
+        self.path.to_path_buf()
+    }
+
+    fn file_name<'a>(&'a self, _: &'a App) -> &'a OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    fn worktree_id(&self, _: &App) -> WorktreeId {
+        self.worktree_id
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn to_proto(&self, _: &App) -> rpc::proto::File {
+        rpc::proto::File {
+            worktree_id: self.worktree_id.to_proto(),
+            entry_id: None,
+            path: self.path.to_string_lossy().to_string(),
+            mtime: Some(self.mtime.into()),
+            is_deleted: false,
+        }
+    }
+
+    fn is_private(&self) -> bool {
+        false
+    }
+}
+
+impl EventEmitter<EditorEvent> for CommitView {}
+
+impl Focusable for CommitView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for CommitView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("{} {}", self.commit.short_sha(), self.commit.subject).into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        Label::new(self.commit.short_sha())
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Commit View Opened")
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for CommitView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .on_action(cx.listener(Self::toggle_split_diff))
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(self.commit.short_sha())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new(self.commit.subject.clone()).truncate())
+                    .child(div().flex_grow())
+                    .child(
+                        Label::new(self.commit.author_name.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        IconButton::new("split", IconName::Split)
+                            .shape(ui::IconButtonShape::Square)
+                            .toggle_state(self.split_diff.is_some())
+                            .tooltip(Tooltip::for_action_title(
+                                "Toggle split diff",
+                                &ToggleSplitDiff,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.toggle_split_diff(&ToggleSplitDiff, window, cx)
+                            })),
+                    ),
+            )
+            .child(div().flex_1().map(|el| match &self.split_diff {
+                Some(split_diff) => el.child(split_diff.clone()),
+                None => el.child(self.editor.clone()),
+            }))
+    }
+}
@@ -0,0 +1,759 @@
+use anyhow::Result;
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent};
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    rems, AnyElement, AnyView, App, AppContext as _, Context, DismissEvent, Entity, EventEmitter,
+    FocusHandle, Focusable, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window,
+};
+use language::{Buffer, BufferEvent};
+use multi_buffer::MultiBuffer;
+use picker::{Picker, PickerDelegate};
+use project::{Project, ProjectPath};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{
+    item::{BreadcrumbText, Item, ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+    ItemNavHistory, ModalView, ToolbarItemLocation, Workspace,
+};
+use zed_actions::diff::CompareWith;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(CompareWithPicker::toggle);
+    })
+    .detach();
+}
+
+/// Shows how one buffer differs from another that isn't related to it by
+/// git, updating as either of them is edited.
+pub struct FileDiffView {
+    old_buffer: Entity<Buffer>,
+    new_buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    editor: Entity<Editor>,
+    multibuffer: Entity<MultiBuffer>,
+    recalculate_task: Option<Task<Result<()>>>,
+    /// Whether a change to the base text or language is still waiting on a
+    /// recalculation, so that a later edit doesn't drop it by replacing the
+    /// task.
+    pending_base_text_change: bool,
+    pending_language_change: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl FileDiffView {
+    /// Opens the changes that turn `old_buffer` into `new_buffer`, or
+    /// activates an existing view of them.
+    pub fn open(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        if let Some(existing) = workspace.items_of_type::<Self>(cx).find(|view| {
+            let view = view.read(cx);
+            view.old_buffer == old_buffer && view.new_buffer == new_buffer
+        }) {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return existing;
+        }
+
+        telemetry::event!("File Diff Opened");
+        let project = workspace.project().clone();
+        let file_diff_view = cx.new(|cx| Self::new(old_buffer, new_buffer, project, window, cx));
+        workspace.add_item_to_active_pane(Box::new(file_diff_view.clone()), None, true, window, cx);
+        file_diff_view
+    }
+
+    fn new(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let diff = cx.new(|cx| BufferDiff::new(&new_buffer.read(cx).text_snapshot(), cx));
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(new_buffer.clone(), cx);
+            multibuffer.add_diff(diff.clone(), cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project), window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_expand_all_diff_hunks(cx);
+            editor
+        });
+
+        let subscriptions = vec![
+            cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone())
+            }),
+            cx.subscribe(&old_buffer, |this, _, event: &BufferEvent, cx| {
+                if let BufferEvent::Edited = event {
+                    this.recalculate_diff(true, false, cx);
+                }
+            }),
+            cx.subscribe(
+                &new_buffer,
+                |this, _, event: &BufferEvent, cx| match event {
+                    BufferEvent::Edited => this.recalculate_diff(false, false, cx),
+                    BufferEvent::LanguageChanged => this.recalculate_diff(false, true, cx),
+                    BufferEvent::FileHandleChanged => cx.emit(EditorEvent::TitleChanged),
+                    _ => {}
+                },
+            ),
+        ];
+
+        let mut this = Self {
+            old_buffer,
+            new_buffer,
+            diff,
+            editor,
+            multibuffer,
+            recalculate_task: None,
+            pending_base_text_change: false,
+            pending_language_change: false,
+            _subscriptions: subscriptions,
+        };
+        this.recalculate_diff(true, true, cx);
+        this
+    }
+
+    /// Diffs the new buffer against the old buffer's current text, only
+    /// rebuilding the base text when the old buffer or the language changed
+    /// since the last recalculation finished.
+    fn recalculate_diff(
+        &mut self,
+        base_text_changed: bool,
+        language_changed: bool,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending_base_text_change |= base_text_changed;
+        self.pending_language_change |= language_changed;
+        let base_text_changed = self.pending_base_text_change;
+        let language_changed = self.pending_language_change;
+        let base_text = Arc::new(self.old_buffer.read(cx).text());
+        let new_buffer = self.new_buffer.read(cx);
+        let snapshot = new_buffer.text_snapshot();
+        let language = new_buffer.language().cloned();
+        let language_registry = new_buffer.language_registry();
+        let diff = self.diff.clone();
+        self.recalculate_task = Some(cx.spawn(|this, mut cx| async move {
+            let new_snapshot = BufferDiff::update_diff(
+                diff.clone(),
+                snapshot.clone(),
+                Some(base_text),
+                base_text_changed,
+                language_changed,
+                language,
+                language_registry,
+                &mut cx,
+            )
+            .await?;
+            diff.update(&mut cx, |diff, cx| {
+                diff.set_snapshot(&snapshot, new_snapshot, language_changed, None, cx);
+            })?;
+            this.update(&mut cx, |this, _| {
+                this.pending_base_text_change = false;
+                this.pending_language_change = false;
+            })
+        }));
+    }
+
+    fn title(buffer: &Entity<Buffer>, cx: &App) -> SharedString {
+        match buffer.read(cx).file() {
+            Some(file) => file.file_name(cx).to_string_lossy().to_string().into(),
+            None => "untitled".into(),
+        }
+    }
+
+    fn full_title(buffer: &Entity<Buffer>, cx: &App) -> SharedString {
+        match buffer.read(cx).file() {
+            Some(file) => file.full_path(cx).to_string_lossy().to_string().into(),
+            None => "untitled".into(),
+        }
+    }
+}
+
+impl EventEmitter<EditorEvent> for FileDiffView {}
+
+impl Focusable for FileDiffView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for FileDiffView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "{} ↔ {}",
+                Self::full_title(&self.old_buffer, cx),
+                Self::full_title(&self.new_buffer, cx)
+            )
+            .into(),
+        )
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(format!(
+            "{} ↔ {}",
+            Self::title(&self.old_buffer, cx),
+            Self::title(&self.new_buffer, cx)
+        ))
+        .color(if params.selected {
+            Color::Default
+        } else {
+            Color::Muted
+        })
+        .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("File Diff Opened")
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.multibuffer.read(cx).is_dirty(cx)
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.multibuffer.read(cx).has_conflict(cx)
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        format: bool,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor.save(format, project, window, cx)
+    }
+
+    fn save_as(
+        &mut self,
+        _: Entity<Project>,
+        _: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        unreachable!()
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor.reload(project, window, cx)
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for FileDiffView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        self.editor.clone()
+    }
+}
+
+/// Something a buffer can be compared with: either a buffer that is already
+/// open, or a project file that is opened when picked.
+#[derive(Clone)]
+enum CompareTarget {
+    Buffer(Entity<Buffer>),
+    Path(ProjectPath),
+}
+
+impl CompareTarget {
+    fn open(self, project: &Entity<Project>, cx: &mut App) -> Task<Result<Entity<Buffer>>> {
+        match self {
+            Self::Buffer(buffer) => Task::ready(Ok(buffer)),
+            Self::Path(path) => project.update(cx, |project, cx| project.open_buffer(path, cx)),
+        }
+    }
+}
+
+/// Lists the open untitled buffers and the project's files, comparing the
+/// picked one with the buffer the picker was opened for.
+pub struct CompareWithPicker {
+    picker: Entity<Picker<CompareWithPickerDelegate>>,
+    _subscription: Subscription,
+}
+
+impl CompareWithPicker {
+    fn toggle(
+        workspace: &mut Workspace,
+        action: &CompareWith,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        let source = match &action.path {
+            Some(path) => {
+                let Some((worktree, path)) = project.read(cx).find_worktree(path, cx) else {
+                    return;
+                };
+                CompareTarget::Path(ProjectPath {
+                    worktree_id: worktree.read(cx).id(),
+                    path: path.into(),
+                })
+            }
+            None => {
+                let Some(buffer) = workspace
+                    .active_item_as::<Editor>(cx)
+                    .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+                else {
+                    return;
+                };
+                CompareTarget::Buffer(buffer)
+            }
+        };
+
+        let mut targets = Vec::new();
+        for editor in workspace.items_of_type::<Editor>(cx) {
+            let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+                continue;
+            };
+            let is_untitled = buffer.read(cx).file().is_none();
+            let is_source = matches!(&source, CompareTarget::Buffer(source) if *source == buffer);
+            if is_untitled
+                && !is_source
+                && !targets
+                    .iter()
+                    .any(|(target, _)| matches!(target, CompareTarget::Buffer(b) if *b == buffer))
+            {
+                let title = editor.read(cx).buffer().read(cx).title(cx).to_string();
+                targets.push((CompareTarget::Buffer(buffer), title));
+            }
+        }
+        let source_path = match &source {
+            CompareTarget::Path(path) => Some(path.clone()),
+            CompareTarget::Buffer(buffer) => buffer.read(cx).file().map(|file| ProjectPath {
+                worktree_id: file.worktree_id(cx),
+                path: file.path().clone(),
+            }),
+        };
+        let include_root_name = project.read(cx).visible_worktrees(cx).count() > 1;
+        for worktree in project.read(cx).visible_worktrees(cx) {
+            let worktree = worktree.read(cx);
+            let worktree_id = worktree.id();
+            for entry in worktree.files(false, 0) {
+                let path = ProjectPath {
+                    worktree_id,
+                    path: entry.path.clone(),
+                };
+                if source_path.as_ref() == Some(&path) {
+                    continue;
+                }
+                let label = if include_root_name {
+                    std::path::Path::new(worktree.root_name()).join(&entry.path)
+                } else {
+                    entry.path.to_path_buf()
+                };
+                targets.push((
+                    CompareTarget::Path(path),
+                    label.to_string_lossy().to_string(),
+                ));
+            }
+        }
+
+        let delegate = CompareWithPickerDelegate {
+            workspace: cx.weak_entity(),
+            project,
+            source,
+            targets,
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        workspace.toggle_modal(window, cx, |window, cx| Self::new(delegate, window, cx));
+    }
+
+    fn new(
+        delegate: CompareWithPickerDelegate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for CompareWithPicker {}
+impl EventEmitter<DismissEvent> for CompareWithPicker {}
+
+impl Focusable for CompareWithPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for CompareWithPicker {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(34.))
+            .child(self.picker.clone())
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                this.picker.update(cx, |this, cx| {
+                    this.cancel(&Default::default(), window, cx);
+                })
+            }))
+    }
+}
+
+pub struct CompareWithPickerDelegate {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    source: CompareTarget,
+    targets: Vec<(CompareTarget, String)>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for CompareWithPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Compare with…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .targets
+            .iter()
+            .enumerate()
+            .map(|(ix, (_, label))| StringMatchCandidate::new(ix, label))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, move |picker, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .take(100)
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(&mut cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(hit) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let old_buffer = self.targets[hit.candidate_id]
+            .0
+            .clone()
+            .open(&self.project, cx);
+        let new_buffer = self.source.clone().open(&self.project, cx);
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, |_, mut cx| async move {
+            let old_buffer = old_buffer.await?;
+            let new_buffer = new_buffer.await?;
+            workspace.update_in(&mut cx, |workspace, window, cx| {
+                FileDiffView::open(old_buffer, new_buffer, workspace, window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to compare files", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        Some(
+            ListItem::new(SharedString::from(format!("compare-with-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::File).color(Color::Muted))
+                .child(HighlightedLabel::new(
+                    hit.string.clone(),
+                    hit.positions.clone(),
+                )),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use editor::test::editor_test_context::assert_state_with_diff;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use unindent::Unindent as _;
+    use util::path;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_diff_updates_as_either_buffer_is_edited(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "old.txt": "one\ntwo\nthree\n",
+                "new.txt": "one\nTWO\nthree\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let old_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/old.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let new_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/new.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let file_diff_view = workspace.update_in(cx, |workspace, window, cx| {
+            FileDiffView::open(
+                old_buffer.clone(),
+                new_buffer.clone(),
+                workspace,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        let editor = file_diff_view.update(cx, |view, _| view.editor.clone());
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                ˇone
+              - two
+              + TWO
+                three
+            "
+            .unindent(),
+        );
+
+        old_buffer.update(cx, |buffer, cx| buffer.edit([(4..7, "TWO")], None, cx));
+        cx.run_until_parked();
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                ˇone
+                TWO
+                three
+            "
+            .unindent(),
+        );
+
+        new_buffer.update(cx, |buffer, cx| buffer.edit([(0..3, "ONE")], None, cx));
+        cx.run_until_parked();
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+              - one
+              + ˇONE
+                TWO
+                three
+            "
+            .unindent(),
+        );
+
+        // Editing the new buffer before the old buffer's recalculation runs
+        // must still pick up the new base text.
+        old_buffer.update(cx, |buffer, cx| buffer.edit([(0..3, "ONE")], None, cx));
+        new_buffer.update(cx, |buffer, cx| buffer.edit([(8..13, "THREE")], None, cx));
+        cx.run_until_parked();
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                ˇONE
+                TWO
+              - three
+              + THREE
+            "
+            .unindent(),
+        );
+    }
+}
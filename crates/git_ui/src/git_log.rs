@@ -0,0 +1,900 @@
+use crate::{commit_view::CommitView, git_panel::GitPanel, rebase_editor::RebaseEditor};
+use editor::Editor;
+use git::repository::{LogEntry, LogOptions, RepoPath};
+use gpui::{
+    anchored, canvas, deferred, fill, point, size, uniform_list, Action, AnyElement, App, Bounds,
+    ClickEvent, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyContext,
+    ListSizingBehavior, MouseButton, MouseDownEvent, PathBuilder, Point, Render, ScrollStrategy,
+    Subscription, Task, UniformListScrollHandle, WeakEntity,
+};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::git::Repository;
+use std::ops::Range;
+use theme::ActiveTheme;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{prelude::*, ContextMenu};
+use util::{maybe, ResultExt as _};
+use workspace::{
+    item::{Item, ItemEvent, TabContentParams},
+    Workspace,
+};
+
+/// The number of commits to load at a time.
+const PAGE_SIZE: usize = 200;
+const LANE_WIDTH: Pixels = px(12.);
+const COMMIT_DOT_SIZE: Pixels = px(7.);
+
+/// Lists the commits of a repository, or of a single file in it, newest first.
+pub struct GitLog {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    path: Option<RepoPath>,
+    entries: Vec<LogEntry>,
+    graph: Vec<GraphRow>,
+    lane_count: usize,
+    selected_entry: Option<usize>,
+    has_more: bool,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    load_task: Option<Task<()>>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+}
+
+/// How a commit's row of the history graph is drawn.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct GraphRow {
+    /// The lane holding the commit.
+    lane: usize,
+    /// The lanes that end at the commit, coming from its children above.
+    incoming: Vec<usize>,
+    /// The lanes that run past the commit without touching it.
+    passing: Vec<usize>,
+    /// The lanes that leave the commit, going to its parents below.
+    outgoing: Vec<usize>,
+}
+
+impl GitLog {
+    pub(crate) fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _cx: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &git::ViewHistory, window, cx| {
+            let git_store = workspace.project().read(cx).git_store().clone();
+            let Some(repository) = git_store.read(cx).active_repository() else {
+                return;
+            };
+            Self::open(repository, None, workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &git::FileHistory, window, cx| {
+            let Some(buffer) = workspace
+                .active_item_as::<Editor>(cx)
+                .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+            else {
+                return;
+            };
+            let git_store = workspace.project().read(cx).git_store().clone();
+            let Some((repository, path)) = git_store
+                .read(cx)
+                .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+            else {
+                return;
+            };
+            Self::open(repository, Some(path), workspace, window, cx);
+        });
+    }
+
+    /// Opens the history of the repository, or of a path in it, reloading an
+    /// existing view of it if there is one.
+    pub fn open(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if let Some(existing) = workspace.items_of_type::<Self>(cx).find(|log| {
+            let log = log.read(cx);
+            log.repository == repository && log.path == path
+        }) {
+            existing.update(cx, |log, cx| log.reload(cx));
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = cx.weak_entity();
+        let log = cx.new(|cx| Self::new(repository, path, workspace_handle, cx));
+        workspace.add_item_to_active_pane(Box::new(log), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            repository,
+            workspace,
+            path,
+            entries: Vec::new(),
+            graph: Vec::new(),
+            lane_count: 0,
+            selected_entry: None,
+            has_more: true,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            load_task: None,
+            context_menu: None,
+        };
+        this.load_more(cx);
+        this
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.graph.clear();
+        self.lane_count = 0;
+        self.selected_entry = None;
+        self.has_more = true;
+        self.load_task = None;
+        self.load_more(cx);
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if !self.has_more || self.load_task.is_some() {
+            return;
+        }
+
+        // Renames are only followed for a single file, and since git then
+        // reports each commit's real parents rather than the ones that touched
+        // the file, the history of a file isn't drawn as a graph.
+        let entries = self.repository.read(cx).log(LogOptions {
+            skip: self.entries.len(),
+            limit: PAGE_SIZE,
+            path: self.path.clone(),
+            follow_renames: self.path.is_some(),
+        });
+        self.load_task = Some(cx.spawn(|this, mut cx| async move {
+            let result = maybe!(async { entries.await? }).await;
+            this.update(&mut cx, |this, cx| {
+                this.load_task = None;
+                match result {
+                    Ok(entries) => {
+                        this.has_more = entries.len() == PAGE_SIZE;
+                        this.entries.extend(entries);
+                        if this.path.is_none() {
+                            this.graph = compute_graph(&this.entries);
+                            this.lane_count = this
+                                .graph
+                                .iter()
+                                .flat_map(|row| {
+                                    row.passing
+                                        .iter()
+                                        .chain(&row.outgoing)
+                                        .chain(Some(&row.lane))
+                                })
+                                .max()
+                                .map_or(0, |lane| lane + 1);
+                        }
+                    }
+                    Err(error) => {
+                        this.has_more = false;
+                        log::error!("failed to load git log: {error:?}");
+                    }
+                }
+                cx.notify();
+            })
+            .log_err();
+        }));
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _window: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select_entry(0, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_entry) = self.selected_entry {
+            self.select_entry(selected_entry.saturating_sub(1), cx);
+        } else {
+            self.select_first(&SelectFirst, window, cx);
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_entry) = self.selected_entry {
+            if selected_entry + 1 < self.entries.len() {
+                self.select_entry(selected_entry + 1, cx);
+            }
+        } else {
+            self.select_first(&SelectFirst, window, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _window: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select_entry(self.entries.len() - 1, cx);
+        }
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_entry) = self.selected_entry {
+            self.open_commit(selected_entry, window, cx);
+        }
+    }
+
+    fn open_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix).cloned() else {
+            return;
+        };
+        let repository = self.repository.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                CommitView::open(entry, repository, workspace, window, cx);
+            })
+            .ok();
+    }
+
+    fn selected_commit(&self) -> Option<LogEntry> {
+        self.entries.get(self.selected_entry?).cloned()
+    }
+
+    fn git_panel(&self, cx: &App) -> Option<Entity<GitPanel>> {
+        self.workspace.upgrade()?.read(cx).panel::<GitPanel>(cx)
+    }
+
+    fn cherry_pick(&mut self, _: &git::CherryPick, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(commit), Some(panel)) = (self.selected_commit(), self.git_panel(cx)) else {
+            return;
+        };
+        let repository = self.repository.clone();
+        panel.update(cx, |panel, cx| {
+            panel.cherry_pick(repository, commit, window, cx);
+        });
+    }
+
+    fn revert(&mut self, _: &git::Revert, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(commit), Some(panel)) = (self.selected_commit(), self.git_panel(cx)) else {
+            return;
+        };
+        let repository = self.repository.clone();
+        panel.update(cx, |panel, cx| {
+            panel.revert_commit(repository, commit, window, cx);
+        });
+    }
+
+    /// Rebases the commits from the selected one up to HEAD, onto the selected
+    /// commit's parent.
+    fn interactive_rebase(
+        &mut self,
+        _: &git::InteractiveRebase,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.selected_commit() else {
+            return;
+        };
+        let onto = commit.parents.first().map(|parent| parent.to_string());
+        let repository = self.repository.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                RebaseEditor::toggle(repository, onto, workspace, window, cx);
+            })
+            .ok();
+    }
+
+    fn deploy_entry_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(self.focus_handle.clone())
+                .action("Open Commit", Confirm.boxed_clone())
+                .separator()
+                .action("Cherry-Pick", git::CherryPick.boxed_clone())
+                .action("Revert", git::Revert.boxed_clone())
+                .action(
+                    "Interactive Rebase from Here",
+                    git::InteractiveRebase.boxed_clone(),
+                )
+        });
+        self.selected_entry = Some(ix);
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn dispatch_context(&self, window: &Window) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("GitLog");
+        if self.focus_handle.is_focused(window) {
+            dispatch_context.add("menu");
+        }
+        dispatch_context
+    }
+
+    fn render_entries(
+        &mut self,
+        range: Range<usize>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        if range.end + PAGE_SIZE / 2 >= self.entries.len() {
+            self.load_more(cx);
+        }
+
+        let graph_width = LANE_WIDTH * self.lane_count;
+        range
+            .filter_map(|ix| {
+                let entry = self.entries.get(ix)?;
+                Some(self.render_entry(ix, entry, graph_width, cx))
+            })
+            .collect()
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &LogEntry,
+        graph_width: Pixels,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let selected = self.selected_entry == Some(ix);
+        let commit_time = OffsetDateTime::from_unix_timestamp(entry.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let formatted_time = format_local_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+
+        h_flex()
+            .id(ElementId::Name(
+                format!("git_log_entry_{}", entry.sha).into(),
+            ))
+            .h_6()
+            .w_full()
+            .pr_3()
+            .gap_2()
+            .overflow_hidden()
+            .when(selected, |this| {
+                this.bg(cx.theme().colors().ghost_element_selected)
+            })
+            .hover(|this| this.bg(cx.theme().colors().ghost_element_hover))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.select_entry(ix, cx);
+                this.open_commit(ix, window, cx);
+            }))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_entry_context_menu(event.position, ix, window, cx);
+                    cx.stop_propagation();
+                }),
+            )
+            .child(
+                div()
+                    .h_full()
+                    .flex_none()
+                    .w(graph_width)
+                    .when_some(self.graph.get(ix).cloned(), |this, row| {
+                        this.child(render_graph_row(row))
+                    }),
+            )
+            .child(
+                Label::new(entry.short_sha())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .child(Label::new(entry.subject.clone()).truncate()),
+            )
+            .child(
+                Label::new(entry.author_name.clone())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                Label::new(formatted_time)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .into_any_element()
+    }
+}
+
+fn render_graph_row(row: GraphRow) -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        move |bounds: Bounds<Pixels>, _, window, cx| {
+            let lane_x = |lane: usize| bounds.left() + LANE_WIDTH * (lane as f32 + 0.5);
+            let top = bounds.top();
+            let bottom = bounds.bottom();
+            let commit = point(lane_x(row.lane), bounds.center().y);
+            let accents = cx.theme().accents().clone();
+
+            let mut paint_line = |from, to, lane: usize| {
+                let mut builder = PathBuilder::stroke(px(1.5));
+                builder.move_to(from);
+                builder.line_to(to);
+                if let Ok(path) = builder.build() {
+                    window.paint_path(path, accents.color_for_index(lane as u32));
+                }
+            };
+            for &lane in &row.passing {
+                paint_line(point(lane_x(lane), top), point(lane_x(lane), bottom), lane);
+            }
+            for &lane in &row.incoming {
+                paint_line(point(lane_x(lane), top), commit, lane);
+            }
+            for &lane in &row.outgoing {
+                paint_line(commit, point(lane_x(lane), bottom), lane);
+            }
+
+            window.paint_quad(
+                fill(
+                    Bounds::centered_at(commit, size(COMMIT_DOT_SIZE, COMMIT_DOT_SIZE)),
+                    accents.color_for_index(row.lane as u32),
+                )
+                .corner_radii(COMMIT_DOT_SIZE * 0.5),
+            );
+        },
+    )
+    .size_full()
+}
+
+/// Assigns each commit to a lane, so that every commit is drawn below its
+/// children and connected to them.
+///
+/// Each lane holds the sha of the commit it is waiting for. A commit takes the
+/// first lane waiting for it, and hands that lane on to its first parent, while
+/// its other parents get the first free lanes.
+fn compute_graph(entries: &[LogEntry]) -> Vec<GraphRow> {
+    let mut lanes: Vec<Option<SharedString>> = Vec::new();
+    entries
+        .iter()
+        .map(|entry| {
+            let incoming = lanes
+                .iter()
+                .enumerate()
+                .filter(|(_, sha)| sha.as_ref() == Some(&entry.sha))
+                .map(|(lane, _)| lane)
+                .collect::<Vec<_>>();
+            let lane = match incoming.first() {
+                Some(lane) => *lane,
+                None => free_lane(&mut lanes),
+            };
+            for lane in &incoming {
+                lanes[*lane] = None;
+            }
+
+            let passing = lanes
+                .iter()
+                .enumerate()
+                .filter(|(_, sha)| sha.is_some())
+                .map(|(lane, _)| lane)
+                .collect();
+
+            let mut outgoing = Vec::new();
+            for (ix, parent) in entry.parents.iter().enumerate() {
+                if let Some(parent_lane) = lanes.iter().position(|sha| sha.as_ref() == Some(parent))
+                {
+                    outgoing.push(parent_lane);
+                    continue;
+                }
+                let parent_lane = if ix == 0 && lanes[lane].is_none() {
+                    lane
+                } else {
+                    free_lane(&mut lanes)
+                };
+                lanes[parent_lane] = Some(parent.clone());
+                outgoing.push(parent_lane);
+            }
+
+            while lanes.last().is_some_and(Option::is_none) {
+                lanes.pop();
+            }
+
+            GraphRow {
+                lane,
+                incoming,
+                passing,
+                outgoing,
+            }
+        })
+        .collect()
+}
+
+fn free_lane(lanes: &mut Vec<Option<SharedString>>) -> usize {
+    if let Some(lane) = lanes.iter().position(Option::is_none) {
+        lane
+    } else {
+        lanes.push(None);
+        lanes.len() - 1
+    }
+}
+
+impl EventEmitter<ItemEvent> for GitLog {}
+
+impl Focusable for GitLog {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for GitLog {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &ItemEvent, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content_text(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some(match &self.path {
+            Some(path) => format!(
+                "History: {}",
+                path.file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+            )
+            .into(),
+            None => "History".into(),
+        })
+    }
+
+    fn tab_content(&self, params: TabContentParams, window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(window, cx).unwrap_or_default())
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        self.path
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string().into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Git Log Opened")
+    }
+}
+
+impl Render for GitLog {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+
+        v_flex()
+            .key_context(self.dispatch_context(window))
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cherry_pick))
+            .on_action(cx.listener(Self::revert))
+            .on_action(cx.listener(Self::interactive_rebase))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .when(entry_count == 0, |this| {
+                this.items_center().justify_center().child(
+                    Label::new(if self.load_task.is_some() {
+                        "Loading history…"
+                    } else {
+                        "No commits"
+                    })
+                    .color(Color::Muted),
+                )
+            })
+            .when(entry_count > 0, |this| {
+                this.child(
+                    uniform_list(
+                        cx.entity().clone(),
+                        "git_log_entries",
+                        entry_count,
+                        |this, range, window, cx| this.render_entries(range, window, cx),
+                    )
+                    .size_full()
+                    .with_sizing_behavior(ListSizingBehavior::Infer)
+                    .track_scroll(self.scroll_handle.clone()),
+                )
+            })
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::{CommitDiff, CommitFile};
+    use gpui::{TestAppContext, VisualTestContext};
+    use pretty_assertions::assert_eq;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    fn entry(sha: &str, parents: &[&str]) -> LogEntry {
+        LogEntry {
+            sha: sha.to_string().into(),
+            parents: parents
+                .iter()
+                .map(|parent| parent.to_string().into())
+                .collect(),
+            subject: SharedString::default(),
+            author_name: SharedString::default(),
+            author_email: SharedString::default(),
+            commit_timestamp: 0,
+        }
+    }
+
+    fn row(lane: usize, incoming: &[usize], passing: &[usize], outgoing: &[usize]) -> GraphRow {
+        GraphRow {
+            lane,
+            incoming: incoming.to_vec(),
+            passing: passing.to_vec(),
+            outgoing: outgoing.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_compute_graph_linear() {
+        let graph = compute_graph(&[entry("c", &["b"]), entry("b", &["a"]), entry("a", &[])]);
+        assert_eq!(
+            graph,
+            vec![
+                row(0, &[], &[], &[0]),
+                row(0, &[0], &[], &[0]),
+                row(0, &[0], &[], &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compute_graph_merge() {
+        // d merges c into b, which both branched off a.
+        let graph = compute_graph(&[
+            entry("d", &["b", "c"]),
+            entry("c", &["a"]),
+            entry("b", &["a"]),
+            entry("a", &[]),
+        ]);
+        assert_eq!(
+            graph,
+            vec![
+                row(0, &[], &[], &[0, 1]),
+                row(1, &[1], &[0], &[1]),
+                row(0, &[0], &[1], &[1]),
+                row(1, &[1], &[], &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compute_graph_branch_tips() {
+        // Two branch tips, b and c, that share the parent a.
+        let graph = compute_graph(&[entry("c", &["a"]), entry("b", &["a"]), entry("a", &[])]);
+        assert_eq!(
+            graph,
+            vec![
+                row(0, &[], &[], &[0]),
+                row(1, &[], &[0], &[0]),
+                row(0, &[0], &[], &[]),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_history_pages_through_commits(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "a",
+            }),
+        )
+        .await;
+        let commit_count = PAGE_SIZE + 50;
+        fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+            state.commit_history = (0..commit_count)
+                .rev()
+                .map(|ix: usize| LogEntry {
+                    subject: format!("commit {ix}").into(),
+                    parents: ix
+                        .checked_sub(1)
+                        .map(|parent| format!("{parent:040x}").into())
+                        .into_iter()
+                        .collect(),
+                    ..entry(&format!("{ix:040x}"), &[])
+                })
+                .collect();
+        });
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        cx.dispatch_action(git::ViewHistory);
+        cx.run_until_parked();
+        let log = active_log(&workspace, cx);
+        log.update(cx, |log, _| {
+            assert_eq!(log.path, None);
+            assert_eq!(log.entries.len(), PAGE_SIZE);
+            assert_eq!(log.graph.len(), PAGE_SIZE);
+            assert!(log.has_more);
+        });
+
+        // The next page is appended, and is the last one since it's shorter.
+        log.update(cx, |log, cx| log.load_more(cx));
+        cx.run_until_parked();
+        log.update(cx, |log, cx| {
+            assert_eq!(log.entries.len(), commit_count);
+            assert_eq!(log.entries.last().unwrap().subject.as_ref(), "commit 0");
+            assert!(!log.has_more);
+
+            log.load_more(cx);
+            assert!(log.load_task.is_none());
+        });
+
+        // Viewing the history again reloads the existing view from the first page.
+        cx.dispatch_action(git::ViewHistory);
+        cx.run_until_parked();
+        assert!(active_log(&workspace, cx) == log);
+        workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.items_of_type::<GitLog>(cx).count(), 1);
+        });
+        log.update(cx, |log, _| assert_eq!(log.entries.len(), PAGE_SIZE));
+    }
+
+    #[gpui::test]
+    async fn test_file_history(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "a3",
+                "b.txt": "b1",
+            }),
+        )
+        .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+            let commits = [
+                ("c3", "c2", "a.txt", "a2", "a3"),
+                ("c2", "c1", "b.txt", "b0", "b1"),
+                ("c1", "", "a.txt", "a1", "a2"),
+            ];
+            for (sha, parent, path, old_text, new_text) in commits {
+                let parents: &[&str] = if parent.is_empty() { &[] } else { &[parent] };
+                state.commit_history.push(LogEntry {
+                    subject: format!("change {path}").into(),
+                    ..entry(sha, parents)
+                });
+                state.commit_diffs.insert(
+                    sha.to_string(),
+                    CommitDiff {
+                        files: vec![CommitFile {
+                            path: path.into(),
+                            old_text: Some(old_text.to_string()),
+                            new_text: Some(new_text.to_string()),
+                        }],
+                    },
+                );
+            }
+        });
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, "a.txt"), None, true, window, cx)
+            })
+            .await
+            .unwrap();
+        cx.dispatch_action(git::FileHistory);
+        cx.run_until_parked();
+
+        // Only the commits that changed the file are listed, without a graph.
+        let log = active_log(&workspace, cx);
+        log.update(cx, |log, _| {
+            assert_eq!(log.path, Some("a.txt".into()));
+            assert_eq!(
+                log.entries
+                    .iter()
+                    .map(|entry| entry.sha.as_ref())
+                    .collect::<Vec<_>>(),
+                ["c3", "c1"]
+            );
+            assert!(log.graph.is_empty());
+            assert!(!log.has_more);
+        });
+
+        // Opening a commit shows its diff.
+        log.update_in(cx, |log, window, cx| log.open_commit(1, window, cx));
+        cx.run_until_parked();
+        let editor = workspace.update(cx, |workspace, cx| {
+            let item = workspace.active_item(cx).unwrap();
+            assert!(item.downcast::<CommitView>().is_some());
+            item.act_as::<Editor>(cx).unwrap()
+        });
+        editor.update(cx, |editor, cx| {
+            assert_eq!(editor.buffer().read(cx).read(cx).text(), "a2");
+        });
+    }
+
+    fn active_log(workspace: &Entity<Workspace>, cx: &mut VisualTestContext) -> Entity<GitLog> {
+        workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<GitLog>(cx).unwrap()
+        })
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+}
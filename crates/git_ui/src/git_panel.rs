@@ -1,6 +1,7 @@
 use crate::askpass_modal::AskPassModal;
 use crate::commit_modal::CommitModal;
 use crate::git_panel_settings::StatusStyle;
+use crate::merge_editor::MergeEditor;
 use crate::project_diff::Diff;
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::repository_selector::filtered_repository_entries;
@@ -19,14 +20,20 @@ use editor::{
     scroll::ScrollbarAutoHide, Editor, EditorElement, EditorMode, EditorSettings, MultiBuffer,
     ShowScrollbar,
 };
-use futures::StreamExt as _;
+use futures::{channel::oneshot, StreamExt as _};
 use git::repository::{
-    Branch, CommitDetails, CommitSummary, DiffType, PushOptions, Remote, RemoteCommandOutput,
-    ResetMode, Upstream, UpstreamTracking, UpstreamTrackingStatus,
+    Branch, CommitDetails, CommitSummary, DiffType, LogEntry, PushOptions, RebaseTodoEntry, Remote,
+    RemoteCommandOutput, ResetMode, SequencerOperation, StashEntry, Upstream, UpstreamTracking,
+    UpstreamTrackingStatus,
 };
 use git::status::StageStatus;
+use git::{
+    parse_git_remote_url, AbortOperation, BranchPullRequest, CheckStatus, ContinueOperation,
+    ExpandCommitEditor, GitHostingProviderRegistry, OpenPullRequest, PullRequestState,
+    RestoreTrackedFiles, StageAll, StashAll, StashAllIncludingUntracked, StashPop, StatusCheck,
+    TrashUntrackedFiles, UnstageAll, ViewHistory,
+};
 use git::{repository::RepoPath, status::FileStatus, Commit, ToggleStaged};
-use git::{ExpandCommitEditor, RestoreTrackedFiles, StageAll, TrashUntrackedFiles, UnstageAll};
 use gpui::{
     actions, anchored, deferred, percentage, uniform_list, Action, Animation, AnimationExt as _,
     Axis, ClickEvent, Corner, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
@@ -105,6 +112,13 @@ enum TrashCancel {
     Cancel,
 }
 
+#[derive(strum::EnumIter, strum::VariantNames)]
+#[strum(serialize_all = "title_case")]
+enum DropCancel {
+    Drop,
+    Cancel,
+}
+
 fn git_panel_context_menu(
     focus_handle: FocusHandle,
     window: &mut Window,
@@ -117,9 +131,17 @@ fn git_panel_context_menu(
             .action("Unstage All", UnstageAll.boxed_clone())
             .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
+            .action("View History", ViewHistory.boxed_clone())
             .separator()
             .action("Discard Tracked Changes", RestoreTrackedFiles.boxed_clone())
             .action("Trash Untracked Files", TrashUntrackedFiles.boxed_clone())
+            .separator()
+            .action("Stash All Changes", StashAll.boxed_clone())
+            .action(
+                "Stash All Changes Including Untracked",
+                StashAllIncludingUntracked.boxed_clone(),
+            )
+            .action("Pop Latest Stash", StashPop.boxed_clone())
     })
 }
 
@@ -156,6 +178,7 @@ enum Section {
     Conflict,
     Tracked,
     New,
+    Stash,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -171,6 +194,7 @@ impl GitHeaderEntry {
             Section::Conflict => repo.has_conflict(&status_entry.repo_path),
             Section::Tracked => !status.is_created(),
             Section::New => status.is_created(),
+            Section::Stash => false,
         }
     }
     pub fn title(&self) -> &'static str {
@@ -178,6 +202,7 @@ impl GitHeaderEntry {
             Section::Conflict => "Conflicts",
             Section::Tracked => "Tracked",
             Section::New => "Untracked",
+            Section::Stash => "Stashes",
         }
     }
 }
@@ -186,6 +211,7 @@ impl GitHeaderEntry {
 enum GitListEntry {
     GitStatusEntry(GitStatusEntry),
     Header(GitHeaderEntry),
+    StashEntry(StashEntry),
 }
 
 impl GitListEntry {
@@ -304,6 +330,12 @@ pub struct GitPanel {
     add_coauthors: bool,
     generate_commit_message_task: Option<Task<Option<()>>>,
     entries: Vec<GitListEntry>,
+    stash_entries: Vec<StashEntry>,
+    sequencer_operation: Option<SequencerOperation>,
+    pull_request: Option<PullRequestStatus>,
+    /// The branch and commit the pull request was last looked up for.
+    pull_request_key: Option<(SharedString, Option<SharedString>)>,
+    refresh_pull_request_task: Task<()>,
     single_staged_entry: Option<GitStatusEntry>,
     single_tracked_entry: Option<GitStatusEntry>,
     focus_handle: FocusHandle,
@@ -331,6 +363,14 @@ pub struct GitPanel {
     _settings_subscription: Subscription,
 }
 
+/// The hosting provider's pull request for the current branch, or a link to
+/// open one when there is none yet.
+struct PullRequestStatus {
+    pull_request: Option<BranchPullRequest>,
+    checks: Vec<StatusCheck>,
+    create_url: Option<SharedString>,
+}
+
 struct RemoteOperationGuard {
     id: u32,
     pending_remote_operations: RemoteOperations,
@@ -464,6 +504,11 @@ impl GitPanel {
             add_coauthors: true,
             generate_commit_message_task: None,
             entries: Vec::new(),
+            stash_entries: Vec::new(),
+            sequencer_operation: None,
+            pull_request: None,
+            pull_request_key: None,
+            refresh_pull_request_task: Task::ready(()),
             focus_handle: cx.focus_handle(),
             fs,
             new_count: 0,
@@ -860,6 +905,10 @@ impl GitPanel {
     }
 
     fn open_diff(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(GitListEntry::StashEntry(entry)) = self.get_selected_entry() {
+            self.show_stash(entry.clone(), window, cx);
+            return;
+        }
         maybe!({
             let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
             let workspace = self.workspace.upgrade()?;
@@ -1235,6 +1284,120 @@ impl GitPanel {
         self.change_file_stage(false, entries, cx);
     }
 
+    pub fn stash_all(&mut self, _: &StashAll, _window: &mut Window, cx: &mut Context<Self>) {
+        self.stash_paths(Vec::new(), false, cx);
+    }
+
+    pub fn stash_all_including_untracked(
+        &mut self,
+        _: &StashAllIncludingUntracked,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stash_paths(Vec::new(), true, cx);
+    }
+
+    pub fn stash_pop(&mut self, _: &StashPop, _window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.stash_entries.first().cloned() {
+            self.pop_stash(entry, cx);
+        }
+    }
+
+    fn stash_paths(
+        &mut self,
+        paths: Vec<RepoPath>,
+        include_untracked: bool,
+        cx: &mut Context<Self>,
+    ) {
+        self.perform_stash_operation("stash", cx, |repo, cx| {
+            repo.stash_push(None, paths, include_untracked, cx)
+        });
+    }
+
+    fn apply_stash(&mut self, entry: StashEntry, cx: &mut Context<Self>) {
+        self.perform_stash_operation("stash apply", cx, |repo, cx| {
+            repo.stash_apply(entry.index, entry.sha.to_string(), cx)
+        });
+    }
+
+    fn pop_stash(&mut self, entry: StashEntry, cx: &mut Context<Self>) {
+        self.perform_stash_operation("stash pop", cx, |repo, cx| {
+            repo.stash_pop(entry.index, entry.sha.to_string(), cx)
+        });
+    }
+
+    fn drop_stash(&mut self, entry: StashEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let prompt = prompt(
+            &format!("Drop {}?", entry.ref_name()),
+            Some(entry.message.as_ref()),
+            window,
+            cx,
+        );
+        cx.spawn(|this, mut cx| async move {
+            if let Ok(DropCancel::Drop) = prompt.await {
+                this.update(&mut cx, |this, cx| {
+                    this.perform_stash_operation("stash drop", cx, |repo, cx| {
+                        repo.stash_drop(entry.index, entry.sha.to_string(), cx)
+                    });
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn perform_stash_operation(
+        &mut self,
+        operation: &'static str,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&mut Repository, &mut Context<Repository>) -> oneshot::Receiver<Result<()>>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let result = active_repository.update(cx, f);
+        cx.spawn(|this, mut cx| async move {
+            if let Err(e) = maybe!(async { result.await? }).await {
+                this.update(&mut cx, |this, cx| this.show_error_toast(operation, e, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    }
+
+    fn show_stash(&mut self, entry: StashEntry, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.as_ref() else {
+            return;
+        };
+        let diff = active_repository.read(cx).stash_show(entry.index);
+        let language = self.project.read(cx).languages().language_for_name("Diff");
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, |_, mut cx| async move {
+            let diff = diff.await??;
+            let language = language.await.log_err();
+            workspace.update_in(&mut cx, |workspace, window, cx| {
+                let buffer = cx.new(|cx| {
+                    let mut buffer = Buffer::local(diff, cx);
+                    buffer.set_language(language, cx);
+                    buffer
+                });
+                let editor = cx.new(|cx| {
+                    let mut editor = Editor::for_buffer(buffer, Some(project), window, cx);
+                    editor.buffer().update(cx, |buffer, cx| {
+                        buffer.set_title(format!("{}: {}", entry.ref_name(), entry.message), cx);
+                    });
+                    editor.set_read_only(true);
+                    editor
+                });
+                workspace.add_item_to_center(Box::new(editor), window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to show stash", window, cx, |e, _, _| {
+            Some(format!("{e}"))
+        });
+    }
+
     fn toggle_staged_for_entry(
         &mut self,
         entry: &GitListEntry,
@@ -1268,6 +1431,7 @@ impl GitPanel {
 
                 (goal_staged_state, entries)
             }
+            GitListEntry::StashEntry(_) => return,
         };
         self.change_file_stage(stage, repo_paths, cx);
     }
@@ -1766,6 +1930,132 @@ impl GitPanel {
             .detach_and_log_err(cx);
     }
 
+    pub(crate) fn cherry_pick(
+        &mut self,
+        repository: Entity<Repository>,
+        commit: LogEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        telemetry::event!("Git Cherry Picked");
+        let action = RemoteAction::CherryPick(commit.short_sha().into());
+        self.run_sequencer_command(
+            repository,
+            action,
+            move |repo, cx| repo.cherry_pick(vec![commit.sha.to_string()], cx),
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn revert_commit(
+        &mut self,
+        repository: Entity<Repository>,
+        commit: LogEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        telemetry::event!("Git Commit Reverted");
+        let action = RemoteAction::Revert(commit.short_sha().into());
+        self.run_sequencer_command(
+            repository,
+            action,
+            move |repo, cx| repo.revert(vec![commit.sha.to_string()], cx),
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn rebase(
+        &mut self,
+        repository: Entity<Repository>,
+        onto: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        telemetry::event!("Git Rebased");
+        self.run_sequencer_command(
+            repository,
+            RemoteAction::Rebase,
+            move |repo, cx| repo.rebase(onto, todo, cx),
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn continue_operation(
+        &mut self,
+        _: &ContinueOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(repository), Some(operation)) =
+            (self.active_repository.clone(), self.sequencer_operation)
+        else {
+            return;
+        };
+        self.run_sequencer_command(
+            repository,
+            RemoteAction::Continue(operation),
+            |repo, cx| repo.continue_operation(cx),
+            window,
+            cx,
+        );
+    }
+
+    pub(crate) fn abort_operation(
+        &mut self,
+        _: &AbortOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(repository), Some(operation)) =
+            (self.active_repository.clone(), self.sequencer_operation)
+        else {
+            return;
+        };
+        self.run_sequencer_command(
+            repository,
+            RemoteAction::Abort(operation),
+            |repo, cx| repo.abort_operation(cx),
+            window,
+            cx,
+        );
+    }
+
+    /// Runs a cherry-pick, revert or rebase command, reporting how it went. When it
+    /// stops for conflicts, the panel offers to continue or abort once they are resolved.
+    fn run_sequencer_command(
+        &mut self,
+        repository: Entity<Repository>,
+        action: RemoteAction,
+        command: impl FnOnce(&Repository, &mut App) -> oneshot::Receiver<Result<RemoteCommandOutput>>
+            + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let guard = self.start_remote_operation();
+        let this = cx.weak_entity();
+        window
+            .spawn(cx, |mut cx| async move {
+                let output = repository.update(&mut cx, |repo, cx| command(repo, cx))?;
+
+                let output = output.await?;
+                drop(guard);
+                this.update(&mut cx, |this, cx| match output {
+                    Ok(output) => this.show_remote_output(action, output, cx),
+                    Err(e) => {
+                        log::error!("Error while running git {}: {:?}", action.name(), e);
+                        this.show_error_toast(action.name(), e, cx)
+                    }
+                })
+                .ok();
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
     pub(crate) fn git_init(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let worktrees = self
             .project
@@ -1981,6 +2271,104 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        tag: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Tag Pushed");
+        let remote = self.get_current_remote(window, cx);
+
+        cx.spawn_in(window, move |this, mut cx| async move {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(&mut cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(&mut cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let guard = this
+                .update(&mut cx, |this, _| this.start_remote_operation())
+                .ok();
+
+            let push = repo.update(&mut cx, |repo, cx| {
+                repo.push_tag(tag.clone(), remote.name.clone(), askpass_delegate, cx)
+            })?;
+
+            let remote_output = push.await?;
+            drop(guard);
+
+            let action = RemoteAction::PushTag(tag, remote);
+            this.update(&mut cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Initializes and updates the given submodules of `repository`, or all
+    /// of its submodules when `paths` is empty.
+    pub(crate) fn update_submodules(
+        &mut self,
+        repository: Entity<Repository>,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        telemetry::event!("Git Submodules Updated");
+        cx.spawn_in(window, move |this, mut cx| async move {
+            let askpass_delegate = this.update_in(&mut cx, |this, window, cx| {
+                this.askpass_delegate("git submodule update", window, cx)
+            })?;
+
+            let guard = this
+                .update(&mut cx, |this, _| this.start_remote_operation())
+                .ok();
+
+            let update = repository.update(&mut cx, |repository, cx| {
+                repository.update_submodules(paths, askpass_delegate, cx)
+            })?;
+
+            let remote_output = update.await?;
+            drop(guard);
+
+            let action = RemoteAction::UpdateSubmodules;
+            this.update(&mut cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while updating submodules {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn askpass_delegate(
         &self,
         operation: impl Into<SharedString>,
@@ -2180,11 +2568,40 @@ impl GitPanel {
         self.update_visible_entries_task = cx.spawn_in(window, |_, mut cx| async move {
             cx.background_executor().timer(UPDATE_DEBOUNCE).await;
             if let Some(git_panel) = handle.upgrade() {
+                let (stash_list, sequencer_operation) = git_panel
+                    .update(&mut cx, |git_panel, cx| {
+                        git_panel.active_repository.as_ref().map(|repo| {
+                            let repo = repo.read(cx);
+                            (repo.stash_list(), repo.sequencer_operation())
+                        })
+                    })
+                    .ok()
+                    .flatten()
+                    .unzip();
+                let stash_entries = match stash_list {
+                    Some(stash_list) => stash_list
+                        .await
+                        .ok()
+                        .and_then(|entries| entries.log_err())
+                        .unwrap_or_default(),
+                    None => Vec::new(),
+                };
+                let sequencer_operation = match sequencer_operation {
+                    Some(sequencer_operation) => sequencer_operation
+                        .await
+                        .ok()
+                        .and_then(|operation| operation.log_err())
+                        .flatten(),
+                    None => None,
+                };
                 git_panel
                     .update_in(&mut cx, |git_panel, window, cx| {
                         if clear_pending {
                             git_panel.clear_pending();
                         }
+                        git_panel.stash_entries = stash_entries;
+                        git_panel.sequencer_operation = sequencer_operation;
+                        git_panel.refresh_pull_request(false, cx);
                         git_panel.update_visible_entries(cx);
                         git_panel.update_editor_placeholder(cx);
                         git_panel.update_scrollbar_properties(window, cx);
@@ -2194,6 +2611,110 @@ impl GitPanel {
         });
     }
 
+    /// Looks up the pull request for the current branch on its upstream's
+    /// hosting provider, unless it was already looked up for the same commit.
+    fn refresh_pull_request(&mut self, force: bool, cx: &mut Context<Self>) {
+        let Some(branch) = self
+            .active_repository
+            .as_ref()
+            .and_then(|repo| repo.read(cx).current_branch().cloned())
+        else {
+            self.pull_request = None;
+            self.pull_request_key = None;
+            self.refresh_pull_request_task = Task::ready(());
+            return;
+        };
+        let key = (
+            branch.name.clone(),
+            branch
+                .most_recent_commit
+                .as_ref()
+                .map(|commit| commit.sha.clone()),
+        );
+        if !force && self.pull_request_key.as_ref() == Some(&key) {
+            return;
+        }
+        self.pull_request_key = Some(key);
+
+        let upstream = branch.upstream.as_ref();
+        let remote_name = upstream
+            .and_then(|upstream| upstream.remote_name())
+            .unwrap_or("origin")
+            .to_string();
+        let branch_name = upstream
+            .and_then(|upstream| upstream.branch_name())
+            .unwrap_or(&branch.name)
+            .to_string();
+        let Some(remote_url) = self
+            .active_repository
+            .as_ref()
+            .map(|repo| repo.read(cx).remote_url(remote_name))
+        else {
+            return;
+        };
+        let provider_registry = GitHostingProviderRegistry::default_global(cx);
+        let http_client = cx.http_client();
+        self.refresh_pull_request_task = cx.spawn(|git_panel, mut cx| async move {
+            let status = async {
+                let Some(remote_url) = remote_url.await?? else {
+                    return anyhow::Ok(None);
+                };
+                let Some((provider, remote)) = parse_git_remote_url(provider_registry, &remote_url)
+                else {
+                    return Ok(None);
+                };
+                if !provider.supports_pull_requests() {
+                    return Ok(None);
+                }
+                let pull_request = provider
+                    .pull_requests_for_branch(&remote, &branch_name, http_client.clone())
+                    .await?
+                    .into_iter()
+                    .next();
+                let checks = match &pull_request {
+                    Some(pull_request) => {
+                        provider
+                            .pull_request_checks(&remote, pull_request, http_client)
+                            .await?
+                    }
+                    None => Vec::new(),
+                };
+                let create_url = provider
+                    .build_create_pull_request_url(&remote, &branch_name)
+                    .map(|url| url.to_string().into());
+                Ok(Some(PullRequestStatus {
+                    pull_request,
+                    checks,
+                    create_url,
+                }))
+            }
+            .await
+            .log_err()
+            .flatten();
+
+            git_panel
+                .update(&mut cx, |git_panel, cx| {
+                    git_panel.pull_request = status;
+                    cx.notify();
+                })
+                .ok();
+        });
+    }
+
+    pub(crate) fn open_pull_request(&mut self, cx: &mut Context<Self>) {
+        let Some(status) = self.pull_request.as_ref() else {
+            return;
+        };
+        match &status.pull_request {
+            Some(pull_request) => cx.open_url(pull_request.url.as_str()),
+            None => {
+                if let Some(create_url) = &status.create_url {
+                    cx.open_url(create_url);
+                }
+            }
+        }
+    }
+
     fn reopen_commit_buffer(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(active_repo) = self.active_repository.as_ref() else {
             return;
@@ -2380,13 +2901,24 @@ impl GitPanel {
             self.entries
                 .extend(new_entries.into_iter().map(GitListEntry::GitStatusEntry));
         }
+        if self.stash_entries.len() > 0 {
+            self.entries.push(GitListEntry::Header(GitHeaderEntry {
+                header: Section::Stash,
+            }));
+            self.entries.extend(
+                self.stash_entries
+                    .iter()
+                    .cloned()
+                    .map(GitListEntry::StashEntry),
+            );
+        }
 
         if let Some((repo_path, _)) = max_width_item {
             self.max_width_item_index = self.entries.iter().position(|entry| match entry {
                 GitListEntry::GitStatusEntry(git_status_entry) => {
                     git_status_entry.repo_path == repo_path
                 }
-                GitListEntry::Header(_) => false,
+                GitListEntry::Header(_) | GitListEntry::StashEntry(_) => false,
             });
         }
 
@@ -2402,6 +2934,7 @@ impl GitPanel {
             Section::New => (self.new_staged_count, self.new_count),
             Section::Tracked => (self.tracked_staged_count, self.tracked_count),
             Section::Conflict => (self.conflicted_staged_count, self.conflicted_count),
+            Section::Stash => (0, 0),
         };
         if staged_count == 0 {
             ToggleState::Unselected
@@ -2822,6 +3355,141 @@ impl GitPanel {
         )
     }
 
+    fn render_sequencer_operation(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let operation = self.sequencer_operation?;
+        let title = match operation {
+            SequencerOperation::CherryPick => "Cherry-pick in progress",
+            SequencerOperation::Revert => "Revert in progress",
+            SequencerOperation::Rebase => "Rebase in progress",
+        };
+
+        Some(
+            h_flex()
+                .px_2()
+                .py_1()
+                .gap_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .child(Label::new(title).size(LabelSize::Small))
+                .child(div().flex_grow())
+                .child(
+                    panel_button("Abort")
+                        .tooltip(Tooltip::for_action_title_in(
+                            "Return to where it started",
+                            &AbortOperation,
+                            &self.focus_handle,
+                        ))
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.abort_operation(&AbortOperation, window, cx)
+                        })),
+                )
+                .child(
+                    panel_filled_button("Continue")
+                        .tooltip(Tooltip::for_action_title_in(
+                            "Continue once conflicts are resolved and staged",
+                            &ContinueOperation,
+                            &self.focus_handle,
+                        ))
+                        .disabled(self.conflicted_count > 0)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.continue_operation(&ContinueOperation, window, cx)
+                        })),
+                ),
+        )
+    }
+
+    fn render_pull_request(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let status = self.pull_request.as_ref()?;
+        let (label, color, tooltip) = match &status.pull_request {
+            Some(pull_request) => {
+                let (color, state) = match pull_request.state {
+                    PullRequestState::Open => (Color::Success, "Open"),
+                    PullRequestState::Draft => (Color::Muted, "Draft"),
+                    PullRequestState::Merged => (Color::Accent, "Merged"),
+                    PullRequestState::Closed => (Color::Error, "Closed"),
+                };
+                (
+                    SharedString::from(format!("#{} {}", pull_request.number, pull_request.title)),
+                    color,
+                    SharedString::from(format!("{state} pull request")),
+                )
+            }
+            None => {
+                status.create_url.as_ref()?;
+                (
+                    "Create Pull Request".into(),
+                    Color::Muted,
+                    "Open a pull request for this branch".into(),
+                )
+            }
+        };
+
+        let checks = StatusCheck::summarize(&status.checks).map(|summary| {
+            let passed = status
+                .checks
+                .iter()
+                .filter(|check| check.status == CheckStatus::Success)
+                .count();
+            let (icon, color) = match summary {
+                CheckStatus::Success => (IconName::Check, Color::Success),
+                CheckStatus::Failure => (IconName::XCircle, Color::Error),
+                CheckStatus::Pending => (IconName::CountdownTimer, Color::Warning),
+                CheckStatus::Neutral => (IconName::Dash, Color::Muted),
+            };
+            let tooltip: SharedString =
+                format!("{passed} of {} checks passed", status.checks.len()).into();
+            div()
+                .id("pull-request-checks")
+                .child(Icon::new(icon).size(IconSize::Small).color(color))
+                .tooltip(Tooltip::text(tooltip))
+        });
+
+        Some(
+            h_flex()
+                .id("pull-request")
+                .px_2()
+                .py_1()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .cursor_pointer()
+                .hover(|this| this.bg(cx.theme().colors().ghost_element_hover))
+                .child(
+                    Icon::new(IconName::PullRequest)
+                        .size(IconSize::Small)
+                        .color(color),
+                )
+                .child(
+                    Label::new(label)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                )
+                .child(div().flex_grow())
+                .children(checks)
+                .child(
+                    panel_icon_button("refresh-pull-request", IconName::RotateCw)
+                        .icon_size(IconSize::XSmall)
+                        .tooltip(Tooltip::text("Refresh"))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            cx.stop_propagation();
+                            this.refresh_pull_request(true, cx);
+                        })),
+                )
+                .tooltip(Tooltip::for_action_title_in(
+                    tooltip,
+                    &OpenPullRequest,
+                    &self.focus_handle,
+                ))
+                .on_click(cx.listener(|this, _, _, cx| this.open_pull_request(cx))),
+        )
+    }
+
     pub fn render_footer(
         &self,
         window: &mut Window,
@@ -3279,6 +3947,15 @@ impl GitPanel {
                                                 cx,
                                             ));
                                         }
+                                        Some(GitListEntry::StashEntry(entry)) => {
+                                            items.push(this.render_stash_entry(
+                                                ix,
+                                                entry,
+                                                has_write_access,
+                                                window,
+                                                cx,
+                                            ));
+                                        }
                                         None => {}
                                     }
                                 }
@@ -3425,6 +4102,9 @@ impl GitPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if let Some(GitListEntry::StashEntry(entry)) = self.entries.get(ix) {
+            return self.deploy_stash_context_menu(position, ix, entry.clone(), window, cx);
+        }
         let Some(entry) = self.entries.get(ix).and_then(|e| e.status_entry()) else {
             return;
         };
@@ -3438,14 +4118,93 @@ impl GitPanel {
         } else {
             "Restore File"
         };
+        let panel = cx.weak_entity();
+        let repo_path = entry.repo_path.clone();
+        let is_untracked = entry.status.is_untracked();
+        let merge_target = self
+            .active_repository
+            .clone()
+            .filter(|_| entry.status.is_conflicted())
+            .map(|repository| (repository, entry.repo_path.clone(), self.workspace.clone()));
         let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
             context_menu
                 .context(self.focus_handle.clone())
                 .action(stage_title, ToggleStaged.boxed_clone())
                 .action(restore_title, git::RestoreFile.boxed_clone())
+                .entry("Stash File", None, move |_, cx| {
+                    panel
+                        .update(cx, |panel, cx| {
+                            panel.stash_paths(vec![repo_path.clone()], is_untracked, cx);
+                        })
+                        .ok();
+                })
                 .separator()
                 .action("Open Diff", Confirm.boxed_clone())
                 .action("Open File", SecondaryConfirm.boxed_clone())
+                .when_some(
+                    merge_target,
+                    |context_menu, (repository, path, workspace)| {
+                        context_menu.entry("Open Merge Editor", None, move |window, cx| {
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    MergeEditor::open(
+                                        repository.clone(),
+                                        path.clone(),
+                                        workspace,
+                                        window,
+                                        cx,
+                                    )
+                                })
+                                .ok();
+                        })
+                    },
+                )
+        });
+        self.selected_entry = Some(ix);
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn deploy_stash_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        entry: StashEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let panel = cx.weak_entity();
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(self.focus_handle.clone())
+                .entry("Apply Stash", None, {
+                    let panel = panel.clone();
+                    let entry = entry.clone();
+                    move |_, cx| {
+                        panel
+                            .update(cx, |panel, cx| panel.apply_stash(entry.clone(), cx))
+                            .ok();
+                    }
+                })
+                .entry("Pop Stash", None, {
+                    let panel = panel.clone();
+                    let entry = entry.clone();
+                    move |_, cx| {
+                        panel
+                            .update(cx, |panel, cx| panel.pop_stash(entry.clone(), cx))
+                            .ok();
+                    }
+                })
+                .entry("Drop Stash", None, {
+                    let panel = panel.clone();
+                    let entry = entry.clone();
+                    move |window, cx| {
+                        panel
+                            .update(cx, |panel, cx| panel.drop_stash(entry.clone(), window, cx))
+                            .ok();
+                    }
+                })
+                .separator()
+                .action("View Changes", Confirm.boxed_clone())
         });
         self.selected_entry = Some(ix);
         self.set_context_menu(context_menu, position, window, cx);
@@ -3709,6 +4468,69 @@ impl GitPanel {
             .into_any_element()
     }
 
+    fn render_stash_entry(
+        &self,
+        ix: usize,
+        entry: &StashEntry,
+        has_write_access: bool,
+        window: &Window,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        let selected = self.selected_entry == Some(ix);
+        let id: ElementId = ElementId::Name(format!("stash_entry_{}", ix).into());
+        let handle = cx.weak_entity();
+
+        let base_bg = if selected {
+            cx.theme().status().info.alpha(0.08)
+        } else {
+            cx.theme().colors().ghost_element_background
+        };
+
+        h_flex()
+            .id(id)
+            .h(self.list_item_height())
+            .w_full()
+            .items_center()
+            .border_1()
+            .when(selected && self.focus_handle.is_focused(window), |el| {
+                el.border_color(cx.theme().colors().border_focused)
+            })
+            .px(rems(0.75)) // ~12px
+            .overflow_hidden()
+            .flex_none()
+            .gap_1p5()
+            .bg(base_bg)
+            .hover(|this| this.bg(cx.theme().colors().ghost_element_hover))
+            .active(|this| this.bg(cx.theme().colors().ghost_element_active))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.selected_entry = Some(ix);
+                cx.notify();
+                this.open_diff(&Default::default(), window, cx);
+                this.focus_handle.focus(window);
+            }))
+            .when(has_write_access, |this| {
+                this.on_mouse_down(
+                    MouseButton::Right,
+                    move |event: &MouseDownEvent, window, cx| {
+                        if event.button != MouseButton::Right {
+                            return;
+                        }
+
+                        let Some(this) = handle.upgrade() else {
+                            return;
+                        };
+                        this.update(cx, |this, cx| {
+                            this.deploy_entry_context_menu(event.position, ix, window, cx);
+                        });
+                        cx.stop_propagation();
+                    },
+                )
+            })
+            .child(self.entry_label(entry.ref_name(), Color::Muted))
+            .child(self.entry_label(entry.message.clone(), Color::Default))
+            .into_any_element()
+    }
+
     fn has_write_access(&self, cx: &App) -> bool {
         !self.project.read(cx).is_read_only(cx)
     }
@@ -3758,6 +4580,11 @@ impl Render for GitPanel {
                     .on_action(cx.listener(Self::restore_tracked_files))
                     .on_action(cx.listener(Self::revert_selected))
                     .on_action(cx.listener(Self::clean_all))
+                    .on_action(cx.listener(Self::stash_all))
+                    .on_action(cx.listener(Self::stash_all_including_untracked))
+                    .on_action(cx.listener(Self::stash_pop))
+                    .on_action(cx.listener(Self::continue_operation))
+                    .on_action(cx.listener(Self::abort_operation))
                     .on_action(cx.listener(Self::generate_commit_message_action))
             })
             .on_action(cx.listener(Self::select_first))
@@ -3789,6 +4616,7 @@ impl Render for GitPanel {
                 v_flex()
                     .size_full()
                     .children(self.render_panel_header(window, cx))
+                    .children(self.render_sequencer_operation(cx))
                     .map(|this| {
                         if has_entries {
                             this.child(self.render_entries(has_write_access, window, cx))
@@ -3796,6 +4624,7 @@ impl Render for GitPanel {
                             this.child(self.render_empty_state(cx).into_any_element())
                         }
                     })
+                    .children(self.render_pull_request(cx))
                     .children(self.render_footer(window, cx))
                     .children(self.render_previous_commit(cx))
                     .into_any_element(),
@@ -4443,7 +5272,7 @@ impl ComponentPreview for PanelRepoFooter {
 #[cfg(test)]
 mod tests {
     use git::status::StatusCode;
-    use gpui::TestAppContext;
+    use gpui::{TestAppContext, VisualTestContext};
     use project::{FakeFs, WorktreeSettings};
     use serde_json::json;
     use settings::SettingsStore;
@@ -4636,4 +5465,239 @@ mod tests {
             ],
         );
     }
+
+    #[gpui::test]
+    async fn test_stash_entries(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "zed": {
+                    ".git": {},
+                    "main.rs": "fn main() {}",
+                },
+            }),
+        )
+        .await;
+
+        let dot_git = Path::new(path!("/root/zed/.git"));
+        fs.set_branch_name(dot_git, Some("main"));
+        fs.with_git_state(dot_git, false, |state| {
+            state.stash_entries.push(StashEntry {
+                index: 0,
+                sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                message: "On main: tweak settings".into(),
+                commit_timestamp: 1733187470,
+            });
+        });
+
+        let project = Project::test(fs.clone(), [path!("/root/zed").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.executor().run_until_parked();
+
+        let app_state = workspace.update(cx, |workspace, _| workspace.app_state().clone());
+        let panel = cx.new_window_entity(|window, cx| {
+            GitPanel::new(workspace.clone(), project.clone(), app_state, window, cx)
+        });
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        let entries = panel.update(cx, |panel, _| panel.entries.clone());
+        pretty_assertions::assert_eq!(
+            entries,
+            [
+                GitListEntry::Header(GitHeaderEntry {
+                    header: Section::Stash
+                }),
+                GitListEntry::StashEntry(StashEntry {
+                    index: 0,
+                    sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    message: "On main: tweak settings".into(),
+                    commit_timestamp: 1733187470,
+                }),
+            ],
+        );
+
+        cx.update_window_entity(&panel, |panel, window, cx| {
+            panel.stash_all(&StashAll, window, cx);
+        });
+        cx.executor().run_until_parked();
+
+        let handle = cx.update_window_entity(&panel, |panel, window, cx| {
+            panel.schedule_update(false, window, cx);
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        let stash_entries = panel.update(cx, |panel, _| {
+            panel
+                .entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    GitListEntry::StashEntry(entry) => {
+                        Some((entry.index, entry.message.to_string()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        pretty_assertions::assert_eq!(
+            stash_entries,
+            [
+                (0, "On main: WIP".to_string()),
+                (1, "On main: tweak settings".to_string()),
+            ],
+        );
+
+        // An entry that moved since it was listed isn't popped by its old index.
+        let stale_entry = StashEntry {
+            index: 0,
+            sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+            message: "On main: tweak settings".into(),
+            commit_timestamp: 1733187470,
+        };
+        panel.update(cx, |panel, cx| panel.pop_stash(stale_entry.clone(), cx));
+        cx.executor().run_until_parked();
+        fs.with_git_state(dot_git, false, |state| {
+            assert_eq!(state.stash_entries.len(), 2);
+        });
+
+        panel.update(cx, |panel, cx| {
+            panel.pop_stash(
+                StashEntry {
+                    index: 1,
+                    ..stale_entry
+                },
+                cx,
+            )
+        });
+        cx.executor().run_until_parked();
+        fs.with_git_state(dot_git, false, |state| {
+            assert_eq!(
+                state
+                    .stash_entries
+                    .iter()
+                    .map(|entry| entry.message.as_ref())
+                    .collect::<Vec<_>>(),
+                ["On main: WIP"]
+            );
+        });
+    }
+
+    /// Refreshes the panel and returns the operation it offers to continue or abort.
+    async fn sequencer_operation(
+        panel: &Entity<GitPanel>,
+        cx: &mut VisualTestContext,
+    ) -> Option<SequencerOperation> {
+        let handle = cx.update_window_entity(panel, |panel, window, cx| {
+            panel.schedule_update(false, window, cx);
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+        panel.update(cx, |panel, _| panel.sequencer_operation)
+    }
+
+    #[gpui::test]
+    async fn test_continue_and_abort_sequencer_operation(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "zed": {
+                    ".git": {},
+                    "main.rs": "fn main() {}",
+                },
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/zed/.git"));
+        let add_conflict = || {
+            fs.with_git_state(dot_git, true, |state| {
+                state
+                    .conflict_versions
+                    .insert("main.rs".into(), Default::default());
+            });
+        };
+
+        let project = Project::test(fs.clone(), [path!("/root/zed").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.executor().run_until_parked();
+
+        let app_state = workspace.update(cx, |workspace, _| workspace.app_state().clone());
+        let panel = cx.new_window_entity(|window, cx| {
+            GitPanel::new(workspace.clone(), project.clone(), app_state, window, cx)
+        });
+        let repository = panel.update(cx, |panel, _| panel.active_repository.clone().unwrap());
+        let commit = LogEntry {
+            sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+            parents: Vec::new(),
+            subject: "generated protobuf".into(),
+            author_name: "John Doe".into(),
+            author_email: "john@example.com".into(),
+            commit_timestamp: 1733180000,
+        };
+
+        // A cherry-pick that conflicts stops, and continues once the conflict is staged.
+        add_conflict();
+        cx.update_window_entity(&panel, |panel, window, cx| {
+            panel.cherry_pick(repository.clone(), commit.clone(), window, cx)
+        });
+        cx.executor().run_until_parked();
+        assert_eq!(
+            sequencer_operation(&panel, cx).await,
+            Some(SequencerOperation::CherryPick)
+        );
+
+        repository
+            .update(cx, |repository, cx| {
+                repository.stage_entries(vec!["main.rs".into()], cx)
+            })
+            .await
+            .unwrap();
+        cx.update_window_entity(&panel, |panel, window, cx| {
+            panel.continue_operation(&ContinueOperation, window, cx)
+        });
+        cx.executor().run_until_parked();
+        assert_eq!(sequencer_operation(&panel, cx).await, None);
+
+        // Aborting a conflicting revert drops the conflict.
+        add_conflict();
+        cx.update_window_entity(&panel, |panel, window, cx| {
+            panel.revert_commit(repository.clone(), commit.clone(), window, cx)
+        });
+        cx.executor().run_until_parked();
+        assert_eq!(
+            sequencer_operation(&panel, cx).await,
+            Some(SequencerOperation::Revert)
+        );
+
+        cx.update_window_entity(&panel, |panel, window, cx| {
+            panel.abort_operation(&AbortOperation, window, cx)
+        });
+        cx.executor().run_until_parked();
+        assert_eq!(sequencer_operation(&panel, cx).await, None);
+
+        fs.with_git_state(dot_git, false, |state| {
+            assert!(state.conflict_versions.is_empty());
+            assert_eq!(
+                state.sequencer_commands,
+                [
+                    format!("cherry-pick {}", commit.sha),
+                    "cherry-pick --continue".to_string(),
+                    format!("revert {}", commit.sha),
+                    "revert --abort".to_string(),
+                ]
+            );
+        });
+    }
 }
@@ -6,8 +6,10 @@ use git::{
     repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
+use git_log::GitLog;
 use git_panel_settings::GitPanelSettings;
 use gpui::{actions, App, Entity, FocusHandle};
+use merge_editor::MergeEditor;
 use onboarding::{clear_dismissed, GitOnboardingModal};
 use project::Project;
 use project_diff::ProjectDiff;
@@ -17,20 +19,31 @@ use workspace::Workspace;
 mod askpass_modal;
 pub mod branch_picker;
 mod commit_modal;
+pub mod commit_view;
+pub mod file_diff_view;
+pub mod git_log;
 pub mod git_panel;
 mod git_panel_settings;
+pub mod merge_editor;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_editor;
 pub(crate) mod remote_output;
 pub mod repository_selector;
+pub mod revision_picker;
+pub mod worktree_picker;
 
 actions!(git, [ResetOnboarding]);
 
 pub fn init(cx: &mut App) {
     GitPanelSettings::register(cx);
     branch_picker::init(cx);
+    worktree_picker::init(cx);
+    file_diff_view::init(cx);
     cx.observe_new(ProjectDiff::register).detach();
+    cx.observe_new(GitLog::register).detach();
+    cx.observe_new(MergeEditor::register).detach();
     commit_modal::init(cx);
     git_panel::init(cx);
 
@@ -72,7 +85,24 @@ pub fn init(cx: &mut App) {
                     panel.pull(window, cx);
                 });
             });
+            workspace.register_action(|workspace, _: &git::UpdateSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.update_submodules(repository, Vec::new(), window, cx);
+                });
+            });
         }
+        workspace.register_action(|workspace, _: &git::OpenPullRequest, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| panel.open_pull_request(cx));
+        });
         workspace.register_action(|workspace, action: &git::StageAll, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -89,6 +119,48 @@ pub fn init(cx: &mut App) {
                 panel.unstage_all(action, window, cx);
             });
         });
+        workspace.register_action(|workspace, action: &git::StashAll, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.stash_all(action, window, cx);
+            });
+        });
+        workspace.register_action(
+            |workspace, action: &git::StashAllIncludingUntracked, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.stash_all_including_untracked(action, window, cx);
+                });
+            },
+        );
+        workspace.register_action(|workspace, action: &git::StashPop, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.stash_pop(action, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, action: &git::ContinueOperation, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.continue_operation(action, window, cx);
+            });
+        });
+        workspace.register_action(|workspace, action: &git::AbortOperation, window, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.abort_operation(action, window, cx);
+            });
+        });
         CommandPaletteFilter::update_global(cx, |filter, _cx| {
             filter.hide_action_types(&[
                 zed_actions::OpenGitIntegrationOnboarding.type_id(),
@@ -0,0 +1,630 @@
+use anyhow::Result;
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent};
+use git::repository::{ConflictVersions, RepoPath};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, Entity, EventEmitter, FocusHandle, Focusable,
+    Render, Task,
+};
+use language::{Buffer, Capability, Language};
+use multi_buffer::MultiBuffer;
+use project::{git::Repository, Project, ProjectPath};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use theme::ActiveTheme;
+use ui::{prelude::*, Divider, DividerColor};
+use util::ResultExt as _;
+use workspace::{
+    item::{Item, ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+    ItemNavHistory, Workspace,
+};
+
+/// Shows the base, ours and theirs versions of a conflicted file side by side,
+/// above an editable result that is the file itself.
+pub struct MergeEditor {
+    repository: Entity<Repository>,
+    path: RepoPath,
+    versions: ConflictVersions,
+    buffer: Entity<Buffer>,
+    ours_editor: Entity<Editor>,
+    base_editor: Entity<Editor>,
+    theirs_editor: Entity<Editor>,
+    result_editor: Entity<Editor>,
+    _diff_tasks: Vec<Task<()>>,
+}
+
+impl MergeEditor {
+    pub(crate) fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _cx: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(|workspace, _: &git::OpenMergeEditor, window, cx| {
+            let Some(buffer) = workspace
+                .active_item_as::<Editor>(cx)
+                .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+            else {
+                return;
+            };
+            let git_store = workspace.project().read(cx).git_store().clone();
+            let Some((repository, path)) = git_store
+                .read(cx)
+                .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+            else {
+                return;
+            };
+            Self::open(repository, path, workspace, window, cx);
+        });
+    }
+
+    /// Opens a merge editor for the conflicted path, or activates an existing one.
+    pub fn open(
+        repository: Entity<Repository>,
+        path: RepoPath,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if let Some(existing) = workspace.items_of_type::<Self>(cx).find(|merge_editor| {
+            let merge_editor = merge_editor.read(cx);
+            merge_editor.repository == repository && merge_editor.path == path
+        }) {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let Some(project_path) = repository.read(cx).repo_path_to_project_path(&path) else {
+            return;
+        };
+        let project = workspace.project().clone();
+        let buffer = project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+        let versions = repository.read(cx).load_conflict_versions(path.clone());
+        cx.spawn_in(window, |workspace, mut cx| async move {
+            let buffer = buffer.await?;
+            let versions = versions.await??;
+            workspace.update_in(&mut cx, |workspace, window, cx| {
+                let merge_editor =
+                    cx.new(|cx| Self::new(repository, path, versions, buffer, project, window, cx));
+                workspace.add_item_to_active_pane(Box::new(merge_editor), None, true, window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to open merge editor", window, cx, |e, _, _| {
+            Some(format!("{e}"))
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        path: RepoPath,
+        versions: ConflictVersions,
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let language = buffer.read(cx).language().cloned();
+        let base_buffer = version_buffer(versions.base.clone(), language.clone(), cx);
+        let ours_buffer = version_buffer(versions.ours.clone(), language.clone(), cx);
+        let theirs_buffer = version_buffer(versions.theirs.clone(), language, cx);
+
+        let base_editor = version_editor(base_buffer.clone(), window, cx);
+        let ours_editor = version_editor(ours_buffer.clone(), window, cx);
+        let theirs_editor = version_editor(theirs_buffer.clone(), window, cx);
+        let diff_tasks = vec![
+            diff_against_base(&ours_editor, ours_buffer, base_buffer.clone(), cx),
+            diff_against_base(&theirs_editor, theirs_buffer, base_buffer, cx),
+        ];
+
+        let result_editor =
+            cx.new(|cx| Editor::for_buffer(buffer.clone(), Some(project), window, cx));
+        cx.subscribe(&result_editor, |_, _, event: &EditorEvent, cx| {
+            cx.emit(event.clone())
+        })
+        .detach();
+
+        Self {
+            repository,
+            path,
+            versions,
+            buffer,
+            ours_editor,
+            base_editor,
+            theirs_editor,
+            result_editor,
+            _diff_tasks: diff_tasks,
+        }
+    }
+
+    /// Replaces the result with one of the versions.
+    fn use_version(&mut self, text: Option<String>, cx: &mut Context<Self>) {
+        let text = text.unwrap_or_default();
+        self.buffer.update(cx, |buffer, cx| {
+            buffer.edit([(0..buffer.len(), text)], None, cx);
+        });
+    }
+
+    fn mark_resolved(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor.update(cx, |editor, cx| {
+            editor.mark_conflicts_resolved(&git::MarkResolved, window, cx)
+        });
+    }
+
+    fn render_pane(
+        &self,
+        title: impl Into<SharedString>,
+        editor: &Entity<Editor>,
+        cx: &App,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .h_full()
+            .overflow_hidden()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(title).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .child(div().flex_1().child(editor.clone()))
+    }
+}
+
+/// Creates a read-only buffer holding one version of the conflicted file.
+fn version_buffer(
+    text: Option<String>,
+    language: Option<Arc<Language>>,
+    cx: &mut App,
+) -> Entity<Buffer> {
+    cx.new(|cx| {
+        let mut buffer = Buffer::local(text.unwrap_or_default(), cx);
+        buffer.set_language(language, cx);
+        buffer.set_capability(Capability::ReadOnly, cx);
+        buffer
+    })
+}
+
+fn version_editor(
+    buffer: Entity<Buffer>,
+    window: &mut Window,
+    cx: &mut Context<MergeEditor>,
+) -> Entity<Editor> {
+    let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    cx.new(|cx| {
+        let mut editor = Editor::for_multibuffer(multibuffer, None, window, cx);
+        editor.disable_inline_diagnostics();
+        editor.set_read_only(true);
+        editor
+    })
+}
+
+/// Shows the hunks between a version and the merge base in the version's editor.
+fn diff_against_base(
+    editor: &Entity<Editor>,
+    buffer: Entity<Buffer>,
+    base_buffer: Entity<Buffer>,
+    cx: &mut Context<MergeEditor>,
+) -> Task<()> {
+    let snapshot = buffer.read(cx).text_snapshot();
+    let diff = cx.new(|cx| BufferDiff::new(&snapshot, cx));
+    let base_text_set = diff.update(cx, |diff, cx| diff.set_base_text(base_buffer, snapshot, cx));
+    let multibuffer = editor.read(cx).buffer().clone();
+    cx.spawn(|_, mut cx| async move {
+        base_text_set.await.ok();
+        multibuffer
+            .update(&mut cx, |multibuffer, cx| multibuffer.add_diff(diff, cx))
+            .log_err();
+    })
+}
+
+impl EventEmitter<EditorEvent> for MergeEditor {}
+
+impl Focusable for MergeEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Item for MergeEditor {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.result_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("Merge {}", self.path.display()).into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Label::new(format!("Merge: {file_name}"))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Merge Editor Opened")
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.result_editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.result_editor.for_each_project_item(cx, f)
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        format: bool,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.result_editor.save(format, project, window, cx)
+    }
+
+    fn save_as(
+        &mut self,
+        _: Entity<Project>,
+        _: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        unreachable!()
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.result_editor.reload(project, window, cx)
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.result_editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for MergeEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border_variant;
+        v_flex()
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .child(Label::new(self.path.display().to_string()).truncate())
+                    .child(div().flex_grow())
+                    .child(
+                        Button::new("use-ours", "Use Ours")
+                            .disabled(self.versions.ours.is_none())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.use_version(this.versions.ours.clone(), cx)
+                            })),
+                    )
+                    .child(
+                        Button::new("use-theirs", "Use Theirs")
+                            .disabled(self.versions.theirs.is_none())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.use_version(this.versions.theirs.clone(), cx)
+                            })),
+                    )
+                    .child(
+                        Button::new("mark-resolved", "Mark Resolved")
+                            .style(ButtonStyle::Filled)
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.mark_resolved(window, cx)),
+                            ),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .items_start()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .child(self.render_pane("Ours", &self.ours_editor, cx))
+                    .child(Divider::vertical().color(DividerColor::Border))
+                    .child(self.render_pane("Base", &self.base_editor, cx))
+                    .child(Divider::vertical().color(DividerColor::Border))
+                    .child(self.render_pane("Theirs", &self.theirs_editor, cx)),
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .child(self.render_pane("Result", &self.result_editor, cx)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collections::HashMap;
+    use git::status::{StatusCode, UnmergedStatus, UnmergedStatusCode};
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::ops::Range;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_use_theirs_and_mark_resolved(cx: &mut TestAppContext) {
+        init_test(cx);
+        let versions = ConflictVersions {
+            base: Some("base\n".into()),
+            ours: Some("ours\n".into()),
+            theirs: Some("theirs\n".into()),
+        };
+        let (fs, repository, workspace, cx) = conflicted_project(
+            "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> theirs\n",
+            versions.clone(),
+            cx,
+        )
+        .await;
+
+        // Opening the same path again activates the existing merge editor.
+        for _ in 0..2 {
+            workspace.update_in(cx, |workspace, window, cx| {
+                MergeEditor::open(repository.clone(), "file.txt".into(), workspace, window, cx)
+            });
+            cx.run_until_parked();
+        }
+        let merge_editor = workspace.update(cx, |workspace, cx| {
+            assert_eq!(workspace.items_of_type::<MergeEditor>(cx).count(), 1);
+            workspace.active_item_as::<MergeEditor>(cx).unwrap()
+        });
+
+        merge_editor.update(cx, |merge_editor, cx| {
+            assert_eq!(merge_editor.versions, versions);
+            assert_eq!(merge_editor.base_editor.read(cx).text(cx), "base\n");
+            assert_eq!(merge_editor.ours_editor.read(cx).text(cx), "ours\n");
+            assert_eq!(merge_editor.theirs_editor.read(cx).text(cx), "theirs\n");
+            assert_eq!(
+                merge_editor.result_editor.read(cx).text(cx),
+                "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> theirs\n"
+            );
+
+            merge_editor.use_version(merge_editor.versions.theirs.clone(), cx);
+            assert_eq!(merge_editor.result_editor.read(cx).text(cx), "theirs\n");
+            assert!(merge_editor.is_dirty(cx));
+        });
+
+        merge_editor.update_in(cx, |merge_editor, window, cx| {
+            merge_editor.mark_resolved(window, cx)
+        });
+        cx.run_until_parked();
+
+        merge_editor.read_with(cx, |merge_editor, cx| assert!(!merge_editor.is_dirty(cx)));
+        assert_eq!(
+            String::from_utf8(fs.read_file_sync(path!("/project/file.txt")).unwrap()).unwrap(),
+            "theirs\n"
+        );
+        let statuses = repository.read_with(cx, |repository, _| {
+            repository
+                .status()
+                .map(|entry| (entry.repo_path, entry.status))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            statuses,
+            [("file.txt".into(), StatusCode::Modified.index())]
+        );
+        assert!(repository
+            .update(cx, |repository, _| repository
+                .load_conflict_versions("file.txt".into()))
+            .await
+            .unwrap()
+            .is_err());
+    }
+
+    #[gpui::test]
+    async fn test_side_deleted_by_them(cx: &mut TestAppContext) {
+        init_test(cx);
+        // Git leaves our version in the working copy when the other side deleted the file.
+        let (_fs, repository, workspace, cx) = conflicted_project(
+            "one\ntwo\nthree\n",
+            ConflictVersions {
+                base: Some("one\ntwo\n".into()),
+                ours: Some("one\ntwo\nthree\n".into()),
+                theirs: None,
+            },
+            cx,
+        )
+        .await;
+        workspace.update_in(cx, |workspace, window, cx| {
+            MergeEditor::open(repository.clone(), "file.txt".into(), workspace, window, cx)
+        });
+        cx.run_until_parked();
+        let merge_editor = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<MergeEditor>(cx).unwrap()
+        });
+
+        merge_editor.update(cx, |merge_editor, cx| {
+            assert_eq!(merge_editor.theirs_editor.read(cx).text(cx), "");
+            // Each side is diffed against the base: ours adds a line, theirs removes everything.
+            assert_eq!(hunks(&merge_editor.ours_editor, cx), [(2..3, 8..8)]);
+            assert_eq!(hunks(&merge_editor.theirs_editor, cx), [(0..0, 0..8)]);
+
+            // Taking the deleting side empties the result.
+            merge_editor.use_version(merge_editor.versions.theirs.clone(), cx);
+            assert_eq!(merge_editor.result_editor.read(cx).text(cx), "");
+            assert!(merge_editor.is_dirty(cx));
+
+            merge_editor.use_version(merge_editor.versions.ours.clone(), cx);
+            assert_eq!(
+                merge_editor.result_editor.read(cx).text(cx),
+                "one\ntwo\nthree\n"
+            );
+        });
+    }
+
+    /// Returns the row ranges of a version editor's hunks, with the base text ranges they replace.
+    fn hunks(editor: &Entity<Editor>, cx: &App) -> Vec<(Range<u32>, Range<usize>)> {
+        let multibuffer = editor.read(cx).buffer().read(cx);
+        let snapshot = multibuffer.as_singleton().unwrap().read(cx).text_snapshot();
+        multibuffer
+            .diff_for(snapshot.remote_id())
+            .unwrap()
+            .read(cx)
+            .hunks(&snapshot, cx)
+            .map(|hunk| {
+                (
+                    hunk.range.start.row..hunk.range.end.row,
+                    hunk.diff_base_byte_range,
+                )
+            })
+            .collect()
+    }
+
+    async fn conflicted_project(
+        text: &str,
+        versions: ConflictVersions,
+        cx: &mut TestAppContext,
+    ) -> (
+        Arc<FakeFs>,
+        Entity<Repository>,
+        Entity<Workspace>,
+        &mut VisualTestContext,
+    ) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.txt": text,
+            }),
+        )
+        .await;
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state.statuses = HashMap::from_iter([(
+                "file.txt".into(),
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: if versions.theirs.is_some() {
+                        UnmergedStatusCode::Updated
+                    } else {
+                        UnmergedStatusCode::Deleted
+                    },
+                }
+                .into(),
+            )]);
+            state.conflict_versions.insert("file.txt".into(), versions);
+        });
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        (fs, repository, workspace, cx)
+    }
+}
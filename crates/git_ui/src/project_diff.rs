@@ -1,14 +1,15 @@
 use crate::{
     git_panel::{GitPanel, GitPanelAddon, GitStatusEntry},
     remote_button::{render_publish_button, render_push_button},
+    revision_picker::RevisionPicker,
 };
 use anyhow::Result;
 use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus};
-use collections::HashSet;
+use collections::{HashMap, HashSet};
 use editor::{
-    actions::{GoToHunk, GoToPreviousHunk},
+    actions::{GoToHunk, GoToPreviousHunk, ToggleSplitDiff},
     scroll::Autoscroll,
-    Editor, EditorEvent,
+    Editor, EditorEvent, SplitDiff,
 };
 use futures::StreamExt;
 use git::{
@@ -20,13 +21,16 @@ use gpui::{
     actions, Action, AnyElement, AnyView, App, AppContext as _, AsyncWindowContext, Entity,
     EventEmitter, FocusHandle, Focusable, Render, Subscription, Task, WeakEntity,
 };
-use language::{Anchor, Buffer, Capability, OffsetRangeExt};
+use language::{Anchor, Buffer, BufferEvent, BufferId, Capability, LineEnding, OffsetRangeExt};
 use multi_buffer::{MultiBuffer, PathKey};
 use project::{
     git::{GitEvent, GitStore},
     Project, ProjectPath,
 };
-use std::any::{Any, TypeId};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
 use theme::ActiveTheme;
 use ui::{prelude::*, vertical_divider, KeyBinding, Tooltip};
 use util::ResultExt as _;
@@ -37,12 +41,20 @@ use workspace::{
     ToolbarItemView, Workspace,
 };
 
-actions!(git, [Diff, Add]);
+actions!(git, [Diff, Add, DiffAgainstRevision]);
 
 pub struct ProjectDiff {
     project: Entity<Project>,
+    /// The revision the working tree is compared against, or `None` if the
+    /// diff shows the uncommitted changes.
+    base_revision: Option<SharedString>,
+    /// Why the working tree couldn't be compared against the base revision.
+    revision_error: Option<SharedString>,
+    revision_diffs: HashMap<BufferId, RevisionDiff>,
     multibuffer: Entity<MultiBuffer>,
     editor: Entity<Editor>,
+    /// Shows the base texts beside the editor, when the diff is split.
+    split_diff: Option<Entity<SplitDiff>>,
     git_store: Entity<GitStore>,
     workspace: WeakEntity<Workspace>,
     focus_handle: FocusHandle,
@@ -61,6 +73,15 @@ struct DiffBuffer {
     file_status: FileStatus,
 }
 
+/// A buffer's diff against the merge base of the base revision, kept up to
+/// date as the buffer is edited.
+struct RevisionDiff {
+    base: SharedString,
+    diff: Entity<BufferDiff>,
+    recalculate_task: Option<Task<Result<()>>>,
+    _subscription: Subscription,
+}
+
 const CONFLICT_NAMESPACE: &'static str = "0";
 const TRACKED_NAMESPACE: &'static str = "1";
 const NEW_NAMESPACE: &'static str = "2";
@@ -75,6 +96,9 @@ impl ProjectDiff {
         workspace.register_action(|workspace, _: &Add, window, cx| {
             Self::deploy(workspace, &Diff, window, cx);
         });
+        workspace.register_action(|workspace, _: &DiffAgainstRevision, window, cx| {
+            RevisionPicker::toggle(workspace, window, cx);
+        });
 
         workspace::register_serializable_item::<ProjectDiff>(cx);
     }
@@ -102,22 +126,7 @@ impl ProjectDiff {
                 "Action"
             }
         );
-        let project_diff = if let Some(existing) = workspace.item_of_type::<Self>(cx) {
-            workspace.activate_item(&existing, true, true, window, cx);
-            existing
-        } else {
-            let workspace_handle = cx.entity();
-            let project_diff =
-                cx.new(|cx| Self::new(workspace.project().clone(), workspace_handle, window, cx));
-            workspace.add_item_to_active_pane(
-                Box::new(project_diff.clone()),
-                None,
-                true,
-                window,
-                cx,
-            );
-            project_diff
-        };
+        let project_diff = Self::open(workspace, None, window, cx);
         if let Some(entry) = entry {
             project_diff.update(cx, |project_diff, cx| {
                 project_diff.move_to_entry(entry, window, cx);
@@ -125,6 +134,46 @@ impl ProjectDiff {
         }
     }
 
+    /// Opens a diff of the working tree against the merge base of `revision`
+    /// and HEAD, which for a branch shows what merging into it would change.
+    pub fn deploy_against(
+        workspace: &mut Workspace,
+        revision: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        telemetry::event!("Git Revision Diff Opened");
+        Self::open(workspace, Some(revision), window, cx);
+    }
+
+    fn open(
+        workspace: &mut Workspace,
+        base_revision: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        if let Some(existing) = workspace
+            .items_of_type::<Self>(cx)
+            .find(|project_diff| project_diff.read(cx).base_revision == base_revision)
+        {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return existing;
+        }
+
+        let workspace_handle = cx.entity();
+        let project_diff = cx.new(|cx| {
+            Self::new(
+                workspace.project().clone(),
+                workspace_handle,
+                base_revision,
+                window,
+                cx,
+            )
+        });
+        workspace.add_item_to_active_pane(Box::new(project_diff.clone()), None, true, window, cx);
+        project_diff
+    }
+
     pub fn autoscroll(&self, cx: &mut Context<Self>) {
         self.editor.update(cx, |editor, cx| {
             editor.request_autoscroll(Autoscroll::fit(), cx);
@@ -134,6 +183,7 @@ impl ProjectDiff {
     fn new(
         project: Entity<Project>,
         workspace: Entity<Workspace>,
+        base_revision: Option<SharedString>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -177,10 +227,14 @@ impl ProjectDiff {
 
         Self {
             project,
+            base_revision,
+            revision_error: None,
+            revision_diffs: HashMap::default(),
             git_store: git_store.clone(),
             workspace: workspace.downgrade(),
             focus_handle,
             editor,
+            split_diff: None,
             multibuffer,
             pending_scroll: None,
             update_needed: send,
@@ -390,6 +444,158 @@ impl ProjectDiff {
         result
     }
 
+    fn load_revision_buffers(
+        &mut self,
+        revision: SharedString,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Task<Result<DiffBuffer>>>>> {
+        let Some(repo) = self.git_store.read(cx).active_repository() else {
+            self.multibuffer.update(cx, |multibuffer, cx| {
+                multibuffer.clear(cx);
+            });
+            return Task::ready(Ok(Vec::new()));
+        };
+
+        let diff = repo.read(cx).diff_since(revision.to_string());
+        cx.spawn(|this, mut cx| async move {
+            let diff = diff.await?;
+            this.update(&mut cx, |this, cx| {
+                let diff = match diff {
+                    Ok(diff) => diff,
+                    Err(error) => {
+                        this.revision_error = Some(error.to_string().into());
+                        this.multibuffer.update(cx, |multibuffer, cx| {
+                            multibuffer.clear(cx);
+                        });
+                        return Vec::new();
+                    }
+                };
+                this.revision_error = None;
+
+                // Untracked files are left out of git's diff, but are new
+                // since any revision.
+                let untracked = repo
+                    .read(cx)
+                    .status()
+                    .filter(|entry| entry.status.is_untracked())
+                    .map(|entry| (entry.repo_path, FileStatus::Untracked))
+                    .collect::<Vec<_>>();
+                let entries = diff
+                    .entries
+                    .into_iter()
+                    .map(|entry| (entry.path, FileStatus::worktree(entry.status)))
+                    .chain(untracked);
+
+                let mut previous_paths = this.multibuffer.read(cx).paths().collect::<HashSet<_>>();
+                let mut result = Vec::new();
+                for (repo_path, file_status) in entries {
+                    let Some(project_path) = repo.read(cx).repo_path_to_project_path(&repo_path)
+                    else {
+                        continue;
+                    };
+                    let namespace = if repo.read(cx).has_conflict(&repo_path) {
+                        CONFLICT_NAMESPACE
+                    } else if file_status.is_created() {
+                        NEW_NAMESPACE
+                    } else {
+                        TRACKED_NAMESPACE
+                    };
+                    let path_key = PathKey::namespaced(namespace, repo_path.0.clone());
+                    previous_paths.remove(&path_key);
+
+                    let load_buffer = this
+                        .project
+                        .update(cx, |project, cx| project.open_buffer(project_path, cx));
+                    let base_text = (file_status != FileStatus::Untracked).then(|| {
+                        repo.read(cx)
+                            .load_revision_text(diff.base.to_string(), repo_path.clone())
+                    });
+                    let base = diff.base.clone();
+                    result.push(cx.spawn(|this, mut cx| async move {
+                        let buffer = load_buffer.await?;
+                        let base_text = match base_text {
+                            Some(base_text) => base_text.await??,
+                            None => None,
+                        };
+                        let diff = this
+                            .update(&mut cx, |this, cx| {
+                                this.revision_diff(buffer.clone(), base, base_text, cx)
+                            })?
+                            .await?;
+                        Ok(DiffBuffer {
+                            path_key,
+                            buffer,
+                            diff,
+                            file_status,
+                        })
+                    }));
+                }
+                this.multibuffer.update(cx, |multibuffer, cx| {
+                    for path in previous_paths {
+                        multibuffer.remove_excerpts_for_path(path, cx);
+                    }
+                });
+                result
+            })
+        })
+    }
+
+    /// Returns the buffer's diff against `base`, creating it if needed.
+    fn revision_diff(
+        &mut self,
+        buffer: Entity<Buffer>,
+        base: SharedString,
+        base_text: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<BufferDiff>>> {
+        let buffer_id = buffer.read(cx).remote_id();
+        if let Some(revision_diff) = self.revision_diffs.get(&buffer_id) {
+            if revision_diff.base == base {
+                return Task::ready(Ok(revision_diff.diff.clone()));
+            }
+        }
+
+        let base_text = base_text.map(|mut text| {
+            LineEnding::normalize(&mut text);
+            Arc::new(text)
+        });
+        let diff = cx.new(|cx| BufferDiff::new(&buffer.read(cx).text_snapshot(), cx));
+        let subscription = cx.subscribe(&buffer, {
+            let diff = diff.clone();
+            let base_text = base_text.clone();
+            move |this, buffer, event: &BufferEvent, cx| {
+                let language_changed = match event {
+                    BufferEvent::Edited => false,
+                    BufferEvent::LanguageChanged => true,
+                    _ => return,
+                };
+                if let Some(revision_diff) = this.revision_diffs.get_mut(&buffer_id) {
+                    revision_diff.recalculate_task = Some(recalculate_revision_diff(
+                        diff.clone(),
+                        &buffer,
+                        base_text.clone(),
+                        language_changed,
+                        cx,
+                    ));
+                }
+            }
+        });
+        let recalculate = recalculate_revision_diff(diff.clone(), &buffer, base_text, true, cx);
+        self.revision_diffs.insert(
+            buffer_id,
+            RevisionDiff {
+                base,
+                diff: diff.clone(),
+                recalculate_task: None,
+                _subscription: subscription,
+            },
+        );
+        cx.background_spawn(async move {
+            recalculate.await?;
+            Ok(diff)
+        })
+    }
+
     fn register_buffer(
         &mut self,
         diff_buffer: DiffBuffer,
@@ -401,14 +607,20 @@ impl ProjectDiff {
         let diff = diff_buffer.diff;
 
         let snapshot = buffer.read(cx).snapshot();
-        let diff = diff.read(cx);
         let diff_hunk_ranges = diff
+            .read(cx)
             .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &snapshot, cx)
             .map(|diff_hunk| diff_hunk.buffer_range.to_point(&snapshot))
             .collect::<Vec<_>>();
 
+        let is_revision_diff = self.base_revision.is_some();
         let (was_empty, is_excerpt_newly_added) = self.multibuffer.update(cx, |multibuffer, cx| {
             let was_empty = multibuffer.is_empty();
+            // The editor shows a buffer's uncommitted changes unless its
+            // multibuffer already has a diff for it.
+            if is_revision_diff {
+                multibuffer.add_diff(diff, cx);
+            }
             let is_newly_added = multibuffer.set_excerpts_for_path(
                 path_key.clone(),
                 buffer,
@@ -469,7 +681,12 @@ impl ProjectDiff {
                 }
             })?;
 
-            let buffers_to_load = this.update(&mut cx, |this, cx| this.load_buffers(cx))?;
+            let buffers_to_load = this
+                .update(&mut cx, |this, cx| match this.base_revision.clone() {
+                    Some(revision) => this.load_revision_buffers(revision, cx),
+                    None => Task::ready(Ok(this.load_buffers(cx))),
+                })?
+                .await?;
             for buffer_to_load in buffers_to_load {
                 if let Some(buffer) = buffer_to_load.await.log_err() {
                     cx.update(|window, cx| {
@@ -480,6 +697,15 @@ impl ProjectDiff {
             }
             this.update(&mut cx, |this, cx| {
                 this.pending_scroll.take();
+                let buffer_ids = this
+                    .multibuffer
+                    .read(cx)
+                    .all_buffers()
+                    .iter()
+                    .map(|buffer| buffer.read(cx).remote_id())
+                    .collect::<HashSet<_>>();
+                this.revision_diffs
+                    .retain(|buffer_id, _| buffer_ids.contains(buffer_id));
                 cx.notify();
             })?;
         }
@@ -497,6 +723,53 @@ impl ProjectDiff {
     }
 }
 
+fn recalculate_revision_diff(
+    diff: Entity<BufferDiff>,
+    buffer: &Entity<Buffer>,
+    base_text: Option<Arc<String>>,
+    rebuild_base: bool,
+    cx: &mut App,
+) -> Task<Result<()>> {
+    let buffer = buffer.read(cx);
+    let snapshot = buffer.text_snapshot();
+    let language = buffer.language().cloned();
+    let language_registry = buffer.language_registry();
+    cx.spawn(|mut cx| async move {
+        let new_snapshot = BufferDiff::update_diff(
+            diff.clone(),
+            snapshot.clone(),
+            base_text,
+            rebuild_base,
+            rebuild_base,
+            language,
+            language_registry,
+            &mut cx,
+        )
+        .await?;
+        diff.update(&mut cx, |diff, cx| {
+            diff.set_snapshot(&snapshot, new_snapshot, rebuild_base, None, cx);
+        })
+    })
+}
+
+impl ProjectDiff {
+    fn toggle_split_diff(
+        &mut self,
+        _: &ToggleSplitDiff,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.split_diff.take() {
+            Some(split_diff) => split_diff.update(cx, |split_diff, cx| split_diff.unsplit(cx)),
+            None => {
+                let editor = self.editor.clone();
+                self.split_diff = Some(cx.new(|cx| SplitDiff::new(editor, window, cx)));
+            }
+        }
+        cx.notify();
+    }
+}
+
 impl EventEmitter<EditorEvent> for ProjectDiff {}
 
 impl Focusable for ProjectDiff {
@@ -536,17 +809,23 @@ impl Item for ProjectDiff {
     }
 
     fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
-        Some("Project Diff".into())
+        Some(match &self.base_revision {
+            Some(revision) => format!("Project Diff against {revision}").into(),
+            None => "Project Diff".into(),
+        })
     }
 
     fn tab_content(&self, params: TabContentParams, _window: &Window, _: &App) -> AnyElement {
-        Label::new("Uncommitted Changes")
-            .color(if params.selected {
-                Color::Default
-            } else {
-                Color::Muted
-            })
-            .into_any_element()
+        Label::new(match &self.base_revision {
+            Some(revision) => SharedString::from(format!("Changes since {revision}")),
+            None => "Uncommitted Changes".into(),
+        })
+        .color(if params.selected {
+            Color::Default
+        } else {
+            Color::Muted
+        })
+        .into_any_element()
     }
 
     fn telemetry_event_text(&self) -> Option<&'static str> {
@@ -590,7 +869,15 @@ impl Item for ProjectDiff {
         Self: Sized,
     {
         let workspace = self.workspace.upgrade()?;
-        Some(cx.new(|cx| ProjectDiff::new(self.project.clone(), workspace, window, cx)))
+        Some(cx.new(|cx| {
+            ProjectDiff::new(
+                self.project.clone(),
+                workspace,
+                self.base_revision.clone(),
+                window,
+                cx,
+            )
+        }))
     }
 
     fn is_dirty(&self, cx: &App) -> bool {
@@ -673,11 +960,20 @@ impl Render for ProjectDiff {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let is_empty = self.multibuffer.read(cx).is_empty();
 
-        let can_push_and_pull = crate::can_push_and_pull(&self.project, cx);
+        let can_push_and_pull =
+            crate::can_push_and_pull(&self.project, cx) && self.base_revision.is_none();
+        let empty_message: SharedString = match (&self.base_revision, &self.revision_error) {
+            (Some(revision), Some(error)) => {
+                format!("Couldn't compare with {revision}: {error}").into()
+            }
+            (Some(revision), None) => format!("No changes since {revision}").into(),
+            (None, _) => "No uncommitted changes".into(),
+        };
 
         div()
             .track_focus(&self.focus_handle)
             .key_context(if is_empty { "EmptyPane" } else { "GitDiff" })
+            .on_action(cx.listener(Self::toggle_split_diff))
             .bg(cx.theme().colors().editor_background)
             .flex()
             .items_center()
@@ -687,11 +983,7 @@ impl Render for ProjectDiff {
                 el.child(
                     v_flex()
                         .gap_1()
-                        .child(
-                            h_flex()
-                                .justify_around()
-                                .child(Label::new("No uncommitted changes")),
-                        )
+                        .child(h_flex().justify_around().child(Label::new(empty_message)))
                         .when(can_push_and_pull, |this_div| {
                             let keybinding_focus_handle = self.focus_handle(cx);
 
@@ -740,7 +1032,10 @@ impl Render for ProjectDiff {
                         }),
                 )
             })
-            .when(!is_empty, |el| el.child(self.editor.clone()))
+            .when(!is_empty, |el| match &self.split_diff {
+                Some(split_diff) => el.child(split_diff.clone()),
+                None => el.child(self.editor.clone()),
+            })
     }
 }
 
@@ -769,7 +1064,15 @@ impl SerializableItem for ProjectDiff {
         window.spawn(cx, |mut cx| async move {
             workspace.update_in(&mut cx, |workspace, window, cx| {
                 let workspace_handle = cx.entity();
-                cx.new(|cx| Self::new(workspace.project().clone(), workspace_handle, window, cx))
+                cx.new(|cx| {
+                    Self::new(
+                        workspace.project().clone(),
+                        workspace_handle,
+                        None,
+                        window,
+                        cx,
+                    )
+                })
             })
         })
     }
@@ -886,6 +1189,8 @@ impl Render for ProjectDiffToolbar {
         };
         let focus_handle = project_diff.focus_handle(cx);
         let button_states = project_diff.read(cx).button_states(cx);
+        // Staging only applies to the uncommitted changes.
+        let can_stage = project_diff.read(cx).base_revision.is_none();
 
         h_group_xl()
             .my_neg_1()
@@ -895,58 +1200,60 @@ impl Render for ProjectDiffToolbar {
             .pr_1()
             .flex_wrap()
             .justify_between()
-            .child(
-                h_group_sm()
-                    .when(button_states.selection, |el| {
-                        el.child(
-                            Button::new("stage", "Toggle Staged")
-                                .tooltip(Tooltip::for_action_title_in(
-                                    "Toggle Staged",
-                                    &ToggleStaged,
-                                    &focus_handle,
-                                ))
-                                .disabled(!button_states.stage && !button_states.unstage)
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    this.dispatch_action(&ToggleStaged, window, cx)
-                                })),
-                        )
-                    })
-                    .when(!button_states.selection, |el| {
-                        el.child(
-                            Button::new("stage", "Stage")
-                                .tooltip(Tooltip::for_action_title_in(
-                                    "Stage and go to next hunk",
-                                    &StageAndNext,
-                                    &focus_handle,
-                                ))
-                                // don't actually disable the button so it's mashable
-                                .color(if button_states.stage {
-                                    Color::Default
-                                } else {
-                                    Color::Disabled
-                                })
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    this.dispatch_action(&StageAndNext, window, cx)
-                                })),
-                        )
-                        .child(
-                            Button::new("unstage", "Unstage")
-                                .tooltip(Tooltip::for_action_title_in(
-                                    "Unstage and go to next hunk",
-                                    &UnstageAndNext,
-                                    &focus_handle,
-                                ))
-                                .color(if button_states.unstage {
-                                    Color::Default
-                                } else {
-                                    Color::Disabled
-                                })
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    this.dispatch_action(&UnstageAndNext, window, cx)
-                                })),
-                        )
-                    }),
-            )
+            .when(can_stage, |el| {
+                el.child(
+                    h_group_sm()
+                        .when(button_states.selection, |el| {
+                            el.child(
+                                Button::new("stage", "Toggle Staged")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Toggle Staged",
+                                        &ToggleStaged,
+                                        &focus_handle,
+                                    ))
+                                    .disabled(!button_states.stage && !button_states.unstage)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&ToggleStaged, window, cx)
+                                    })),
+                            )
+                        })
+                        .when(!button_states.selection, |el| {
+                            el.child(
+                                Button::new("stage", "Stage")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Stage and go to next hunk",
+                                        &StageAndNext,
+                                        &focus_handle,
+                                    ))
+                                    // don't actually disable the button so it's mashable
+                                    .color(if button_states.stage {
+                                        Color::Default
+                                    } else {
+                                        Color::Disabled
+                                    })
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&StageAndNext, window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("unstage", "Unstage")
+                                    .tooltip(Tooltip::for_action_title_in(
+                                        "Unstage and go to next hunk",
+                                        &UnstageAndNext,
+                                        &focus_handle,
+                                    ))
+                                    .color(if button_states.unstage {
+                                        Color::Default
+                                    } else {
+                                        Color::Disabled
+                                    })
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.dispatch_action(&UnstageAndNext, window, cx)
+                                    })),
+                            )
+                        }),
+                )
+            })
             // n.b. the only reason these arrows are here is because we don't
             // support "undo" for staging so we need a way to go back.
             .child(
@@ -976,60 +1283,74 @@ impl Render for ProjectDiffToolbar {
                             .on_click(cx.listener(|this, _, window, cx| {
                                 this.dispatch_action(&GoToHunk, window, cx)
                             })),
-                    ),
-            )
-            .child(vertical_divider())
-            .child(
-                h_group_sm()
-                    .when(
-                        button_states.unstage_all && !button_states.stage_all,
-                        |el| {
-                            el.child(
-                                Button::new("unstage-all", "Unstage All")
-                                    .tooltip(Tooltip::for_action_title_in(
-                                        "Unstage all changes",
-                                        &UnstageAll,
-                                        &focus_handle,
-                                    ))
-                                    .on_click(cx.listener(|this, _, window, cx| {
-                                        this.unstage_all(window, cx)
-                                    })),
-                            )
-                        },
-                    )
-                    .when(
-                        !button_states.unstage_all || button_states.stage_all,
-                        |el| {
-                            el.child(
-                                // todo make it so that changing to say "Unstaged"
-                                // doesn't change the position.
-                                div().child(
-                                    Button::new("stage-all", "Stage All")
-                                        .disabled(!button_states.stage_all)
-                                        .tooltip(Tooltip::for_action_title_in(
-                                            "Stage all changes",
-                                            &StageAll,
-                                            &focus_handle,
-                                        ))
-                                        .on_click(cx.listener(|this, _, window, cx| {
-                                            this.stage_all(window, cx)
-                                        })),
-                                ),
-                            )
-                        },
                     )
                     .child(
-                        Button::new("commit", "Commit")
+                        IconButton::new("split", IconName::Split)
+                            .shape(ui::IconButtonShape::Square)
+                            .toggle_state(project_diff.read(cx).split_diff.is_some())
                             .tooltip(Tooltip::for_action_title_in(
-                                "Commit",
-                                &Commit,
+                                "Toggle split diff",
+                                &ToggleSplitDiff,
                                 &focus_handle,
                             ))
                             .on_click(cx.listener(|this, _, window, cx| {
-                                this.dispatch_action(&Commit, window, cx);
+                                this.dispatch_action(&ToggleSplitDiff, window, cx)
                             })),
                     ),
             )
+            .when(can_stage, |el| {
+                el.child(vertical_divider()).child(
+                    h_group_sm()
+                        .when(
+                            button_states.unstage_all && !button_states.stage_all,
+                            |el| {
+                                el.child(
+                                    Button::new("unstage-all", "Unstage All")
+                                        .tooltip(Tooltip::for_action_title_in(
+                                            "Unstage all changes",
+                                            &UnstageAll,
+                                            &focus_handle,
+                                        ))
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.unstage_all(window, cx)
+                                        })),
+                                )
+                            },
+                        )
+                        .when(
+                            !button_states.unstage_all || button_states.stage_all,
+                            |el| {
+                                el.child(
+                                    // todo make it so that changing to say "Unstaged"
+                                    // doesn't change the position.
+                                    div().child(
+                                        Button::new("stage-all", "Stage All")
+                                            .disabled(!button_states.stage_all)
+                                            .tooltip(Tooltip::for_action_title_in(
+                                                "Stage all changes",
+                                                &StageAll,
+                                                &focus_handle,
+                                            ))
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.stage_all(window, cx)
+                                            })),
+                                    ),
+                                )
+                            },
+                        )
+                        .child(
+                            Button::new("commit", "Commit")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "Commit",
+                                    &Commit,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.dispatch_action(&Commit, window, cx);
+                                })),
+                        ),
+                )
+            })
     }
 }
 
@@ -1367,7 +1688,7 @@ mod tests {
         let (workspace, cx) =
             cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
         let diff = cx.new_window_entity(|window, cx| {
-            ProjectDiff::new(project.clone(), workspace, window, cx)
+            ProjectDiff::new(project.clone(), workspace, None, window, cx)
         });
         cx.run_until_parked();
 
@@ -1431,7 +1752,7 @@ mod tests {
         let (workspace, cx) =
             cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
         let diff = cx.new_window_entity(|window, cx| {
-            ProjectDiff::new(project.clone(), workspace, window, cx)
+            ProjectDiff::new(project.clone(), workspace, None, window, cx)
         });
         cx.run_until_parked();
 
@@ -1533,7 +1854,7 @@ mod tests {
             Editor::for_buffer(buffer, Some(project.clone()), window, cx)
         });
         let diff = cx.new_window_entity(|window, cx| {
-            ProjectDiff::new(project.clone(), workspace, window, cx)
+            ProjectDiff::new(project.clone(), workspace, None, window, cx)
         });
         cx.run_until_parked();
 
@@ -1610,6 +1931,92 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_diff_against_revision_follows_ref(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "new\n",
+                "b.txt": "b\n",
+            }),
+        )
+        .await;
+        // Only `a.txt` changed since the ref, in a commit, so git reports no status for it.
+        let set_ref_base = |base: &str, base_text: &str| {
+            fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+                state.revision_diffs.insert(
+                    "origin/main".into(),
+                    git::repository::RevisionDiff {
+                        base: base.to_string().into(),
+                        entries: vec![git::repository::RevisionDiffEntry {
+                            path: "a.txt".into(),
+                            status: StatusCode::Modified,
+                        }],
+                    },
+                );
+                state.revision_contents.insert(
+                    base.to_string(),
+                    HashMap::from_iter([("a.txt".into(), base_text.to_string())]),
+                );
+            });
+        };
+        set_ref_base("aaa", "old\n");
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let diff = cx.new_window_entity(|window, cx| {
+            ProjectDiff::new(
+                project.clone(),
+                workspace,
+                Some("origin/main".into()),
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        let editor = diff.update(cx, |diff, cx| {
+            assert_eq!(diff.excerpt_paths(cx), ["a.txt"]);
+            diff.editor.clone()
+        });
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                - old
+                + ˇnew
+            "
+            .unindent(),
+        );
+
+        // Moving the ref to another merge base reloads the base text from it.
+        set_ref_base("bbb", "older\n");
+        cx.run_until_parked();
+        assert_state_with_diff(
+            &editor,
+            cx,
+            &"
+                - older
+                + ˇnew
+            "
+            .unindent(),
+        );
+        diff.update(cx, |diff, _| {
+            assert_eq!(
+                diff.revision_diffs
+                    .values()
+                    .map(|revision_diff| revision_diff.base.as_ref())
+                    .collect::<Vec<_>>(),
+                ["bbb"]
+            );
+        });
+    }
+
     use crate::project_diff::{self, ProjectDiff};
 
     #[gpui::test]
@@ -0,0 +1,580 @@
+use crate::git_panel::GitPanel;
+use editor::Editor;
+use git::repository::{RebaseAction, RebaseTodoEntry};
+use gpui::{
+    actions, App, ClickEvent, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    KeyContext, Render, ScrollHandle, Task, WeakEntity,
+};
+use menu::{Cancel, Confirm, SelectNext, SelectPrevious};
+use project::git::Repository;
+use ui::{prelude::*, KeyBinding};
+use util::{maybe, ResultExt as _};
+use workspace::{ModalView, Workspace};
+
+actions!(
+    rebase_editor,
+    [MoveUp, MoveDown, Pick, Reword, Squash, Fixup, DropCommit]
+);
+
+/// Edits the todo list of an interactive rebase before starting it.
+pub struct RebaseEditor {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    onto: Option<String>,
+    rows: Vec<TodoRow>,
+    selected_row: usize,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    load_task: Option<Task<()>>,
+}
+
+struct TodoRow {
+    entry: RebaseTodoEntry,
+    /// Edits the new message of the commit, while it is being reworded.
+    message_editor: Option<Entity<Editor>>,
+}
+
+impl RebaseEditor {
+    /// Opens the todo list for rebasing the commits after `onto` up to HEAD,
+    /// or every commit up to HEAD if there is no `onto`.
+    pub fn toggle(
+        repository: Entity<Repository>,
+        onto: Option<String>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let workspace_handle = cx.weak_entity();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(repository, onto, workspace_handle, window, cx)
+        });
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        onto: Option<String>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let todo = repository.read(cx).rebase_todo(onto.clone());
+        let load_task = cx.spawn_in(window, |this, mut cx| async move {
+            let result = maybe!(async { todo.await? }).await;
+            this.update(&mut cx, |this, cx| {
+                this.load_task = None;
+                match result {
+                    Ok(entries) => {
+                        this.rows = entries
+                            .into_iter()
+                            .map(|entry| TodoRow {
+                                entry,
+                                message_editor: None,
+                            })
+                            .collect();
+                        if this.rows.is_empty() {
+                            this.error = Some("There are no commits to rebase".into());
+                        }
+                    }
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .log_err();
+        });
+
+        Self {
+            repository,
+            workspace,
+            onto,
+            rows: Vec::new(),
+            selected_row: 0,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+            load_task: Some(load_task),
+        }
+    }
+
+    fn select_row(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_row = ix;
+        self.scroll_handle.scroll_to_item(ix);
+        cx.notify();
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_row(self.selected_row.saturating_sub(1), cx);
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_row + 1 < self.rows.len() {
+            self.select_row(self.selected_row + 1, cx);
+        }
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_row > 0 && self.selected_row < self.rows.len() {
+            self.rows.swap(self.selected_row - 1, self.selected_row);
+            self.select_row(self.selected_row - 1, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_row + 1 < self.rows.len() {
+            self.rows.swap(self.selected_row, self.selected_row + 1);
+            self.select_row(self.selected_row + 1, cx);
+        }
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(row) = self.rows.get_mut(ix) else {
+            return;
+        };
+        row.entry.action = action;
+        if action != RebaseAction::Reword {
+            row.message_editor = None;
+        } else if row.message_editor.is_none() {
+            let subject = row.entry.subject.clone();
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::auto_height(8, window, cx);
+                editor.set_text(subject.to_string(), window, cx);
+                editor
+            });
+            // Start from the full message of the commit, not only its subject.
+            let details = self.repository.read(cx).show(row.entry.sha.to_string());
+            cx.spawn_in(window, {
+                let editor = editor.downgrade();
+                |_, mut cx| async move {
+                    let details = details.await??;
+                    editor.update_in(&mut cx, |editor, window, cx| {
+                        editor.set_text(details.message.trim_end(), window, cx);
+                    })
+                }
+            })
+            .detach_and_log_err(cx);
+            row.message_editor = Some(editor);
+        }
+        self.error = None;
+        cx.notify();
+    }
+
+    fn cycle_action(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(row) = self.rows.get(ix) else {
+            return;
+        };
+        let position = RebaseAction::ALL
+            .iter()
+            .position(|action| *action == row.entry.action)
+            .unwrap_or(0);
+        let action = RebaseAction::ALL[(position + 1) % RebaseAction::ALL.len()];
+        self.set_action(ix, action, window, cx);
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let squashes_first = self
+            .rows
+            .iter()
+            .find(|row| row.entry.action != RebaseAction::Drop)
+            .is_some_and(|row| {
+                matches!(row.entry.action, RebaseAction::Squash | RebaseAction::Fixup)
+            });
+        if squashes_first {
+            self.error = Some("The first commit can't be squashed into a previous one".into());
+            cx.notify();
+            return;
+        }
+        let Some(git_panel) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+        else {
+            return;
+        };
+
+        let todo = self
+            .rows
+            .iter()
+            .map(|row| RebaseTodoEntry {
+                message: row
+                    .message_editor
+                    .as_ref()
+                    .map(|editor| editor.read(cx).text(cx)),
+                ..row.entry.clone()
+            })
+            .collect();
+        let repository = self.repository.clone();
+        let onto = self.onto.clone();
+        git_panel.update(cx, |git_panel, cx| {
+            git_panel.rebase(repository, onto, todo, window, cx);
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn dispatch_context(&self, window: &Window) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("RebaseEditor");
+        if self.focus_handle.is_focused(window) {
+            dispatch_context.add("menu");
+        }
+        dispatch_context
+    }
+
+    fn render_row(&self, ix: usize, row: &TodoRow, cx: &Context<Self>) -> impl IntoElement {
+        let action = row.entry.action;
+        let subject = Label::new(row.entry.subject.clone())
+            .when(action == RebaseAction::Drop, |label| {
+                label.strikethrough().color(Color::Muted)
+            })
+            .truncate();
+
+        v_flex()
+            .id(("rebase-todo-row", ix))
+            .w_full()
+            .px_2()
+            .py_0p5()
+            .gap_1()
+            .rounded_sm()
+            .when(ix == self.selected_row, |this| {
+                this.bg(cx.theme().colors().ghost_element_selected)
+            })
+            .hover(|this| this.bg(cx.theme().colors().ghost_element_hover))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                window.focus(&this.focus_handle);
+                this.select_row(ix, cx);
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new(("rebase-todo-action", ix), action.as_str())
+                            .label_size(LabelSize::Small)
+                            .color(match action {
+                                RebaseAction::Pick => Color::Default,
+                                RebaseAction::Drop => Color::Error,
+                                _ => Color::Accent,
+                            })
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.select_row(ix, cx);
+                                this.cycle_action(ix, window, cx);
+                            })),
+                    )
+                    .child(
+                        Label::new(row.entry.sha.chars().take(7).collect::<String>())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(div().flex_1().overflow_hidden().child(subject)),
+            )
+            .when_some(row.message_editor.clone(), |this, editor| {
+                this.child(
+                    div()
+                        .ml_8()
+                        .p_1()
+                        .rounded_sm()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .bg(cx.theme().colors().editor_background)
+                        .child(editor),
+                )
+            })
+    }
+}
+
+impl EventEmitter<DismissEvent> for RebaseEditor {}
+
+impl ModalView for RebaseEditor {}
+
+impl Focusable for RebaseEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+
+        v_flex()
+            .key_context(self.dispatch_context(window))
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(|this, _: &Pick, window, cx| {
+                this.set_action(this.selected_row, RebaseAction::Pick, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &Reword, window, cx| {
+                this.set_action(this.selected_row, RebaseAction::Reword, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &Squash, window, cx| {
+                this.set_action(this.selected_row, RebaseAction::Squash, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &Fixup, window, cx| {
+                this.set_action(this.selected_row, RebaseAction::Fixup, window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &DropCommit, window, cx| {
+                this.set_action(this.selected_row, RebaseAction::Drop, window, cx)
+            }))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_1p5()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(Headline::new("Interactive Rebase").size(HeadlineSize::XSmall))
+                    .child(div().flex_1())
+                    .child(
+                        Label::new(match &self.onto {
+                            Some(onto) => {
+                                format!("onto {}", onto.chars().take(7).collect::<String>())
+                            }
+                            None => "from the root commit".to_string(),
+                        })
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("rebase-todo")
+                    .p_1()
+                    .max_h(rems(30.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .when(self.rows.is_empty() && self.load_task.is_some(), |this| {
+                        this.child(
+                            div()
+                                .px_2()
+                                .py_1()
+                                .child(Label::new("Loading commits…").color(Color::Muted)),
+                        )
+                    })
+                    .children(
+                        self.rows
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, row)| self.render_row(ix, row, cx)),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    h_flex()
+                        .px_3()
+                        .py_1()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(Label::new(error).size(LabelSize::Small)),
+                )
+            })
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .gap_1()
+                    .justify_end()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Button::new("cancel", "Cancel")
+                            .key_binding(KeyBinding::for_action_in(
+                                &Cancel,
+                                &focus_handle,
+                                window,
+                                cx,
+                            ))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.cancel(&Cancel, window, cx)),
+                            ),
+                    )
+                    .child(
+                        Button::new("start-rebase", "Start Rebase")
+                            .disabled(self.rows.is_empty())
+                            .key_binding(KeyBinding::for_action_in(
+                                &Confirm,
+                                &focus_handle,
+                                window,
+                                cx,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.confirm(&Confirm, window, cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::LogEntry;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::{path::Path, sync::Arc};
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_reorder_and_reword_commits(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (fs, editor, cx) = open_rebase_editor(cx).await;
+
+        editor.update_in(cx, |editor, window, cx| {
+            // Rows don't move past either end of the list.
+            editor.move_up(&MoveUp, window, cx);
+            assert_eq!(shas(editor), ["aaa", "bbb", "ccc"]);
+            editor.select_row(2, cx);
+            editor.move_down(&MoveDown, window, cx);
+            assert_eq!(shas(editor), ["aaa", "bbb", "ccc"]);
+
+            editor.move_up(&MoveUp, window, cx);
+            assert_eq!(shas(editor), ["aaa", "ccc", "bbb"]);
+            assert_eq!(editor.selected_row, 1);
+            editor.set_action(1, RebaseAction::Reword, window, cx);
+        });
+        cx.run_until_parked();
+
+        editor.update_in(cx, |editor, window, cx| {
+            let message_editor = editor.rows[1].message_editor.clone().unwrap();
+            message_editor.update(cx, |message_editor, cx| {
+                assert_eq!(message_editor.text(cx), "commit ccc");
+                message_editor.set_text("reworded", window, cx);
+            });
+            editor.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+
+        fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+            assert_eq!(
+                state
+                    .rebase_todo
+                    .iter()
+                    .map(|entry| (entry.action, entry.sha.as_ref(), entry.message.as_deref()))
+                    .collect::<Vec<_>>(),
+                [
+                    (RebaseAction::Pick, "aaa", None),
+                    (RebaseAction::Reword, "ccc", Some("reworded")),
+                    (RebaseAction::Pick, "bbb", None),
+                ]
+            );
+            assert_eq!(state.sequencer_commands, ["rebase --interactive --root"]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_squashing_the_first_kept_commit_is_rejected(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (fs, editor, cx) = open_rebase_editor(cx).await;
+
+        // Once the first commit is dropped, the second one has nothing to be squashed into.
+        editor.update_in(cx, |editor, window, cx| {
+            editor.set_action(0, RebaseAction::Drop, window, cx);
+            editor.set_action(1, RebaseAction::Fixup, window, cx);
+            editor.confirm(&Confirm, window, cx);
+            assert!(editor.error.is_some());
+
+            // Changing an action clears the error.
+            editor.set_action(1, RebaseAction::Pick, window, cx);
+            assert_eq!(editor.error, None);
+        });
+        cx.run_until_parked();
+
+        fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+            assert!(state.sequencer_commands.is_empty());
+        });
+    }
+
+    fn shas(editor: &RebaseEditor) -> Vec<&str> {
+        editor
+            .rows
+            .iter()
+            .map(|row| row.entry.sha.as_ref())
+            .collect()
+    }
+
+    async fn open_rebase_editor(
+        cx: &mut TestAppContext,
+    ) -> (Arc<FakeFs>, Entity<RebaseEditor>, &mut VisualTestContext) {
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/project"), json!({ ".git": {}, "a.txt": "a\n" }))
+            .await;
+        fs.with_git_state(Path::new(path!("/project/.git")), false, |state| {
+            state.commit_history = ["ccc", "bbb", "aaa"]
+                .into_iter()
+                .map(|sha| LogEntry {
+                    sha: sha.into(),
+                    parents: Vec::new(),
+                    subject: format!("commit {sha}").into(),
+                    author_name: "Test".into(),
+                    author_email: "test@example.com".into(),
+                    commit_timestamp: 0,
+                })
+                .collect();
+        });
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        let app_state = workspace.update(cx, |workspace, _| workspace.app_state().clone());
+        let panel = cx.new_window_entity(|window, cx| {
+            GitPanel::new(workspace.clone(), project.clone(), app_state, window, cx)
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_panel(panel, window, cx)
+        });
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        workspace.update_in(cx, |workspace, window, cx| {
+            RebaseEditor::toggle(repository, None, workspace, window, cx)
+        });
+        cx.run_until_parked();
+        let editor = workspace.update(cx, |workspace, cx| {
+            workspace.active_modal::<RebaseEditor>(cx).unwrap()
+        });
+        editor.update(cx, |editor, _| {
+            assert_eq!(shas(editor), ["aaa", "bbb", "ccc"]);
+        });
+        (fs, editor, cx)
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use git::repository::{Remote, RemoteCommandOutput};
+use git::repository::{Remote, RemoteCommandOutput, SequencerOperation};
 use linkify::{LinkFinder, LinkKind};
 use ui::SharedString;
 use util::ResultExt as _;
@@ -9,6 +9,13 @@ pub enum RemoteAction {
     Fetch,
     Pull(Remote),
     Push(SharedString, Remote),
+    PushTag(SharedString, Remote),
+    CherryPick(SharedString),
+    Revert(SharedString),
+    Rebase,
+    UpdateSubmodules,
+    Continue(SequencerOperation),
+    Abort(SequencerOperation),
 }
 
 impl RemoteAction {
@@ -16,7 +23,21 @@ impl RemoteAction {
         match self {
             RemoteAction::Fetch => "fetch",
             RemoteAction::Pull(_) => "pull",
-            RemoteAction::Push(_, _) => "push",
+            RemoteAction::Push(_, _) | RemoteAction::PushTag(_, _) => "push",
+            RemoteAction::CherryPick(_) => "cherry-pick",
+            RemoteAction::Revert(_) => "revert",
+            RemoteAction::Rebase => "rebase",
+            RemoteAction::UpdateSubmodules => "submodule update",
+            RemoteAction::Continue(operation) => match operation {
+                SequencerOperation::CherryPick => "cherry-pick --continue",
+                SequencerOperation::Revert => "revert --continue",
+                SequencerOperation::Rebase => "rebase --continue",
+            },
+            RemoteAction::Abort(operation) => match operation {
+                SequencerOperation::CherryPick => "cherry-pick --abort",
+                SequencerOperation::Revert => "revert --abort",
+                SequencerOperation::Rebase => "rebase --abort",
+            },
         }
     }
 }
@@ -148,5 +169,42 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 }
             }
         }
+        RemoteAction::PushTag(tag_name, remote_ref) => {
+            if output.stderr.starts_with("Everything up to date") {
+                SuccessMessage {
+                    message: output.stderr.trim().to_owned(),
+                    style: SuccessStyle::Toast,
+                }
+            } else {
+                SuccessMessage {
+                    message: format!("Pushed tag {} to {}", tag_name, remote_ref.name),
+                    style: SuccessStyle::ToastWithLog { output },
+                }
+            }
+        }
+        RemoteAction::CherryPick(sha) => SuccessMessage {
+            message: format!("Cherry-picked {}", sha),
+            style: SuccessStyle::ToastWithLog { output },
+        },
+        RemoteAction::Revert(sha) => SuccessMessage {
+            message: format!("Reverted {}", sha),
+            style: SuccessStyle::ToastWithLog { output },
+        },
+        RemoteAction::Rebase => SuccessMessage {
+            message: "Successfully rebased".into(),
+            style: SuccessStyle::ToastWithLog { output },
+        },
+        RemoteAction::UpdateSubmodules => SuccessMessage {
+            message: "Updated submodules".into(),
+            style: SuccessStyle::ToastWithLog { output },
+        },
+        RemoteAction::Continue(operation) => SuccessMessage {
+            message: format!("Finished {}", operation.command()),
+            style: SuccessStyle::ToastWithLog { output },
+        },
+        RemoteAction::Abort(operation) => SuccessMessage {
+            message: format!("Aborted {}", operation.command()),
+            style: SuccessStyle::Toast,
+        },
     }
 }
@@ -1,5 +1,9 @@
+use crate::git_panel::GitPanel;
+use collections::HashMap;
+use git::repository::{RepoPath, Submodule, SubmoduleStatus};
 use gpui::{
-    AnyElement, App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+    AnyElement, App, DismissEvent, Entity, EntityId, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity,
 };
 use itertools::Itertools;
 use picker::{Picker, PickerDelegate};
@@ -9,9 +13,11 @@ use project::{
 };
 use std::sync::Arc;
 use ui::{prelude::*, ListItem, ListItemSpacing};
+use workspace::Workspace;
 
 pub struct RepositorySelector {
     picker: Entity<Picker<RepositorySelectorDelegate>>,
+    _load_submodules: Task<()>,
 }
 
 impl RepositorySelector {
@@ -24,23 +30,36 @@ impl RepositorySelector {
         let repository_entries = git_store.update(cx, |git_store, cx| {
             filtered_repository_entries(git_store, cx)
         });
-        let project = project_handle.read(cx);
         let filtered_repositories = repository_entries.clone();
 
-        let widest_item_ix = repository_entries.iter().position_max_by(|a, b| {
-            a.read(cx)
-                .display_name(project, cx)
-                .len()
-                .cmp(&b.read(cx).display_name(project, cx).len())
-        });
+        let submodules = repository_entries
+            .iter()
+            .map(|repository| (repository.entity_id(), repository.read(cx).submodules()))
+            .collect::<Vec<_>>();
 
-        let delegate = RepositorySelectorDelegate {
+        let mut delegate = RepositorySelectorDelegate {
             project: project_handle.downgrade(),
             repository_selector: cx.entity().downgrade(),
             repository_entries,
             filtered_repositories,
+            submodules: HashMap::default(),
+            entries: Vec::new(),
             selected_index: 0,
         };
+        delegate.rebuild_entries(cx);
+
+        let project = project_handle.read(cx);
+        let widest_item_ix = delegate
+            .entries
+            .iter()
+            .position_max_by_key(|entry| match entry {
+                SelectorEntry::Repository {
+                    repository, depth, ..
+                } => repository.read(cx).display_name(project, cx).len() + depth,
+                SelectorEntry::UninitializedSubmodule { path, depth, .. } => {
+                    path.as_os_str().len() + depth
+                }
+            });
 
         let picker = cx.new(|cx| {
             Picker::nonsearchable_uniform_list(delegate, window, cx)
@@ -48,7 +67,32 @@ impl RepositorySelector {
                 .max_height(Some(rems(20.).into()))
         });
 
-        RepositorySelector { picker }
+        let _load_submodules = cx.spawn(|this, mut cx| async move {
+            let mut loaded = HashMap::default();
+            for (repository_id, submodules) in submodules {
+                if let Ok(Ok(submodules)) = submodules.await {
+                    if !submodules.is_empty() {
+                        loaded.insert(repository_id, submodules);
+                    }
+                }
+            }
+            if loaded.is_empty() {
+                return;
+            }
+            this.update(&mut cx, |this, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.submodules = loaded;
+                    picker.delegate.rebuild_entries(cx);
+                    cx.notify();
+                })
+            })
+            .ok();
+        });
+
+        RepositorySelector {
+            picker,
+            _load_submodules,
+        }
     }
 }
 
@@ -95,19 +139,122 @@ impl Render for RepositorySelector {
     }
 }
 
+/// A row of the repository selector. Submodules are listed under the
+/// repository that contains them.
+enum SelectorEntry {
+    Repository {
+        repository: Entity<Repository>,
+        status: Option<SubmoduleStatus>,
+        depth: usize,
+    },
+    /// A submodule that hasn't been cloned yet, so it has no repository of
+    /// its own.
+    UninitializedSubmodule {
+        parent: Entity<Repository>,
+        path: RepoPath,
+        depth: usize,
+    },
+}
+
 pub struct RepositorySelectorDelegate {
     project: WeakEntity<Project>,
     repository_selector: WeakEntity<RepositorySelector>,
     repository_entries: Vec<Entity<Repository>>,
     filtered_repositories: Vec<Entity<Repository>>,
+    submodules: HashMap<EntityId, Vec<Submodule>>,
+    entries: Vec<SelectorEntry>,
     selected_index: usize,
 }
 
 impl RepositorySelectorDelegate {
-    pub fn update_repository_entries(&mut self, all_repositories: Vec<Entity<Repository>>) {
+    pub fn update_repository_entries(
+        &mut self,
+        all_repositories: Vec<Entity<Repository>>,
+        cx: &App,
+    ) {
         self.repository_entries = all_repositories.clone();
         self.filtered_repositories = all_repositories;
         self.selected_index = 0;
+        self.rebuild_entries(cx);
+    }
+
+    fn rebuild_entries(&mut self, cx: &App) {
+        let repositories = &self.filtered_repositories;
+        let submodule_of = |parent: &Entity<Repository>, child: &Entity<Repository>| {
+            let submodules = self.submodules.get(&parent.entity_id())?;
+            let child_root = child
+                .read(cx)
+                .repo_path_to_project_path(&RepoPath::default())?;
+            submodules
+                .iter()
+                .find(|submodule| {
+                    parent
+                        .read(cx)
+                        .repo_path_to_project_path(&submodule.path)
+                        .as_ref()
+                        == Some(&child_root)
+                })
+                .map(|submodule| submodule.status)
+        };
+
+        let mut children = HashMap::<EntityId, Vec<(usize, SubmoduleStatus)>>::default();
+        let mut is_child = vec![false; repositories.len()];
+        for (child_ix, child) in repositories.iter().enumerate() {
+            for parent in repositories {
+                if parent == child {
+                    continue;
+                }
+                if let Some(status) = submodule_of(parent, child) {
+                    children
+                        .entry(parent.entity_id())
+                        .or_default()
+                        .push((child_ix, status));
+                    is_child[child_ix] = true;
+                    break;
+                }
+            }
+        }
+
+        fn push_repository(
+            delegate: &RepositorySelectorDelegate,
+            entries: &mut Vec<SelectorEntry>,
+            children: &HashMap<EntityId, Vec<(usize, SubmoduleStatus)>>,
+            repository: &Entity<Repository>,
+            status: Option<SubmoduleStatus>,
+            depth: usize,
+        ) {
+            entries.push(SelectorEntry::Repository {
+                repository: repository.clone(),
+                status,
+                depth,
+            });
+            let nested = children.get(&repository.entity_id());
+            for (child_ix, status) in nested.into_iter().flatten() {
+                let child = &delegate.filtered_repositories[*child_ix];
+                push_repository(delegate, entries, children, child, Some(*status), depth + 1);
+            }
+            let submodules = delegate.submodules.get(&repository.entity_id());
+            for submodule in submodules.into_iter().flatten() {
+                if submodule.status == SubmoduleStatus::Uninitialized {
+                    entries.push(SelectorEntry::UninitializedSubmodule {
+                        parent: repository.clone(),
+                        path: submodule.path.clone(),
+                        depth: depth + 1,
+                    });
+                }
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (ix, repository) in repositories.iter().enumerate() {
+            if !is_child[ix] {
+                push_repository(self, &mut entries, &children, repository, None, 0);
+            }
+        }
+        self.entries = entries;
+        self.selected_index = self
+            .selected_index
+            .min(self.entries.len().saturating_sub(1));
     }
 }
 
@@ -115,7 +262,7 @@ impl PickerDelegate for RepositorySelectorDelegate {
     type ListItem = ListItem;
 
     fn match_count(&self) -> usize {
-        self.filtered_repositories.len()
+        self.entries.len()
     }
 
     fn selected_index(&self) -> usize {
@@ -128,7 +275,7 @@ impl PickerDelegate for RepositorySelectorDelegate {
         _window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) {
-        self.selected_index = ix.min(self.filtered_repositories.len().saturating_sub(1));
+        self.selected_index = ix.min(self.entries.len().saturating_sub(1));
         cx.notify();
     }
 
@@ -163,6 +310,7 @@ impl PickerDelegate for RepositorySelectorDelegate {
 
             this.update_in(&mut cx, |this, window, cx| {
                 this.delegate.filtered_repositories = filtered_repositories;
+                this.delegate.rebuild_entries(cx);
                 this.delegate.set_selected_index(0, window, cx);
                 cx.notify();
             })
@@ -171,10 +319,25 @@ impl PickerDelegate for RepositorySelectorDelegate {
     }
 
     fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
-        let Some(selected_repo) = self.filtered_repositories.get(self.selected_index) else {
-            return;
-        };
-        selected_repo.update(cx, |selected_repo, cx| selected_repo.activate(cx));
+        match self.entries.get(self.selected_index) {
+            Some(SelectorEntry::Repository { repository, .. }) => {
+                repository.update(cx, |repository, cx| repository.activate(cx));
+            }
+            Some(SelectorEntry::UninitializedSubmodule { parent, path, .. }) => {
+                let Some(git_panel) = window
+                    .root::<Workspace>()
+                    .flatten()
+                    .and_then(|workspace| workspace.read(cx).panel::<GitPanel>(cx))
+                else {
+                    return;
+                };
+                let (parent, path) = (parent.clone(), path.clone());
+                git_panel.update(cx, |git_panel, cx| {
+                    git_panel.update_submodules(parent, vec![path], window, cx);
+                });
+            }
+            None => return,
+        }
         self.dismissed(window, cx);
     }
 
@@ -201,14 +364,192 @@ impl PickerDelegate for RepositorySelectorDelegate {
         cx: &mut Context<Picker<Self>>,
     ) -> Option<Self::ListItem> {
         let project = self.project.upgrade()?;
-        let repo_info = self.filtered_repositories.get(ix)?;
-        let display_name = repo_info.read(cx).display_name(project.read(cx), cx);
-        Some(
-            ListItem::new(ix)
-                .inset(true)
-                .spacing(ListItemSpacing::Sparse)
-                .toggle_state(selected)
-                .child(Label::new(display_name)),
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+        match self.entries.get(ix)? {
+            SelectorEntry::Repository {
+                repository,
+                status,
+                depth,
+            } => {
+                let display_name = repository.read(cx).display_name(project.read(cx), cx);
+                let status = status.and_then(|status| match status {
+                    SubmoduleStatus::UpToDate | SubmoduleStatus::Uninitialized => None,
+                    SubmoduleStatus::OutOfDate => Some(("out of date", Color::Modified)),
+                    SubmoduleStatus::Conflicted => Some(("conflicted", Color::Conflict)),
+                });
+                Some(
+                    item.indent_level(*depth).indent_step_size(px(12.)).child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(display_name))
+                            .children(status.map(|(status, color)| {
+                                Label::new(status).size(LabelSize::Small).color(color)
+                            })),
+                    ),
+                )
+            }
+            SelectorEntry::UninitializedSubmodule { path, depth, .. } => Some(
+                item.indent_level(*depth).indent_step_size(px(12.)).child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(path.display().to_string()).color(Color::Muted))
+                        .child(
+                            Label::new("not initialized")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::Submodule;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    /// Describes the selector's entries as `(depth, path, status)`.
+    fn entries(
+        selector: &Entity<RepositorySelector>,
+        cx: &mut VisualTestContext,
+    ) -> Vec<(usize, String, Option<SubmoduleStatus>)> {
+        selector.update(cx, |selector, cx| {
+            selector
+                .picker
+                .read(cx)
+                .delegate
+                .entries
+                .iter()
+                .map(|entry| match entry {
+                    SelectorEntry::Repository {
+                        repository,
+                        status,
+                        depth,
+                    } => (
+                        *depth,
+                        repository.read(cx).worktree_abs_path.display().to_string(),
+                        *status,
+                    ),
+                    SelectorEntry::UninitializedSubmodule { path, depth, .. } => (
+                        *depth,
+                        path.display().to_string(),
+                        Some(SubmoduleStatus::Uninitialized),
+                    ),
+                })
+                .collect()
+        })
+    }
+
+    #[gpui::test]
+    async fn test_submodule_entries(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root/zed"),
+            json!({
+                ".git": {},
+                "main.rs": "fn main() {}",
+                "libs": {
+                    "two": {
+                        ".git": {},
+                        "lib.rs": "",
+                    },
+                },
+            }),
         )
+        .await;
+        let dot_git = Path::new(path!("/root/zed/.git"));
+        fs.with_git_state(dot_git, false, |state| {
+            state.submodules = [
+                ("libs/one", SubmoduleStatus::Uninitialized),
+                ("libs/two", SubmoduleStatus::OutOfDate),
+            ]
+            .into_iter()
+            .map(|(path, status)| Submodule {
+                path: path.into(),
+                sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                status,
+            })
+            .collect();
+        });
+
+        let project = Project::test(fs.clone(), [path!("/root/zed").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        let app_state = workspace.update(cx, |workspace, _| workspace.app_state().clone());
+        let panel = cx.new_window_entity(|window, cx| {
+            GitPanel::new(workspace.clone(), project.clone(), app_state, window, cx)
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_panel(panel, window, cx)
+        });
+
+        let selector =
+            cx.new_window_entity(|window, cx| RepositorySelector::new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        // Initialized submodules are nested under their superproject, and uninitialized ones are
+        // listed after them.
+        assert_eq!(
+            entries(&selector, cx),
+            [
+                (0, path!("/root/zed").to_string(), None),
+                (
+                    1,
+                    path!("/root/zed/libs/two").to_string(),
+                    Some(SubmoduleStatus::OutOfDate)
+                ),
+                (
+                    1,
+                    "libs/one".to_string(),
+                    Some(SubmoduleStatus::Uninitialized)
+                ),
+            ]
+        );
+
+        // Confirming an uninitialized submodule updates it through the git panel.
+        selector.update_in(cx, |selector, window, cx| {
+            selector.picker.update(cx, |picker, cx| {
+                picker.delegate.selected_index = 2;
+                picker.delegate.confirm(false, window, cx);
+            })
+        });
+        cx.run_until_parked();
+
+        fs.with_git_state(dot_git, false, |state| {
+            assert_eq!(
+                state
+                    .submodules
+                    .iter()
+                    .map(|submodule| submodule.status)
+                    .collect::<Vec<_>>(),
+                [SubmoduleStatus::UpToDate, SubmoduleStatus::OutOfDate]
+            );
+        });
     }
 }
@@ -0,0 +1,231 @@
+use crate::project_diff::ProjectDiff;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    rems, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+/// Picks the branch, tag, or commit to diff the working tree against.
+pub struct RevisionPicker {
+    picker: Entity<Picker<RevisionPickerDelegate>>,
+    _subscription: Subscription,
+}
+
+impl RevisionPicker {
+    /// Opens the picker, offering the active repository's branches and their
+    /// upstreams, along with whatever revision is typed in.
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let branches = repository.read(cx).branches();
+        let workspace_handle = cx.weak_entity();
+        cx.spawn_in(window, |workspace, mut cx| async move {
+            let branches = branches.await??;
+            let mut revisions = Vec::<SharedString>::new();
+            for branch in &branches {
+                if !revisions.contains(&branch.name) {
+                    revisions.push(branch.name.clone());
+                }
+            }
+            for branch in &branches {
+                let Some(upstream) = branch
+                    .upstream
+                    .as_ref()
+                    .and_then(|upstream| upstream.ref_name.strip_prefix("refs/remotes/"))
+                else {
+                    continue;
+                };
+                let upstream = SharedString::from(upstream.to_string());
+                if !revisions.contains(&upstream) {
+                    revisions.push(upstream);
+                }
+            }
+
+            workspace.update_in(&mut cx, |workspace, window, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    let delegate = RevisionPickerDelegate {
+                        workspace: workspace_handle,
+                        revisions,
+                        matches: Vec::new(),
+                        query: String::new(),
+                        selected_index: 0,
+                    };
+                    Self::new(delegate, window, cx)
+                })
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn new(delegate: RevisionPickerDelegate, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for RevisionPicker {}
+impl EventEmitter<DismissEvent> for RevisionPicker {}
+
+impl Focusable for RevisionPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for RevisionPicker {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(34.))
+            .child(self.picker.clone())
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                this.picker.update(cx, |this, cx| {
+                    this.cancel(&Default::default(), window, cx);
+                })
+            }))
+    }
+}
+
+pub struct RevisionPickerDelegate {
+    workspace: WeakEntity<Workspace>,
+    revisions: Vec<SharedString>,
+    matches: Vec<StringMatch>,
+    query: String,
+    selected_index: usize,
+}
+
+impl RevisionPickerDelegate {
+    /// Whether the typed revision is offered after the matching branches.
+    fn offers_query(&self) -> bool {
+        !self.query.trim().is_empty()
+            && !self
+                .matches
+                .iter()
+                .any(|hit| hit.string == self.query.trim())
+    }
+}
+
+impl PickerDelegate for RevisionPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Compare with a branch, tag, or commit…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len() + self.offers_query() as usize
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .revisions
+            .iter()
+            .enumerate()
+            .map(|(ix, revision)| StringMatchCandidate::new(ix, revision))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, move |picker, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(&mut cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.query = query;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.match_count().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let revision = match self.matches.get(self.selected_index) {
+            Some(hit) => SharedString::from(hit.string.clone()),
+            None if self.offers_query() => SharedString::from(self.query.trim().to_string()),
+            None => return,
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                ProjectDiff::deploy_against(workspace, revision, window, cx);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(SharedString::from(format!("revision-picker-{ix}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+        Some(match self.matches.get(ix) {
+            Some(hit) => item
+                .start_slot(Icon::new(IconName::GitBranch).color(Color::Muted))
+                .child(HighlightedLabel::new(
+                    hit.string.clone(),
+                    hit.positions.clone(),
+                )),
+            None => item.child(Label::new(format!(
+                "Compare with \"{}\"",
+                self.query.trim()
+            ))),
+        })
+    }
+}
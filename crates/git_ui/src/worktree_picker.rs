@@ -0,0 +1,439 @@
+use anyhow::{anyhow, Result};
+use fuzzy::{StringMatch, StringMatchCandidate};
+use git::repository::GitWorktree;
+use gpui::{
+    rems, App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
+    Focusable, InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled,
+    Subscription, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use project::git::Repository;
+use std::path::PathBuf;
+use std::sync::Arc;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, OpenOptions, Workspace};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &git::SwitchWorktree, window, cx| {
+            WorktreePicker::toggle(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Lists the linked worktrees of the active repository, opening the picked
+/// one in a new window and creating one when a branch name is typed.
+pub struct WorktreePicker {
+    picker: Entity<Picker<WorktreePickerDelegate>>,
+    _subscription: Subscription,
+}
+
+impl WorktreePicker {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        let worktrees = repository.read(cx).worktrees();
+        let branches = repository.read(cx).branches();
+        let workspace_handle = cx.weak_entity();
+        cx.spawn_in(window, |workspace, mut cx| async move {
+            let worktrees = worktrees.await??;
+            let branches = branches
+                .await??
+                .into_iter()
+                .map(|branch| branch.name)
+                .collect();
+
+            workspace.update_in(&mut cx, |workspace, window, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    let delegate = WorktreePickerDelegate {
+                        workspace: workspace_handle,
+                        repository,
+                        worktrees,
+                        branches,
+                        matches: Vec::new(),
+                        query: String::new(),
+                        selected_index: 0,
+                    };
+                    Self::new(delegate, window, cx)
+                })
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn new(delegate: WorktreePickerDelegate, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for WorktreePicker {}
+impl EventEmitter<DismissEvent> for WorktreePicker {}
+
+impl Focusable for WorktreePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for WorktreePicker {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(34.))
+            .child(self.picker.clone())
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                this.picker.update(cx, |this, cx| {
+                    this.cancel(&Default::default(), window, cx);
+                })
+            }))
+    }
+}
+
+pub struct WorktreePickerDelegate {
+    workspace: WeakEntity<Workspace>,
+    repository: Entity<Repository>,
+    worktrees: Vec<GitWorktree>,
+    branches: Vec<SharedString>,
+    matches: Vec<StringMatch>,
+    query: String,
+    selected_index: usize,
+}
+
+impl WorktreePickerDelegate {
+    /// Whether a new worktree for the typed branch is offered after the
+    /// matching worktrees.
+    fn offers_new_worktree(&self) -> bool {
+        let branch = self.query.trim();
+        !branch.is_empty()
+            && !self
+                .worktrees
+                .iter()
+                .any(|worktree| worktree.branch.as_deref() == Some(branch))
+    }
+
+    fn worktree_label(worktree: &GitWorktree) -> String {
+        match &worktree.branch {
+            Some(branch) => branch.to_string(),
+            None => format!("({})", worktree.sha.get(..7).unwrap_or(&worktree.sha)),
+        }
+    }
+
+    /// New worktrees are placed next to the main one, named after it and the
+    /// branch they check out.
+    fn new_worktree_path(&self, branch: &str) -> Option<PathBuf> {
+        let main_worktree = self.worktrees.iter().find(|worktree| worktree.is_main)?;
+        let name = main_worktree.path.file_name()?.to_string_lossy();
+        let suffix = branch.replace(['/', '\\'], "-");
+        Some(
+            main_worktree
+                .path
+                .parent()?
+                .join(format!("{name}-{suffix}")),
+        )
+    }
+
+    async fn open_worktree(
+        path: PathBuf,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<()> {
+        let open = workspace.update(cx, |workspace, cx| {
+            if !workspace.project().read(cx).is_local() {
+                return Err(anyhow!(
+                    "Opening worktrees is only supported in local projects"
+                ));
+            }
+            Ok(workspace::open_paths(
+                &[path],
+                workspace.app_state().clone(),
+                OpenOptions {
+                    open_new_workspace: Some(true),
+                    ..Default::default()
+                },
+                cx,
+            ))
+        })??;
+        open.await?;
+        Ok(())
+    }
+}
+
+impl PickerDelegate for WorktreePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Switch to a worktree or type a branch to create one…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len() + self.offers_new_worktree() as usize
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .worktrees
+            .iter()
+            .enumerate()
+            .map(|(ix, worktree)| StringMatchCandidate::new(ix, &Self::worktree_label(worktree)))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, move |picker, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(&mut cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.query = query;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.match_count().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let workspace = self.workspace.clone();
+        if let Some(hit) = self.matches.get(self.selected_index) {
+            let path = self.worktrees[hit.candidate_id].path.clone();
+            cx.spawn_in(window, |_, mut cx| async move {
+                Self::open_worktree(path, workspace, &mut cx).await
+            })
+            .detach_and_prompt_err(
+                "Failed to open worktree",
+                window,
+                cx,
+                |e, _, _| Some(e.to_string()),
+            );
+            cx.emit(DismissEvent);
+            return;
+        }
+        if !self.offers_new_worktree() {
+            return;
+        }
+
+        let branch = self.query.trim().to_string();
+        let Some(path) = self.new_worktree_path(&branch) else {
+            return;
+        };
+        let create_branch = !self.branches.iter().any(|name| name.as_ref() == branch);
+        let create = self
+            .repository
+            .read(cx)
+            .create_worktree(path.clone(), branch, create_branch);
+        cx.spawn_in(window, |_, mut cx| async move {
+            create.await??;
+            Self::open_worktree(path, workspace, &mut cx).await
+        })
+        .detach_and_prompt_err("Failed to create worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(SharedString::from(format!("worktree-picker-{ix}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+        Some(match self.matches.get(ix) {
+            Some(hit) => {
+                let worktree = &self.worktrees[hit.candidate_id];
+                item.start_slot(Icon::new(IconName::GitBranch).color(Color::Muted))
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .justify_between()
+                            .child(HighlightedLabel::new(
+                                hit.string.clone(),
+                                hit.positions.clone(),
+                            ))
+                            .child(
+                                Label::new(worktree.path.to_string_lossy().to_string())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .truncate(),
+                            ),
+                    )
+            }
+            None => item.child(Label::new(format!(
+                "Create worktree for branch \"{}\"",
+                self.query.trim()
+            ))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    fn set_query(
+        picker: &Entity<Picker<WorktreePickerDelegate>>,
+        query: &str,
+        cx: &mut VisualTestContext,
+    ) {
+        picker.update_in(cx, |picker, window, cx| picker.set_query(query, window, cx));
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    async fn test_create_worktree(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "zed": {
+                    ".git": {},
+                    "main.rs": "fn main() {}",
+                },
+                "zed-feature": {},
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/root/zed/.git"));
+        fs.insert_branches(dot_git, &["main", "fix"]);
+        fs.with_git_state(dot_git, false, |state| {
+            state.worktrees = vec![
+                GitWorktree {
+                    path: PathBuf::from(path!("/root/zed")),
+                    sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    branch: Some("main".into()),
+                    is_main: true,
+                },
+                GitWorktree {
+                    path: PathBuf::from(path!("/root/zed-fix")),
+                    sha: "d3c9b1e2f1e9c2a1e8b7e6f5d4c3b2a1f0e9d8c7".into(),
+                    branch: Some("fix".into()),
+                    is_main: false,
+                },
+            ];
+        });
+
+        let project = Project::test(fs.clone(), [path!("/root/zed").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            WorktreePicker::toggle(workspace, window, cx)
+        });
+        cx.run_until_parked();
+        let picker = workspace.update(cx, |workspace, cx| {
+            let modal = workspace.active_modal::<WorktreePicker>(cx).unwrap();
+            modal.read(cx).picker.clone()
+        });
+
+        // Typing the branch of an existing worktree only offers that worktree.
+        set_query(&picker, "fix", cx);
+        picker.update(cx, |picker, _| {
+            assert_eq!(picker.delegate.match_count(), 1);
+            assert!(!picker.delegate.offers_new_worktree());
+        });
+
+        // Any other branch is offered a new worktree next to the main one.
+        set_query(&picker, "feature", cx);
+        picker.update(cx, |picker, _| {
+            assert_eq!(picker.delegate.match_count(), 1);
+            assert!(picker.delegate.offers_new_worktree());
+            assert_eq!(
+                picker.delegate.new_worktree_path("feature"),
+                Some(PathBuf::from(path!("/root/zed-feature")))
+            );
+        });
+
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.confirm(false, window, cx)
+        });
+        cx.run_until_parked();
+
+        fs.with_git_state(dot_git, false, |state| {
+            assert!(state.branches.contains("feature"));
+            assert_eq!(
+                state.worktrees.last(),
+                Some(&GitWorktree {
+                    path: PathBuf::from(path!("/root/zed-feature")),
+                    sha: SharedString::default(),
+                    branch: Some("feature".into()),
+                    is_main: false,
+                })
+            );
+        });
+    }
+}
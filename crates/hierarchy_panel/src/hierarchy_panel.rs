@@ -0,0 +1,962 @@
+mod hierarchy_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{scroll::Autoscroll, Editor};
+use gpui::{
+    actions, uniform_list, Action, AsyncWindowContext, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, ScrollStrategy, Task, UniformListScrollHandle, WeakEntity,
+};
+use hierarchy_panel_settings::{HierarchyPanelDockPosition, HierarchyPanelSettings};
+use language::{Anchor, Buffer, ToPoint as _};
+use menu::{Confirm, SelectNext, SelectPrevious};
+use project::{CallHierarchyItem, Fs, Project, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{prelude::*, ListItem, Tooltip};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+actions!(
+    hierarchy_panel,
+    [
+        CollapseSelectedEntry,
+        ExpandSelectedEntry,
+        ShowIncomingCalls,
+        ShowOutgoingCalls,
+        ShowSubtypes,
+        ShowSupertypes,
+        ToggleFocus,
+    ]
+);
+
+const HIERARCHY_PANEL_KEY: &str = "HierarchyPanel";
+
+pub fn init(cx: &mut App) {
+    HierarchyPanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<HierarchyPanel>(window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+            HierarchyPanel::show(workspace, HierarchyKind::IncomingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+            HierarchyPanel::show(workspace, HierarchyKind::OutgoingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSupertypes, window, cx| {
+            HierarchyPanel::show(workspace, HierarchyKind::Supertypes, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSubtypes, window, cx| {
+            HierarchyPanel::show(workspace, HierarchyKind::Subtypes, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// The relation between symbols that the panel shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HierarchyKind {
+    /// The children of a symbol are the symbols calling it.
+    IncomingCalls,
+    /// The children of a symbol are the symbols it calls.
+    OutgoingCalls,
+    /// The children of a type are the types it extends or implements.
+    Supertypes,
+    /// The children of a type are the types extending or implementing it.
+    Subtypes,
+}
+
+impl HierarchyKind {
+    fn title(&self) -> &'static str {
+        match self {
+            HierarchyKind::IncomingCalls => "Incoming Calls",
+            HierarchyKind::OutgoingCalls => "Outgoing Calls",
+            HierarchyKind::Supertypes => "Supertypes",
+            HierarchyKind::Subtypes => "Subtypes",
+        }
+    }
+
+    fn is_call_hierarchy(&self) -> bool {
+        matches!(
+            self,
+            HierarchyKind::IncomingCalls | HierarchyKind::OutgoingCalls
+        )
+    }
+}
+
+#[derive(Clone)]
+enum HierarchyItem {
+    Call(CallHierarchyItem),
+    Type(TypeHierarchyItem),
+}
+
+impl HierarchyItem {
+    fn name(&self) -> &str {
+        match self {
+            HierarchyItem::Call(item) => &item.name,
+            HierarchyItem::Type(item) => &item.name,
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            HierarchyItem::Call(item) => item.detail.as_deref(),
+            HierarchyItem::Type(item) => item.detail.as_deref(),
+        }
+    }
+
+    fn buffer(&self) -> &Entity<Buffer> {
+        match self {
+            HierarchyItem::Call(item) => &item.buffer,
+            HierarchyItem::Type(item) => &item.buffer,
+        }
+    }
+
+    fn selection_range(&self) -> &Range<Anchor> {
+        match self {
+            HierarchyItem::Call(item) => &item.selection_range,
+            HierarchyItem::Type(item) => &item.selection_range,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedHierarchyPanel {
+    width: Option<Pixels>,
+}
+
+struct HierarchyNode {
+    item: HierarchyItem,
+    depth: usize,
+    /// The buffer and range to open when the node is confirmed.
+    target: (Entity<Buffer>, Range<Anchor>),
+    call_count: usize,
+    /// `None` until the children were fetched from the language server.
+    children: Option<Vec<usize>>,
+    expanded: bool,
+}
+
+pub struct HierarchyPanel {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    kind: HierarchyKind,
+    nodes: Vec<HierarchyNode>,
+    roots: Vec<usize>,
+    visible_entries: Vec<usize>,
+    selected_node: Option<usize>,
+    loading: bool,
+    // Incremented whenever the hierarchy is replaced, to discard responses for the previous one.
+    generation: usize,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+}
+
+impl HierarchyPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(HIERARCHY_PANEL_KEY) })
+            .await
+            .context("loading hierarchy panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedHierarchyPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace_handle = cx.entity().downgrade();
+        cx.new(|cx| Self {
+            project,
+            workspace: workspace_handle,
+            fs,
+            focus_handle: cx.focus_handle(),
+            width: None,
+            kind: HierarchyKind::IncomingCalls,
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_node: None,
+            loading: false,
+            generation: 0,
+            scroll_handle: UniformListScrollHandle::new(),
+            pending_serialization: Task::ready(None),
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        HIERARCHY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedHierarchyPanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Shows the hierarchy of the symbol under the newest cursor of the active editor.
+    fn show(
+        workspace: &mut Workspace,
+        kind: HierarchyKind,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(editor) = workspace
+            .active_item(cx)
+            .and_then(|item| item.act_as::<Editor>(cx))
+        else {
+            return;
+        };
+        let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+            let head = editor.selections.newest_anchor().head();
+            editor.buffer().read(cx).text_anchor_for_position(head, cx)
+        }) else {
+            return;
+        };
+        if let Some(panel) = workspace.focus_panel::<HierarchyPanel>(window, cx) {
+            panel.update(cx, |panel, cx| {
+                panel.show_hierarchy(buffer, position, kind, window, cx)
+            });
+        }
+    }
+
+    pub fn show_hierarchy(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: Anchor,
+        kind: HierarchyKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.kind = kind;
+        self.nodes.clear();
+        self.roots.clear();
+        self.selected_node = None;
+        self.generation += 1;
+        self.loading = true;
+        self.update_visible_entries(cx);
+
+        let generation = self.generation;
+        let project = self.project.clone();
+        cx.spawn_in(window, |this, mut cx| async move {
+            let items = async {
+                anyhow::Ok(if kind.is_call_hierarchy() {
+                    project
+                        .update(&mut cx, |project, cx| {
+                            project.prepare_call_hierarchy(&buffer, position, cx)
+                        })?
+                        .await?
+                        .into_iter()
+                        .map(HierarchyItem::Call)
+                        .collect::<Vec<_>>()
+                } else {
+                    project
+                        .update(&mut cx, |project, cx| {
+                            project.prepare_type_hierarchy(&buffer, position, cx)
+                        })?
+                        .await?
+                        .into_iter()
+                        .map(HierarchyItem::Type)
+                        .collect()
+                })
+            }
+            .await;
+            this.update(&mut cx, |this, cx| {
+                if this.generation != generation {
+                    return Ok(());
+                }
+                this.loading = false;
+                for item in items? {
+                    let target = (item.buffer().clone(), item.selection_range().clone());
+                    this.roots.push(this.nodes.len());
+                    this.nodes.push(HierarchyNode {
+                        item,
+                        depth: 0,
+                        target,
+                        call_count: 0,
+                        children: None,
+                        expanded: false,
+                    });
+                }
+                this.selected_node = this.roots.first().copied();
+                if let Some(root) = this.selected_node {
+                    this.set_expanded(root, true, cx);
+                }
+                this.update_visible_entries(cx);
+                anyhow::Ok(())
+            })?
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn set_expanded(&mut self, node_ix: usize, expanded: bool, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get_mut(node_ix) else {
+            return;
+        };
+        node.expanded = expanded;
+        if expanded && node.children.is_none() {
+            self.fetch_children(node_ix, cx);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn fetch_children(&mut self, node_ix: usize, cx: &mut Context<Self>) {
+        let item = self.nodes[node_ix].item.clone();
+        self.nodes[node_ix].children = Some(Vec::new());
+        let kind = self.kind;
+        let project = self.project.clone();
+        let generation = self.generation;
+        cx.spawn(|this, mut cx| async move {
+            let children: Vec<(HierarchyItem, Vec<Range<Anchor>>)> = match (kind, item) {
+                (HierarchyKind::IncomingCalls, HierarchyItem::Call(item)) => project
+                    .update(&mut cx, |project, cx| project.incoming_calls(&item, cx))?
+                    .await?
+                    .into_iter()
+                    .map(|call| (HierarchyItem::Call(call.item), call.ranges))
+                    .collect(),
+                (HierarchyKind::OutgoingCalls, HierarchyItem::Call(item)) => project
+                    .update(&mut cx, |project, cx| project.outgoing_calls(&item, cx))?
+                    .await?
+                    .into_iter()
+                    .map(|call| (HierarchyItem::Call(call.item), call.ranges))
+                    .collect(),
+                (HierarchyKind::Supertypes, HierarchyItem::Type(item)) => project
+                    .update(&mut cx, |project, cx| project.supertypes(&item, cx))?
+                    .await?
+                    .into_iter()
+                    .map(|item| (HierarchyItem::Type(item), Vec::new()))
+                    .collect(),
+                (HierarchyKind::Subtypes, HierarchyItem::Type(item)) => project
+                    .update(&mut cx, |project, cx| project.subtypes(&item, cx))?
+                    .await?
+                    .into_iter()
+                    .map(|item| (HierarchyItem::Type(item), Vec::new()))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            this.update(&mut cx, |this, cx| {
+                if this.generation == generation {
+                    this.insert_children(node_ix, children, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Inserts the children of a node, along with the ranges of their calls, if any.
+    fn insert_children(
+        &mut self,
+        parent_ix: usize,
+        children: Vec<(HierarchyItem, Vec<Range<Anchor>>)>,
+        cx: &mut Context<Self>,
+    ) {
+        let depth = self.nodes[parent_ix].depth + 1;
+        let mut child_ixs = Vec::with_capacity(children.len());
+        for (item, call_ranges) in children {
+            let target = match (self.kind, call_ranges.first()) {
+                // Callers are opened at the first call site, which is in their own buffer.
+                (HierarchyKind::IncomingCalls, Some(range)) => {
+                    (item.buffer().clone(), range.clone())
+                }
+                _ => (item.buffer().clone(), item.selection_range().clone()),
+            };
+            child_ixs.push(self.nodes.len());
+            self.nodes.push(HierarchyNode {
+                item,
+                depth,
+                target,
+                call_count: call_ranges.len(),
+                children: None,
+                expanded: false,
+            });
+        }
+        self.nodes[parent_ix].children = Some(child_ixs);
+        self.update_visible_entries(cx);
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        fn push_visible(nodes: &[HierarchyNode], node_ix: usize, entries: &mut Vec<usize>) {
+            entries.push(node_ix);
+            let node = &nodes[node_ix];
+            if node.expanded {
+                for child_ix in node.children.iter().flatten() {
+                    push_visible(nodes, *child_ix, entries);
+                }
+            }
+        }
+
+        self.visible_entries.clear();
+        for root_ix in &self.roots {
+            push_visible(&self.nodes, *root_ix, &mut self.visible_entries);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_offset(1, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_offset(-1, cx);
+    }
+
+    fn select_offset(&mut self, offset: isize, cx: &mut Context<Self>) {
+        if self.visible_entries.is_empty() {
+            return;
+        }
+        let entry_ix = self
+            .selected_node
+            .and_then(|node_ix| self.visible_entries.iter().position(|ix| *ix == node_ix))
+            .map_or(0, |entry_ix| {
+                entry_ix
+                    .saturating_add_signed(offset)
+                    .min(self.visible_entries.len() - 1)
+            });
+        self.selected_node = Some(self.visible_entries[entry_ix]);
+        self.scroll_handle
+            .scroll_to_item(entry_ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(node_ix) = self.selected_node {
+            self.set_expanded(node_ix, true, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(node_ix) = self.selected_node {
+            self.set_expanded(node_ix, false, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(node_ix) = self.selected_node {
+            self.open_node(node_ix, window, cx);
+        }
+    }
+
+    fn open_node(&mut self, node_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get(node_ix) else {
+            return;
+        };
+        let (buffer, range) = node.target.clone();
+        let range = {
+            let buffer = buffer.read(cx);
+            range.start.to_point(buffer)..range.end.to_point(buffer)
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = workspace.active_pane().clone();
+                let editor =
+                    workspace.open_project_item::<Editor>(pane, buffer, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_ranges([range])
+                    });
+                });
+            })
+            .log_err();
+    }
+
+    fn render_entry(
+        &self,
+        entry_ix: usize,
+        node_ix: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let node = &self.nodes[node_ix];
+        let buffer = node.item.buffer().read(cx);
+        let location = buffer.file().map(|file| {
+            let row = node.item.selection_range().start.to_point(buffer).row;
+            format!("{}:{}", file.file_name(cx).to_string_lossy(), row + 1)
+        });
+        let is_expandable = node
+            .children
+            .as_ref()
+            .map_or(true, |children| !children.is_empty());
+
+        ListItem::new(("hierarchy-entry", entry_ix))
+            .indent_level(node.depth)
+            .indent_step_size(px(12.))
+            .toggle_state(self.selected_node == Some(node_ix))
+            .toggle(is_expandable.then_some(node.expanded))
+            .on_toggle(cx.listener(move |this, _, _, cx| {
+                let expanded = this.nodes[node_ix].expanded;
+                this.set_expanded(node_ix, !expanded, cx);
+            }))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_node = Some(node_ix);
+                this.open_node(node_ix, window, cx);
+                cx.notify();
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(node.item.name().to_string()).size(LabelSize::Small))
+                    .when_some(node.item.detail().map(str::to_string), |this, detail| {
+                        this.child(
+                            Label::new(detail)
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted)
+                                .single_line(),
+                        )
+                    }),
+            )
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .when(node.call_count > 1, |this| {
+                        this.child(
+                            Label::new(format!("×{}", node.call_count))
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .when_some(location, |this, location| {
+                        this.child(
+                            Label::new(location)
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+    }
+
+    fn render_kind_button(
+        &self,
+        kind: HierarchyKind,
+        icon: IconName,
+        action: &dyn Action,
+    ) -> IconButton {
+        let title = format!("Show {}", kind.title());
+        let action = action.boxed_clone();
+        IconButton::new(kind.title(), icon)
+            .icon_size(IconSize::Small)
+            .toggle_state(self.kind == kind)
+            .tooltip(Tooltip::for_action_title(title, action.as_ref()))
+            .on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("HierarchyPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+}
+
+impl Panel for HierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "Hierarchy Panel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match HierarchyPanelSettings::get_global(cx).dock {
+            HierarchyPanelDockPosition::Left => DockPosition::Left,
+            HierarchyPanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<HierarchyPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left | DockPosition::Bottom => HierarchyPanelDockPosition::Left,
+                    DockPosition::Right => HierarchyPanelDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| HierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        HierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
+
+impl Focusable for HierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for HierarchyPanel {}
+
+impl Render for HierarchyPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let header = h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(self.kind.title())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(if self.kind.is_call_hierarchy() {
+                h_flex()
+                    .gap_1()
+                    .child(self.render_kind_button(
+                        HierarchyKind::IncomingCalls,
+                        IconName::ArrowDown,
+                        &ShowIncomingCalls,
+                    ))
+                    .child(self.render_kind_button(
+                        HierarchyKind::OutgoingCalls,
+                        IconName::ArrowUp,
+                        &ShowOutgoingCalls,
+                    ))
+            } else {
+                h_flex()
+                    .gap_1()
+                    .child(self.render_kind_button(
+                        HierarchyKind::Supertypes,
+                        IconName::ArrowUp,
+                        &ShowSupertypes,
+                    ))
+                    .child(self.render_kind_button(
+                        HierarchyKind::Subtypes,
+                        IconName::ArrowDown,
+                        &ShowSubtypes,
+                    ))
+            });
+
+        let content = if self.visible_entries.is_empty() {
+            let message = if self.loading {
+                "Loading…"
+            } else {
+                "Place the cursor on a symbol and show its call or type hierarchy."
+            };
+            div()
+                .p_2()
+                .child(
+                    Label::new(message)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            uniform_list(
+                cx.entity().clone(),
+                "hierarchy-entries",
+                self.visible_entries.len(),
+                |this, range, _, cx| {
+                    range
+                        .map(|entry_ix| {
+                            let node_ix = this.visible_entries[entry_ix];
+                            this.render_entry(entry_ix, node_ix, cx)
+                        })
+                        .collect()
+                },
+            )
+            .size_full()
+            .track_scroll(self.scroll_handle.clone())
+            .into_any_element()
+        };
+
+        v_flex()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(header)
+            .child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{FutureExt as _, StreamExt};
+    use gpui::{SemanticVersion, TestAppContext};
+    use language::{
+        FakeLspAdapter, Language, LanguageConfig, LanguageMatcher, OffsetRangeExt, Point,
+    };
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_type_hierarchy_children_are_fetched_once(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "shapes.rs": "trait Shape {}\nstruct Circle;\nstruct Square;\nstruct Disk;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_servers =
+            language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+        let (buffer, _handle) = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer_with_lsp(path!("/dir/shapes.rs"), cx)
+            })
+            .await
+            .unwrap();
+
+        let fake_server = fake_servers.next().await.unwrap();
+        fake_server
+            .request::<lsp::request::RegisterCapability>(lsp::RegistrationParams {
+                registrations: vec![lsp::Registration {
+                    id: "type-hierarchy".to_string(),
+                    method: "textDocument/prepareTypeHierarchy".to_string(),
+                    register_options: None,
+                }],
+            })
+            .await
+            .unwrap();
+        let types = ["Shape", "Circle", "Square", "Disk"];
+        let lsp_item = move |name: &str| {
+            let row = types
+                .iter()
+                .position(|type_name| *type_name == name)
+                .unwrap() as u32;
+            let column = if row == 0 { 6 } else { 7 };
+            lsp::TypeHierarchyItem {
+                name: name.to_string(),
+                kind: lsp::SymbolKind::STRUCT,
+                tags: None,
+                detail: None,
+                uri: lsp::Url::from_file_path(path!("/dir/shapes.rs")).unwrap(),
+                range: lsp::Range::new(lsp::Position::new(row, 0), lsp::Position::new(row, 14)),
+                selection_range: lsp::Range::new(
+                    lsp::Position::new(row, column),
+                    lsp::Position::new(row, column + name.len() as u32),
+                ),
+                data: None,
+            }
+        };
+        fake_server.handle_request::<lsp::request::TypeHierarchyPrepare, _, _>(
+            move |params, _| async move {
+                let row = params.text_document_position_params.position.line;
+                Ok(Some(vec![lsp_item(types[row as usize])]))
+            },
+        );
+        let mut subtype_requests = fake_server
+            .handle_request::<lsp::request::TypeHierarchySubtypes, _, _>(
+                move |params, _| async move {
+                    let subtypes: &[&str] = match params.item.name.as_str() {
+                        "Shape" => &["Circle", "Square"],
+                        "Circle" => &["Disk"],
+                        _ => &[],
+                    };
+                    Ok(Some(subtypes.iter().map(|name| lsp_item(name)).collect()))
+                },
+            );
+        fake_server.handle_request::<lsp::request::TypeHierarchySupertypes, _, _>(
+            move |params, _| async move {
+                let supertypes: &[&str] = match params.item.name.as_str() {
+                    "Circle" | "Square" => &["Shape"],
+                    "Disk" => &["Circle"],
+                    _ => &[],
+                };
+                Ok(Some(supertypes.iter().map(|name| lsp_item(name)).collect()))
+            },
+        );
+
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let panel = workspace.update(cx, |workspace, cx| HierarchyPanel::new(workspace, cx));
+
+        // The root is expanded as soon as the hierarchy is shown.
+        let position = buffer.read_with(cx, |buffer, _| buffer.anchor_before(8));
+        panel.update_in(cx, |panel, window, cx| {
+            panel.show_hierarchy(
+                buffer.clone(),
+                position,
+                HierarchyKind::Subtypes,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(display_entries(panel), ["Shape", "  Circle", "  Square"]);
+
+            // Types are opened at their name.
+            let (target_buffer, target_range) = &panel.nodes[panel.visible_entries[1]].target;
+            assert_eq!(target_range.to_offset(target_buffer.read(cx)), 22..28);
+
+            panel.select_offset(1, cx);
+            panel.set_expanded(panel.selected_node.unwrap(), true, cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(
+                display_entries(panel),
+                ["Shape", "  Circle", "    Disk", "  Square"]
+            );
+
+            // Collapsing and expanding a type again reuses the subtypes fetched before.
+            let circle = panel.selected_node.unwrap();
+            panel.set_expanded(circle, false, cx);
+            assert_eq!(display_entries(panel), ["Shape", "  Circle", "  Square"]);
+            panel.set_expanded(circle, true, cx);
+            assert_eq!(
+                display_entries(panel),
+                ["Shape", "  Circle", "    Disk", "  Square"]
+            );
+
+            // Types without subtypes are expanded to nothing.
+            panel.select_offset(1, cx);
+            panel.set_expanded(panel.selected_node.unwrap(), true, cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(
+                display_entries(panel),
+                ["Shape", "  Circle", "    Disk", "  Square"]
+            );
+        });
+        for _ in 0..3 {
+            subtype_requests.next().await.unwrap();
+        }
+        assert!(subtype_requests.next().now_or_never().is_none());
+
+        let position = buffer.read_with(cx, |buffer, _| buffer.anchor_before(Point::new(3, 8)));
+        panel.update_in(cx, |panel, window, cx| {
+            panel.show_hierarchy(
+                buffer.clone(),
+                position,
+                HierarchyKind::Supertypes,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(display_entries(panel), ["Disk", "  Circle"]);
+            panel.select_offset(1, cx);
+            panel.set_expanded(panel.selected_node.unwrap(), true, cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, _| {
+            assert_eq!(display_entries(panel), ["Disk", "  Circle", "    Shape"]);
+        });
+    }
+
+    fn display_entries(panel: &HierarchyPanel) -> Vec<String> {
+        panel
+            .visible_entries
+            .iter()
+            .map(|node_ix| {
+                let node = &panel.nodes[*node_ix];
+                format!("{}{}", "  ".repeat(node.depth), node.item.name())
+            })
+            .collect()
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            release_channel::init(SemanticVersion::default(), cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            super::init(cx);
+        });
+    }
+}
@@ -61,7 +61,8 @@ pub use text::{
     Anchor, Bias, Buffer as TextBuffer, BufferId, BufferSnapshot as TextBufferSnapshot, Edit,
     OffsetRangeExt, OffsetUtf16, Patch, Point, PointUtf16, Rope, Selection, SelectionGoal,
     Subscription, TextDimension, TextSummary, ToOffset, ToOffsetUtf16, ToPoint, ToPointUtf16,
-    Transaction, TransactionId, Unclipped,
+    Transaction, TransactionId, Unclipped, UndoHistory, UndoHistoryTransaction, UndoTreeDistance,
+    UndoTreeNode,
 };
 use theme::{ActiveTheme as _, SyntaxTheme};
 #[cfg(any(test, feature = "test-support"))]
@@ -2058,6 +2059,16 @@ impl Buffer {
         self.text.merge_transactions(transaction, destination);
     }
 
+    /// Fills the empty undo and redo stacks of the buffer with the given
+    /// history, leaving its text untouched until a restored transaction is
+    /// undone or redone.
+    ///
+    /// Returns false if the buffer already has history or the given history
+    /// doesn't fit its text.
+    pub fn restore_undo_history(&mut self, history: &UndoHistory) -> bool {
+        self.text.restore_undo_history(history)
+    }
+
     /// Waits for the buffer to receive operations with the given timestamps.
     pub fn wait_for_edits(
         &mut self,
@@ -2638,6 +2649,54 @@ impl Buffer {
         redone
     }
 
+    /// Undoes and redoes transactions until the buffer is in the given state of
+    /// its undo tree, or back at its original text if `None` is given. Returns
+    /// the last transaction that was undone or redone.
+    pub fn travel_to_transaction(
+        &mut self,
+        transaction_id: Option<TransactionId>,
+        cx: &mut Context<Self>,
+    ) -> Option<TransactionId> {
+        self.travel_undo_tree(|text| text.travel_to_transaction(transaction_id), cx)
+    }
+
+    /// Travels to an earlier state of the buffer's undo tree, across branches.
+    pub fn earlier(
+        &mut self,
+        distance: UndoTreeDistance,
+        cx: &mut Context<Self>,
+    ) -> Option<TransactionId> {
+        self.travel_undo_tree(|text| text.earlier(distance), cx)
+    }
+
+    /// Travels to a later state of the buffer's undo tree, across branches.
+    pub fn later(
+        &mut self,
+        distance: UndoTreeDistance,
+        cx: &mut Context<Self>,
+    ) -> Option<TransactionId> {
+        self.travel_undo_tree(|text| text.later(distance), cx)
+    }
+
+    fn travel_undo_tree(
+        &mut self,
+        travel: impl FnOnce(&mut TextBuffer) -> Vec<(TransactionId, text::Operation)>,
+        cx: &mut Context<Self>,
+    ) -> Option<TransactionId> {
+        let was_dirty = self.is_dirty();
+        let old_version = self.version.clone();
+
+        let mut last_transaction_id = None;
+        for (transaction_id, operation) in travel(&mut self.text) {
+            self.send_operation(Operation::Buffer(operation), true, cx);
+            last_transaction_id = Some(transaction_id);
+        }
+        if last_transaction_id.is_some() {
+            self.did_edit(&old_version, was_dirty, cx)
+        }
+        last_transaction_id
+    }
+
     /// Override current completion triggers with the user-provided completion triggers.
     pub fn set_completion_triggers(
         &mut self,
@@ -259,6 +259,58 @@ fn file(path: &str) -> Arc<dyn File> {
     })
 }
 
+#[gpui::test]
+fn test_restore_undo_history(cx: &mut gpui::App) {
+    let original = cx.new(|cx| {
+        let mut buffer = Buffer::local("fn a() {}", cx);
+        buffer.edit([(5..5, "b: u8")], None, cx);
+        buffer.edit([(12..12, " b }")], None, cx);
+        buffer.edit([(0..0, "pub ")], None, cx);
+        buffer.undo(cx);
+        buffer
+    });
+    let text = original.read(cx).text();
+    assert_eq!(text, "fn a(b: u8) { b }");
+    let history = original.read(cx).undo_history();
+
+    let restored = cx.new(|cx| Buffer::local(text.clone(), cx));
+    restored.update(cx, |buffer, cx| {
+        let version = buffer.version();
+        assert!(buffer.restore_undo_history(&history));
+        // Restoring the history doesn't edit the buffer.
+        assert_eq!(buffer.version(), version);
+        assert_eq!(buffer.text(), text);
+        assert!(!buffer.is_dirty());
+
+        buffer.redo(cx);
+        assert_eq!(buffer.text(), "pub fn a(b: u8) { b }");
+        buffer.undo(cx);
+        buffer.undo(cx);
+        assert_eq!(buffer.text(), "fn a(b: u8) {}");
+        buffer.undo(cx);
+        assert_eq!(buffer.text(), "fn a() {}");
+        assert_eq!(buffer.undo(cx), None);
+
+        // Once applied, restored transactions are redone like any other.
+        buffer.redo(cx);
+        assert_eq!(buffer.text(), "fn a(b: u8) {}");
+        buffer.undo(cx);
+        assert_eq!(buffer.text(), "fn a() {}");
+
+        // Buffers that already have history are left alone.
+        assert!(!buffer.restore_undo_history(&history));
+        assert_eq!(buffer.text(), "fn a() {}");
+    });
+
+    // History that doesn't match the buffer's text isn't restored.
+    let modified = cx.new(|cx| Buffer::local("fn a(b: u8) { c }", cx));
+    modified.update(cx, |buffer, cx| {
+        assert!(!buffer.restore_undo_history(&history));
+        assert_eq!(buffer.text(), "fn a(b: u8) { c }");
+        assert_eq!(buffer.undo(cx), None);
+    });
+}
+
 #[gpui::test]
 fn test_edit_events(cx: &mut gpui::App) {
     let mut now = Instant::now();
@@ -143,6 +143,10 @@ pub struct LanguageSettings {
     pub code_actions_on_format: HashMap<String, bool>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
+    /// Whether to layer semantic token highlights from language servers over syntax highlights.
+    pub semantic_tokens: bool,
+    /// Whether to fold code using folding ranges from language servers.
+    pub lsp_folding_ranges: bool,
     /// Task configuration for this language.
     pub tasks: LanguageTaskConfig,
     /// Whether to pop the completions menu while typing in an editor without
@@ -507,6 +511,16 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub linked_edits: Option<bool>,
+    /// Whether to highlight code using semantic tokens from language servers, on top of the
+    /// syntax highlighting. This distinguishes e.g. mutable from immutable variables in Rust.
+    ///
+    /// Default: false
+    pub semantic_tokens: Option<bool>,
+    /// Whether to fold code using folding ranges from language servers. These take precedence
+    /// over indentation-based folding, and allow folding regions like `#region` markers.
+    ///
+    /// Default: false
+    pub lsp_folding_ranges: Option<bool>,
     /// Whether indentation of pasted content should be adjusted based on the context.
     ///
     /// Default: true
@@ -1373,6 +1387,8 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.code_actions_on_format.clone(),
     );
     merge(&mut settings.linked_edits, src.linked_edits);
+    merge(&mut settings.semantic_tokens, src.semantic_tokens);
+    merge(&mut settings.lsp_folding_ranges, src.lsp_folding_ranges);
     merge(&mut settings.tasks, src.tasks.clone());
 
     merge(
@@ -0,0 +1,3 @@
+pub mod breakpoint_store;
+pub mod dap_store;
+pub mod session;
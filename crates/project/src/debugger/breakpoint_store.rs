@@ -0,0 +1,286 @@
+use crate::{
+    buffer_store::{BufferStore, BufferStoreEvent},
+    File,
+};
+use collections::BTreeMap;
+use dap::SourceBreakpoint;
+use gpui::{App, Context, Entity, EventEmitter, Subscription, WeakEntity};
+use language::{Buffer, BufferSnapshot};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use text::{Anchor, Bias, Point, ToPoint as _};
+
+/// A breakpoint set by the user. The position is tracked separately, as an anchor into the buffer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Breakpoint {
+    /// When set, the debug adapter logs this message instead of stopping.
+    pub log_message: Option<Arc<str>>,
+    /// An expression that has to evaluate to true for the breakpoint to stop.
+    pub condition: Option<Arc<str>>,
+}
+
+enum BreakpointsInFile {
+    /// The file is open, so its breakpoints are anchored in the buffer and follow its edits.
+    Open {
+        buffer: WeakEntity<Buffer>,
+        breakpoints: Vec<(Anchor, Breakpoint)>,
+        _release: Subscription,
+    },
+    /// The file's buffer was released, so only the rows are kept until it's opened again.
+    Closed { breakpoints: Vec<(u32, Breakpoint)> },
+}
+
+impl BreakpointsInFile {
+    fn rows(&self, cx: &App) -> Vec<(u32, Breakpoint)> {
+        match self {
+            Self::Open {
+                buffer,
+                breakpoints,
+                ..
+            } => {
+                let Some(buffer) = buffer.upgrade() else {
+                    return Vec::new();
+                };
+                let snapshot = buffer.read(cx).snapshot();
+                breakpoints
+                    .iter()
+                    .map(|(anchor, breakpoint)| {
+                        (anchor.to_point(&snapshot).row, breakpoint.clone())
+                    })
+                    .collect()
+            }
+            Self::Closed { breakpoints } => breakpoints.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakpointStoreEvent {
+    BreakpointsUpdated(Arc<Path>),
+}
+
+/// Keeps track of the breakpoints of a project, keyed by the absolute path of the file they're in.
+pub struct BreakpointStore {
+    breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    _buffer_store_subscription: Subscription,
+}
+
+impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
+
+impl BreakpointStore {
+    pub fn new(buffer_store: &Entity<BufferStore>, cx: &mut Context<Self>) -> Self {
+        Self {
+            breakpoints: BTreeMap::default(),
+            _buffer_store_subscription: cx.subscribe(buffer_store, Self::on_buffer_store_event),
+        }
+    }
+
+    fn on_buffer_store_event(
+        &mut self,
+        _: Entity<BufferStore>,
+        event: &BufferStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let BufferStoreEvent::BufferAdded(buffer) = event {
+            let Some(abs_path) = Self::abs_path_for_buffer(buffer, cx) else {
+                return;
+            };
+            if self.breakpoints.contains_key(&abs_path) {
+                self.anchored_breakpoints(&abs_path, buffer, cx);
+                cx.notify();
+            }
+        }
+    }
+
+    fn abs_path_for_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        let file = File::from_dyn(buffer.read(cx).file())?;
+        let abs_path: PathBuf = file.worktree.read(cx).absolutize(&file.path).ok()?;
+        Some(abs_path.into())
+    }
+
+    /// Returns the breakpoints of the file at `abs_path`, anchoring them in `buffer` if they
+    /// belonged to a previous buffer for the same file.
+    fn anchored_breakpoints(
+        &mut self,
+        abs_path: &Arc<Path>,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> &mut Vec<(Anchor, Breakpoint)> {
+        let file =
+            self.breakpoints
+                .entry(abs_path.clone())
+                .or_insert_with(|| BreakpointsInFile::Closed {
+                    breakpoints: Vec::new(),
+                });
+        let is_anchored = matches!(
+            file,
+            BreakpointsInFile::Open { buffer: existing, .. } if *existing == buffer.downgrade()
+        );
+        if !is_anchored {
+            let snapshot = buffer.read(cx).snapshot();
+            let mut breakpoints: Vec<(Anchor, Breakpoint)> = Vec::new();
+            for (row, breakpoint) in file.rows(cx) {
+                let point = snapshot.clip_point(Point::new(row, 0), Bias::Left);
+                // Rows past the end of a file that shrank while closed collapse onto its last row.
+                if breakpoints.last().map_or(true, |(anchor, _)| {
+                    anchor.to_point(&snapshot).row < point.row
+                }) {
+                    breakpoints.push((snapshot.anchor_before(point), breakpoint));
+                }
+            }
+
+            let abs_path = abs_path.clone();
+            let weak_buffer = buffer.downgrade();
+            let release = cx.observe_release(buffer, move |this, buffer, _| {
+                this.buffer_released(&abs_path, &weak_buffer, buffer);
+            });
+            *file = BreakpointsInFile::Open {
+                buffer: buffer.downgrade(),
+                breakpoints,
+                _release: release,
+            };
+        }
+
+        match file {
+            BreakpointsInFile::Open { breakpoints, .. } => breakpoints,
+            BreakpointsInFile::Closed { .. } => unreachable!(),
+        }
+    }
+
+    fn buffer_released(
+        &mut self,
+        abs_path: &Arc<Path>,
+        weak_buffer: &WeakEntity<Buffer>,
+        buffer: &mut Buffer,
+    ) {
+        let Some(file) = self.breakpoints.get_mut(abs_path) else {
+            return;
+        };
+        if let BreakpointsInFile::Open {
+            buffer: existing,
+            breakpoints,
+            ..
+        } = file
+        {
+            if existing == weak_buffer {
+                let snapshot = buffer.snapshot();
+                let breakpoints = breakpoints
+                    .drain(..)
+                    .map(|(anchor, breakpoint)| (anchor.to_point(&snapshot).row, breakpoint))
+                    .collect();
+                *file = BreakpointsInFile::Closed { breakpoints };
+            }
+        }
+    }
+
+    /// Adds a breakpoint on the row containing `position`, or removes the existing one.
+    pub fn toggle_breakpoint(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_for_buffer(&buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let row = position.to_point(&snapshot).row;
+
+        let breakpoints = self.anchored_breakpoints(&abs_path, &buffer, cx);
+        if let Some(ix) = breakpoints
+            .iter()
+            .position(|(anchor, _)| anchor.to_point(&snapshot).row == row)
+        {
+            breakpoints.remove(ix);
+        } else {
+            let anchor = snapshot.anchor_before(Point::new(row, 0));
+            let ix = breakpoints
+                .partition_point(|(existing, _)| existing.cmp(&anchor, &snapshot).is_lt());
+            breakpoints.insert(ix, (anchor, Breakpoint::default()));
+        }
+
+        if breakpoints.is_empty() {
+            self.breakpoints.remove(&abs_path);
+        }
+        cx.emit(BreakpointStoreEvent::BreakpointsUpdated(abs_path));
+        cx.notify();
+    }
+
+    /// Returns the breakpoints of the given buffer, optionally restricted to a range.
+    pub fn breakpoints<'a>(
+        &'a self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<Anchor>>,
+        snapshot: &'a BufferSnapshot,
+        cx: &App,
+    ) -> impl 'a + Iterator<Item = &'a (Anchor, Breakpoint)> {
+        let breakpoints = Self::abs_path_for_buffer(buffer, cx)
+            .and_then(|abs_path| self.breakpoints.get(&abs_path))
+            .and_then(|file| match file {
+                BreakpointsInFile::Open {
+                    buffer: existing,
+                    breakpoints,
+                    ..
+                } if *existing == buffer.downgrade() => Some(breakpoints),
+                _ => None,
+            });
+        let (start_ix, end_ix) = match (breakpoints, range) {
+            (Some(breakpoints), Some(range)) => (
+                breakpoints
+                    .partition_point(|(anchor, _)| anchor.cmp(&range.start, snapshot).is_lt()),
+                breakpoints.partition_point(|(anchor, _)| anchor.cmp(&range.end, snapshot).is_le()),
+            ),
+            (Some(breakpoints), None) => (0, breakpoints.len()),
+            (None, _) => (0, 0),
+        };
+        breakpoints
+            .into_iter()
+            .flat_map(move |breakpoints| &breakpoints[start_ix..end_ix.max(start_ix)])
+    }
+
+    /// Returns the breakpoints of the file at `abs_path`, as expected by the `setBreakpoints` request.
+    pub fn source_breakpoints(&self, abs_path: &Path, cx: &App) -> Vec<SourceBreakpoint> {
+        let Some(file) = self.breakpoints.get(abs_path) else {
+            return Vec::new();
+        };
+        file.rows(cx)
+            .into_iter()
+            .map(|(row, breakpoint)| SourceBreakpoint {
+                line: row as u64 + 1,
+                column: None,
+                condition: breakpoint.condition.as_ref().map(|c| c.to_string()),
+                hit_condition: None,
+                log_message: breakpoint.log_message.as_ref().map(|m| m.to_string()),
+                mode: None,
+            })
+            .collect()
+    }
+
+    /// Returns the paths of all files containing breakpoints.
+    pub fn breakpoint_paths(&self) -> impl Iterator<Item = &Arc<Path>> {
+        self.breakpoints.keys()
+    }
+
+    /// Returns every breakpoint along with the zero-based row it's currently on.
+    pub fn all_breakpoints(&self, cx: &App) -> Vec<(Arc<Path>, u32, Breakpoint)> {
+        self.breakpoints
+            .iter()
+            .flat_map(|(abs_path, file)| {
+                file.rows(cx)
+                    .into_iter()
+                    .map(|(row, breakpoint)| (abs_path.clone(), row, breakpoint))
+            })
+            .collect()
+    }
+
+    pub fn clear_breakpoints(&mut self, cx: &mut Context<Self>) {
+        let breakpoints = std::mem::take(&mut self.breakpoints);
+        for abs_path in breakpoints.into_keys() {
+            cx.emit(BreakpointStoreEvent::BreakpointsUpdated(abs_path));
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,145 @@
+use super::{
+    breakpoint_store::{BreakpointStore, BreakpointStoreEvent},
+    session::{Session, SessionEvent, SessionId},
+};
+use anyhow::{anyhow, Result};
+use collections::BTreeMap;
+use dap::DebugAdapterConfig;
+use gpui::{Context, Entity, EventEmitter, Subscription, Task};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DapStoreEvent {
+    SessionStarted(SessionId),
+    SessionShutdown(SessionId),
+}
+
+enum DapStoreMode {
+    Local,
+    Remote,
+}
+
+/// Owns the debug sessions of a project.
+pub struct DapStore {
+    mode: DapStoreMode,
+    breakpoint_store: Entity<BreakpointStore>,
+    sessions: BTreeMap<SessionId, (Entity<Session>, Subscription)>,
+    next_session_id: u32,
+    _breakpoint_store_subscription: Subscription,
+}
+
+impl EventEmitter<DapStoreEvent> for DapStore {}
+
+impl DapStore {
+    pub fn new_local(breakpoint_store: Entity<BreakpointStore>, cx: &mut Context<Self>) -> Self {
+        Self::new(DapStoreMode::Local, breakpoint_store, cx)
+    }
+
+    /// Debugging is only supported for local projects, so remote stores refuse to start sessions.
+    pub fn new_remote(breakpoint_store: Entity<BreakpointStore>, cx: &mut Context<Self>) -> Self {
+        Self::new(DapStoreMode::Remote, breakpoint_store, cx)
+    }
+
+    fn new(
+        mode: DapStoreMode,
+        breakpoint_store: Entity<BreakpointStore>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.subscribe(&breakpoint_store, Self::on_breakpoint_store_event);
+        Self {
+            mode,
+            breakpoint_store,
+            sessions: BTreeMap::default(),
+            next_session_id: 0,
+            _breakpoint_store_subscription: subscription,
+        }
+    }
+
+    /// Whether this store can start debug sessions, which is only the case for local projects.
+    pub fn can_start_sessions(&self) -> bool {
+        matches!(self.mode, DapStoreMode::Local)
+    }
+
+    pub fn breakpoint_store(&self) -> &Entity<BreakpointStore> {
+        &self.breakpoint_store
+    }
+
+    pub fn sessions(&self) -> impl Iterator<Item = &Entity<Session>> {
+        self.sessions.values().map(|(session, _)| session)
+    }
+
+    pub fn session_by_id(&self, session_id: SessionId) -> Option<Entity<Session>> {
+        self.sessions
+            .get(&session_id)
+            .map(|(session, _)| session.clone())
+    }
+
+    pub fn new_session(
+        &mut self,
+        config: DebugAdapterConfig,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Session>>> {
+        if !self.can_start_sessions() {
+            return Task::ready(Err(anyhow!(
+                "debugging isn't supported in remote projects yet, open the project locally to debug it"
+            )));
+        }
+
+        let session_id = SessionId(self.next_session_id);
+        self.next_session_id += 1;
+        let start = Session::start(session_id, config, self.breakpoint_store.clone(), cx);
+        cx.spawn(|this, mut cx| async move {
+            let session = start.await?;
+            this.update(&mut cx, |this, cx| {
+                let subscription = cx.subscribe(&session, move |this, _, event, cx| {
+                    if *event == SessionEvent::Terminated {
+                        this.shutdown_session(session_id, cx).detach();
+                    }
+                });
+                this.sessions
+                    .insert(session_id, (session.clone(), subscription));
+                cx.emit(DapStoreEvent::SessionStarted(session_id));
+                cx.notify();
+            })?;
+            Ok(session)
+        })
+    }
+
+    pub fn shutdown_session(&mut self, session_id: SessionId, cx: &mut Context<Self>) -> Task<()> {
+        let Some((session, _)) = self.sessions.remove(&session_id) else {
+            return Task::ready(());
+        };
+        cx.emit(DapStoreEvent::SessionShutdown(session_id));
+        cx.notify();
+        session.update(cx, |session, cx| session.shutdown(cx))
+    }
+
+    pub fn shutdown_sessions(&mut self, cx: &mut Context<Self>) -> Task<()> {
+        let session_ids = self.sessions.keys().copied().collect::<Vec<_>>();
+        let tasks = session_ids
+            .into_iter()
+            .map(|session_id| self.shutdown_session(session_id, cx))
+            .collect::<Vec<_>>();
+        cx.background_executor().spawn(async move {
+            futures::future::join_all(tasks).await;
+        })
+    }
+
+    fn on_breakpoint_store_event(
+        &mut self,
+        _: Entity<BreakpointStore>,
+        event: &BreakpointStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            BreakpointStoreEvent::BreakpointsUpdated(abs_path) => {
+                for (session, _) in self.sessions.values() {
+                    session
+                        .update(cx, |session, cx| {
+                            session.send_breakpoints(abs_path.clone(), cx)
+                        })
+                        .detach_and_log_err(cx);
+                }
+            }
+        }
+    }
+}
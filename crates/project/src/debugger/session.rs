@@ -0,0 +1,634 @@
+use super::breakpoint_store::BreakpointStore;
+use anyhow::{anyhow, Result};
+use collections::BTreeMap;
+use dap::{
+    events,
+    requests::{
+        Attach, ConfigurationDone, Continue, Evaluate, Initialize, Launch, Next, Pause, Scopes,
+        SetBreakpoints, StackTrace, StepIn, StepOut, Threads, Variables,
+    },
+    AttachRequestArguments, Capabilities, ConfigurationDoneArguments, ContinueArguments,
+    DebugAdapterClient, DebugAdapterClientId, DebugAdapterConfig, DebugRequestType,
+    EvaluateArguments, EvaluateArgumentsContext, EvaluateResponse, InitializeRequestArguments,
+    LaunchRequestArguments, NextArguments, OutputEvent, PauseArguments, Scope, ScopesArguments,
+    SetBreakpointsArguments, Source, StackFrame, StackTraceArguments, StepInArguments,
+    StepOutArguments, Thread, ThreadEventReason, Variable, VariablesArguments,
+};
+use futures::channel::oneshot;
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Task, WeakEntity};
+use std::{fmt, path::Path, sync::Arc};
+use util::ResultExt as _;
+
+/// Identifies a debug session within a project.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SessionId(pub u32);
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ThreadStatus {
+    Running,
+    Stopped,
+    Exited,
+}
+
+#[derive(Clone, Debug)]
+pub struct ThreadState {
+    pub thread: Thread,
+    pub status: ThreadStatus,
+    /// The call stack of the thread, innermost frame first. Only available while the thread is stopped.
+    pub stack_frames: Vec<StackFrame>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionEvent {
+    Stopped(Option<u64>),
+    Continued,
+    Threads,
+    StackFrames(u64),
+    Output,
+    Terminated,
+}
+
+/// A running debug adapter, along with the state of the debuggee as reported by the adapter.
+pub struct Session {
+    id: SessionId,
+    config: DebugAdapterConfig,
+    client: Arc<DebugAdapterClient>,
+    capabilities: Capabilities,
+    threads: BTreeMap<u64, ThreadState>,
+    active_thread: Option<u64>,
+    output: Vec<OutputEvent>,
+    terminated: bool,
+    breakpoint_store: Entity<BreakpointStore>,
+    _subscriptions: Vec<dap::Subscription>,
+}
+
+impl EventEmitter<SessionEvent> for Session {}
+
+impl Session {
+    /// Starts the debug adapter described by `config`, then launches or attaches to the debuggee.
+    pub fn start(
+        id: SessionId,
+        config: DebugAdapterConfig,
+        breakpoint_store: Entity<BreakpointStore>,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(|mut cx| async move {
+            let client = Arc::new(DebugAdapterClient::start(
+                DebugAdapterClientId(id.0 as usize),
+                config.adapter.as_str().into(),
+                config.binary.clone(),
+                cx.clone(),
+            )?);
+            Self::boot(id, config, client, breakpoint_store, &mut cx).await
+        })
+    }
+
+    /// Performs the initialization sequence with an already running adapter.
+    pub async fn boot(
+        id: SessionId,
+        config: DebugAdapterConfig,
+        client: Arc<DebugAdapterClient>,
+        breakpoint_store: Entity<BreakpointStore>,
+        cx: &mut AsyncApp,
+    ) -> Result<Entity<Self>> {
+        // The adapter may send the `initialized` event right after responding to `initialize`,
+        // so the handler has to be in place before sending the request.
+        let (initialized_tx, initialized_rx) = oneshot::channel();
+        let mut initialized_tx = Some(initialized_tx);
+        let initialized_subscription = client.on_event::<events::Initialized, _>(move |_, _| {
+            if let Some(tx) = initialized_tx.take() {
+                tx.send(()).ok();
+            }
+        });
+
+        let capabilities = client
+            .request::<Initialize>(InitializeRequestArguments {
+                client_id: Some("zed".to_string()),
+                client_name: Some("Zed".to_string()),
+                adapter_id: config.adapter.clone(),
+                locale: Some("en-US".to_string()),
+                lines_start_at1: Some(true),
+                columns_start_at1: Some(true),
+                path_format: Some(dap::InitializeRequestArgumentsPathFormat::Path),
+                supports_variable_type: Some(true),
+                supports_variable_paging: Some(false),
+                supports_run_in_terminal_request: Some(false),
+                supports_memory_references: None,
+                supports_progress_reporting: None,
+                supports_invalidated_event: None,
+                supports_memory_event: None,
+                supports_args_can_be_interpreted_by_shell: None,
+                supports_start_debugging_request: None,
+            })
+            .await?;
+
+        let request = config.request;
+        let args = config.args.clone();
+        let this = cx.new(|cx| {
+            Self::new(
+                id,
+                config,
+                client.clone(),
+                capabilities,
+                breakpoint_store,
+                initialized_subscription,
+                cx,
+            )
+        })?;
+
+        // Some adapters only respond to `launch` once the configuration is done,
+        // so both have to be in flight at the same time.
+        let start = async {
+            match request {
+                DebugRequestType::Launch => {
+                    client
+                        .request::<Launch>(LaunchRequestArguments { raw: args })
+                        .await
+                }
+                DebugRequestType::Attach => {
+                    client
+                        .request::<Attach>(AttachRequestArguments { raw: args })
+                        .await
+                }
+            }
+        };
+        let configure = {
+            let this = this.clone();
+            let client = client.clone();
+            let mut cx = cx.clone();
+            async move {
+                initialized_rx
+                    .await
+                    .map_err(|_| anyhow!("debug adapter shut down before initialization"))?;
+                this.update(&mut cx, |this, cx| this.send_all_breakpoints(cx))?
+                    .await?;
+                let supports_configuration_done = this.update(&mut cx, |this, _| {
+                    this.capabilities
+                        .supports_configuration_done_request
+                        .unwrap_or(false)
+                })?;
+                if supports_configuration_done {
+                    client
+                        .request::<ConfigurationDone>(ConfigurationDoneArguments)
+                        .await?;
+                }
+                anyhow::Ok(())
+            }
+        };
+        let (start, configure) = futures::join!(start, configure);
+        start?;
+        configure?;
+
+        Ok(this)
+    }
+
+    fn new(
+        id: SessionId,
+        config: DebugAdapterConfig,
+        client: Arc<DebugAdapterClient>,
+        capabilities: Capabilities,
+        breakpoint_store: Entity<BreakpointStore>,
+        initialized_subscription: dap::Subscription,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let this = cx.weak_entity();
+        let subscriptions = vec![
+            initialized_subscription,
+            client.on_event::<events::Stopped, _>({
+                let this = this.clone();
+                move |event, mut cx| {
+                    this.update(&mut cx, |this, cx| {
+                        this.handle_stopped(event.thread_id, event.all_threads_stopped, cx)
+                    })
+                    .ok();
+                }
+            }),
+            client.on_event::<events::Continued, _>({
+                let this = this.clone();
+                move |event, mut cx| {
+                    this.update(&mut cx, |this, cx| {
+                        let all_threads = event.all_threads_continued.unwrap_or(true);
+                        this.mark_running(Some(event.thread_id).filter(|_| !all_threads), cx)
+                    })
+                    .ok();
+                }
+            }),
+            client.on_event::<events::Thread, _>({
+                let this = this.clone();
+                move |event, mut cx| {
+                    this.update(&mut cx, |this, cx| {
+                        match event.reason {
+                            ThreadEventReason::Exited => {
+                                this.threads.remove(&event.thread_id);
+                                if this.active_thread == Some(event.thread_id) {
+                                    this.active_thread = None;
+                                }
+                            }
+                            _ => {
+                                this.threads.entry(event.thread_id).or_insert_with(|| {
+                                    ThreadState {
+                                        thread: Thread {
+                                            id: event.thread_id,
+                                            name: format!("Thread {}", event.thread_id),
+                                        },
+                                        status: ThreadStatus::Running,
+                                        stack_frames: Vec::new(),
+                                    }
+                                });
+                            }
+                        }
+                        cx.emit(SessionEvent::Threads);
+                        cx.notify();
+                    })
+                    .ok();
+                }
+            }),
+            client.on_event::<events::Output, _>({
+                let this = this.clone();
+                move |event, mut cx| {
+                    this.update(&mut cx, |this, cx| {
+                        this.output.push(event);
+                        cx.emit(SessionEvent::Output);
+                        cx.notify();
+                    })
+                    .ok();
+                }
+            }),
+            client.on_event::<events::Terminated, _>({
+                let this = this.clone();
+                move |_, cx| Self::defer_terminated(this.clone(), cx)
+            }),
+            client.on_event::<events::Exited, _>({
+                let this = this.clone();
+                move |_, cx| Self::defer_terminated(this.clone(), cx)
+            }),
+        ];
+
+        Self {
+            id,
+            config,
+            client,
+            capabilities,
+            threads: BTreeMap::default(),
+            active_thread: None,
+            output: Vec::new(),
+            terminated: false,
+            breakpoint_store,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    // Event handlers are invoked while the client's handlers are locked, and reacting to
+    // termination drops the subscriptions, so it has to happen outside of the handler.
+    fn defer_terminated(this: WeakEntity<Self>, cx: AsyncApp) {
+        cx.spawn(|mut cx| async move {
+            this.update(&mut cx, |this, cx| {
+                if !this.terminated {
+                    this.terminated = true;
+                    for thread in this.threads.values_mut() {
+                        thread.status = ThreadStatus::Exited;
+                        thread.stack_frames.clear();
+                    }
+                    cx.emit(SessionEvent::Terminated);
+                    cx.notify();
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    pub fn id(&self) -> SessionId {
+        self.id
+    }
+
+    pub fn config(&self) -> &DebugAdapterConfig {
+        &self.config
+    }
+
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    pub fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+
+    pub fn threads(&self) -> impl Iterator<Item = &ThreadState> {
+        self.threads.values()
+    }
+
+    pub fn thread(&self, thread_id: u64) -> Option<&ThreadState> {
+        self.threads.get(&thread_id)
+    }
+
+    /// The thread that last stopped, which stepping commands act on.
+    pub fn active_thread(&self) -> Option<u64> {
+        self.active_thread
+    }
+
+    pub fn set_active_thread(&mut self, thread_id: u64, cx: &mut Context<Self>) {
+        if self.threads.contains_key(&thread_id) {
+            self.active_thread = Some(thread_id);
+            cx.notify();
+        }
+    }
+
+    pub fn output(&self) -> &[OutputEvent] {
+        &self.output
+    }
+
+    fn handle_stopped(
+        &mut self,
+        thread_id: Option<u64>,
+        all_threads_stopped: Option<bool>,
+        cx: &mut Context<Self>,
+    ) {
+        let all_threads_stopped = all_threads_stopped.unwrap_or(false) || thread_id.is_none();
+        for (id, thread) in &mut self.threads {
+            if all_threads_stopped || Some(*id) == thread_id {
+                thread.status = ThreadStatus::Stopped;
+            }
+        }
+        if let Some(thread_id) = thread_id {
+            self.active_thread = Some(thread_id);
+        }
+        cx.emit(SessionEvent::Stopped(thread_id));
+        cx.notify();
+
+        let fetch_threads = self.client.request::<Threads>(());
+        cx.spawn(|this, mut cx| async move {
+            let threads = fetch_threads.await?.threads;
+            this.update(&mut cx, |this, cx| {
+                let mut previous = std::mem::take(&mut this.threads);
+                for thread in threads {
+                    let status = if all_threads_stopped || Some(thread.id) == thread_id {
+                        ThreadStatus::Stopped
+                    } else {
+                        previous
+                            .remove(&thread.id)
+                            .map_or(ThreadStatus::Running, |state| state.status)
+                    };
+                    this.threads.insert(
+                        thread.id,
+                        ThreadState {
+                            thread,
+                            status,
+                            stack_frames: Vec::new(),
+                        },
+                    );
+                }
+                if this
+                    .active_thread
+                    .map_or(true, |id| !this.threads.contains_key(&id))
+                {
+                    this.active_thread = this.threads.keys().next().copied();
+                }
+                cx.emit(SessionEvent::Threads);
+                cx.notify();
+
+                this.active_thread
+                    .map(|thread_id| this.fetch_stack_frames(thread_id, cx))
+            })?
+            .unwrap_or_else(|| Task::ready(Ok(())))
+            .await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn mark_running(&mut self, thread_id: Option<u64>, cx: &mut Context<Self>) {
+        for (id, thread) in &mut self.threads {
+            if thread_id.map_or(true, |thread_id| thread_id == *id) {
+                thread.status = ThreadStatus::Running;
+                thread.stack_frames.clear();
+            }
+        }
+        cx.emit(SessionEvent::Continued);
+        cx.notify();
+    }
+
+    /// Fetches the call stack of a stopped thread.
+    pub fn fetch_stack_frames(
+        &mut self,
+        thread_id: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let request = self.client.request::<StackTrace>(StackTraceArguments {
+            thread_id,
+            start_frame: None,
+            levels: None,
+            format: None,
+        });
+        cx.spawn(|this, mut cx| async move {
+            let stack_frames = request.await?.stack_frames;
+            this.update(&mut cx, |this, cx| {
+                if let Some(thread) = this.threads.get_mut(&thread_id) {
+                    if thread.status == ThreadStatus::Stopped {
+                        thread.stack_frames = stack_frames;
+                        cx.emit(SessionEvent::StackFrames(thread_id));
+                        cx.notify();
+                    }
+                }
+            })
+        })
+    }
+
+    pub fn scopes(&self, frame_id: u64, cx: &App) -> Task<Result<Vec<Scope>>> {
+        let request = self.client.request::<Scopes>(ScopesArguments { frame_id });
+        cx.background_spawn(async move { Ok(request.await?.scopes) })
+    }
+
+    pub fn variables(&self, variables_reference: u64, cx: &App) -> Task<Result<Vec<Variable>>> {
+        let request = self.client.request::<Variables>(VariablesArguments {
+            variables_reference,
+            filter: None,
+            start: None,
+            count: None,
+            format: None,
+        });
+        cx.background_spawn(async move { Ok(request.await?.variables) })
+    }
+
+    /// Evaluates an expression typed into the debug console, in the context of the given frame.
+    pub fn evaluate(
+        &mut self,
+        expression: String,
+        frame_id: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<EvaluateResponse>> {
+        let request = self.client.request::<Evaluate>(EvaluateArguments {
+            expression,
+            frame_id,
+            context: Some(EvaluateArgumentsContext::Repl),
+            format: None,
+        });
+        cx.background_spawn(request)
+    }
+
+    fn step_thread(&self) -> Result<u64> {
+        self.active_thread
+            .ok_or_else(|| anyhow!("no thread is stopped"))
+    }
+
+    pub fn continue_thread(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let Some(thread_id) = self
+            .active_thread
+            .or_else(|| self.threads.keys().next().copied())
+        else {
+            return Task::ready(Err(anyhow!("debuggee has no threads")));
+        };
+        let request = self.client.request::<Continue>(ContinueArguments {
+            thread_id,
+            single_thread: None,
+        });
+        cx.spawn(|this, mut cx| async move {
+            let response = request.await?;
+            this.update(&mut cx, |this, cx| {
+                let all_threads = response.all_threads_continued.unwrap_or(true);
+                this.mark_running(Some(thread_id).filter(|_| !all_threads), cx)
+            })
+        })
+    }
+
+    pub fn step_over(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let thread_id = match self.step_thread() {
+            Ok(thread_id) => thread_id,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let request = self.client.request::<Next>(NextArguments {
+            thread_id,
+            single_thread: None,
+            granularity: None,
+        });
+        self.resume_after(request, thread_id, cx)
+    }
+
+    pub fn step_in(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let thread_id = match self.step_thread() {
+            Ok(thread_id) => thread_id,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let request = self.client.request::<StepIn>(StepInArguments {
+            thread_id,
+            single_thread: None,
+            target_id: None,
+            granularity: None,
+        });
+        self.resume_after(request, thread_id, cx)
+    }
+
+    pub fn step_out(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let thread_id = match self.step_thread() {
+            Ok(thread_id) => thread_id,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let request = self.client.request::<StepOut>(StepOutArguments {
+            thread_id,
+            single_thread: None,
+            granularity: None,
+        });
+        self.resume_after(request, thread_id, cx)
+    }
+
+    fn resume_after(
+        &mut self,
+        request: impl 'static + std::future::Future<Output = Result<()>>,
+        thread_id: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        cx.spawn(|this, mut cx| async move {
+            request.await?;
+            this.update(&mut cx, |this, cx| this.mark_running(Some(thread_id), cx))
+        })
+    }
+
+    pub fn pause(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let Some(thread_id) = self
+            .active_thread
+            .or_else(|| self.threads.keys().next().copied())
+        else {
+            return Task::ready(Err(anyhow!("debuggee has no threads")));
+        };
+        let request = self.client.request::<Pause>(PauseArguments { thread_id });
+        cx.background_spawn(request)
+    }
+
+    /// Sends the breakpoints of the file at `abs_path` to the adapter, replacing the previous ones.
+    pub fn send_breakpoints(
+        &mut self,
+        abs_path: Arc<Path>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if self.terminated {
+            return Task::ready(Ok(()));
+        }
+        let breakpoints = self
+            .breakpoint_store
+            .read(cx)
+            .source_breakpoints(&abs_path, cx);
+        let request = self
+            .client
+            .request::<SetBreakpoints>(SetBreakpointsArguments {
+                source: Source {
+                    name: abs_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned()),
+                    path: Some(abs_path.to_string_lossy().into_owned()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                },
+                breakpoints: Some(breakpoints),
+                lines: None,
+                source_modified: Some(false),
+            });
+        cx.background_spawn(async move {
+            request.await?;
+            Ok(())
+        })
+    }
+
+    fn send_all_breakpoints(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let paths = self
+            .breakpoint_store
+            .read(cx)
+            .breakpoint_paths()
+            .cloned()
+            .collect::<Vec<_>>();
+        let tasks = paths
+            .into_iter()
+            .map(|abs_path| self.send_breakpoints(abs_path, cx))
+            .collect::<Vec<_>>();
+        cx.background_spawn(async move {
+            for result in futures::future::join_all(tasks).await {
+                result.log_err();
+            }
+            Ok(())
+        })
+    }
+
+    /// Disconnects from the adapter, terminating the debuggee if it was launched by this session.
+    pub fn shutdown(&mut self, cx: &mut Context<Self>) -> Task<()> {
+        self.terminated = true;
+        self.threads.clear();
+        self.active_thread = None;
+        cx.notify();
+
+        let terminate_debuggee = self.config.request == DebugRequestType::Launch;
+        match self.client.shutdown(terminate_debuggee) {
+            Some(shutdown) => cx.background_spawn(async move {
+                shutdown.await;
+            }),
+            None => Task::ready(()),
+        }
+    }
+}
@@ -17,10 +17,12 @@ use futures::{
 use git::repository::DiffType;
 use git::{
     repository::{
-        Branch, CommitDetails, GitRepository, PushOptions, Remote, RemoteCommandOutput, RepoPath,
-        ResetMode,
+        Branch, CommitDetails, CommitDiff, CommitFile, ConflictVersions, GitRepository,
+        GitWorktree, LogEntry, LogOptions, PushOptions, RebaseAction, RebaseTodoEntry, Remote,
+        RemoteCommandOutput, RepoPath, ResetMode, RevisionDiff, RevisionDiffEntry,
+        SequencerOperation, StashEntry, Submodule, SubmoduleStatus, Tag,
     },
-    status::FileStatus,
+    status::{FileStatus, StatusCode},
 };
 use gpui::{
     App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
@@ -211,6 +213,18 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_get_branches);
         client.add_entity_request_handler(Self::handle_change_branch);
         client.add_entity_request_handler(Self::handle_create_branch);
+        client.add_entity_request_handler(Self::handle_delete_branch);
+        client.add_entity_request_handler(Self::handle_rename_branch);
+        client.add_entity_request_handler(Self::handle_set_upstream_branch);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_get_submodules);
+        client.add_entity_request_handler(Self::handle_update_submodules);
+        client.add_entity_request_handler(Self::handle_get_worktrees);
+        client.add_entity_request_handler(Self::handle_create_worktree);
+        client.add_entity_request_handler(Self::handle_get_remote_url);
         client.add_entity_request_handler(Self::handle_git_init);
         client.add_entity_request_handler(Self::handle_push);
         client.add_entity_request_handler(Self::handle_pull);
@@ -220,12 +234,30 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_reset);
         client.add_entity_request_handler(Self::handle_show);
+        client.add_entity_request_handler(Self::handle_log);
+        client.add_entity_request_handler(Self::handle_load_commit_diff);
+        client.add_entity_request_handler(Self::handle_load_conflict_versions);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_rebase_todo);
+        client.add_entity_request_handler(Self::handle_rebase);
+        client.add_entity_request_handler(Self::handle_get_sequencer_operation);
+        client.add_entity_request_handler(Self::handle_continue_operation);
+        client.add_entity_request_handler(Self::handle_abort_operation);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
         client.add_entity_request_handler(Self::handle_askpass);
         client.add_entity_request_handler(Self::handle_check_for_pushed_commits);
         client.add_entity_request_handler(Self::handle_git_diff);
+        client.add_entity_request_handler(Self::handle_diff_since);
+        client.add_entity_request_handler(Self::handle_load_revision_text);
+        client.add_entity_request_handler(Self::handle_stash_push);
+        client.add_entity_request_handler(Self::handle_stash_list);
+        client.add_entity_request_handler(Self::handle_stash_show);
+        client.add_entity_request_handler(Self::handle_stash_apply);
+        client.add_entity_request_handler(Self::handle_stash_pop);
+        client.add_entity_request_handler(Self::handle_stash_drop);
     }
 
     pub fn active_repository(&self) -> Option<Entity<Repository>> {
@@ -435,7 +467,7 @@ impl GitStore {
         Some(status.status)
     }
 
-    fn repository_and_path_for_buffer_id(
+    pub fn repository_and_path_for_buffer_id(
         &self,
         buffer_id: BufferId,
         cx: &App,
@@ -835,33 +867,29 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
-    async fn handle_show(
+    async fn handle_delete_branch(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitShow>,
+        envelope: TypedEnvelope<proto::GitDeleteBranch>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GitCommitDetails> {
+    ) -> Result<proto::Ack> {
         let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
         let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
         let repository_handle =
             Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
 
-        let commit = repository_handle
+        repository_handle
             .update(&mut cx, |repository_handle, _| {
-                repository_handle.show(envelope.payload.commit)
+                repository_handle
+                    .delete_branch(envelope.payload.branch_name, envelope.payload.force)
             })?
             .await??;
-        Ok(proto::GitCommitDetails {
-            sha: commit.sha.into(),
-            message: commit.message.into(),
-            commit_timestamp: commit.commit_timestamp,
-            committer_email: commit.committer_email.into(),
-            committer_name: commit.committer_name.into(),
-        })
+
+        Ok(proto::Ack {})
     }
 
-    async fn handle_reset(
+    async fn handle_rename_branch(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitReset>,
+        envelope: TypedEnvelope<proto::GitRenameBranch>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
@@ -869,232 +897,1052 @@ impl GitStore {
         let repository_handle =
             Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
 
-        let mode = match envelope.payload.mode() {
-            git_reset::ResetMode::Soft => ResetMode::Soft,
-            git_reset::ResetMode::Mixed => ResetMode::Mixed,
-        };
-
         repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.reset(envelope.payload.commit, mode, cx)
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle
+                    .rename_branch(envelope.payload.branch_name, envelope.payload.new_name)
             })?
             .await??;
+
         Ok(proto::Ack {})
     }
 
-    async fn handle_checkout_files(
+    async fn handle_set_upstream_branch(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        envelope: TypedEnvelope<proto::GitSetUpstreamBranch>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
         let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
         let repository_handle =
             Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
-        let paths = envelope
-            .payload
-            .paths
-            .iter()
-            .map(|s| RepoPath::from_str(s))
-            .collect();
 
         repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle
+                    .set_upstream_branch(envelope.payload.branch_name, envelope.payload.upstream)
             })?
             .await??;
+
         Ok(proto::Ack {})
     }
 
-    async fn handle_open_commit_message_buffer(
+    async fn handle_get_tags(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenBufferResponse> {
+    ) -> Result<proto::GitTagsResponse> {
         let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
         let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
-        let repository =
+        let repository_handle =
             Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
-        let buffer = repository
-            .update(&mut cx, |repository, cx| {
-                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
-            })?
-            .await?;
 
-        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
-        this.update(&mut cx, |this, cx| {
-            this.buffer_store.update(cx, |buffer_store, cx| {
-                buffer_store
-                    .create_buffer_for_peer(
-                        &buffer,
-                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
-                        cx,
-                    )
-                    .detach_and_log_err(cx);
-            })
-        })?;
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
 
-        Ok(proto::OpenBufferResponse {
-            buffer_id: buffer_id.to_proto(),
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
         })
     }
 
-    async fn handle_askpass(
+    async fn handle_create_tag(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::AskPassRequest>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
         mut cx: AsyncApp,
-    ) -> Result<proto::AskPassResponse> {
+    ) -> Result<proto::Ack> {
         let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
         let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
-        let repository =
+        let repository_handle =
             Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
 
-        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
-        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
-            debug_panic!("no askpass found");
-            return Err(anyhow::anyhow!("no askpass found"));
-        };
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(
+                    envelope.payload.tag_name,
+                    envelope.payload.target,
+                    envelope.payload.message,
+                )
+            })?
+            .await??;
 
-        let response = askpass.ask_password(envelope.payload.prompt).await?;
+        Ok(proto::Ack {})
+    }
 
-        delegates
-            .lock()
-            .insert(envelope.payload.askpass_id, askpass);
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
 
-        Ok(proto::AskPassResponse { response })
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(envelope.payload.tag_name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
     }
 
-    async fn handle_check_for_pushed_commits(
+    async fn handle_push_tag(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
         mut cx: AsyncApp,
-    ) -> Result<proto::CheckForPushedCommitsResponse> {
+    ) -> Result<proto::RemoteMessageResponse> {
         let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
         let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
         let repository_handle =
             Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
 
-        let branches = repository_handle
-            .update(&mut cx, |repository_handle, _| {
-                repository_handle.check_for_pushed_commits()
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            worktree_id,
+            work_directory_id,
+            envelope.payload.askpass_id,
+            &mut cx,
+        );
+
+        let tag_name = envelope.payload.tag_name.into();
+        let remote_name = envelope.payload.remote_name.into();
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(tag_name, remote_name, askpass, cx)
             })?
             .await??;
-        Ok(proto::CheckForPushedCommitsResponse {
-            pushed_to: branches
-                .into_iter()
-                .map(|commit| commit.to_string())
-                .collect(),
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
         })
     }
 
-    async fn handle_git_diff(
+    async fn handle_get_submodules(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitDiff>,
+        envelope: TypedEnvelope<proto::GitGetSubmodules>,
         mut cx: AsyncApp,
-    ) -> Result<proto::GitDiffResponse> {
+    ) -> Result<proto::GitSubmodulesResponse> {
         let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
         let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
         let repository_handle =
             Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
-        let diff_type = match envelope.payload.diff_type() {
-            proto::git_diff::DiffType::HeadToIndex => DiffType::HeadToIndex,
-            proto::git_diff::DiffType::HeadToWorktree => DiffType::HeadToWorktree,
-        };
 
-        let mut diff = repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.diff(diff_type, cx)
+        let submodules = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodules()
             })?
             .await??;
-        const ONE_MB: usize = 1_000_000;
-        if diff.len() > ONE_MB {
-            diff = diff.chars().take(ONE_MB).collect()
-        }
 
-        Ok(proto::GitDiffResponse { diff })
+        Ok(proto::GitSubmodulesResponse {
+            submodules: submodules.into_iter().map(submodule_to_proto).collect(),
+        })
     }
 
-    fn repository_for_request(
-        this: &Entity<Self>,
-        worktree_id: WorktreeId,
-        work_directory_id: ProjectEntryId,
-        cx: &mut AsyncApp,
-    ) -> Result<Entity<Repository>> {
-        this.update(cx, |this, cx| {
-            this.repositories
-                .iter()
-                .find(|repository_handle| {
-                    repository_handle.read(cx).worktree_id == worktree_id
-                        && repository_handle
-                            .read(cx)
-                            .repository_entry
-                            .work_directory_id()
-                            == work_directory_id
-                })
-                .context("missing repository handle")
-                .cloned()
-        })?
-    }
-}
+    async fn handle_update_submodules(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitUpdateSubmodules>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
 
-fn make_remote_delegate(
-    this: Entity<GitStore>,
-    project_id: u64,
-    worktree_id: WorktreeId,
-    work_directory_id: ProjectEntryId,
-    askpass_id: u64,
-    cx: &mut AsyncApp,
-) -> AskPassDelegate {
-    AskPassDelegate::new(cx, move |prompt, tx, cx| {
-        this.update(cx, |this, cx| {
-            let response = this.client().request(proto::AskPassRequest {
-                project_id,
-                worktree_id: worktree_id.to_proto(),
-                work_directory_id: work_directory_id.to_proto(),
-                askpass_id,
-                prompt,
-            });
-            cx.spawn(|_, _| async move {
-                tx.send(response.await?.response).ok();
-                anyhow::Ok(())
-            })
-            .detach_and_log_err(cx);
-        })
-        .log_err();
-    })
-}
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            worktree_id,
+            work_directory_id,
+            envelope.payload.askpass_id,
+            &mut cx,
+        );
 
-impl GitRepo {}
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_str(path))
+            .collect();
 
-impl Repository {
-    pub fn git_store(&self) -> Option<Entity<GitStore>> {
-        self.git_store.upgrade()
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.update_submodules(paths, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
     }
 
-    fn id(&self) -> (WorktreeId, ProjectEntryId) {
-        (self.worktree_id, self.repository_entry.work_directory_id())
-    }
+    async fn handle_get_worktrees(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetWorktrees>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitWorktreesResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
 
-    pub fn current_branch(&self) -> Option<&Branch> {
-        self.repository_entry.branch()
-    }
+        let worktrees = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.worktrees()
+            })?
+            .await??;
 
-    fn send_job<F, Fut, R>(&self, job: F) -> oneshot::Receiver<R>
-    where
-        F: FnOnce(GitRepo, AsyncApp) -> Fut + 'static,
-        Fut: Future<Output = R> + 'static,
-        R: Send + 'static,
-    {
-        self.send_keyed_job(None, job)
+        Ok(proto::GitWorktreesResponse {
+            worktrees: worktrees
+                .into_iter()
+                .map(|worktree| proto::git_worktrees_response::Worktree {
+                    path: worktree.path.to_string_lossy().to_string(),
+                    sha: worktree.sha.to_string(),
+                    branch: worktree.branch.map(|branch| branch.to_string()),
+                    is_main: worktree.is_main,
+                })
+                .collect(),
+        })
     }
 
-    fn send_keyed_job<F, Fut, R>(&self, key: Option<GitJobKey>, job: F) -> oneshot::Receiver<R>
-    where
-        F: FnOnce(GitRepo, AsyncApp) -> Fut + 'static,
-        Fut: Future<Output = R> + 'static,
-        R: Send + 'static,
-    {
-        let (result_tx, result_rx) = futures::channel::oneshot::channel();
+    async fn handle_create_worktree(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateWorktree>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_worktree(
+                    PathBuf::from(envelope.payload.path),
+                    envelope.payload.branch,
+                    envelope.payload.create_branch,
+                )
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_remote_url(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetRemoteUrl>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitGetRemoteUrlResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let url = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.remote_url(envelope.payload.remote_name)
+            })?
+            .await??;
+
+        Ok(proto::GitGetRemoteUrlResponse { url })
+    }
+
+    async fn handle_show(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitShow>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCommitDetails> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let commit = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.show(envelope.payload.commit)
+            })?
+            .await??;
+        Ok(proto::GitCommitDetails {
+            sha: commit.sha.into(),
+            message: commit.message.into(),
+            commit_timestamp: commit.commit_timestamp,
+            committer_email: commit.committer_email.into(),
+            committer_name: commit.committer_name.into(),
+        })
+    }
+
+    async fn handle_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLogResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+        let options = LogOptions {
+            skip: envelope.payload.skip as usize,
+            limit: envelope.payload.limit as usize,
+            path: envelope.payload.path.as_deref().map(RepoPath::from_str),
+            follow_renames: envelope.payload.follow_renames,
+        };
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.log(options)
+            })?
+            .await??;
+        Ok(proto::GitLogResponse {
+            entries: entries
+                .into_iter()
+                .map(|entry| proto::GitLogEntry {
+                    sha: entry.sha.into(),
+                    parents: entry.parents.into_iter().map(Into::into).collect(),
+                    subject: entry.subject.into(),
+                    author_name: entry.author_name.into(),
+                    author_email: entry.author_email.into(),
+                    commit_timestamp: entry.commit_timestamp,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_load_commit_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::LoadCommitDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::LoadCommitDiffResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let commit_diff = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_commit_diff(envelope.payload.commit)
+            })?
+            .await??;
+        Ok(proto::LoadCommitDiffResponse {
+            files: commit_diff
+                .files
+                .into_iter()
+                .map(|file| proto::CommitFile {
+                    path: file.path.to_string_lossy().to_string(),
+                    old_text: file.old_text,
+                    new_text: file.new_text,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_load_conflict_versions(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::LoadConflictVersions>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::LoadConflictVersionsResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let versions = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_conflict_versions(RepoPath::from_str(&envelope.payload.path))
+            })?
+            .await??;
+        Ok(proto::LoadConflictVersionsResponse {
+            base: versions.base,
+            ours: versions.ours,
+            theirs: versions.theirs,
+        })
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.cherry_pick(envelope.payload.commits, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commits, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    async fn handle_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(envelope.payload.onto)
+            })?
+            .await??;
+        Ok(proto::GitRebaseTodoResponse {
+            entries: entries
+                .into_iter()
+                .map(rebase_todo_entry_to_proto)
+                .collect(),
+        })
+    }
+
+    async fn handle_rebase(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebase>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let todo = envelope
+            .payload
+            .todo
+            .into_iter()
+            .map(rebase_todo_entry_from_proto)
+            .collect();
+
+        let output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase(envelope.payload.onto, todo, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    async fn handle_get_sequencer_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetSequencerOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitGetSequencerOperationResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let operation = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.sequencer_operation()
+            })?
+            .await??;
+        Ok(proto::GitGetSequencerOperationResponse {
+            operation: operation.map(|operation| {
+                use proto::git_get_sequencer_operation_response::Operation;
+                let operation = match operation {
+                    SequencerOperation::CherryPick => Operation::CherryPick,
+                    SequencerOperation::Revert => Operation::Revert,
+                    SequencerOperation::Rebase => Operation::Rebase,
+                };
+                operation as i32
+            }),
+        })
+    }
+
+    async fn handle_continue_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitContinueOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.continue_operation(cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    async fn handle_abort_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitAbortOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.abort_operation(cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+
+    async fn handle_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let mode = match envelope.payload.mode() {
+            git_reset::ResetMode::Soft => ResetMode::Soft,
+            git_reset::ResetMode::Mixed => ResetMode::Mixed,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.reset(envelope.payload.commit, mode, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_str(s))
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_open_commit_message_buffer(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenBufferResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+        let buffer = repository
+            .update(&mut cx, |repository, cx| {
+                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+            })?
+            .await?;
+
+        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
+        this.update(&mut cx, |this, cx| {
+            this.buffer_store.update(cx, |buffer_store, cx| {
+                buffer_store
+                    .create_buffer_for_peer(
+                        &buffer,
+                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+            })
+        })?;
+
+        Ok(proto::OpenBufferResponse {
+            buffer_id: buffer_id.to_proto(),
+        })
+    }
+
+    async fn handle_askpass(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::AskPassRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::AskPassResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
+        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
+            debug_panic!("no askpass found");
+            return Err(anyhow::anyhow!("no askpass found"));
+        };
+
+        let response = askpass.ask_password(envelope.payload.prompt).await?;
+
+        delegates
+            .lock()
+            .insert(envelope.payload.askpass_id, askpass);
+
+        Ok(proto::AskPassResponse { response })
+    }
+
+    async fn handle_check_for_pushed_commits(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::CheckForPushedCommits>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::CheckForPushedCommitsResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let branches = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.check_for_pushed_commits()
+            })?
+            .await??;
+        Ok(proto::CheckForPushedCommitsResponse {
+            pushed_to: branches
+                .into_iter()
+                .map(|commit| commit.to_string())
+                .collect(),
+        })
+    }
+
+    async fn handle_git_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitDiffResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+        let diff_type = match envelope.payload.diff_type() {
+            proto::git_diff::DiffType::HeadToIndex => DiffType::HeadToIndex,
+            proto::git_diff::DiffType::HeadToWorktree => DiffType::HeadToWorktree,
+            proto::git_diff::DiffType::RevisionToWorktree => DiffType::RevisionToWorktree(
+                envelope
+                    .payload
+                    .revision
+                    .context("missing revision to diff against")?,
+            ),
+        };
+
+        let mut diff = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.diff(diff_type, cx)
+            })?
+            .await??;
+        const ONE_MB: usize = 1_000_000;
+        if diff.len() > ONE_MB {
+            diff = diff.chars().take(ONE_MB).collect()
+        }
+
+        Ok(proto::GitDiffResponse { diff })
+    }
+
+    async fn handle_diff_since(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDiffSince>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitDiffSinceResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let diff = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.diff_since(envelope.payload.revision)
+            })?
+            .await??;
+        Ok(proto::GitDiffSinceResponse {
+            base: diff.base.to_string(),
+            entries: diff
+                .entries
+                .into_iter()
+                .map(|entry| proto::git_diff_since_response::Entry {
+                    path: entry.path.to_string_lossy().to_string(),
+                    status: match entry.status {
+                        StatusCode::Added => proto::GitStatus::Added,
+                        StatusCode::Deleted => proto::GitStatus::Deleted,
+                        StatusCode::TypeChanged => proto::GitStatus::TypeChanged,
+                        _ => proto::GitStatus::Modified,
+                    } as i32,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_load_revision_text(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLoadRevisionText>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLoadRevisionTextResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let text = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_revision_text(
+                    envelope.payload.revision,
+                    RepoPath::from_str(&envelope.payload.path),
+                )
+            })?
+            .await??;
+        Ok(proto::GitLoadRevisionTextResponse { text })
+    }
+
+    async fn handle_stash_push(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashPush>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_str(s))
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_push(
+                    envelope.payload.message,
+                    paths,
+                    envelope.payload.include_untracked,
+                    cx,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_list(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashList>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitStashListResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_list()
+            })?
+            .await??;
+        Ok(proto::GitStashListResponse {
+            entries: entries
+                .into_iter()
+                .map(|entry| proto::GitStashEntry {
+                    index: entry.index as u64,
+                    sha: entry.sha.into(),
+                    message: entry.message.into(),
+                    commit_timestamp: entry.commit_timestamp,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_stash_show(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashShow>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitStashShowResponse> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        let mut diff = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_show(envelope.payload.index as usize)
+            })?
+            .await??;
+        const ONE_MB: usize = 1_000_000;
+        if diff.len() > ONE_MB {
+            diff = diff.chars().take(ONE_MB).collect()
+        }
+
+        Ok(proto::GitStashShowResponse { diff })
+    }
+
+    async fn handle_stash_apply(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashApply>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_apply(
+                    envelope.payload.index as usize,
+                    envelope.payload.sha,
+                    cx,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_pop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashPop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_pop(
+                    envelope.payload.index as usize,
+                    envelope.payload.sha,
+                    cx,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_drop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashDrop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
+        let work_directory_id = ProjectEntryId::from_proto(envelope.payload.work_directory_id);
+        let repository_handle =
+            Self::repository_for_request(&this, worktree_id, work_directory_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_drop(
+                    envelope.payload.index as usize,
+                    envelope.payload.sha,
+                    cx,
+                )
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    fn repository_for_request(
+        this: &Entity<Self>,
+        worktree_id: WorktreeId,
+        work_directory_id: ProjectEntryId,
+        cx: &mut AsyncApp,
+    ) -> Result<Entity<Repository>> {
+        this.update(cx, |this, cx| {
+            this.repositories
+                .iter()
+                .find(|repository_handle| {
+                    repository_handle.read(cx).worktree_id == worktree_id
+                        && repository_handle
+                            .read(cx)
+                            .repository_entry
+                            .work_directory_id()
+                            == work_directory_id
+                })
+                .context("missing repository handle")
+                .cloned()
+        })?
+    }
+}
+
+fn rebase_todo_entry_to_proto(entry: RebaseTodoEntry) -> proto::RebaseTodoEntry {
+    use proto::rebase_todo_entry::Action;
+    proto::RebaseTodoEntry {
+        action: match entry.action {
+            RebaseAction::Pick => Action::Pick,
+            RebaseAction::Reword => Action::Reword,
+            RebaseAction::Squash => Action::Squash,
+            RebaseAction::Fixup => Action::Fixup,
+            RebaseAction::Drop => Action::Drop,
+        } as i32,
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message,
+    }
+}
+
+fn rebase_todo_entry_from_proto(entry: proto::RebaseTodoEntry) -> RebaseTodoEntry {
+    use proto::rebase_todo_entry::Action;
+    RebaseTodoEntry {
+        action: match entry.action() {
+            Action::Pick => RebaseAction::Pick,
+            Action::Reword => RebaseAction::Reword,
+            Action::Squash => RebaseAction::Squash,
+            Action::Fixup => RebaseAction::Fixup,
+            Action::Drop => RebaseAction::Drop,
+        },
+        sha: entry.sha.into(),
+        subject: entry.subject.into(),
+        message: entry.message,
+    }
+}
+
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        subject: tag.subject.to_string(),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(tag: proto::GitTag) -> Tag {
+    Tag {
+        name: tag.name.into(),
+        sha: tag.sha.into(),
+        subject: tag.subject.into(),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn submodule_to_proto(submodule: Submodule) -> proto::git_submodules_response::Submodule {
+    use proto::git_submodules_response::Status;
+    proto::git_submodules_response::Submodule {
+        path: submodule.path.as_ref().to_proto(),
+        sha: submodule.sha.to_string(),
+        status: match submodule.status {
+            SubmoduleStatus::Uninitialized => Status::Uninitialized,
+            SubmoduleStatus::UpToDate => Status::UpToDate,
+            SubmoduleStatus::OutOfDate => Status::OutOfDate,
+            SubmoduleStatus::Conflicted => Status::Conflicted,
+        } as i32,
+    }
+}
+
+fn submodule_from_proto(submodule: proto::git_submodules_response::Submodule) -> Submodule {
+    use proto::git_submodules_response::Status;
+    Submodule {
+        path: RepoPath::from_str(&submodule.path),
+        sha: submodule.sha.into(),
+        status: match submodule.status() {
+            Status::Uninitialized => SubmoduleStatus::Uninitialized,
+            Status::UpToDate => SubmoduleStatus::UpToDate,
+            Status::OutOfDate => SubmoduleStatus::OutOfDate,
+            Status::Conflicted => SubmoduleStatus::Conflicted,
+        },
+    }
+}
+
+fn make_remote_delegate(
+    this: Entity<GitStore>,
+    project_id: u64,
+    worktree_id: WorktreeId,
+    work_directory_id: ProjectEntryId,
+    askpass_id: u64,
+    cx: &mut AsyncApp,
+) -> AskPassDelegate {
+    AskPassDelegate::new(cx, move |prompt, tx, cx| {
+        this.update(cx, |this, cx| {
+            let response = this.client().request(proto::AskPassRequest {
+                project_id,
+                worktree_id: worktree_id.to_proto(),
+                work_directory_id: work_directory_id.to_proto(),
+                askpass_id,
+                prompt,
+            });
+            cx.spawn(|_, _| async move {
+                tx.send(response.await?.response).ok();
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        })
+        .log_err();
+    })
+}
+
+impl GitRepo {}
+
+impl Repository {
+    pub fn git_store(&self) -> Option<Entity<GitStore>> {
+        self.git_store.upgrade()
+    }
+
+    fn id(&self) -> (WorktreeId, ProjectEntryId) {
+        (self.worktree_id, self.repository_entry.work_directory_id())
+    }
+
+    pub fn current_branch(&self) -> Option<&Branch> {
+        self.repository_entry.branch()
+    }
+
+    fn send_job<F, Fut, R>(&self, job: F) -> oneshot::Receiver<R>
+    where
+        F: FnOnce(GitRepo, AsyncApp) -> Fut + 'static,
+        Fut: Future<Output = R> + 'static,
+        R: Send + 'static,
+    {
+        self.send_keyed_job(None, job)
+    }
+
+    fn send_keyed_job<F, Fut, R>(&self, key: Option<GitJobKey>, job: F) -> oneshot::Receiver<R>
+    where
+        F: FnOnce(GitRepo, AsyncApp) -> Fut + 'static,
+        Fut: Future<Output = R> + 'static,
+        R: Send + 'static,
+    {
+        let (result_tx, result_rx) = futures::channel::oneshot::channel();
         let git_repo = self.git_repo.clone();
         self.job_sender
             .unbounded_send(GitJob {
@@ -1105,492 +1953,1365 @@ impl Repository {
                         let result = job.await;
                         result_tx.send(result).ok();
                     })
-                }),
+                }),
+            })
+            .ok();
+        result_rx
+    }
+
+    pub fn display_name(&self, project: &Project, cx: &App) -> SharedString {
+        maybe!({
+            let project_path = self.repo_path_to_project_path(&"".into())?;
+            let worktree_name = project
+                .worktree_for_id(project_path.worktree_id, cx)?
+                .read(cx)
+                .root_name();
+
+            let mut path = PathBuf::new();
+            path = path.join(worktree_name);
+            path = path.join(project_path.path);
+            Some(path.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| self.repository_entry.work_directory.display_name())
+        .into()
+    }
+
+    pub fn activate(&self, cx: &mut Context<Self>) {
+        let Some(git_store) = self.git_store.upgrade() else {
+            return;
+        };
+        let entity = cx.entity();
+        git_store.update(cx, |git_store, cx| {
+            let Some(index) = git_store
+                .repositories
+                .iter()
+                .position(|handle| *handle == entity)
+            else {
+                return;
+            };
+            git_store.active_index = Some(index);
+            cx.emit(GitEvent::ActiveRepositoryChanged);
+        });
+    }
+
+    pub fn status(&self) -> impl '_ + Iterator<Item = StatusEntry> {
+        self.repository_entry.status()
+    }
+
+    pub fn has_conflict(&self, path: &RepoPath) -> bool {
+        self.repository_entry
+            .current_merge_conflicts
+            .contains(&path)
+    }
+
+    pub fn repo_path_to_project_path(&self, path: &RepoPath) -> Option<ProjectPath> {
+        let path = self.repository_entry.try_unrelativize(path)?;
+        Some((self.worktree_id, path).into())
+    }
+
+    pub fn project_path_to_repo_path(&self, path: &ProjectPath) -> Option<RepoPath> {
+        self.worktree_id_path_to_repo_path(path.worktree_id, &path.path)
+    }
+
+    // note: callers must verify these come from the same worktree
+    pub fn contains_sub_repo(&self, other: &Entity<Self>, cx: &App) -> bool {
+        let other_work_dir = &other.read(cx).repository_entry.work_directory;
+        match (&self.repository_entry.work_directory, other_work_dir) {
+            (WorkDirectory::InProject { .. }, WorkDirectory::AboveProject { .. }) => false,
+            (WorkDirectory::AboveProject { .. }, WorkDirectory::InProject { .. }) => true,
+            (
+                WorkDirectory::InProject {
+                    relative_path: this_path,
+                },
+                WorkDirectory::InProject {
+                    relative_path: other_path,
+                },
+            ) => other_path.starts_with(this_path),
+            (
+                WorkDirectory::AboveProject {
+                    absolute_path: this_path,
+                    ..
+                },
+                WorkDirectory::AboveProject {
+                    absolute_path: other_path,
+                    ..
+                },
+            ) => other_path.starts_with(this_path),
+        }
+    }
+
+    pub fn worktree_id_path_to_repo_path(
+        &self,
+        worktree_id: WorktreeId,
+        path: &Path,
+    ) -> Option<RepoPath> {
+        if worktree_id != self.worktree_id {
+            return None;
+        }
+        self.repository_entry.relativize(path).log_err()
+    }
+
+    pub fn open_commit_buffer(
+        &mut self,
+        languages: Option<Arc<LanguageRegistry>>,
+        buffer_store: Entity<BufferStore>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Buffer>>> {
+        if let Some(buffer) = self.commit_message_buffer.clone() {
+            return Task::ready(Ok(buffer));
+        }
+
+        if let GitRepo::Remote {
+            project_id,
+            client,
+            worktree_id,
+            work_directory_id,
+        } = self.git_repo.clone()
+        {
+            let client = client.clone();
+            cx.spawn(|repository, mut cx| async move {
+                let request = client.request(proto::OpenCommitMessageBuffer {
+                    project_id: project_id.0,
+                    worktree_id: worktree_id.to_proto(),
+                    work_directory_id: work_directory_id.to_proto(),
+                });
+                let response = request.await.context("requesting to open commit buffer")?;
+                let buffer_id = BufferId::new(response.buffer_id)?;
+                let buffer = buffer_store
+                    .update(&mut cx, |buffer_store, cx| {
+                        buffer_store.wait_for_remote_buffer(buffer_id, cx)
+                    })?
+                    .await?;
+                if let Some(language_registry) = languages {
+                    let git_commit_language =
+                        language_registry.language_for_name("Git Commit").await?;
+                    buffer.update(&mut cx, |buffer, cx| {
+                        buffer.set_language(Some(git_commit_language), cx);
+                    })?;
+                }
+                repository.update(&mut cx, |repository, _| {
+                    repository.commit_message_buffer = Some(buffer.clone());
+                })?;
+                Ok(buffer)
+            })
+        } else {
+            self.open_local_commit_buffer(languages, buffer_store, cx)
+        }
+    }
+
+    fn open_local_commit_buffer(
+        &mut self,
+        language_registry: Option<Arc<LanguageRegistry>>,
+        buffer_store: Entity<BufferStore>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Buffer>>> {
+        let merge_message = self.merge_message.clone();
+        cx.spawn(|repository, mut cx| async move {
+            let buffer = buffer_store
+                .update(&mut cx, |buffer_store, cx| buffer_store.create_buffer(cx))?
+                .await?;
+
+            if let Some(language_registry) = language_registry {
+                let git_commit_language = language_registry.language_for_name("Git Commit").await?;
+                buffer.update(&mut cx, |buffer, cx| {
+                    buffer.set_language(Some(git_commit_language), cx);
+                })?;
+            }
+
+            if let Some(merge_message) = merge_message {
+                buffer.update(&mut cx, |buffer, cx| {
+                    buffer.set_text(merge_message.as_str(), cx)
+                })?;
+            }
+
+            repository.update(&mut cx, |repository, _| {
+                repository.commit_message_buffer = Some(buffer.clone());
+            })?;
+            Ok(buffer)
+        })
+    }
+
+    pub fn checkout_files(
+        &self,
+        commit: &str,
+        paths: Vec<RepoPath>,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let commit = commit.to_string();
+        let env = self.worktree_environment(cx);
+
+        self.send_job(|git_repo, _| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.checkout_files(commit, paths, env.await).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitCheckoutFiles {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            commit,
+                            paths: paths
+                                .into_iter()
+                                .map(|p| p.to_string_lossy().to_string())
+                                .collect(),
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn reset(
+        &self,
+        commit: String,
+        reset_mode: ResetMode,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let commit = commit.to_string();
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, _| async move {
+            match git_repo {
+                GitRepo::Local(git_repo) => {
+                    let env = env.await;
+                    git_repo.reset(commit, reset_mode, env).await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitReset {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            commit,
+                            mode: match reset_mode {
+                                ResetMode::Soft => git_reset::ResetMode::Soft.into(),
+                                ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
+                            },
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn show(&self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => git_repository.show(commit, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let resp = client
+                        .request(proto::GitShow {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+
+                    Ok(CommitDetails {
+                        sha: resp.sha.into(),
+                        message: resp.message.into(),
+                        commit_timestamp: resp.commit_timestamp,
+                        committer_email: resp.committer_email.into(),
+                        committer_name: resp.committer_name.into(),
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn log(&self, options: LogOptions) -> oneshot::Receiver<Result<Vec<LogEntry>>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => git_repository.log(options, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitLog {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            skip: options.skip as u64,
+                            limit: options.limit as u64,
+                            path: options.path.map(|path| path.to_string_lossy().to_string()),
+                            follow_renames: options.follow_renames,
+                        })
+                        .await?;
+
+                    let entries = response
+                        .entries
+                        .into_iter()
+                        .map(|entry| LogEntry {
+                            sha: entry.sha.into(),
+                            parents: entry.parents.into_iter().map(Into::into).collect(),
+                            subject: entry.subject.into(),
+                            author_name: entry.author_name.into(),
+                            author_email: entry.author_email.into(),
+                            commit_timestamp: entry.commit_timestamp,
+                        })
+                        .collect();
+
+                    Ok(entries)
+                }
+            }
+        })
+    }
+
+    pub fn load_commit_diff(&self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => git_repository.load_commit_diff(commit, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::LoadCommitDiff {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+
+                    let files = response
+                        .files
+                        .into_iter()
+                        .map(|file| CommitFile {
+                            path: RepoPath::from_str(&file.path),
+                            old_text: file.old_text,
+                            new_text: file.new_text,
+                        })
+                        .collect();
+
+                    Ok(CommitDiff { files })
+                }
+            }
+        })
+    }
+
+    pub fn load_conflict_versions(
+        &self,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<ConflictVersions>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => {
+                    git_repository.load_conflict_versions(path, cx).await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::LoadConflictVersions {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            path: path.to_string_lossy().to_string(),
+                        })
+                        .await?;
+
+                    Ok(ConflictVersions {
+                        base: response.base,
+                        ours: response.ours,
+                        theirs: response.theirs,
+                    })
+                }
+            }
+        })
+    }
+
+    /// Lists the files that differ between the working tree and the merge base
+    /// of `revision` and HEAD.
+    pub fn diff_since(&self, revision: String) -> oneshot::Receiver<Result<RevisionDiff>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => git_repository.diff_since(revision, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitDiffSince {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            revision,
+                        })
+                        .await?;
+
+                    Ok(RevisionDiff {
+                        base: response.base.into(),
+                        entries: response
+                            .entries
+                            .into_iter()
+                            .map(|entry| RevisionDiffEntry {
+                                status: match entry.status() {
+                                    proto::GitStatus::Added => StatusCode::Added,
+                                    proto::GitStatus::Deleted => StatusCode::Deleted,
+                                    proto::GitStatus::TypeChanged => StatusCode::TypeChanged,
+                                    _ => StatusCode::Modified,
+                                },
+                                path: RepoPath::from_str(&entry.path),
+                            })
+                            .collect(),
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn load_revision_text(
+        &self,
+        revision: String,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<Option<String>>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => {
+                    Ok(git_repository.load_revision_text(revision, path, cx).await)
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitLoadRevisionText {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            revision,
+                            path: path.to_string_lossy().to_string(),
+                        })
+                        .await?;
+
+                    Ok(response.text)
+                }
+            }
+        })
+    }
+
+    pub fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.cherry_pick(commits, env.await, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitCherryPick {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            commits,
+                        })
+                        .await
+                        .context("sending cherry-pick request")?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn revert(
+        &self,
+        commits: Vec<String>,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.revert(commits, env.await, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitRevert {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            commits,
+                        })
+                        .await
+                        .context("sending revert request")?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn rebase_todo(
+        &self,
+        onto: Option<String>,
+    ) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.rebase_todo(onto, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            onto,
+                        })
+                        .await?;
+
+                    Ok(response
+                        .entries
+                        .into_iter()
+                        .map(rebase_todo_entry_from_proto)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase(
+        &self,
+        onto: Option<String>,
+        todo: Vec<RebaseTodoEntry>,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.rebase(onto, todo, env.await, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitRebase {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            onto,
+                            todo: todo.into_iter().map(rebase_todo_entry_to_proto).collect(),
+                        })
+                        .await
+                        .context("sending rebase request")?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn sequencer_operation(&self) -> oneshot::Receiver<Result<Option<SequencerOperation>>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.sequencer_operation(cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitGetSequencerOperation {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.operation.map(|_| {
+                        use proto::git_get_sequencer_operation_response::Operation;
+                        match response.operation() {
+                            Operation::CherryPick => SequencerOperation::CherryPick,
+                            Operation::Revert => SequencerOperation::Revert,
+                            Operation::Rebase => SequencerOperation::Rebase,
+                        }
+                    }))
+                }
+            }
+        })
+    }
+
+    pub fn continue_operation(
+        &self,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.continue_operation(env.await, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitContinueOperation {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                        })
+                        .await
+                        .context("sending continue request")?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn abort_operation(&self, cx: &mut App) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.abort_operation(env.await, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitAbortOperation {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                        })
+                        .await
+                        .context("sending abort request")?;
+
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
+            }
+        })
+    }
+
+    fn buffer_store(&self, cx: &App) -> Option<Entity<BufferStore>> {
+        Some(self.git_store.upgrade()?.read(cx).buffer_store.clone())
+    }
+
+    pub fn stage_entries(
+        &self,
+        entries: Vec<RepoPath>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        if entries.is_empty() {
+            return Task::ready(Ok(()));
+        }
+        let env = self.worktree_environment(cx);
+
+        let mut save_futures = Vec::new();
+        if let Some(buffer_store) = self.buffer_store(cx) {
+            buffer_store.update(cx, |buffer_store, cx| {
+                for path in &entries {
+                    let Some(path) = self.repository_entry.try_unrelativize(path) else {
+                        continue;
+                    };
+                    let project_path = (self.worktree_id, path).into();
+                    if let Some(buffer) = buffer_store.get_by_path(&project_path, cx) {
+                        if buffer
+                            .read(cx)
+                            .file()
+                            .map_or(false, |file| file.disk_state().exists())
+                        {
+                            save_futures.push(buffer_store.save_buffer(buffer, cx));
+                        }
+                    }
+                }
             })
-            .ok();
-        result_rx
+        }
+
+        cx.spawn(|this, mut cx| async move {
+            for save_future in save_futures {
+                save_future.await?;
+            }
+            let env = env.await;
+
+            this.update(&mut cx, |this, _| {
+                this.send_job(|git_repo, cx| async move {
+                    match git_repo {
+                        GitRepo::Local(repo) => repo.stage_paths(entries, env, cx).await,
+                        GitRepo::Remote {
+                            project_id,
+                            client,
+                            worktree_id,
+                            work_directory_id,
+                        } => {
+                            client
+                                .request(proto::Stage {
+                                    project_id: project_id.0,
+                                    worktree_id: worktree_id.to_proto(),
+                                    work_directory_id: work_directory_id.to_proto(),
+                                    paths: entries
+                                        .into_iter()
+                                        .map(|repo_path| repo_path.as_ref().to_proto())
+                                        .collect(),
+                                })
+                                .await
+                                .context("sending stage request")?;
+
+                            Ok(())
+                        }
+                    }
+                })
+            })?
+            .await??;
+
+            Ok(())
+        })
     }
 
-    pub fn display_name(&self, project: &Project, cx: &App) -> SharedString {
-        maybe!({
-            let project_path = self.repo_path_to_project_path(&"".into())?;
-            let worktree_name = project
-                .worktree_for_id(project_path.worktree_id, cx)?
-                .read(cx)
-                .root_name();
+    pub fn unstage_entries(
+        &self,
+        entries: Vec<RepoPath>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        if entries.is_empty() {
+            return Task::ready(Ok(()));
+        }
+        let env = self.worktree_environment(cx);
 
-            let mut path = PathBuf::new();
-            path = path.join(worktree_name);
-            path = path.join(project_path.path);
-            Some(path.to_string_lossy().to_string())
+        let mut save_futures = Vec::new();
+        if let Some(buffer_store) = self.buffer_store(cx) {
+            buffer_store.update(cx, |buffer_store, cx| {
+                for path in &entries {
+                    let Some(path) = self.repository_entry.try_unrelativize(path) else {
+                        continue;
+                    };
+                    let project_path = (self.worktree_id, path).into();
+                    if let Some(buffer) = buffer_store.get_by_path(&project_path, cx) {
+                        if buffer
+                            .read(cx)
+                            .file()
+                            .map_or(false, |file| file.disk_state().exists())
+                        {
+                            save_futures.push(buffer_store.save_buffer(buffer, cx));
+                        }
+                    }
+                }
+            })
+        }
+
+        cx.spawn(move |this, mut cx| async move {
+            for save_future in save_futures {
+                save_future.await?;
+            }
+            let env = env.await;
+
+            this.update(&mut cx, |this, _| {
+                this.send_job(|git_repo, cx| async move {
+                    match git_repo {
+                        GitRepo::Local(repo) => repo.unstage_paths(entries, env, cx).await,
+                        GitRepo::Remote {
+                            project_id,
+                            client,
+                            worktree_id,
+                            work_directory_id,
+                        } => {
+                            client
+                                .request(proto::Unstage {
+                                    project_id: project_id.0,
+                                    worktree_id: worktree_id.to_proto(),
+                                    work_directory_id: work_directory_id.to_proto(),
+                                    paths: entries
+                                        .into_iter()
+                                        .map(|repo_path| repo_path.as_ref().to_proto())
+                                        .collect(),
+                                })
+                                .await
+                                .context("sending unstage request")?;
+
+                            Ok(())
+                        }
+                    }
+                })
+            })?
+            .await??;
+
+            Ok(())
         })
-        .unwrap_or_else(|| self.repository_entry.work_directory.display_name())
-        .into()
     }
 
-    pub fn activate(&self, cx: &mut Context<Self>) {
-        let Some(git_store) = self.git_store.upgrade() else {
-            return;
-        };
-        let entity = cx.entity();
-        git_store.update(cx, |git_store, cx| {
-            let Some(index) = git_store
-                .repositories
-                .iter()
-                .position(|handle| *handle == entity)
-            else {
-                return;
-            };
-            git_store.active_index = Some(index);
-            cx.emit(GitEvent::ActiveRepositoryChanged);
-        });
+    pub fn stage_all(&self, cx: &mut Context<Self>) -> Task<anyhow::Result<()>> {
+        let to_stage = self
+            .repository_entry
+            .status()
+            .filter(|entry| !entry.status.staging().is_fully_staged())
+            .map(|entry| entry.repo_path.clone())
+            .collect();
+        self.stage_entries(to_stage, cx)
     }
 
-    pub fn status(&self) -> impl '_ + Iterator<Item = StatusEntry> {
-        self.repository_entry.status()
+    pub fn unstage_all(&self, cx: &mut Context<Self>) -> Task<anyhow::Result<()>> {
+        let to_unstage = self
+            .repository_entry
+            .status()
+            .filter(|entry| entry.status.staging().has_staged())
+            .map(|entry| entry.repo_path.clone())
+            .collect();
+        self.unstage_entries(to_unstage, cx)
     }
 
-    pub fn has_conflict(&self, path: &RepoPath) -> bool {
-        self.repository_entry
-            .current_merge_conflicts
-            .contains(&path)
+    /// Get a count of all entries in the active repository, including
+    /// untracked files.
+    pub fn entry_count(&self) -> usize {
+        self.repository_entry.status_len()
     }
 
-    pub fn repo_path_to_project_path(&self, path: &RepoPath) -> Option<ProjectPath> {
-        let path = self.repository_entry.try_unrelativize(path)?;
-        Some((self.worktree_id, path).into())
+    fn worktree_environment(
+        &self,
+        cx: &mut App,
+    ) -> impl Future<Output = HashMap<String, String>> + 'static {
+        let task = self.project_environment.as_ref().and_then(|env| {
+            env.update(cx, |env, cx| {
+                env.get_environment(
+                    Some(self.worktree_id),
+                    Some(self.worktree_abs_path.clone()),
+                    cx,
+                )
+            })
+            .ok()
+        });
+        async move { OptionFuture::from(task).await.flatten().unwrap_or_default() }
     }
 
-    pub fn project_path_to_repo_path(&self, path: &ProjectPath) -> Option<RepoPath> {
-        self.worktree_id_path_to_repo_path(path.worktree_id, &path.path)
+    pub fn commit(
+        &self,
+        message: SharedString,
+        name_and_email: Option<(SharedString, SharedString)>,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => {
+                    let env = env.await;
+                    repo.commit(message, name_and_email, env, cx).await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let (name, email) = name_and_email.unzip();
+                    client
+                        .request(proto::Commit {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            message: String::from(message),
+                            name: name.map(String::from),
+                            email: email.map(String::from),
+                        })
+                        .await
+                        .context("sending commit request")?;
+
+                    Ok(())
+                }
+            }
+        })
     }
 
-    // note: callers must verify these come from the same worktree
-    pub fn contains_sub_repo(&self, other: &Entity<Self>, cx: &App) -> bool {
-        let other_work_dir = &other.read(cx).repository_entry.work_directory;
-        match (&self.repository_entry.work_directory, other_work_dir) {
-            (WorkDirectory::InProject { .. }, WorkDirectory::AboveProject { .. }) => false,
-            (WorkDirectory::AboveProject { .. }, WorkDirectory::InProject { .. }) => true,
-            (
-                WorkDirectory::InProject {
-                    relative_path: this_path,
-                },
-                WorkDirectory::InProject {
-                    relative_path: other_path,
-                },
-            ) => other_path.starts_with(this_path),
-            (
-                WorkDirectory::AboveProject {
-                    absolute_path: this_path,
-                    ..
-                },
-                WorkDirectory::AboveProject {
-                    absolute_path: other_path,
-                    ..
-                },
-            ) => other_path.starts_with(this_path),
-        }
-    }
+    pub fn fetch(
+        &mut self,
+        askpass: AskPassDelegate,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let executor = cx.background_executor().clone();
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let env = self.worktree_environment(cx);
 
-    pub fn worktree_id_path_to_repo_path(
-        &self,
-        worktree_id: WorktreeId,
-        path: &Path,
-    ) -> Option<RepoPath> {
-        if worktree_id != self.worktree_id {
-            return None;
-        }
-        self.repository_entry.relativize(path).log_err()
-    }
+        self.send_job(move |git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => {
+                    let askpass = AskPassSession::new(&executor, askpass).await?;
+                    let env = env.await;
+                    git_repository.fetch(askpass, env, cx).await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    askpass_delegates.lock().insert(askpass_id, askpass);
+                    let _defer = util::defer(|| {
+                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                        debug_assert!(askpass_delegate.is_some());
+                    });
 
-    pub fn open_commit_buffer(
-        &mut self,
-        languages: Option<Arc<LanguageRegistry>>,
-        buffer_store: Entity<BufferStore>,
-        cx: &mut Context<Self>,
-    ) -> Task<Result<Entity<Buffer>>> {
-        if let Some(buffer) = self.commit_message_buffer.clone() {
-            return Task::ready(Ok(buffer));
-        }
+                    let response = client
+                        .request(proto::Fetch {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            askpass_id,
+                        })
+                        .await
+                        .context("sending fetch request")?;
 
-        if let GitRepo::Remote {
-            project_id,
-            client,
-            worktree_id,
-            work_directory_id,
-        } = self.git_repo.clone()
-        {
-            let client = client.clone();
-            cx.spawn(|repository, mut cx| async move {
-                let request = client.request(proto::OpenCommitMessageBuffer {
-                    project_id: project_id.0,
-                    worktree_id: worktree_id.to_proto(),
-                    work_directory_id: work_directory_id.to_proto(),
-                });
-                let response = request.await.context("requesting to open commit buffer")?;
-                let buffer_id = BufferId::new(response.buffer_id)?;
-                let buffer = buffer_store
-                    .update(&mut cx, |buffer_store, cx| {
-                        buffer_store.wait_for_remote_buffer(buffer_id, cx)
-                    })?
-                    .await?;
-                if let Some(language_registry) = languages {
-                    let git_commit_language =
-                        language_registry.language_for_name("Git Commit").await?;
-                    buffer.update(&mut cx, |buffer, cx| {
-                        buffer.set_language(Some(git_commit_language), cx);
-                    })?;
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
                 }
-                repository.update(&mut cx, |repository, _| {
-                    repository.commit_message_buffer = Some(buffer.clone());
-                })?;
-                Ok(buffer)
-            })
-        } else {
-            self.open_local_commit_buffer(languages, buffer_store, cx)
-        }
+            }
+        })
     }
 
-    fn open_local_commit_buffer(
+    pub fn push(
         &mut self,
-        language_registry: Option<Arc<LanguageRegistry>>,
-        buffer_store: Entity<BufferStore>,
-        cx: &mut Context<Self>,
-    ) -> Task<Result<Entity<Buffer>>> {
-        let merge_message = self.merge_message.clone();
-        cx.spawn(|repository, mut cx| async move {
-            let buffer = buffer_store
-                .update(&mut cx, |buffer_store, cx| buffer_store.create_buffer(cx))?
-                .await?;
+        branch: SharedString,
+        remote: SharedString,
+        options: Option<PushOptions>,
+        askpass: AskPassDelegate,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let executor = cx.background_executor().clone();
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let env = self.worktree_environment(cx);
 
-            if let Some(language_registry) = language_registry {
-                let git_commit_language = language_registry.language_for_name("Git Commit").await?;
-                buffer.update(&mut cx, |buffer, cx| {
-                    buffer.set_language(Some(git_commit_language), cx);
-                })?;
-            }
+        self.send_job(move |git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => {
+                    let env = env.await;
+                    let askpass = AskPassSession::new(&executor, askpass).await?;
+                    git_repository
+                        .push(
+                            branch.to_string(),
+                            remote.to_string(),
+                            options,
+                            askpass,
+                            env,
+                            cx,
+                        )
+                        .await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    askpass_delegates.lock().insert(askpass_id, askpass);
+                    let _defer = util::defer(|| {
+                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                        debug_assert!(askpass_delegate.is_some());
+                    });
+                    let response = client
+                        .request(proto::Push {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            askpass_id,
+                            branch_name: branch.to_string(),
+                            remote_name: remote.to_string(),
+                            options: options.map(|options| match options {
+                                PushOptions::Force => proto::push::PushOptions::Force,
+                                PushOptions::SetUpstream => proto::push::PushOptions::SetUpstream,
+                            } as i32),
+                        })
+                        .await
+                        .context("sending push request")?;
 
-            if let Some(merge_message) = merge_message {
-                buffer.update(&mut cx, |buffer, cx| {
-                    buffer.set_text(merge_message.as_str(), cx)
-                })?;
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
+                }
             }
-
-            repository.update(&mut cx, |repository, _| {
-                repository.commit_message_buffer = Some(buffer.clone());
-            })?;
-            Ok(buffer)
         })
     }
 
-    pub fn checkout_files(
-        &self,
-        commit: &str,
-        paths: Vec<RepoPath>,
+    pub fn pull(
+        &mut self,
+        branch: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
         cx: &mut App,
-    ) -> oneshot::Receiver<Result<()>> {
-        let commit = commit.to_string();
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let executor = cx.background_executor().clone();
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
         let env = self.worktree_environment(cx);
 
-        self.send_job(|git_repo, _| async move {
+        self.send_job(move |git_repo, cx| async move {
             match git_repo {
-                GitRepo::Local(repo) => repo.checkout_files(commit, paths, env.await).await,
+                GitRepo::Local(git_repository) => {
+                    let askpass = AskPassSession::new(&executor, askpass).await?;
+                    let env = env.await;
+                    git_repository
+                        .pull(branch.to_string(), remote.to_string(), askpass, env, cx)
+                        .await
+                }
                 GitRepo::Remote {
                     project_id,
                     client,
                     worktree_id,
                     work_directory_id,
                 } => {
-                    client
-                        .request(proto::GitCheckoutFiles {
+                    askpass_delegates.lock().insert(askpass_id, askpass);
+                    let _defer = util::defer(|| {
+                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                        debug_assert!(askpass_delegate.is_some());
+                    });
+                    let response = client
+                        .request(proto::Pull {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            commit,
-                            paths: paths
-                                .into_iter()
-                                .map(|p| p.to_string_lossy().to_string())
-                                .collect(),
+                            askpass_id,
+                            branch_name: branch.to_string(),
+                            remote_name: remote.to_string(),
                         })
-                        .await?;
+                        .await
+                        .context("sending pull request")?;
 
-                    Ok(())
+                    Ok(RemoteCommandOutput {
+                        stdout: response.stdout,
+                        stderr: response.stderr,
+                    })
                 }
             }
         })
     }
 
-    pub fn reset(
+    fn set_index_text(
         &self,
-        commit: String,
-        reset_mode: ResetMode,
+        path: RepoPath,
+        content: Option<String>,
         cx: &mut App,
-    ) -> oneshot::Receiver<Result<()>> {
-        let commit = commit.to_string();
+    ) -> oneshot::Receiver<anyhow::Result<()>> {
         let env = self.worktree_environment(cx);
-        self.send_job(|git_repo, _| async move {
-            match git_repo {
-                GitRepo::Local(git_repo) => {
-                    let env = env.await;
-                    git_repo.reset(commit, reset_mode, env).await
+
+        self.send_keyed_job(
+            Some(GitJobKey::WriteIndex(path.clone())),
+            |git_repo, cx| async move {
+                match git_repo {
+                    GitRepo::Local(repo) => repo.set_index_text(path, content, env.await, cx).await,
+                    GitRepo::Remote {
+                        project_id,
+                        client,
+                        worktree_id,
+                        work_directory_id,
+                    } => {
+                        client
+                            .request(proto::SetIndexText {
+                                project_id: project_id.0,
+                                worktree_id: worktree_id.to_proto(),
+                                work_directory_id: work_directory_id.to_proto(),
+                                path: path.as_ref().to_proto(),
+                                text: content,
+                            })
+                            .await?;
+                        Ok(())
+                    }
                 }
+            },
+        )
+    }
+
+    pub fn get_remotes(
+        &self,
+        branch_name: Option<String>,
+    ) -> oneshot::Receiver<Result<Vec<Remote>>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => git_repository.get_remotes(branch_name, cx).await,
                 GitRepo::Remote {
                     project_id,
                     client,
                     worktree_id,
                     work_directory_id,
                 } => {
-                    client
-                        .request(proto::GitReset {
+                    let response = client
+                        .request(proto::GetRemotes {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            commit,
-                            mode: match reset_mode {
-                                ResetMode::Soft => git_reset::ResetMode::Soft.into(),
-                                ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
-                            },
+                            branch_name,
                         })
                         .await?;
 
-                    Ok(())
+                    let remotes = response
+                        .remotes
+                        .into_iter()
+                        .map(|remotes| git::repository::Remote {
+                            name: remotes.name.into(),
+                        })
+                        .collect();
+
+                    Ok(remotes)
                 }
             }
         })
     }
 
-    pub fn show(&self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
-        self.send_job(|git_repo, cx| async move {
-            match git_repo {
-                GitRepo::Local(git_repository) => git_repository.show(commit, cx).await,
+    pub fn branches(&self) -> oneshot::Receiver<Result<Vec<Branch>>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => {
+                    let git_repository = git_repository.clone();
+                    cx.background_spawn(async move { git_repository.branches().await })
+                        .await
+                }
                 GitRepo::Remote {
                     project_id,
                     client,
                     worktree_id,
                     work_directory_id,
                 } => {
-                    let resp = client
-                        .request(proto::GitShow {
+                    let response = client
+                        .request(proto::GitGetBranches {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            commit,
                         })
                         .await?;
 
-                    Ok(CommitDetails {
-                        sha: resp.sha.into(),
-                        message: resp.message.into(),
-                        commit_timestamp: resp.commit_timestamp,
-                        committer_email: resp.committer_email.into(),
-                        committer_name: resp.committer_name.into(),
-                    })
-                }
-            }
-        })
-    }
-
-    fn buffer_store(&self, cx: &App) -> Option<Entity<BufferStore>> {
-        Some(self.git_store.upgrade()?.read(cx).buffer_store.clone())
-    }
-
-    pub fn stage_entries(
-        &self,
-        entries: Vec<RepoPath>,
-        cx: &mut Context<Self>,
-    ) -> Task<anyhow::Result<()>> {
-        if entries.is_empty() {
-            return Task::ready(Ok(()));
-        }
-        let env = self.worktree_environment(cx);
+                    let branches = response
+                        .branches
+                        .into_iter()
+                        .map(|branch| worktree::proto_to_branch(&branch))
+                        .collect();
 
-        let mut save_futures = Vec::new();
-        if let Some(buffer_store) = self.buffer_store(cx) {
-            buffer_store.update(cx, |buffer_store, cx| {
-                for path in &entries {
-                    let Some(path) = self.repository_entry.try_unrelativize(path) else {
-                        continue;
-                    };
-                    let project_path = (self.worktree_id, path).into();
-                    if let Some(buffer) = buffer_store.get_by_path(&project_path, cx) {
-                        if buffer
-                            .read(cx)
-                            .file()
-                            .map_or(false, |file| file.disk_state().exists())
-                        {
-                            save_futures.push(buffer_store.save_buffer(buffer, cx));
-                        }
-                    }
+                    Ok(branches)
                 }
-            })
-        }
-
-        cx.spawn(|this, mut cx| async move {
-            for save_future in save_futures {
-                save_future.await?;
             }
-            let env = env.await;
-
-            this.update(&mut cx, |this, _| {
-                this.send_job(|git_repo, cx| async move {
-                    match git_repo {
-                        GitRepo::Local(repo) => repo.stage_paths(entries, env, cx).await,
-                        GitRepo::Remote {
-                            project_id,
-                            client,
-                            worktree_id,
-                            work_directory_id,
-                        } => {
-                            client
-                                .request(proto::Stage {
-                                    project_id: project_id.0,
-                                    worktree_id: worktree_id.to_proto(),
-                                    work_directory_id: work_directory_id.to_proto(),
-                                    paths: entries
-                                        .into_iter()
-                                        .map(|repo_path| repo_path.as_ref().to_proto())
-                                        .collect(),
-                                })
-                                .await
-                                .context("sending stage request")?;
-
-                            Ok(())
-                        }
-                    }
-                })
-            })?
-            .await??;
-
-            Ok(())
         })
     }
 
-    pub fn unstage_entries(
-        &self,
-        entries: Vec<RepoPath>,
-        cx: &mut Context<Self>,
-    ) -> Task<anyhow::Result<()>> {
-        if entries.is_empty() {
-            return Task::ready(Ok(()));
-        }
-        let env = self.worktree_environment(cx);
-
-        let mut save_futures = Vec::new();
-        if let Some(buffer_store) = self.buffer_store(cx) {
-            buffer_store.update(cx, |buffer_store, cx| {
-                for path in &entries {
-                    let Some(path) = self.repository_entry.try_unrelativize(path) else {
-                        continue;
-                    };
-                    let project_path = (self.worktree_id, path).into();
-                    if let Some(buffer) = buffer_store.get_by_path(&project_path, cx) {
-                        if buffer
-                            .read(cx)
-                            .file()
-                            .map_or(false, |file| file.disk_state().exists())
-                        {
-                            save_futures.push(buffer_store.save_buffer(buffer, cx));
-                        }
-                    }
-                }
-            })
-        }
-
-        cx.spawn(move |this, mut cx| async move {
-            for save_future in save_futures {
-                save_future.await?;
-            }
-            let env = env.await;
-
-            this.update(&mut cx, |this, _| {
-                this.send_job(|git_repo, cx| async move {
-                    match git_repo {
-                        GitRepo::Local(repo) => repo.unstage_paths(entries, env, cx).await,
-                        GitRepo::Remote {
-                            project_id,
-                            client,
-                            worktree_id,
-                            work_directory_id,
-                        } => {
-                            client
-                                .request(proto::Unstage {
-                                    project_id: project_id.0,
-                                    worktree_id: worktree_id.to_proto(),
-                                    work_directory_id: work_directory_id.to_proto(),
-                                    paths: entries
-                                        .into_iter()
-                                        .map(|repo_path| repo_path.as_ref().to_proto())
-                                        .collect(),
-                                })
-                                .await
-                                .context("sending unstage request")?;
-
-                            Ok(())
+    pub fn diff(&self, diff_type: DiffType, _cx: &App) -> oneshot::Receiver<Result<String>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => git_repository.diff(diff_type, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                    ..
+                } => {
+                    let (diff_type, revision) = match diff_type {
+                        DiffType::HeadToIndex => (proto::git_diff::DiffType::HeadToIndex, None),
+                        DiffType::HeadToWorktree => {
+                            (proto::git_diff::DiffType::HeadToWorktree, None)
                         }
-                    }
-                })
-            })?
-            .await??;
+                        DiffType::RevisionToWorktree(revision) => (
+                            proto::git_diff::DiffType::RevisionToWorktree,
+                            Some(revision),
+                        ),
+                    };
+                    let response = client
+                        .request(proto::GitDiff {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            diff_type: diff_type.into(),
+                            revision,
+                        })
+                        .await?;
 
-            Ok(())
+                    Ok(response.diff)
+                }
+            }
         })
     }
 
-    pub fn stage_all(&self, cx: &mut Context<Self>) -> Task<anyhow::Result<()>> {
-        let to_stage = self
-            .repository_entry
-            .status()
-            .filter(|entry| !entry.status.staging().is_fully_staged())
-            .map(|entry| entry.repo_path.clone())
-            .collect();
-        self.stage_entries(to_stage, cx)
+    pub fn create_branch(&self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => {
+                    git_repository.create_branch(branch_name, cx).await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitCreateBranch {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            branch_name,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
     }
 
-    pub fn unstage_all(&self, cx: &mut Context<Self>) -> Task<anyhow::Result<()>> {
-        let to_unstage = self
-            .repository_entry
-            .status()
-            .filter(|entry| entry.status.staging().has_staged())
-            .map(|entry| entry.repo_path.clone())
-            .collect();
-        self.unstage_entries(to_unstage, cx)
+    pub fn change_branch(&self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => {
+                    git_repository.change_branch(branch_name, cx).await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitChangeBranch {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            branch_name,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
     }
 
-    /// Get a count of all entries in the active repository, including
-    /// untracked files.
-    pub fn entry_count(&self) -> usize {
-        self.repository_entry.status_len()
+    pub fn delete_branch(&self, branch_name: String, force: bool) -> oneshot::Receiver<Result<()>> {
+        self.send_job(move |repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => {
+                    git_repository.delete_branch(branch_name, force, cx).await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitDeleteBranch {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            branch_name,
+                            force,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
     }
 
-    fn worktree_environment(
+    pub fn rename_branch(
         &self,
-        cx: &mut App,
-    ) -> impl Future<Output = HashMap<String, String>> + 'static {
-        let task = self.project_environment.as_ref().and_then(|env| {
-            env.update(cx, |env, cx| {
-                env.get_environment(
-                    Some(self.worktree_id),
-                    Some(self.worktree_abs_path.clone()),
-                    cx,
-                )
-            })
-            .ok()
-        });
-        async move { OptionFuture::from(task).await.flatten().unwrap_or_default() }
+        branch_name: String,
+        new_name: String,
+    ) -> oneshot::Receiver<Result<()>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => {
+                    git_repository
+                        .rename_branch(branch_name, new_name, cx)
+                        .await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitRenameBranch {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            branch_name,
+                            new_name,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
     }
 
-    pub fn commit(
+    pub fn set_upstream_branch(
         &self,
-        message: SharedString,
-        name_and_email: Option<(SharedString, SharedString)>,
-        cx: &mut App,
+        branch_name: String,
+        upstream: Option<String>,
     ) -> oneshot::Receiver<Result<()>> {
-        let env = self.worktree_environment(cx);
-        self.send_job(|git_repo, cx| async move {
-            match git_repo {
-                GitRepo::Local(repo) => {
-                    let env = env.await;
-                    repo.commit(message, name_and_email, env, cx).await
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => {
+                    git_repository
+                        .set_upstream_branch(branch_name, upstream, cx)
+                        .await
                 }
                 GitRepo::Remote {
                     project_id,
@@ -1598,18 +3319,15 @@ impl Repository {
                     worktree_id,
                     work_directory_id,
                 } => {
-                    let (name, email) = name_and_email.unzip();
                     client
-                        .request(proto::Commit {
+                        .request(proto::GitSetUpstreamBranch {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            message: String::from(message),
-                            name: name.map(String::from),
-                            email: email.map(String::from),
+                            branch_name,
+                            upstream,
                         })
-                        .await
-                        .context("sending commit request")?;
+                        .await?;
 
                     Ok(())
                 }
@@ -1617,22 +3335,42 @@ impl Repository {
         })
     }
 
-    pub fn fetch(
-        &mut self,
-        askpass: AskPassDelegate,
-        cx: &mut App,
-    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
-        let executor = cx.background_executor().clone();
-        let askpass_delegates = self.askpass_delegates.clone();
-        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
-        let env = self.worktree_environment(cx);
+    pub fn tags(&self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => git_repository.tags(cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                        })
+                        .await?;
 
-        self.send_job(move |git_repo, cx| async move {
-            match git_repo {
+                    Ok(response.tags.into_iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_tag(
+        &self,
+        tag_name: String,
+        target: Option<String>,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
                 GitRepo::Local(git_repository) => {
-                    let askpass = AskPassSession::new(&executor, askpass).await?;
-                    let env = env.await;
-                    git_repository.fetch(askpass, env, cx).await
+                    git_repository
+                        .create_tag(tag_name, target, message, cx)
+                        .await
                 }
                 GitRepo::Remote {
                     project_id,
@@ -1640,36 +3378,52 @@ impl Repository {
                     worktree_id,
                     work_directory_id,
                 } => {
-                    askpass_delegates.lock().insert(askpass_id, askpass);
-                    let _defer = util::defer(|| {
-                        let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
-                        debug_assert!(askpass_delegate.is_some());
-                    });
+                    client
+                        .request(proto::GitCreateTag {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            tag_name,
+                            target,
+                            message,
+                        })
+                        .await?;
 
-                    let response = client
-                        .request(proto::Fetch {
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn delete_tag(&self, tag_name: String) -> oneshot::Receiver<Result<()>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => git_repository.delete_tag(tag_name, cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitDeleteTag {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            askpass_id,
+                            tag_name,
                         })
-                        .await
-                        .context("sending fetch request")?;
+                        .await?;
 
-                    Ok(RemoteCommandOutput {
-                        stdout: response.stdout,
-                        stderr: response.stderr,
-                    })
+                    Ok(())
                 }
             }
         })
     }
 
-    pub fn push(
+    pub fn push_tag(
         &mut self,
-        branch: SharedString,
+        tag: SharedString,
         remote: SharedString,
-        options: Option<PushOptions>,
         askpass: AskPassDelegate,
         cx: &mut App,
     ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
@@ -1678,20 +3432,13 @@ impl Repository {
         let askpass_id = util::post_inc(&mut self.latest_askpass_id);
         let env = self.worktree_environment(cx);
 
-        self.send_job(move |git_repo, cx| async move {
-            match git_repo {
-                GitRepo::Local(git_repository) => {
-                    let env = env.await;
-                    let askpass = AskPassSession::new(&executor, askpass).await?;
-                    git_repository
-                        .push(
-                            branch.to_string(),
-                            remote.to_string(),
-                            options,
-                            askpass,
-                            env,
-                            cx,
-                        )
+        self.send_job(move |git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(git_repository) => {
+                    let env = env.await;
+                    let askpass = AskPassSession::new(&executor, askpass).await?;
+                    git_repository
+                        .push_tag(tag.to_string(), remote.to_string(), askpass, env, cx)
                         .await
                 }
                 GitRepo::Remote {
@@ -1706,20 +3453,16 @@ impl Repository {
                         debug_assert!(askpass_delegate.is_some());
                     });
                     let response = client
-                        .request(proto::Push {
+                        .request(proto::GitPushTag {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            askpass_id,
-                            branch_name: branch.to_string(),
+                            tag_name: tag.to_string(),
                             remote_name: remote.to_string(),
-                            options: options.map(|options| match options {
-                                PushOptions::Force => proto::push::PushOptions::Force,
-                                PushOptions::SetUpstream => proto::push::PushOptions::SetUpstream,
-                            } as i32),
+                            askpass_id,
                         })
                         .await
-                        .context("sending push request")?;
+                        .context("sending push tag request")?;
 
                     Ok(RemoteCommandOutput {
                         stdout: response.stdout,
@@ -1730,10 +3473,37 @@ impl Repository {
         })
     }
 
-    pub fn pull(
+    pub fn submodules(&self) -> oneshot::Receiver<Result<Vec<Submodule>>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => git_repository.submodules(cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitGetSubmodules {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response
+                        .submodules
+                        .into_iter()
+                        .map(submodule_from_proto)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn update_submodules(
         &mut self,
-        branch: SharedString,
-        remote: SharedString,
+        paths: Vec<RepoPath>,
         askpass: AskPassDelegate,
         cx: &mut App,
     ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
@@ -1748,7 +3518,7 @@ impl Repository {
                     let askpass = AskPassSession::new(&executor, askpass).await?;
                     let env = env.await;
                     git_repository
-                        .pull(branch.to_string(), remote.to_string(), askpass, env, cx)
+                        .update_submodules(paths, askpass, env, cx)
                         .await
                 }
                 GitRepo::Remote {
@@ -1763,16 +3533,15 @@ impl Repository {
                         debug_assert!(askpass_delegate.is_some());
                     });
                     let response = client
-                        .request(proto::Pull {
+                        .request(proto::GitUpdateSubmodules {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
+                            paths: paths.iter().map(|path| path.as_ref().to_proto()).collect(),
                             askpass_id,
-                            branch_name: branch.to_string(),
-                            remote_name: remote.to_string(),
                         })
                         .await
-                        .context("sending pull request")?;
+                        .context("sending update submodules request")?;
 
                     Ok(RemoteCommandOutput {
                         stdout: response.stdout,
@@ -1783,48 +3552,44 @@ impl Repository {
         })
     }
 
-    fn set_index_text(
-        &self,
-        path: RepoPath,
-        content: Option<String>,
-        cx: &mut App,
-    ) -> oneshot::Receiver<anyhow::Result<()>> {
-        let env = self.worktree_environment(cx);
+    pub fn worktrees(&self) -> oneshot::Receiver<Result<Vec<GitWorktree>>> {
+        self.send_job(|repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => git_repository.worktrees(cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::GitGetWorktrees {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                        })
+                        .await?;
 
-        self.send_keyed_job(
-            Some(GitJobKey::WriteIndex(path.clone())),
-            |git_repo, cx| async move {
-                match git_repo {
-                    GitRepo::Local(repo) => repo.set_index_text(path, content, env.await, cx).await,
-                    GitRepo::Remote {
-                        project_id,
-                        client,
-                        worktree_id,
-                        work_directory_id,
-                    } => {
-                        client
-                            .request(proto::SetIndexText {
-                                project_id: project_id.0,
-                                worktree_id: worktree_id.to_proto(),
-                                work_directory_id: work_directory_id.to_proto(),
-                                path: path.as_ref().to_proto(),
-                                text: content,
-                            })
-                            .await?;
-                        Ok(())
-                    }
+                    Ok(response
+                        .worktrees
+                        .into_iter()
+                        .map(|worktree| GitWorktree {
+                            path: PathBuf::from(worktree.path),
+                            sha: worktree.sha.into(),
+                            branch: worktree.branch.map(Into::into),
+                            is_main: worktree.is_main,
+                        })
+                        .collect())
                 }
-            },
-        )
+            }
+        })
     }
 
-    pub fn get_remotes(
-        &self,
-        branch_name: Option<String>,
-    ) -> oneshot::Receiver<Result<Vec<Remote>>> {
-        self.send_job(|repo, cx| async move {
+    /// Returns the URL the named remote fetches from, if it exists.
+    pub fn remote_url(&self, remote_name: String) -> oneshot::Receiver<Result<Option<String>>> {
+        self.send_job(|repo, _| async move {
             match repo {
-                GitRepo::Local(git_repository) => git_repository.get_remotes(branch_name, cx).await,
+                GitRepo::Local(git_repository) => Ok(git_repository.remote_url(&remote_name)),
                 GitRepo::Remote {
                     project_id,
                     client,
@@ -1832,36 +3597,126 @@ impl Repository {
                     work_directory_id,
                 } => {
                     let response = client
-                        .request(proto::GetRemotes {
+                        .request(proto::GitGetRemoteUrl {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            branch_name,
+                            remote_name,
                         })
                         .await?;
 
-                    let remotes = response
-                        .remotes
-                        .into_iter()
-                        .map(|remotes| git::repository::Remote {
-                            name: remotes.name.into(),
+                    Ok(response.url)
+                }
+            }
+        })
+    }
+
+    pub fn create_worktree(
+        &self,
+        path: PathBuf,
+        branch: String,
+        create_branch: bool,
+    ) -> oneshot::Receiver<Result<()>> {
+        self.send_job(move |repo, cx| async move {
+            match repo {
+                GitRepo::Local(git_repository) => {
+                    git_repository
+                        .create_worktree(path, branch, create_branch, cx)
+                        .await
+                }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitCreateWorktree {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            path: path.to_string_lossy().to_string(),
+                            branch,
+                            create_branch,
                         })
-                        .collect();
+                        .await?;
 
-                    Ok(remotes)
+                    Ok(())
                 }
             }
         })
     }
 
-    pub fn branches(&self) -> oneshot::Receiver<Result<Vec<Branch>>> {
+    pub fn check_for_pushed_commits(&self) -> oneshot::Receiver<Result<Vec<SharedString>>> {
         self.send_job(|repo, cx| async move {
             match repo {
-                GitRepo::Local(git_repository) => {
-                    let git_repository = git_repository.clone();
-                    cx.background_spawn(async move { git_repository.branches().await })
+                GitRepo::Local(git_repository) => git_repository.check_for_pushed_commit(cx).await,
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    let response = client
+                        .request(proto::CheckForPushedCommits {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                        })
+                        .await?;
+
+                    let branches = response.pushed_to.into_iter().map(Into::into).collect();
+
+                    Ok(branches)
+                }
+            }
+        })
+    }
+
+    pub fn stash_push(
+        &self,
+        message: Option<String>,
+        paths: Vec<RepoPath>,
+        include_untracked: bool,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => {
+                    repo.stash_push(message, paths, include_untracked, env.await, cx)
                         .await
                 }
+                GitRepo::Remote {
+                    project_id,
+                    client,
+                    worktree_id,
+                    work_directory_id,
+                } => {
+                    client
+                        .request(proto::GitStashPush {
+                            project_id: project_id.0,
+                            worktree_id: worktree_id.to_proto(),
+                            work_directory_id: work_directory_id.to_proto(),
+                            message,
+                            paths: paths
+                                .into_iter()
+                                .map(|p| p.to_string_lossy().to_string())
+                                .collect(),
+                            include_untracked,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn stash_list(&self) -> oneshot::Receiver<Result<Vec<StashEntry>>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.stash_list(cx).await,
                 GitRepo::Remote {
                     project_id,
                     client,
@@ -1869,49 +3724,46 @@ impl Repository {
                     work_directory_id,
                 } => {
                     let response = client
-                        .request(proto::GitGetBranches {
+                        .request(proto::GitStashList {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
                         })
                         .await?;
 
-                    let branches = response
-                        .branches
+                    let entries = response
+                        .entries
                         .into_iter()
-                        .map(|branch| worktree::proto_to_branch(&branch))
+                        .map(|entry| StashEntry {
+                            index: entry.index as usize,
+                            sha: entry.sha.into(),
+                            message: entry.message.into(),
+                            commit_timestamp: entry.commit_timestamp,
+                        })
                         .collect();
 
-                    Ok(branches)
+                    Ok(entries)
                 }
             }
         })
     }
 
-    pub fn diff(&self, diff_type: DiffType, _cx: &App) -> oneshot::Receiver<Result<String>> {
-        self.send_job(|repo, cx| async move {
-            match repo {
-                GitRepo::Local(git_repository) => git_repository.diff(diff_type, cx).await,
+    pub fn stash_show(&self, index: usize) -> oneshot::Receiver<Result<String>> {
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.stash_show(index, cx).await,
                 GitRepo::Remote {
                     project_id,
                     client,
                     worktree_id,
                     work_directory_id,
-                    ..
                 } => {
                     let response = client
-                        .request(proto::GitDiff {
+                        .request(proto::GitStashShow {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            diff_type: match diff_type {
-                                DiffType::HeadToIndex => {
-                                    proto::git_diff::DiffType::HeadToIndex.into()
-                                }
-                                DiffType::HeadToWorktree => {
-                                    proto::git_diff::DiffType::HeadToWorktree.into()
-                                }
-                            },
+                            index: index as u64,
                         })
                         .await?;
 
@@ -1921,12 +3773,16 @@ impl Repository {
         })
     }
 
-    pub fn create_branch(&self, branch_name: String) -> oneshot::Receiver<Result<()>> {
-        self.send_job(|repo, cx| async move {
-            match repo {
-                GitRepo::Local(git_repository) => {
-                    git_repository.create_branch(branch_name, cx).await
-                }
+    pub fn stash_apply(
+        &self,
+        index: usize,
+        sha: String,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.stash_apply(index, sha, env.await, cx).await,
                 GitRepo::Remote {
                     project_id,
                     client,
@@ -1934,11 +3790,12 @@ impl Repository {
                     work_directory_id,
                 } => {
                     client
-                        .request(proto::GitCreateBranch {
+                        .request(proto::GitStashApply {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            branch_name,
+                            index: index as u64,
+                            sha,
                         })
                         .await?;
 
@@ -1948,12 +3805,16 @@ impl Repository {
         })
     }
 
-    pub fn change_branch(&self, branch_name: String) -> oneshot::Receiver<Result<()>> {
-        self.send_job(|repo, cx| async move {
-            match repo {
-                GitRepo::Local(git_repository) => {
-                    git_repository.change_branch(branch_name, cx).await
-                }
+    pub fn stash_pop(
+        &self,
+        index: usize,
+        sha: String,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.stash_pop(index, sha, env.await, cx).await,
                 GitRepo::Remote {
                     project_id,
                     client,
@@ -1961,11 +3822,12 @@ impl Repository {
                     work_directory_id,
                 } => {
                     client
-                        .request(proto::GitChangeBranch {
+                        .request(proto::GitStashPop {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
-                            branch_name,
+                            index: index as u64,
+                            sha,
                         })
                         .await?;
 
@@ -1975,27 +3837,33 @@ impl Repository {
         })
     }
 
-    pub fn check_for_pushed_commits(&self) -> oneshot::Receiver<Result<Vec<SharedString>>> {
-        self.send_job(|repo, cx| async move {
-            match repo {
-                GitRepo::Local(git_repository) => git_repository.check_for_pushed_commit(cx).await,
+    pub fn stash_drop(
+        &self,
+        index: usize,
+        sha: String,
+        cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let env = self.worktree_environment(cx);
+        self.send_job(|git_repo, cx| async move {
+            match git_repo {
+                GitRepo::Local(repo) => repo.stash_drop(index, sha, env.await, cx).await,
                 GitRepo::Remote {
                     project_id,
                     client,
                     worktree_id,
                     work_directory_id,
                 } => {
-                    let response = client
-                        .request(proto::CheckForPushedCommits {
+                    client
+                        .request(proto::GitStashDrop {
                             project_id: project_id.0,
                             worktree_id: worktree_id.to_proto(),
                             work_directory_id: work_directory_id.to_proto(),
+                            index: index as u64,
+                            sha,
                         })
                         .await?;
 
-                    let branches = response.pushed_to.into_iter().map(Into::into).collect();
-
-                    Ok(branches)
+                    Ok(())
                 }
             }
         })
@@ -1,6 +1,8 @@
 pub mod clangd_ext;
 pub mod lsp_ext_command;
+mod pull_diagnostics;
 pub mod rust_analyzer_ext;
+pub mod semantic_tokens;
 
 use crate::{
     buffer_store::{BufferStore, BufferStoreEvent},
@@ -14,8 +16,9 @@ use crate::{
     toolchain_store::{EmptyToolchainStore, ToolchainStoreEvent},
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
     yarn::YarnPathStore,
-    CodeAction, Completion, CompletionSource, CoreCompletion, Hover, InlayHint, LspAction,
-    ProjectItem as _, ProjectPath, ProjectTransaction, ResolveState, Symbol, ToolchainStore,
+    CodeAction, Completion, CompletionSource, CoreCompletion, DocumentLink, FoldingRange, Hover,
+    InlayHint, LspAction, ProjectItem as _, ProjectPath, ProjectTransaction, ResolveState, Symbol,
+    ToolchainStore,
 };
 use anyhow::{anyhow, Context as _, Result};
 use async_trait::async_trait;
@@ -56,12 +59,14 @@ use lsp::{
 use node_runtime::read_package_installed_version;
 use parking_lot::Mutex;
 use postage::watch;
+use pull_diagnostics::PulledDiagnostics;
 use rand::prelude::*;
 
 use rpc::{
     proto::{FromProto, ToProto},
     AnyProtoClient,
 };
+use semantic_tokens::{BufferSemanticTokens, CachedSemanticTokens, SemanticToken};
 use serde::Serialize;
 use settings::{Settings, SettingsLocation, SettingsStore};
 use sha2::{Digest, Sha256};
@@ -166,6 +171,8 @@ pub struct LocalLspStore {
         >,
     >,
     buffer_snapshots: HashMap<BufferId, HashMap<LanguageServerId, Vec<LspBufferSnapshot>>>, // buffer_id -> server_id -> vec of snapshots
+    semantic_tokens: HashMap<BufferId, HashMap<LanguageServerId, CachedSemanticTokens>>,
+    pulled_diagnostics: PulledDiagnostics,
     _subscription: gpui::Subscription,
     lsp_tree: Entity<LanguageServerTree>,
     registered_buffers: HashMap<BufferId, usize>,
@@ -690,6 +697,14 @@ impl LocalLspStore {
                                         anyhow::Ok(())
                                     })??;
                                 }
+                                "textDocument/prepareTypeHierarchy" => {
+                                    this.update(&mut cx, |this, _| {
+                                        if let Some(server) = this.language_server_for_id(server_id)
+                                        {
+                                            server.set_type_hierarchy_provider(true);
+                                        }
+                                    })?;
+                                }
                                 _ => log::warn!("unhandled capability registration: {reg:?}"),
                             }
                         }
@@ -761,6 +776,14 @@ impl LocalLspStore {
                                         }
                                     })?;
                                 }
+                                "textDocument/prepareTypeHierarchy" => {
+                                    this.update(&mut cx, |this, _| {
+                                        if let Some(server) = this.language_server_for_id(server_id)
+                                        {
+                                            server.set_type_hierarchy_provider(false);
+                                        }
+                                    })?;
+                                }
                                 _ => log::warn!("unhandled capability unregistration: {unreg:?}"),
                             }
                         }
@@ -828,6 +851,42 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = this.clone();
+                move |(), mut cx| {
+                    let this = this.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
+        language_server
+            .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
+                let this = this.clone();
+                move |(), mut cx| {
+                    let this = this.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            this.pull_diagnostics_for_server(server_id, cx);
+                        })?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::ShowMessageRequest, _, _>({
                 let this = this.clone();
@@ -2048,6 +2107,8 @@ impl LocalLspStore {
     ) {
         buffer.update(cx, |buffer, cx| {
             let _ = self.buffer_snapshots.remove(&buffer.remote_id());
+            let _ = self.semantic_tokens.remove(&buffer.remote_id());
+            self.pulled_diagnostics.remove_buffer(buffer.remote_id());
 
             for (_, language_server) in self.language_servers_for_buffer(buffer, cx) {
                 language_server.unregister_buffer(file_url.clone());
@@ -2899,6 +2960,9 @@ pub struct LspStore {
     _maintain_buffer_languages: Task<()>,
     diagnostic_summaries:
         HashMap<WorktreeId, HashMap<Arc<Path>, HashMap<LanguageServerId, DiagnosticSummary>>>,
+    /// The folding ranges of buffers, along with the buffer version they were computed for.
+    folding_ranges: HashMap<BufferId, (clock::Global, Vec<FoldingRange>)>,
+    document_links: HashMap<BufferId, (clock::Global, Vec<DocumentLink>)>,
 }
 
 pub enum LspStoreEvent {
@@ -2917,6 +2981,7 @@ pub enum LspStoreEvent {
     Notification(String),
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         language_server_id: LanguageServerId,
         path: ProjectPath,
@@ -2973,6 +3038,8 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_get_semantic_tokens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -2985,6 +3052,16 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<GetTypeDefinition>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetReferences>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<CallHierarchyIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<CallHierarchyOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<TypeHierarchySupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<TypeHierarchySubtypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetFoldingRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentLinks>);
+        client.add_entity_request_handler(Self::handle_resolve_document_link);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<lsp_ext_command::ExpandMacro>);
@@ -3083,6 +3160,8 @@ impl LspStore {
                 language_server_watcher_registrations: Default::default(),
                 buffers_being_formatted: Default::default(),
                 buffer_snapshots: Default::default(),
+                semantic_tokens: Default::default(),
+                pulled_diagnostics: Default::default(),
                 prettier_store,
                 environment,
                 http_client,
@@ -3105,6 +3184,8 @@ impl LspStore {
             language_server_statuses: Default::default(),
             nonce: StdRng::from_entropy().gen(),
             diagnostic_summaries: Default::default(),
+            folding_ranges: Default::default(),
+            document_links: Default::default(),
             active_entry: None,
 
             _maintain_workspace_config,
@@ -3161,6 +3242,8 @@ impl LspStore {
             language_server_statuses: Default::default(),
             nonce: StdRng::from_entropy().gen(),
             diagnostic_summaries: Default::default(),
+            folding_ranges: Default::default(),
+            document_links: Default::default(),
             active_entry: None,
             toolchain_store,
             _maintain_workspace_config,
@@ -3178,6 +3261,10 @@ impl LspStore {
             BufferStoreEvent::BufferAdded(buffer) => {
                 self.on_buffer_added(buffer, cx).log_err();
             }
+            BufferStoreEvent::BufferDropped(buffer_id) => {
+                self.folding_ranges.remove(buffer_id);
+                self.document_links.remove(buffer_id);
+            }
             BufferStoreEvent::BufferChangedFilePath { buffer, old_file } => {
                 let buffer_id = buffer.read(cx).remote_id();
                 if let Some(local) = self.as_local_mut() {
@@ -3288,11 +3375,13 @@ impl LspStore {
     ) {
         match event {
             language::BufferEvent::Edited { .. } => {
-                self.on_buffer_edited(buffer, cx);
+                self.on_buffer_edited(buffer.clone(), cx);
+                self.pull_document_diagnostics(&buffer, Some(pull_diagnostics::EDIT_DEBOUNCE), cx);
             }
 
             language::BufferEvent::Saved => {
-                self.on_buffer_saved(buffer, cx);
+                self.on_buffer_saved(buffer.clone(), cx);
+                self.pull_workspace_diagnostics_for_buffer(&buffer, cx);
             }
 
             _ => {}
@@ -3443,6 +3532,7 @@ impl LspStore {
 
             if !ignore_refcounts || *refcount == 1 {
                 local.register_buffer_with_language_servers(buffer, cx);
+                self.pull_document_diagnostics(buffer, None, cx);
             }
             if !ignore_refcounts {
                 cx.observe_release(&handle, move |this, buffer, cx| {
@@ -5138,6 +5228,288 @@ impl LspStore {
         }
     }
 
+    /// Returns the folding ranges of the buffer, reusing the last response while the buffer is
+    /// unchanged.
+    pub fn folding_ranges(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<FoldingRange>>> {
+        let (buffer_id, version) = {
+            let buffer = buffer.read(cx);
+            (buffer.remote_id(), buffer.version())
+        };
+        if let Some((cached_version, ranges)) = self.folding_ranges.get(&buffer_id) {
+            if *cached_version == version {
+                return Task::ready(Ok(ranges.clone()));
+            }
+        }
+
+        let request = self.request_lsp(
+            buffer,
+            LanguageServerToQuery::FirstCapable,
+            GetFoldingRanges,
+            cx,
+        );
+        cx.spawn(move |this, mut cx| async move {
+            let ranges = request.await?;
+            this.update(&mut cx, |this, _| {
+                this.folding_ranges
+                    .insert(buffer_id, (version, ranges.clone()));
+            })?;
+            Ok(ranges)
+        })
+    }
+
+    /// Returns the document links of the buffer, reusing the last response while the buffer is
+    /// unchanged.
+    pub fn document_links(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        let (buffer_id, version) = {
+            let buffer = buffer.read(cx);
+            (buffer.remote_id(), buffer.version())
+        };
+        if let Some((cached_version, links)) = self.document_links.get(&buffer_id) {
+            if *cached_version == version {
+                return Task::ready(Ok(links.clone()));
+            }
+        }
+
+        let request = self.request_lsp(
+            buffer,
+            LanguageServerToQuery::FirstCapable,
+            GetDocumentLinks,
+            cx,
+        );
+        cx.spawn(move |this, mut cx| async move {
+            let links = request.await?;
+            this.update(&mut cx, |this, _| {
+                this.document_links
+                    .insert(buffer_id, (version, links.clone()));
+            })?;
+            Ok(links)
+        })
+    }
+
+    /// Asks the language server for the target of a link that was reported without one.
+    pub fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        let ResolveState::CanResolve(server_id, _) = &link.resolve_state else {
+            return Task::ready(Ok(link));
+        };
+        let server_id = *server_id;
+
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request = proto::ResolveDocumentLink {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                link: Some(GetDocumentLinks::project_to_proto_link(link.clone())),
+            };
+            cx.spawn(move |_, mut cx| async move {
+                let response = upstream_client
+                    .request(request)
+                    .await
+                    .context("document link proto request")?;
+                let Some(resolved_link) = response.link else {
+                    return Ok(link);
+                };
+                let resolved_link = GetDocumentLinks::proto_to_project_link(resolved_link)?;
+                buffer
+                    .update(&mut cx, |buffer, _| {
+                        buffer
+                            .wait_for_anchors([resolved_link.range.start, resolved_link.range.end])
+                    })?
+                    .await?;
+                Ok(resolved_link)
+            })
+        } else {
+            let Some(server) = buffer.update(cx, |buffer, cx| {
+                self.language_server_for_local_buffer(buffer, server_id, cx)
+                    .map(|(_, server)| server.clone())
+            }) else {
+                return Task::ready(Ok(link));
+            };
+            if !GetDocumentLinks::can_resolve_links(&server.capabilities()) {
+                return Task::ready(Ok(link));
+            }
+            let lsp_link =
+                match GetDocumentLinks::project_to_lsp_link(link, &buffer.read(cx).snapshot()) {
+                    Ok(lsp_link) => lsp_link,
+                    Err(error) => return Task::ready(Err(error)),
+                };
+            cx.spawn(move |_, cx| async move {
+                let resolved_link = server
+                    .request::<lsp::request::DocumentLinkResolve>(lsp_link)
+                    .await
+                    .context("document link resolve LSP request")?;
+                buffer.read_with(&cx, |buffer, _| {
+                    GetDocumentLinks::lsp_to_project_link(resolved_link, buffer, server_id, false)
+                })
+            })
+        }
+    }
+
+    /// Fetches the semantic tokens of the buffer from the first language server providing them,
+    /// requesting only the changes since the previous response when the server supports it.
+    pub fn semantic_tokens(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<BufferSemanticTokens>> {
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request = proto::GetSemanticTokens {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                version: serialize_version(&buffer.read(cx).version()),
+            };
+            return cx.spawn(move |_, mut cx| async move {
+                let response = client
+                    .request(request)
+                    .await
+                    .context("semantic tokens proto request")?;
+                buffer
+                    .update(&mut cx, |buffer, _| {
+                        buffer.wait_for_version(deserialize_version(&response.version))
+                    })?
+                    .await?;
+                let tokens = response
+                    .tokens
+                    .into_iter()
+                    .map(|token| {
+                        let start = token.start.and_then(deserialize_anchor);
+                        let end = token.end.and_then(deserialize_anchor);
+                        Ok(SemanticToken {
+                            range: start.context("missing token start")?
+                                ..end.context("missing token end")?,
+                            token_type: token.token_type,
+                            token_modifiers: token.token_modifiers,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(BufferSemanticTokens {
+                    legend: Arc::new(lsp::SemanticTokensLegend {
+                        token_types: response
+                            .token_types
+                            .into_iter()
+                            .map(lsp::SemanticTokenType::from)
+                            .collect(),
+                        token_modifiers: response
+                            .token_modifiers
+                            .into_iter()
+                            .map(lsp::SemanticTokenModifier::from)
+                            .collect(),
+                    }),
+                    tokens,
+                })
+            });
+        }
+
+        let Some(local) = self.as_local() else {
+            return Task::ready(Ok(BufferSemanticTokens::default()));
+        };
+        let Some((server, options)) = buffer.update(cx, |buffer, cx| {
+            self.language_servers_for_local_buffer(buffer, cx)
+                .find_map(|(_, server)| {
+                    let options =
+                        semantic_tokens::semantic_tokens_options(&server.capabilities())?.clone();
+                    semantic_tokens::supports_full_requests(&options)
+                        .then(|| (server.clone(), options))
+                })
+        }) else {
+            return Task::ready(Ok(BufferSemanticTokens::default()));
+        };
+        let Some(abs_path) = File::from_dyn(buffer.read(cx).file()).map(|file| file.abs_path(cx))
+        else {
+            return Task::ready(Ok(BufferSemanticTokens::default()));
+        };
+        let text_document = match lsp::Url::from_file_path(&abs_path) {
+            Ok(uri) => lsp::TextDocumentIdentifier::new(uri),
+            Err(()) => return Task::ready(Err(anyhow!("invalid file path {abs_path:?}"))),
+        };
+
+        let buffer_id = buffer.read(cx).remote_id();
+        let server_id = server.server_id();
+        let snapshot = buffer.read(cx).text_snapshot();
+        let previous = semantic_tokens::supports_delta_requests(&options)
+            .then(|| {
+                let cached = local.semantic_tokens.get(&buffer_id)?.get(&server_id)?;
+                Some((cached.result_id.clone(), cached.data.clone()))
+            })
+            .flatten();
+        let legend = Arc::new(options.legend);
+        cx.spawn(move |this, mut cx| async move {
+            let (result_id, data) = if let Some((previous_result_id, mut data)) = previous {
+                let response = server
+                    .request::<lsp::request::SemanticTokensFullDeltaRequest>(
+                        lsp::SemanticTokensDeltaParams {
+                            text_document,
+                            previous_result_id,
+                            work_done_progress_params: Default::default(),
+                            partial_result_params: Default::default(),
+                        },
+                    )
+                    .await?;
+                match response {
+                    Some(lsp::SemanticTokensFullDeltaResult::Tokens(tokens)) => (
+                        tokens.result_id,
+                        semantic_tokens::encode_tokens(&tokens.data),
+                    ),
+                    Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(delta)) => {
+                        semantic_tokens::apply_edits(&mut data, delta.edits);
+                        (delta.result_id, data)
+                    }
+                    Some(lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { edits }) => {
+                        semantic_tokens::apply_edits(&mut data, edits);
+                        (None, data)
+                    }
+                    None => (None, Vec::new()),
+                }
+            } else {
+                let response = server
+                    .request::<lsp::request::SemanticTokensFullRequest>(lsp::SemanticTokensParams {
+                        text_document,
+                        work_done_progress_params: Default::default(),
+                        partial_result_params: Default::default(),
+                    })
+                    .await?;
+                match response {
+                    Some(lsp::SemanticTokensResult::Tokens(tokens)) => (
+                        tokens.result_id,
+                        semantic_tokens::encode_tokens(&tokens.data),
+                    ),
+                    Some(lsp::SemanticTokensResult::Partial(partial)) => {
+                        (None, semantic_tokens::encode_tokens(&partial.data))
+                    }
+                    None => (None, Vec::new()),
+                }
+            };
+
+            let tokens = semantic_tokens::decode_tokens(&data, &snapshot);
+            this.update(&mut cx, |this, _| {
+                if let Some(local) = this.as_local_mut() {
+                    let cached_tokens = local.semantic_tokens.entry(buffer_id).or_default();
+                    match result_id {
+                        Some(result_id) => {
+                            cached_tokens
+                                .insert(server_id, CachedSemanticTokens { result_id, data });
+                        }
+                        None => {
+                            cached_tokens.remove(&server_id);
+                        }
+                    }
+                }
+            })?;
+            Ok(BufferSemanticTokens { legend, tokens })
+        })
+    }
+
     pub fn signature_help<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -6629,6 +7001,8 @@ impl LspStore {
                     progress_tokens: Default::default(),
                 },
             );
+            this.folding_ranges.clear();
+            this.document_links.clear();
             cx.emit(LspStoreEvent::LanguageServerAdded(
                 server_id,
                 LanguageServerName(server.name.into()),
@@ -7340,6 +7714,35 @@ impl LspStore {
         })
     }
 
+    async fn handle_resolve_document_link(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::ResolveDocumentLink>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ResolveDocumentLinkResponse> {
+        let link = envelope
+            .payload
+            .link
+            .context("missing document link")
+            .and_then(GetDocumentLinks::proto_to_project_link)?;
+        let buffer = this.update(&mut cx, |this, cx| {
+            let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+            this.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors([link.range.start, link.range.end])
+            })?
+            .await?;
+        let link = this
+            .update(&mut cx, |this, cx| {
+                this.resolve_document_link(link, buffer, cx)
+            })?
+            .await?;
+        Ok(proto::ResolveDocumentLinkResponse {
+            link: Some(GetDocumentLinks::project_to_proto_link(link)),
+        })
+    }
+
     async fn handle_refresh_code_lens(
         this: Entity<Self>,
         _: TypedEnvelope<proto::RefreshCodeLens>,
@@ -7351,6 +7754,66 @@ impl LspStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_refresh_semantic_tokens(
+        this: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |_, cx| {
+            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_semantic_tokens(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GetSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GetSemanticTokensResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = this.update(&mut cx, |this, cx| {
+            this.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&envelope.payload.version))
+            })?
+            .await
+            .with_context(|| format!("waiting for version for buffer {}", buffer.entity_id()))?;
+
+        let semantic_tokens = this
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.semantic_tokens(buffer.clone(), cx)
+            })?
+            .await?;
+        let version = buffer.update(&mut cx, |buffer, _| buffer.version())?;
+        Ok(proto::GetSemanticTokensResponse {
+            token_types: semantic_tokens
+                .legend
+                .token_types
+                .iter()
+                .map(|token_type| token_type.as_str().to_string())
+                .collect(),
+            token_modifiers: semantic_tokens
+                .legend
+                .token_modifiers
+                .iter()
+                .map(|modifier| modifier.as_str().to_string())
+                .collect(),
+            tokens: semantic_tokens
+                .tokens
+                .into_iter()
+                .map(|token| proto::SemanticToken {
+                    start: Some(serialize_anchor(&token.range.start)),
+                    end: Some(serialize_anchor(&token.range.end)),
+                    token_type: token.token_type,
+                    token_modifiers: token.token_modifiers,
+                })
+                .collect(),
+            version: serialize_version(&version),
+        })
+    }
+
     async fn handle_open_buffer_for_symbol(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenBufferForSymbol>,
@@ -7857,6 +8320,7 @@ impl LspStore {
             });
         }
         local.language_server_watched_paths.remove(&server_id);
+        local.pulled_diagnostics.remove_server(server_id);
         let server_state = local.language_servers.remove(&server_id);
         cx.notify();
         cx.emit(LspStoreEvent::LanguageServerRemoved(server_id));
@@ -8110,6 +8574,9 @@ impl LspStore {
             Some(key.0),
         ));
         cx.emit(LspStoreEvent::RefreshInlayHints);
+        cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        self.folding_ranges.clear();
+        self.document_links.clear();
 
         if let Some((downstream_client, project_id)) = self.downstream_client.as_ref() {
             downstream_client
@@ -8204,6 +8671,7 @@ impl LspStore {
             }
         });
 
+        self.pull_diagnostics_for_server(server_id, cx);
         cx.notify();
     }
 
@@ -0,0 +1,334 @@
+use std::{sync::Arc, time::Duration};
+
+use collections::HashMap;
+use gpui::{Context, Entity, Task};
+use language::{Buffer, CachedLspAdapter};
+use lsp::{LanguageServer, LanguageServerId};
+use text::BufferId;
+use util::ResultExt as _;
+
+use crate::{lsp_store::LspStore, File};
+
+/// How long to wait after an edit before pulling the edited document's diagnostics.
+pub(crate) const EDIT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// The state of diagnostics that language servers provide on request, via
+/// `textDocument/diagnostic` and `workspace/diagnostic`, as opposed to publishing them.
+#[derive(Default)]
+pub(crate) struct PulledDiagnostics {
+    /// The id of the last report for each document, which servers report changes against.
+    result_ids: HashMap<LanguageServerId, HashMap<lsp::Url, String>>,
+    document_pulls: HashMap<(BufferId, LanguageServerId), Task<()>>,
+    workspace_pulls: HashMap<LanguageServerId, Task<()>>,
+}
+
+impl PulledDiagnostics {
+    pub(crate) fn remove_buffer(&mut self, buffer_id: BufferId) {
+        self.document_pulls.retain(|(id, _), _| *id != buffer_id);
+    }
+
+    pub(crate) fn remove_server(&mut self, server_id: LanguageServerId) {
+        self.result_ids.remove(&server_id);
+        self.document_pulls.retain(|(_, id), _| *id != server_id);
+        self.workspace_pulls.remove(&server_id);
+    }
+
+    fn previous_result_id(&self, server_id: LanguageServerId, uri: &lsp::Url) -> Option<String> {
+        self.result_ids.get(&server_id)?.get(uri).cloned()
+    }
+
+    fn previous_result_ids(&self, server_id: LanguageServerId) -> Vec<lsp::PreviousResultId> {
+        self.result_ids
+            .get(&server_id)
+            .into_iter()
+            .flatten()
+            .map(|(uri, result_id)| lsp::PreviousResultId {
+                uri: uri.clone(),
+                value: result_id.clone(),
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn diagnostic_options(
+    capabilities: &lsp::ServerCapabilities,
+) -> Option<&lsp::DiagnosticOptions> {
+    match capabilities.diagnostic_provider.as_ref()? {
+        lsp::DiagnosticServerCapabilities::Options(options) => Some(options),
+        lsp::DiagnosticServerCapabilities::RegistrationOptions(options) => {
+            Some(&options.diagnostic_options)
+        }
+    }
+}
+
+impl LspStore {
+    /// Pulls the diagnostics of a buffer from each of its language servers that provides them on
+    /// request, after waiting for the given debounce.
+    pub(crate) fn pull_document_diagnostics(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        debounce: Option<Duration>,
+        cx: &mut Context<Self>,
+    ) {
+        let servers = buffer.update(cx, |buffer, cx| {
+            self.language_servers_for_local_buffer(buffer, cx)
+                .filter(|(_, server)| diagnostic_options(&server.capabilities()).is_some())
+                .map(|(adapter, server)| (adapter.clone(), server.clone()))
+                .collect::<Vec<_>>()
+        });
+        for (adapter, server) in servers {
+            self.pull_document_diagnostics_from_server(buffer, adapter, server, debounce, cx);
+        }
+    }
+
+    /// Pulls the diagnostics of every open buffer that the language server handles, and of the
+    /// whole workspace if the server supports it.
+    pub(crate) fn pull_diagnostics_for_server(
+        &mut self,
+        server_id: LanguageServerId,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(local) = self.as_local() else {
+            return;
+        };
+        let Some(server) = local.running_language_server_for_id(server_id).cloned() else {
+            return;
+        };
+        let Some(adapter) = self.language_server_adapter_for_id(server_id) else {
+            return;
+        };
+        let Some(options) = diagnostic_options(&server.capabilities()).cloned() else {
+            return;
+        };
+
+        let buffers = local
+            .registered_buffers
+            .keys()
+            .filter_map(|buffer_id| self.buffer_store.read(cx).get(*buffer_id))
+            .collect::<Vec<_>>();
+        for buffer in buffers {
+            let served = buffer.update(cx, |buffer, cx| {
+                self.language_servers_for_local_buffer(buffer, cx)
+                    .any(|(_, server)| server.server_id() == server_id)
+            });
+            if served {
+                self.pull_document_diagnostics_from_server(
+                    &buffer,
+                    adapter.clone(),
+                    server.clone(),
+                    None,
+                    cx,
+                );
+            }
+        }
+
+        if options.workspace_diagnostics {
+            self.pull_workspace_diagnostics(adapter, server, options.identifier, cx);
+        }
+    }
+
+    /// Pulls the diagnostics of the whole workspace from the buffer's language servers, if they
+    /// report diagnostics that depend on other files.
+    pub(crate) fn pull_workspace_diagnostics_for_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) {
+        let servers = buffer.update(cx, |buffer, cx| {
+            self.language_servers_for_local_buffer(buffer, cx)
+                .filter_map(|(adapter, server)| {
+                    let options = diagnostic_options(&server.capabilities())?.clone();
+                    (options.workspace_diagnostics && options.inter_file_dependencies)
+                        .then(|| (adapter.clone(), server.clone(), options.identifier))
+                })
+                .collect::<Vec<_>>()
+        });
+        for (adapter, server, identifier) in servers {
+            self.pull_workspace_diagnostics(adapter, server, identifier, cx);
+        }
+    }
+
+    fn pull_document_diagnostics_from_server(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        adapter: Arc<CachedLspAdapter>,
+        server: Arc<LanguageServer>,
+        debounce: Option<Duration>,
+        cx: &mut Context<Self>,
+    ) {
+        let buffer_id = buffer.read(cx).remote_id();
+        let Some(abs_path) = File::from_dyn(buffer.read(cx).file()).map(|file| file.abs_path(cx))
+        else {
+            return;
+        };
+        let Some(uri) = lsp::Url::from_file_path(abs_path).log_err() else {
+            return;
+        };
+        let Some(local) = self.as_local_mut() else {
+            return;
+        };
+
+        let server_id = server.server_id();
+        let task = cx.spawn(move |this, mut cx| async move {
+            if let Some(debounce) = debounce {
+                cx.background_executor().timer(debounce).await;
+            }
+
+            let Some(request) = this
+                .update(&mut cx, |this, _| {
+                    let previous_result_id = this.as_local().and_then(|local| {
+                        local.pulled_diagnostics.previous_result_id(server_id, &uri)
+                    });
+                    server.request::<lsp::request::DocumentDiagnosticRequest>(
+                        lsp::DocumentDiagnosticParams {
+                            text_document: lsp::TextDocumentIdentifier { uri: uri.clone() },
+                            identifier: diagnostic_options(&server.capabilities())
+                                .and_then(|options| options.identifier.clone()),
+                            previous_result_id,
+                            work_done_progress_params: Default::default(),
+                            partial_result_params: Default::default(),
+                        },
+                    )
+                })
+                .ok()
+            else {
+                return;
+            };
+            let Some(result) = request.await.log_err() else {
+                return;
+            };
+
+            let (report, related_documents) = match result {
+                lsp::DocumentDiagnosticReportResult::Report(
+                    lsp::DocumentDiagnosticReport::Full(report),
+                ) => (
+                    Some(lsp::DocumentDiagnosticReportKind::Full(
+                        report.full_document_diagnostic_report,
+                    )),
+                    report.related_documents,
+                ),
+                lsp::DocumentDiagnosticReportResult::Report(
+                    lsp::DocumentDiagnosticReport::Unchanged(report),
+                ) => (
+                    Some(lsp::DocumentDiagnosticReportKind::Unchanged(
+                        report.unchanged_document_diagnostic_report,
+                    )),
+                    report.related_documents,
+                ),
+                lsp::DocumentDiagnosticReportResult::Partial(partial) => {
+                    (None, partial.related_documents)
+                }
+            };
+            this.update(&mut cx, |this, cx| {
+                let reports = report
+                    .map(|report| (uri, report))
+                    .into_iter()
+                    .chain(related_documents.into_iter().flatten());
+                for (uri, report) in reports {
+                    this.apply_diagnostic_report(server_id, uri, report, &adapter, cx);
+                }
+            })
+            .ok();
+        });
+        local
+            .pulled_diagnostics
+            .document_pulls
+            .insert((buffer_id, server_id), task);
+    }
+
+    fn pull_workspace_diagnostics(
+        &mut self,
+        adapter: Arc<CachedLspAdapter>,
+        server: Arc<LanguageServer>,
+        identifier: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(local) = self.as_local_mut() else {
+            return;
+        };
+        let server_id = server.server_id();
+        let request = server.request::<lsp::request::WorkspaceDiagnosticRequest>(
+            lsp::WorkspaceDiagnosticParams {
+                identifier,
+                previous_result_ids: local.pulled_diagnostics.previous_result_ids(server_id),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+        );
+        let task = cx.spawn(move |this, mut cx| async move {
+            let Some(result) = request.await.log_err() else {
+                return;
+            };
+            let items = match result {
+                lsp::WorkspaceDiagnosticReportResult::Report(report) => report.items,
+                lsp::WorkspaceDiagnosticReportResult::Partial(partial) => partial.items,
+            };
+            this.update(&mut cx, |this, cx| {
+                for item in items {
+                    let (uri, report) = match item {
+                        lsp::WorkspaceDocumentDiagnosticReport::Full(report) => (
+                            report.uri,
+                            lsp::DocumentDiagnosticReportKind::Full(
+                                report.full_document_diagnostic_report,
+                            ),
+                        ),
+                        lsp::WorkspaceDocumentDiagnosticReport::Unchanged(report) => (
+                            report.uri,
+                            lsp::DocumentDiagnosticReportKind::Unchanged(
+                                report.unchanged_document_diagnostic_report,
+                            ),
+                        ),
+                    };
+                    this.apply_diagnostic_report(server_id, uri, report, &adapter, cx);
+                }
+            })
+            .ok();
+        });
+        local
+            .pulled_diagnostics
+            .workspace_pulls
+            .insert(server_id, task);
+    }
+
+    fn apply_diagnostic_report(
+        &mut self,
+        server_id: LanguageServerId,
+        uri: lsp::Url,
+        report: lsp::DocumentDiagnosticReportKind,
+        adapter: &CachedLspAdapter,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(local) = self.as_local_mut() else {
+            return;
+        };
+        let result_ids = local
+            .pulled_diagnostics
+            .result_ids
+            .entry(server_id)
+            .or_default();
+        match report {
+            lsp::DocumentDiagnosticReportKind::Full(report) => {
+                match report.result_id {
+                    Some(result_id) => result_ids.insert(uri.clone(), result_id),
+                    None => result_ids.remove(&uri),
+                };
+                let mut params = lsp::PublishDiagnosticsParams {
+                    uri,
+                    diagnostics: report.items,
+                    version: None,
+                };
+                adapter.process_diagnostics(&mut params);
+                self.update_diagnostics(
+                    server_id,
+                    params,
+                    &adapter.disk_based_diagnostic_sources,
+                    cx,
+                )
+                .log_err();
+            }
+            lsp::DocumentDiagnosticReportKind::Unchanged(report) => {
+                result_ids.insert(uri, report.result_id);
+            }
+        }
+    }
+}
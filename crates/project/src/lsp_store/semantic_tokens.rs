@@ -0,0 +1,215 @@
+use std::{cmp::Reverse, ops::Range, sync::Arc};
+
+use language::{Bias, PointUtf16, TextBufferSnapshot, Unclipped};
+use text::Anchor;
+
+/// The semantic tokens a language server reported for a buffer.
+#[derive(Clone, Debug, Default)]
+pub struct BufferSemanticTokens {
+    /// The token types and modifiers that the tokens refer to by index.
+    pub legend: Arc<lsp::SemanticTokensLegend>,
+    /// Non-overlapping tokens, ordered by their position in the buffer.
+    pub tokens: Vec<SemanticToken>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SemanticToken {
+    pub range: Range<Anchor>,
+    /// An index into the legend's token types.
+    pub token_type: u32,
+    /// A bitset of indices into the legend's token modifiers.
+    pub token_modifiers: u32,
+}
+
+impl BufferSemanticTokens {
+    pub fn token_type(&self, token: &SemanticToken) -> Option<&str> {
+        self.legend
+            .token_types
+            .get(token.token_type as usize)
+            .map(|token_type| token_type.as_str())
+    }
+
+    pub fn token_modifiers<'a>(
+        &'a self,
+        token: &SemanticToken,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let bitset = token.token_modifiers;
+        self.legend
+            .token_modifiers
+            .iter()
+            .take(u32::BITS as usize)
+            .enumerate()
+            .filter(move |(ix, _)| bitset & (1 << ix) != 0)
+            .map(|(_, modifier)| modifier.as_str())
+    }
+}
+
+/// The encoded tokens of the last response of a language server for a buffer, which it computes
+/// deltas against.
+#[derive(Debug)]
+pub(crate) struct CachedSemanticTokens {
+    pub result_id: String,
+    pub data: Vec<u32>,
+}
+
+pub(crate) fn semantic_tokens_options(
+    capabilities: &lsp::ServerCapabilities,
+) -> Option<&lsp::SemanticTokensOptions> {
+    match capabilities.semantic_tokens_provider.as_ref()? {
+        lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => Some(options),
+        lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+            Some(&options.semantic_tokens_options)
+        }
+    }
+}
+
+pub(crate) fn supports_full_requests(options: &lsp::SemanticTokensOptions) -> bool {
+    match options.full {
+        Some(lsp::SemanticTokensFullOptions::Bool(supported)) => supported,
+        Some(lsp::SemanticTokensFullOptions::Delta { .. }) => true,
+        None => false,
+    }
+}
+
+pub(crate) fn supports_delta_requests(options: &lsp::SemanticTokensOptions) -> bool {
+    matches!(
+        options.full,
+        Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) })
+    )
+}
+
+/// Encodes tokens as the flat integer array that delta edits refer to.
+pub(crate) fn encode_tokens(tokens: &[lsp::SemanticToken]) -> Vec<u32> {
+    tokens
+        .iter()
+        .flat_map(|token| {
+            [
+                token.delta_line,
+                token.delta_start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            ]
+        })
+        .collect()
+}
+
+pub(crate) fn apply_edits(data: &mut Vec<u32>, mut edits: Vec<lsp::SemanticTokensEdit>) {
+    // Edits are expressed in terms of the previous data, so apply them back to front.
+    edits.sort_by_key(|edit| Reverse(edit.start));
+    for edit in edits {
+        let start = (edit.start as usize).min(data.len());
+        let end = (start + edit.delete_count as usize).min(data.len());
+        data.splice(
+            start..end,
+            encode_tokens(edit.data.as_deref().unwrap_or_default()),
+        );
+    }
+}
+
+/// Decodes the tokens from their relative positions in the snapshot the language server computed
+/// them for.
+pub(crate) fn decode_tokens(data: &[u32], snapshot: &TextBufferSnapshot) -> Vec<SemanticToken> {
+    let mut tokens = Vec::with_capacity(data.len() / 5);
+    let mut row = 0;
+    let mut column = 0;
+    for token in data.chunks_exact(5) {
+        let [delta_line, delta_start, length, token_type, token_modifiers] = *token else {
+            continue;
+        };
+        if delta_line > 0 {
+            row += delta_line;
+            column = delta_start;
+        } else {
+            column += delta_start;
+        }
+        let start = snapshot.clip_point_utf16(Unclipped(PointUtf16::new(row, column)), Bias::Left);
+        let end =
+            snapshot.clip_point_utf16(Unclipped(PointUtf16::new(row, column + length)), Bias::Left);
+        if start == end {
+            continue;
+        }
+        tokens.push(SemanticToken {
+            range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+            token_type,
+            token_modifiers,
+        });
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use text::{BufferId, OffsetRangeExt as _};
+
+    fn token(delta_line: u32, delta_start: u32, length: u32) -> lsp::SemanticToken {
+        lsp::SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type: 0,
+            token_modifiers_bitset: 0,
+        }
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let mut data = encode_tokens(&[token(0, 0, 1), token(1, 2, 3), token(0, 4, 1)]);
+        apply_edits(
+            &mut data,
+            vec![
+                lsp::SemanticTokensEdit {
+                    start: 10,
+                    delete_count: 5,
+                    data: None,
+                },
+                lsp::SemanticTokensEdit {
+                    start: 0,
+                    delete_count: 0,
+                    data: Some(vec![token(0, 0, 2)]),
+                },
+            ],
+        );
+        assert_eq!(
+            data,
+            encode_tokens(&[token(0, 0, 2), token(0, 0, 1), token(1, 2, 3)])
+        );
+    }
+
+    #[test]
+    fn test_decode_tokens() {
+        let buffer = text::Buffer::new(
+            0,
+            BufferId::new(1).unwrap(),
+            "let a = 1;\nlet 😀b = a;".to_string(),
+        );
+        let snapshot = buffer.snapshot();
+        let mut data = [
+            // `a`, on the first line.
+            [0, 4, 1, 0, 0],
+            // `😀b`, whose columns count the emoji as two UTF-16 code units.
+            [1, 4, 3, 1, 0b10],
+            // A zero-length token, relative to the previous one.
+            [0, 3, 0, 1, 0],
+            // `a;`, clipped at the end of the line.
+            [0, 3, 5, 2, 0],
+        ]
+        .concat();
+        // An incomplete token.
+        data.extend([0, 1]);
+
+        let tokens = decode_tokens(&data, snapshot);
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| (
+                    token.range.to_offset(snapshot),
+                    token.token_type,
+                    token.token_modifiers
+                ))
+                .collect::<Vec<_>>(),
+            [(4..5, 0, 0), (15..20, 1, 0b10), (23..25, 2, 0)]
+        );
+    }
+}
@@ -2,6 +2,7 @@ pub mod buffer_store;
 mod color_extractor;
 pub mod connection_manager;
 pub mod debounced_delay;
+pub mod debugger;
 pub mod git;
 pub mod image_store;
 pub mod lsp_command;
@@ -36,6 +37,7 @@ use client::{
 use clock::ReplicaId;
 use collections::{BTreeSet, HashMap, HashSet};
 use debounced_delay::DebouncedDelay;
+use debugger::{breakpoint_store::BreakpointStore, dap_store::DapStore};
 pub use environment::ProjectEnvironment;
 use futures::{
     channel::mpsc::{self, UnboundedReceiver},
@@ -111,6 +113,7 @@ pub use task_inventory::{
 
 pub use buffer_store::ProjectTransaction;
 pub use lsp_store::{
+    semantic_tokens::{BufferSemanticTokens, SemanticToken},
     DiagnosticSummary, LanguageServerLogType, LanguageServerProgress, LanguageServerPromptRequest,
     LanguageServerStatus, LanguageServerToQuery, LspStore, LspStoreEvent,
     SERVER_PROGRESS_THROTTLE_TIMEOUT,
@@ -157,6 +160,8 @@ pub struct Project {
     ssh_client: Option<Entity<SshRemoteClient>>,
     client_state: ProjectClientState,
     git_store: Entity<GitStore>,
+    breakpoint_store: Entity<BreakpointStore>,
+    dap_store: Entity<DapStore>,
     collaborators: HashMap<proto::PeerId, Collaborator>,
     client_subscriptions: Vec<client::Subscription>,
     worktree_store: Entity<WorktreeStore>,
@@ -281,6 +286,7 @@ pub enum Event {
     Rejoined,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -623,6 +629,66 @@ pub struct DocumentHighlight {
     pub kind: DocumentHighlightKind,
 }
 
+/// A symbol that can be the root or a node of a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    pub buffer: Entity<Buffer>,
+    /// The range of the whole symbol, including its body.
+    pub range: Range<language::Anchor>,
+    /// The range to reveal when navigating to the symbol, e.g. its name.
+    pub selection_range: Range<language::Anchor>,
+    pub server_id: LanguageServerId,
+    /// The item as reported by the language server, which expects it back when asked for calls.
+    pub lsp_item: lsp::CallHierarchyItem,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller of incoming calls, or the callee of outgoing calls.
+    pub item: CallHierarchyItem,
+    /// The ranges of the call expressions, in the caller's buffer.
+    pub ranges: Vec<Range<language::Anchor>>,
+}
+
+/// A type that can be the root or a node of a type hierarchy.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    pub buffer: Entity<Buffer>,
+    /// The range of the whole type declaration.
+    pub range: Range<language::Anchor>,
+    /// The range to reveal when navigating to the type, e.g. its name.
+    pub selection_range: Range<language::Anchor>,
+    pub server_id: LanguageServerId,
+    /// The item as reported by the language server, which expects it back when asked for
+    /// supertypes or subtypes.
+    pub lsp_item: lsp::TypeHierarchyItem,
+}
+
+/// A range of lines that a language server reported as foldable.
+#[derive(Clone, Debug)]
+pub struct FoldingRange {
+    /// The folded range, from the end of the first line to the end of the last line.
+    pub range: Range<language::Anchor>,
+    /// The text to display in place of the folded range.
+    pub collapsed_text: Option<String>,
+}
+
+/// A range of text that a language server reported as linking to a file or URL.
+#[derive(Clone, Debug)]
+pub struct DocumentLink {
+    pub range: Range<language::Anchor>,
+    /// The URI the link points to, which may only be known after the link is resolved.
+    pub target: Option<String>,
+    pub tooltip: Option<String>,
+    pub resolve_state: ResolveState,
+}
+
 #[derive(Clone, Debug)]
 pub struct Symbol {
     pub language_server_name: LanguageServerName,
@@ -870,6 +936,9 @@ impl Project {
 
             cx.subscribe(&lsp_store, Self::on_lsp_store_event).detach();
 
+            let breakpoint_store = cx.new(|cx| BreakpointStore::new(&buffer_store, cx));
+            let dap_store = cx.new(|cx| DapStore::new_local(breakpoint_store.clone(), cx));
+
             Self {
                 buffer_ordered_messages_tx: tx,
                 collaborators: Default::default(),
@@ -880,6 +949,8 @@ impl Project {
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
                 git_store,
+                breakpoint_store,
+                dap_store,
                 client_subscriptions: Vec::new(),
                 _subscriptions: vec![cx.on_release(Self::release)],
                 active_entry: None,
@@ -999,6 +1070,9 @@ impl Project {
 
             cx.subscribe(&ssh, Self::on_ssh_event).detach();
 
+            let breakpoint_store = cx.new(|cx| BreakpointStore::new(&buffer_store, cx));
+            let dap_store = cx.new(|cx| DapStore::new_remote(breakpoint_store.clone(), cx));
+
             let this = Self {
                 buffer_ordered_messages_tx: tx,
                 collaborators: Default::default(),
@@ -1009,6 +1083,8 @@ impl Project {
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
                 git_store,
+                breakpoint_store,
+                dap_store,
                 client_subscriptions: Vec::new(),
                 _subscriptions: vec![
                     cx.on_release(Self::release),
@@ -1204,6 +1280,9 @@ impl Project {
             )
         })?;
 
+        let breakpoint_store = cx.new(|cx| BreakpointStore::new(&buffer_store, cx))?;
+        let dap_store = cx.new(|cx| DapStore::new_remote(breakpoint_store.clone(), cx))?;
+
         let this = cx.new(|cx| {
             let replica_id = response.payload.replica_id as ReplicaId;
 
@@ -1255,6 +1334,8 @@ impl Project {
                     replica_id,
                 },
                 git_store,
+                breakpoint_store,
+                dap_store,
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
                 terminals: Terminals {
@@ -2497,6 +2578,7 @@ impl Project {
             }
             LspStoreEvent::RefreshInlayHints => cx.emit(Event::RefreshInlayHints),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3030,6 +3112,98 @@ impl Project {
         )
     }
 
+    /// Resolves the symbols at the given position that a call hierarchy can be built for.
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            CallHierarchyIncomingCalls {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            CallHierarchyOutgoingCalls {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Resolves the types at the given position that a type hierarchy can be built for.
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            TypeHierarchySupertypes {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::Other(item.server_id),
+            TypeHierarchySubtypes {
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
     fn document_highlights_impl(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -3299,6 +3473,60 @@ impl Project {
         })
     }
 
+    /// Returns, for each position, the ranges that a language server considers meaningful to
+    /// select around it, from the innermost to the outermost.
+    pub fn selection_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<language::Anchor>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<language::Anchor>>>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
+    pub fn folding_ranges(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<FoldingRange>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.folding_ranges(buffer, cx))
+    }
+
+    pub fn document_links(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.document_links(buffer, cx))
+    }
+
+    pub fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_document_link(link, buffer, cx)
+        })
+    }
+
+    pub fn semantic_tokens(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<BufferSemanticTokens>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.semantic_tokens(buffer, cx))
+    }
+
     pub fn resolve_inlay_hint(
         &self,
         hint: InlayHint,
@@ -4468,6 +4696,27 @@ impl Project {
         })
     }
 
+    pub fn any_language_server_supports_selection_ranges(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> bool {
+        if !self.is_local() {
+            // The capabilities of the host's language servers aren't known to guests.
+            return true;
+        }
+        self.lsp_store.update(cx, |this, cx| {
+            this.language_servers_for_local_buffer(buffer, cx)
+                .any(
+                    |(_, server)| match server.capabilities().selection_range_provider {
+                        Some(lsp::SelectionRangeProviderCapability::Simple(enabled)) => enabled,
+                        Some(_) => true,
+                        None => false,
+                    },
+                )
+        })
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -4513,6 +4762,14 @@ impl Project {
         &self.git_store
     }
 
+    pub fn breakpoint_store(&self) -> &Entity<BreakpointStore> {
+        &self.breakpoint_store
+    }
+
+    pub fn dap_store(&self) -> &Entity<DapStore> {
+        &self.dap_store
+    }
+
     pub fn active_repository(&self, cx: &App) -> Option<Entity<Repository>> {
         self.git_store.read(cx).active_repository()
     }
@@ -3,7 +3,7 @@ use buffer_diff::{
     assert_hunks, BufferDiffEvent, DiffHunkSecondaryStatus, DiffHunkStatus, DiffHunkStatusKind,
 };
 use fs::FakeFs;
-use futures::{future, StreamExt};
+use futures::{future, FutureExt as _, StreamExt};
 use gpui::{App, SemanticVersion, UpdateGlobal};
 use http_client::Url;
 use language::{
@@ -2279,6 +2279,124 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_pulled_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "a.rs": "let one = 1;" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                diagnostic_provider: Some(lsp::DiagnosticServerCapabilities::Options(
+                    lsp::DiagnosticOptions::default(),
+                )),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let mut requests = fake_server.handle_request::<lsp::request::DocumentDiagnosticRequest, _, _>(
+        |params, _| async move {
+            assert_eq!(
+                params.text_document.uri,
+                lsp::Url::from_file_path(path!("/dir/a.rs")).unwrap()
+            );
+            // The server only reports a change when the client has no previous result.
+            let report = match params.previous_result_id {
+                None => {
+                    lsp::DocumentDiagnosticReport::Full(lsp::RelatedFullDocumentDiagnosticReport {
+                        related_documents: None,
+                        full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport {
+                            result_id: Some("1".to_string()),
+                            items: vec![lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 4),
+                                    lsp::Position::new(0, 7),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                                message: "unused variable `one`".to_string(),
+                                ..Default::default()
+                            }],
+                        },
+                    })
+                }
+                Some(result_id) => {
+                    assert_eq!(result_id, "1");
+                    lsp::DocumentDiagnosticReport::Unchanged(
+                        lsp::RelatedUnchangedDocumentDiagnosticReport {
+                            related_documents: None,
+                            unchanged_document_diagnostic_report:
+                                lsp::UnchangedDocumentDiagnosticReport { result_id },
+                        },
+                    )
+                }
+            };
+            Ok(lsp::DocumentDiagnosticReportResult::Report(report))
+        },
+    );
+
+    let assert_diagnostics = |cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            let diagnostics = buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .map(|entry| (entry.range, entry.diagnostic.message))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                diagnostics,
+                [(
+                    Point::new(0, 4)..Point::new(0, 7),
+                    "unused variable `one`".to_string()
+                )]
+            );
+        });
+        lsp_store.read_with(cx, |lsp_store, cx| {
+            assert_eq!(
+                lsp_store.diagnostic_summary(false, cx),
+                DiagnosticSummary {
+                    error_count: 0,
+                    warning_count: 1,
+                }
+            );
+        });
+    };
+
+    fake_server
+        .request::<lsp::request::WorkspaceDiagnosticRefresh>(())
+        .await
+        .unwrap();
+    requests.next().await.unwrap();
+    cx.executor().run_until_parked();
+    assert_diagnostics(cx);
+
+    // An unchanged report keeps the previously pulled diagnostics.
+    fake_server
+        .request::<lsp::request::WorkspaceDiagnosticRefresh>(())
+        .await
+        .unwrap();
+    requests.next().await.unwrap();
+    cx.executor().run_until_parked();
+    assert_diagnostics(cx);
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -2767,6 +2885,472 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() {}\nfn b() { a(); a() }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let uri = lsp::Url::from_file_path(path!("/dir/a.rs")).unwrap();
+    let lsp_item = |name: &str, row: u32| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: uri.clone(),
+        range: lsp::Range::new(lsp::Position::new(row, 0), lsp::Position::new(row, 9)),
+        selection_range: lsp::Range::new(lsp::Position::new(row, 3), lsp::Position::new(row, 4)),
+        data: None,
+    };
+    fake_server.handle_request::<lsp::request::CallHierarchyPrepare, _, _>({
+        let item = lsp_item("a", 0);
+        move |params, _| {
+            let item = item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 3)
+                );
+                Ok(Some(vec![item]))
+            }
+        }
+    });
+    fake_server.handle_request::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+        let from = lsp_item("b", 1);
+        move |params, _| {
+            let from = from.clone();
+            async move {
+                assert_eq!(params.item.name, "a");
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from,
+                    from_ranges: vec![
+                        lsp::Range::new(lsp::Position::new(1, 9), lsp::Position::new(1, 10)),
+                        lsp::Range::new(lsp::Position::new(1, 14), lsp::Position::new(1, 15)),
+                    ],
+                }]))
+            }
+        }
+    });
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, Point::new(0, 3), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "a");
+
+    let calls = project
+        .update(cx, |project, cx| project.incoming_calls(&items[0], cx))
+        .await
+        .unwrap();
+    assert_eq!(calls.len(), 1);
+    cx.update(|cx| {
+        let call = &calls[0];
+        assert_eq!(call.item.name, "b");
+        let caller_buffer = call.item.buffer.read(cx);
+        assert_eq!(
+            call.ranges
+                .iter()
+                .map(|range| range.to_offset(caller_buffer))
+                .collect::<Vec<_>>(),
+            [19..20, 24..25]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_folding_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() {\n    b();\n}\n// region\nfn b() {}\n// endregion",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let mut requests =
+        fake_server.handle_request::<lsp::request::FoldingRangeRequest, _, _>(|_, _| async move {
+            Ok(Some(vec![
+                lsp::FoldingRange {
+                    start_line: 3,
+                    start_character: None,
+                    end_line: 5,
+                    end_character: None,
+                    kind: Some(lsp::FoldingRangeKind::Region),
+                    collapsed_text: Some("region".to_string()),
+                },
+                lsp::FoldingRange {
+                    start_line: 0,
+                    start_character: None,
+                    end_line: 1,
+                    end_character: None,
+                    kind: None,
+                    collapsed_text: None,
+                },
+                lsp::FoldingRange {
+                    start_line: 4,
+                    start_character: None,
+                    end_line: 4,
+                    end_character: None,
+                    kind: None,
+                    collapsed_text: None,
+                },
+            ]))
+        });
+
+    let ranges = project
+        .update(cx, |project, cx| project.folding_ranges(buffer.clone(), cx))
+        .await
+        .unwrap();
+    requests.next().await.unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            ranges
+                .iter()
+                .map(|range| range.range.to_point(buffer))
+                .collect::<Vec<_>>(),
+            [
+                Point::new(0, 8)..Point::new(1, 8),
+                Point::new(3, 9)..Point::new(5, 12),
+            ]
+        );
+        assert_eq!(ranges[1].collapsed_text.as_deref(), Some("region"));
+    });
+
+    // The ranges are reused until the buffer changes.
+    project
+        .update(cx, |project, cx| project.folding_ranges(buffer.clone(), cx))
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    assert!(requests.next().now_or_never().is_none());
+
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+    project
+        .update(cx, |project, cx| project.folding_ranges(buffer.clone(), cx))
+        .await
+        .unwrap();
+    requests.next().await.unwrap();
+}
+
+#[gpui::test]
+async fn test_type_hierarchy_registration(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "trait Shape {}\nstruct Circle;\nimpl Shape for Circle {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let shape = lsp::TypeHierarchyItem {
+        name: "Shape".to_string(),
+        kind: lsp::SymbolKind::INTERFACE,
+        tags: None,
+        detail: None,
+        uri: lsp::Url::from_file_path(path!("/dir/a.rs")).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 14)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 11)),
+        data: None,
+    };
+    let mut prepare_requests = fake_server
+        .handle_request::<lsp::request::TypeHierarchyPrepare, _, _>({
+            let shape = shape.clone();
+            move |_, _| {
+                let shape = shape.clone();
+                async move { Ok(Some(vec![shape])) }
+            }
+        });
+    fake_server.handle_request::<lsp::request::TypeHierarchySupertypes, _, _>(|_, _| async move {
+        Ok(None)
+    });
+
+    // Servers that didn't announce type hierarchy support aren't asked for one.
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, Point::new(0, 8), cx)
+        })
+        .await
+        .unwrap();
+    assert!(items.is_empty());
+    cx.executor().run_until_parked();
+    assert!(prepare_requests.next().now_or_never().is_none());
+
+    fake_server
+        .request::<lsp::request::RegisterCapability>(lsp::RegistrationParams {
+            registrations: vec![lsp::Registration {
+                id: "type-hierarchy".to_string(),
+                method: "textDocument/prepareTypeHierarchy".to_string(),
+                register_options: None,
+            }],
+        })
+        .await
+        .unwrap();
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, Point::new(0, 8), cx)
+        })
+        .await
+        .unwrap();
+    prepare_requests.next().await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Shape");
+    assert_eq!(items[0].lsp_item, shape);
+
+    // A type without supertypes is reported as `null`.
+    let supertypes = project
+        .update(cx, |project, cx| project.supertypes(&items[0], cx))
+        .await
+        .unwrap();
+    assert!(supertypes.is_empty());
+
+    fake_server
+        .request::<lsp::request::UnregisterCapability>(lsp::UnregistrationParams {
+            unregisterations: vec![lsp::Unregistration {
+                id: "type-hierarchy".to_string(),
+                method: "textDocument/prepareTypeHierarchy".to_string(),
+            }],
+        })
+        .await
+        .unwrap();
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, Point::new(0, 8), cx)
+        })
+        .await
+        .unwrap();
+    assert!(items.is_empty());
+    cx.executor().run_until_parked();
+    assert!(prepare_requests.next().now_or_never().is_none());
+}
+
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn main() { let x = 1; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp::SemanticTokensOptions {
+                            legend: lsp::SemanticTokensLegend {
+                                token_types: vec![
+                                    lsp::SemanticTokenType::VARIABLE,
+                                    lsp::SemanticTokenType::FUNCTION,
+                                ],
+                                token_modifiers: vec![
+                                    lsp::SemanticTokenModifier::DECLARATION,
+                                    lsp::SemanticTokenModifier::READONLY,
+                                ],
+                            },
+                            full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            ..Default::default()
+                        },
+                    ),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let token =
+        |delta_start: u32, length: u32, token_type: u32, modifiers: u32| lsp::SemanticToken {
+            delta_line: 0,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset: modifiers,
+        };
+    let fake_server = fake_servers.next().await.unwrap();
+    let mut full_requests = fake_server
+        .handle_request::<lsp::request::SemanticTokensFullRequest, _, _>(move |_, _| async move {
+            Ok(Some(lsp::SemanticTokensResult::Tokens(
+                lsp::SemanticTokens {
+                    result_id: Some("1".to_string()),
+                    // `main` and `x`.
+                    data: vec![token(3, 4, 1, 0b01), token(13, 1, 0, 0b11)],
+                },
+            )))
+        });
+    let mut delta_requests = fake_server
+        .handle_request::<lsp::request::SemanticTokensFullDeltaRequest, _, _>(
+            move |params, _| async move {
+                assert_eq!(params.previous_result_id, "1");
+                Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(
+                    lsp::SemanticTokensDelta {
+                        result_id: Some("2".to_string()),
+                        // `y` and `x` in the added statement.
+                        edits: vec![lsp::SemanticTokensEdit {
+                            start: 10,
+                            delete_count: 0,
+                            data: Some(vec![token(11, 1, 0, 0b01), token(4, 1, 0, 0)]),
+                        }],
+                    },
+                )))
+            },
+        );
+
+    let describe_tokens = |tokens: &BufferSemanticTokens, cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            tokens
+                .tokens
+                .iter()
+                .map(|token| {
+                    (
+                        token.range.to_offset(buffer),
+                        tokens.token_type(token).unwrap().to_string(),
+                        tokens.token_modifiers(token).collect::<Vec<_>>().join(" "),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let tokens = project
+        .update(cx, |project, cx| {
+            project.semantic_tokens(buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    full_requests.next().await.unwrap();
+    assert_eq!(
+        describe_tokens(&tokens, cx),
+        [
+            (3..7, "function".to_string(), "declaration".to_string()),
+            (
+                16..17,
+                "variable".to_string(),
+                "declaration readonly".to_string()
+            ),
+        ]
+    );
+
+    // Once the server returned a result id, only the changes since that result are requested.
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(22..22, " let y = x;")], None, cx)
+    });
+    let tokens = project
+        .update(cx, |project, cx| {
+            project.semantic_tokens(buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    delta_requests.next().await.unwrap();
+    assert_eq!(
+        describe_tokens(&tokens, cx),
+        [
+            (3..7, "function".to_string(), "declaration".to_string()),
+            (
+                16..17,
+                "variable".to_string(),
+                "declaration readonly".to_string()
+            ),
+            (27..28, "variable".to_string(), "declaration".to_string()),
+            (31..32, "variable".to_string(), "".to_string()),
+        ]
+    );
+    cx.run_until_parked();
+    assert!(full_requests.next().now_or_never().is_none());
+}
+
 #[gpui::test]
 async fn test_completions_without_edit_ranges(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -3065,40 +3649,286 @@ async fn test_apply_code_actions_with_commands(cx: &mut gpui::TestAppContext) {
     });
 }
 
-#[gpui::test(iterations = 10)]
-async fn test_save_file(cx: &mut gpui::TestAppContext) {
+#[gpui::test(iterations = 10)]
+async fn test_save_file(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "file1": "the old contents",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/file1"), cx))
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, cx| {
+        assert_eq!(buffer.text(), "the old contents");
+        buffer.edit([(0..0, "a line of text.\n".repeat(10 * 1024))], None, cx);
+    });
+
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+
+    let new_text = fs
+        .load(Path::new(path!("/dir/file1")))
+        .await
+        .unwrap()
+        .replace("\r\n", "\n");
+    assert_eq!(new_text, buffer.update(cx, |buffer, _| buffer.text()));
+}
+
+#[gpui::test]
+async fn test_breakpoints_follow_edits(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.rs": "fn main() {\n    let a = 1;\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/main.rs"), cx))
+        .await
+        .unwrap();
+    let breakpoint_store = project.update(cx, |project, _| project.breakpoint_store().clone());
+
+    breakpoint_store.update(cx, |store, cx| {
+        let position = buffer.read(cx).anchor_before(Point::new(1, 8));
+        store.toggle_breakpoint(buffer.clone(), position, cx);
+    });
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(0..0, "use std::fmt;\n\n")], None, cx);
+    });
+
+    let abs_path = Path::new(path!("/dir/main.rs"));
+    breakpoint_store.update(cx, |store, cx| {
+        let lines = store
+            .source_breakpoints(abs_path, cx)
+            .into_iter()
+            .map(|breakpoint| breakpoint.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [4]);
+
+        // Toggling anywhere on the same row removes the breakpoint.
+        let position = buffer.read(cx).anchor_before(Point::new(3, 0));
+        store.toggle_breakpoint(buffer.clone(), position, cx);
+        assert!(store.source_breakpoints(abs_path, cx).is_empty());
+        assert_eq!(store.breakpoint_paths().count(), 0);
+    });
+}
+
+#[gpui::test]
+async fn test_breakpoints_survive_reopening_buffer(cx: &mut gpui::TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(cx.executor());
     fs.insert_tree(
         path!("/dir"),
         json!({
-            "file1": "the old contents",
+            "main.rs": "fn main() {\n    let a = 1;\n}\n",
         }),
     )
     .await;
 
     let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
     let buffer = project
-        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/file1"), cx))
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/main.rs"), cx))
         .await
         .unwrap();
-    buffer.update(cx, |buffer, cx| {
-        assert_eq!(buffer.text(), "the old contents");
-        buffer.edit([(0..0, "a line of text.\n".repeat(10 * 1024))], None, cx);
+    let breakpoint_store = project.update(cx, |project, _| project.breakpoint_store().clone());
+    breakpoint_store.update(cx, |store, cx| {
+        let position = buffer.read(cx).anchor_before(Point::new(1, 4));
+        store.toggle_breakpoint(buffer.clone(), position, cx);
     });
 
-    project
-        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+    // The store doesn't keep closed buffers alive, but remembers their breakpoints.
+    let weak_buffer = buffer.downgrade();
+    drop(buffer);
+    cx.run_until_parked();
+    assert!(weak_buffer.upgrade().is_none());
+    let abs_path = Path::new(path!("/dir/main.rs"));
+    breakpoint_store.update(cx, |store, cx| {
+        let lines = store
+            .source_breakpoints(abs_path, cx)
+            .into_iter()
+            .map(|breakpoint| breakpoint.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2]);
+    });
+
+    // Reopening the file anchors the breakpoints in the new buffer.
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/main.rs"), cx))
         .await
         .unwrap();
+    breakpoint_store.update(cx, |store, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        let rows = store
+            .breakpoints(&buffer, None, &snapshot, cx)
+            .map(|(anchor, _)| anchor.to_point(&snapshot).row)
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [1]);
+    });
+}
 
-    let new_text = fs
-        .load(Path::new(path!("/dir/file1")))
+#[gpui::test]
+async fn test_debug_session(cx: &mut gpui::TestAppContext) {
+    use dap::{
+        events, requests, Capabilities, DebugAdapterBinary, DebugAdapterClientId,
+        DebugAdapterConfig, DebugRequestType, FakeDebugAdapter, SetBreakpointsResponse, StackFrame,
+        StackTraceResponse, StoppedEvent, StoppedEventReason, Thread, ThreadsResponse,
+    };
+    use debugger::session::{Session, SessionId, ThreadStatus};
+
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.rs": "fn main() {\n    let a = 1;\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/main.rs"), cx))
         .await
-        .unwrap()
-        .replace("\r\n", "\n");
-    assert_eq!(new_text, buffer.update(cx, |buffer, _| buffer.text()));
+        .unwrap();
+    let breakpoint_store = project.update(cx, |project, _| project.breakpoint_store().clone());
+    breakpoint_store.update(cx, |store, cx| {
+        let position = buffer.read(cx).anchor_before(Point::new(1, 0));
+        store.toggle_breakpoint(buffer.clone(), position, cx);
+    });
+
+    let (client, fake) = FakeDebugAdapter::new(
+        DebugAdapterClientId(0),
+        "fake-adapter",
+        Capabilities {
+            supports_configuration_done_request: Some(true),
+            ..Default::default()
+        },
+        cx.to_async(),
+    );
+    let sent_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    fake.handle_request::<requests::Launch, _, _>({
+        let fake = fake.clone();
+        move |_, _| {
+            fake.send_event::<events::Initialized>(());
+            async move { Ok(()) }
+        }
+    });
+    fake.handle_request::<requests::SetBreakpoints, _, _>({
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |args, _| {
+            sent_breakpoints.lock().push((
+                args.source.path,
+                args.breakpoints
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|breakpoint| breakpoint.line)
+                    .collect::<Vec<_>>(),
+            ));
+            async move {
+                Ok(SetBreakpointsResponse {
+                    breakpoints: Vec::new(),
+                })
+            }
+        }
+    });
+    let mut configuration_done =
+        fake.handle_request::<requests::ConfigurationDone, _, _>(|_, _| async move { Ok(()) });
+    fake.handle_request::<requests::Threads, _, _>(|_, _| async move {
+        Ok(ThreadsResponse {
+            threads: vec![Thread {
+                id: 1,
+                name: "main".to_string(),
+            }],
+        })
+    });
+    fake.handle_request::<requests::StackTrace, _, _>(|_, _| async move {
+        Ok(StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 10,
+                name: "main".to_string(),
+                source: None,
+                line: 2,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let config = DebugAdapterConfig {
+        label: "Debug main".to_string(),
+        adapter: "fake-adapter".to_string(),
+        binary: DebugAdapterBinary {
+            command: "path/to/fake-adapter".into(),
+            arguments: Vec::new(),
+            env: None,
+            cwd: None,
+        },
+        request: DebugRequestType::Launch,
+        args: json!({}),
+    };
+    let session = Session::boot(
+        SessionId(0),
+        config,
+        Arc::new(client),
+        breakpoint_store.clone(),
+        &mut cx.to_async(),
+    )
+    .await
+    .unwrap();
+    configuration_done.next().await.unwrap();
+    assert_eq!(
+        *sent_breakpoints.lock(),
+        [(Some(path!("/dir/main.rs").to_string()), vec![2])]
+    );
+
+    fake.send_event::<events::Stopped>(StoppedEvent {
+        reason: StoppedEventReason::Breakpoint,
+        description: None,
+        thread_id: Some(1),
+        preserve_focus_hint: None,
+        text: None,
+        all_threads_stopped: Some(true),
+        hit_breakpoint_ids: None,
+    });
+    cx.run_until_parked();
+    session.read_with(cx, |session, _| {
+        assert_eq!(session.active_thread(), Some(1));
+        let thread = session.thread(1).unwrap();
+        assert_eq!(thread.status, ThreadStatus::Stopped);
+        assert_eq!(
+            thread
+                .stack_frames
+                .iter()
+                .map(|frame| frame.id)
+                .collect::<Vec<_>>(),
+            [10]
+        );
+    });
 }
 
 #[gpui::test(iterations = 30)]
@@ -6584,6 +7414,399 @@ async fn test_single_file_diffs(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_sequencer_operations_stop_on_conflicts(cx: &mut gpui::TestAppContext) {
+    use ::git::repository::{
+        ConflictVersions, LogEntry, RebaseAction, RebaseTodoEntry, SequencerOperation,
+    };
+
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".git": {},
+            "a.txt": "a\n",
+        }),
+    )
+    .await;
+    let dot_git = Path::new(path!("/dir/.git"));
+    fs.with_git_state(dot_git, false, |state| {
+        state.commit_history = ["ccc", "bbb", "aaa"]
+            .into_iter()
+            .map(|sha| LogEntry {
+                sha: sha.into(),
+                parents: Vec::new(),
+                subject: format!("commit {sha}").into(),
+                author_name: "Test".into(),
+                author_email: "test@example.com".into(),
+                commit_timestamp: 0,
+            })
+            .collect();
+    });
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+    let sequencer_operation = |cx: &mut gpui::TestAppContext| {
+        let operation = repository.update(cx, |repository, _| repository.sequencer_operation());
+        async move { operation.await.unwrap().unwrap() }
+    };
+    let sequencer_commands = || {
+        let mut commands = Vec::new();
+        fs.with_git_state(dot_git, false, |state| {
+            commands = mem::take(&mut state.sequencer_commands)
+        });
+        commands
+    };
+
+    // Commits that apply cleanly leave no operation behind.
+    repository
+        .update(cx, |repository, cx| {
+            repository.cherry_pick(vec!["aaa".into(), "bbb".into()], cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sequencer_operation(cx).await, None);
+    assert_eq!(sequencer_commands(), ["cherry-pick aaa bbb"]);
+
+    // A conflicting revert stops until the conflict is resolved and staged.
+    fs.with_git_state(dot_git, true, |state| {
+        state.conflict_versions.insert(
+            "a.txt".into(),
+            ConflictVersions {
+                base: Some("a\n".into()),
+                ours: Some("a\n".into()),
+                theirs: None,
+            },
+        );
+    });
+    repository
+        .update(cx, |repository, cx| {
+            repository.revert(vec!["bbb".into()], cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        sequencer_operation(cx).await,
+        Some(SequencerOperation::Revert)
+    );
+    assert!(repository
+        .update(cx, |repository, cx| repository.continue_operation(cx))
+        .await
+        .unwrap()
+        .is_err());
+    repository
+        .update(cx, |repository, cx| {
+            repository.stage_entries(vec!["a.txt".into()], cx)
+        })
+        .await
+        .unwrap();
+    repository
+        .update(cx, |repository, cx| repository.continue_operation(cx))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sequencer_operation(cx).await, None);
+    assert_eq!(sequencer_commands(), ["revert bbb", "revert --continue"]);
+
+    // The todo list covers the commits after `onto`, oldest first.
+    let mut todo = repository
+        .update(cx, |repository, _| {
+            repository.rebase_todo(Some("aaa".into()))
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        todo.iter()
+            .map(|entry| entry.sha.as_ref())
+            .collect::<Vec<_>>(),
+        ["bbb", "ccc"]
+    );
+    todo[1].action = RebaseAction::Reword;
+    todo[1].message = Some("reworded".into());
+
+    // Aborting a stopped rebase drops its conflicts.
+    fs.with_git_state(dot_git, true, |state| {
+        state
+            .conflict_versions
+            .insert("a.txt".into(), ConflictVersions::default());
+    });
+    repository
+        .update(cx, |repository, cx| {
+            repository.rebase(Some("aaa".into()), todo.clone(), cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        sequencer_operation(cx).await,
+        Some(SequencerOperation::Rebase)
+    );
+    assert!(repository
+        .update(cx, |repository, cx| {
+            repository.cherry_pick(vec!["ccc".into()], cx)
+        })
+        .await
+        .unwrap()
+        .is_err());
+    repository
+        .update(cx, |repository, cx| repository.abort_operation(cx))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sequencer_operation(cx).await, None);
+    assert_eq!(
+        sequencer_commands(),
+        ["rebase --interactive aaa", "rebase --abort"]
+    );
+    fs.with_git_state(dot_git, false, |state| {
+        assert!(state.conflict_versions.is_empty());
+        assert_eq!(
+            state.rebase_todo,
+            [
+                RebaseTodoEntry {
+                    action: RebaseAction::Pick,
+                    sha: "bbb".into(),
+                    subject: "commit bbb".into(),
+                    message: None,
+                },
+                RebaseTodoEntry {
+                    action: RebaseAction::Reword,
+                    sha: "ccc".into(),
+                    subject: "commit ccc".into(),
+                    message: Some("reworded".into()),
+                },
+            ]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_set_upstream_branch_and_push_tag(cx: &mut gpui::TestAppContext) {
+    use ::git::repository::Tag;
+    use askpass::AskPassDelegate;
+
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(path!("/dir"), json!({ ".git": {}, "a.txt": "a\n" }))
+        .await;
+    let dot_git = Path::new(path!("/dir/.git"));
+    fs.insert_branches(dot_git, &["main", "feature"]);
+    fs.with_git_state(dot_git, false, |state| {
+        state.tags.push(Tag {
+            name: "v1.0".into(),
+            sha: "aaa".into(),
+            subject: "Release 1.0".into(),
+            timestamp: None,
+        });
+    });
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+    let upstreams = |cx: &mut gpui::TestAppContext| {
+        let branches = repository.update(cx, |repository, _| repository.branches());
+        async move {
+            let mut upstreams = branches
+                .await
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|branch| {
+                    let upstream = branch.upstream.map(|upstream| upstream.ref_name);
+                    (branch.name, upstream)
+                })
+                .collect::<Vec<_>>();
+            upstreams.sort();
+            upstreams
+        }
+    };
+
+    repository
+        .update(cx, |repository, _| {
+            repository.set_upstream_branch("feature".into(), Some("origin/feature".into()))
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        upstreams(cx).await,
+        [
+            ("feature".into(), Some("refs/remotes/origin/feature".into())),
+            ("main".into(), None),
+        ]
+    );
+
+    repository
+        .update(cx, |repository, _| {
+            repository.set_upstream_branch("feature".into(), None)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        upstreams(cx).await,
+        [("feature".into(), None), ("main".into(), None)]
+    );
+
+    assert!(repository
+        .update(cx, |repository, _| {
+            repository.set_upstream_branch("missing".into(), Some("origin/missing".into()))
+        })
+        .await
+        .unwrap()
+        .is_err());
+
+    for tag in ["v1.0", "v2.0"] {
+        let askpass = AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {});
+        let result = repository
+            .update(cx, |repository, cx| {
+                repository.push_tag(tag.into(), "origin".into(), askpass, cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.is_ok(), tag == "v1.0");
+    }
+    fs.with_git_state(dot_git, false, |state| {
+        assert_eq!(
+            state.pushed_tags,
+            [("v1.0".to_string(), "origin".to_string())]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_submodules_and_worktrees(cx: &mut gpui::TestAppContext) {
+    use ::git::repository::{GitWorktree, RepoPath, Submodule, SubmoduleStatus};
+    use askpass::AskPassDelegate;
+
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(path!("/dir"), json!({ ".git": {}, "a.txt": "a\n" }))
+        .await;
+    let dot_git = Path::new(path!("/dir/.git"));
+    fs.insert_branches(dot_git, &["main", "existing"]);
+    fs.with_git_state(dot_git, false, |state| {
+        state.submodules = [
+            ("libs/one", SubmoduleStatus::Uninitialized),
+            ("libs/two", SubmoduleStatus::OutOfDate),
+            ("libs/three", SubmoduleStatus::Conflicted),
+        ]
+        .into_iter()
+        .map(|(path, status)| Submodule {
+            path: path.into(),
+            sha: "aaa".into(),
+            status,
+        })
+        .collect();
+        state.worktrees = vec![GitWorktree {
+            path: PathBuf::from(path!("/dir")),
+            sha: "aaa".into(),
+            branch: Some("main".into()),
+            is_main: true,
+        }];
+    });
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    cx.run_until_parked();
+    let repository = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+    let submodule_statuses = |cx: &mut gpui::TestAppContext| {
+        let submodules = repository.update(cx, |repository, _| repository.submodules());
+        async move {
+            submodules
+                .await
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|submodule| {
+                    (
+                        submodule.path.to_string_lossy().into_owned(),
+                        submodule.status,
+                    )
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+    let update_submodules = |paths: &[&str], cx: &mut gpui::TestAppContext| {
+        let paths = paths.iter().map(|path| RepoPath::from(*path)).collect();
+        let askpass = AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {});
+        let update = repository.update(cx, |repository, cx| {
+            repository.update_submodules(paths, askpass, cx)
+        });
+        async move { update.await.unwrap() }
+    };
+
+    update_submodules(&["libs/one"], cx).await.unwrap();
+    assert_eq!(
+        submodule_statuses(cx).await,
+        [
+            ("libs/one".to_string(), SubmoduleStatus::UpToDate),
+            ("libs/two".to_string(), SubmoduleStatus::OutOfDate),
+            ("libs/three".to_string(), SubmoduleStatus::Conflicted),
+        ]
+    );
+
+    assert!(update_submodules(&["libs/missing"], cx).await.is_err());
+
+    // Updating without paths updates every submodule that isn't conflicted.
+    update_submodules(&[], cx).await.unwrap();
+    assert_eq!(
+        submodule_statuses(cx).await,
+        [
+            ("libs/one".to_string(), SubmoduleStatus::UpToDate),
+            ("libs/two".to_string(), SubmoduleStatus::UpToDate),
+            ("libs/three".to_string(), SubmoduleStatus::Conflicted),
+        ]
+    );
+
+    for (branch, create_branch) in [("existing", false), ("feature", true)] {
+        repository
+            .update(cx, |repository, _| {
+                repository.create_worktree(
+                    PathBuf::from(format!("/dir-{branch}")),
+                    branch.into(),
+                    create_branch,
+                )
+            })
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    let worktrees = repository
+        .update(cx, |repository, _| repository.worktrees())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        worktrees
+            .iter()
+            .map(|worktree| (worktree.branch.as_deref(), worktree.is_main))
+            .collect::<Vec<_>>(),
+        [
+            (Some("main"), true),
+            (Some("existing"), false),
+            (Some("feature"), false),
+        ]
+    );
+    let mut branches = repository
+        .update(cx, |repository, _| repository.branches())
+        .await
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|branch| branch.name.to_string())
+        .collect::<Vec<_>>();
+    branches.sort();
+    assert_eq!(branches, ["existing", "feature", "main"]);
+}
+
 async fn search(
     project: &Entity<Project>,
     query: SearchQuery,
@@ -274,6 +274,8 @@ impl Project {
                     show_summary: spawn_task.show_summary,
                     show_command: spawn_task.show_command,
                     show_rerun: spawn_task.show_rerun,
+                    cwd: path.as_deref().map(Path::to_path_buf),
+                    problem_matcher: spawn_task.problem_matcher,
                     completion_rx,
                 });
 
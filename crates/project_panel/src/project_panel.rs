@@ -687,6 +687,7 @@ impl ProjectPanel {
             let is_read_only = project.is_read_only(cx);
             let is_remote = project.is_via_collab();
             let is_local = project.is_local();
+            let abs_path = worktree.abs_path().join(&entry.path);
 
             let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
                 menu.context(self.focus_handle.clone()).map(|menu| {
@@ -712,6 +713,14 @@ impl ProjectPanel {
                                 menu.separator()
                                     .action("Find in Folder…", Box::new(NewSearchInDirectory))
                             })
+                            .when(!is_dir, |menu| {
+                                menu.separator().action(
+                                    "Compare With…",
+                                    Box::new(zed_actions::diff::CompareWith {
+                                        path: Some(abs_path),
+                                    }),
+                                )
+                            })
                             .when(is_unfoldable, |menu| {
                                 menu.action("Unfold Directory", Box::new(UnfoldDirectory))
                             })
@@ -268,6 +268,18 @@ messages!(
     (GetProjectSymbolsResponse, Background),
     (GetReferences, Background),
     (GetReferencesResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (CallHierarchyIncomingCalls, Background),
+    (CallHierarchyIncomingCallsResponse, Background),
+    (CallHierarchyOutgoingCalls, Background),
+    (CallHierarchyOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (TypeHierarchySupertypes, Background),
+    (TypeHierarchySupertypesResponse, Background),
+    (TypeHierarchySubtypes, Background),
+    (TypeHierarchySubtypesResponse, Background),
     (GetSignatureHelp, Background),
     (GetSignatureHelpResponse, Background),
     (GetSupermavenApiKey, Background),
@@ -341,6 +353,17 @@ messages!(
     (ResolveInlayHint, Background),
     (ResolveInlayHintResponse, Background),
     (RefreshCodeLens, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
+    (RefreshSemanticTokens, Background),
+    (GetFoldingRanges, Background),
+    (GetFoldingRangesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
     (RespondToChannelInvite, Foreground),
@@ -464,6 +487,49 @@ messages!(
     (GitDiff, Background),
     (GitDiffResponse, Background),
     (GitInit, Background),
+    (GitStashPush, Background),
+    (GitStashList, Background),
+    (GitStashListResponse, Background),
+    (GitStashShow, Background),
+    (GitStashShowResponse, Background),
+    (GitStashApply, Background),
+    (GitStashPop, Background),
+    (GitStashDrop, Background),
+    (GitLog, Background),
+    (GitLogResponse, Background),
+    (LoadCommitDiff, Background),
+    (LoadCommitDiffResponse, Background),
+    (LoadConflictVersions, Background),
+    (LoadConflictVersionsResponse, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitRebaseTodo, Background),
+    (GitRebaseTodoResponse, Background),
+    (GitRebase, Background),
+    (GitGetSequencerOperation, Background),
+    (GitGetSequencerOperationResponse, Background),
+    (GitContinueOperation, Background),
+    (GitAbortOperation, Background),
+    (GitDiffSince, Background),
+    (GitDiffSinceResponse, Background),
+    (GitLoadRevisionText, Background),
+    (GitLoadRevisionTextResponse, Background),
+    (GitDeleteBranch, Background),
+    (GitRenameBranch, Background),
+    (GitSetUpstreamBranch, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitPushTag, Background),
+    (GitGetSubmodules, Background),
+    (GitSubmodulesResponse, Background),
+    (GitUpdateSubmodules, Background),
+    (GitGetWorktrees, Background),
+    (GitWorktreesResponse, Background),
+    (GitCreateWorktree, Background),
+    (GitGetRemoteUrl, Background),
+    (GitGetRemoteUrlResponse, Background),
 );
 
 request_messages!(
@@ -506,6 +572,21 @@ request_messages!(
     (GetPrivateUserInfo, GetPrivateUserInfoResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
     (GetReferences, GetReferencesResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (
+        CallHierarchyIncomingCalls,
+        CallHierarchyIncomingCallsResponse
+    ),
+    (
+        CallHierarchyOutgoingCalls,
+        PrepareTypeHierarchy,
+        TypeHierarchySupertypes,
+        TypeHierarchySubtypes,
+        CallHierarchyOutgoingCallsResponse
+    ),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (TypeHierarchySupertypes, TypeHierarchySupertypesResponse),
+    (TypeHierarchySubtypes, TypeHierarchySubtypesResponse),
     (GetSignatureHelp, GetSignatureHelpResponse),
     (OpenUnstagedDiff, OpenUnstagedDiffResponse),
     (OpenUncommittedDiff, OpenUncommittedDiffResponse),
@@ -539,6 +620,12 @@ request_messages!(
     (CountLanguageModelTokens, CountLanguageModelTokensResponse),
     (RefreshInlayHints, Ack),
     (RefreshCodeLens, Ack),
+    (GetSemanticTokens, GetSemanticTokensResponse),
+    (RefreshSemanticTokens, Ack),
+    (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
     (ReloadBuffers, ReloadBuffersResponse),
@@ -614,6 +701,36 @@ request_messages!(
     (CheckForPushedCommits, CheckForPushedCommitsResponse),
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
+    (GitStashPush, Ack),
+    (GitStashList, GitStashListResponse),
+    (GitStashShow, GitStashShowResponse),
+    (GitStashApply, Ack),
+    (GitStashPop, Ack),
+    (GitStashDrop, Ack),
+    (GitLog, GitLogResponse),
+    (LoadCommitDiff, LoadCommitDiffResponse),
+    (LoadConflictVersions, LoadConflictVersionsResponse),
+    (GitCherryPick, RemoteMessageResponse),
+    (GitRevert, RemoteMessageResponse),
+    (GitRebaseTodo, GitRebaseTodoResponse),
+    (GitRebase, RemoteMessageResponse),
+    (GitGetSequencerOperation, GitGetSequencerOperationResponse),
+    (GitContinueOperation, RemoteMessageResponse),
+    (GitAbortOperation, RemoteMessageResponse),
+    (GitDiffSince, GitDiffSinceResponse),
+    (GitLoadRevisionText, GitLoadRevisionTextResponse),
+    (GitDeleteBranch, Ack),
+    (GitRenameBranch, Ack),
+    (GitSetUpstreamBranch, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitGetSubmodules, GitSubmodulesResponse),
+    (GitUpdateSubmodules, RemoteMessageResponse),
+    (GitGetWorktrees, GitWorktreesResponse),
+    (GitCreateWorktree, Ack),
+    (GitGetRemoteUrl, GitGetRemoteUrlResponse),
 );
 
 entity_messages!(
@@ -647,6 +764,9 @@ entity_messages!(
     GetProjectSymbols,
     GetReferences,
     GetSignatureHelp,
+    PrepareCallHierarchy,
+    CallHierarchyIncomingCalls,
+    CallHierarchyOutgoingCalls,
     OpenUnstagedDiff,
     OpenUncommittedDiff,
     GetTypeDefinition,
@@ -666,6 +786,12 @@ entity_messages!(
     PrepareRename,
     RefreshInlayHints,
     RefreshCodeLens,
+    GetSemanticTokens,
+    RefreshSemanticTokens,
+    GetFoldingRanges,
+    GetSelectionRanges,
+    GetDocumentLinks,
+    ResolveDocumentLink,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
@@ -723,6 +849,36 @@ entity_messages!(
     CheckForPushedCommits,
     GitDiff,
     GitInit,
+    GitStashPush,
+    GitStashList,
+    GitStashShow,
+    GitStashApply,
+    GitStashPop,
+    GitStashDrop,
+    GitLog,
+    LoadCommitDiff,
+    LoadConflictVersions,
+    GitCherryPick,
+    GitRevert,
+    GitRebaseTodo,
+    GitRebase,
+    GitGetSequencerOperation,
+    GitContinueOperation,
+    GitAbortOperation,
+    GitDiffSince,
+    GitLoadRevisionText,
+    GitDeleteBranch,
+    GitRenameBranch,
+    GitSetUpstreamBranch,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitPushTag,
+    GitGetSubmodules,
+    GitUpdateSubmodules,
+    GitGetWorktrees,
+    GitCreateWorktree,
+    GitGetRemoteUrl,
 );
 
 entity_messages!(
@@ -0,0 +1,517 @@
+mod quickfix_panel_settings;
+
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{
+    actions, uniform_list, Action, AsyncWindowContext, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity,
+};
+use language::DiagnosticSeverity;
+use menu::{Confirm, SelectNext, SelectPrevious};
+use project::Fs;
+use quickfix_panel_settings::{QuickfixPanelDockPosition, QuickfixPanelSettings};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{prelude::*, ListItem};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    quickfix::{QuickfixEntry, QuickfixList, QuickfixListEvent},
+    Workspace,
+};
+
+actions!(quickfix_panel, [ToggleFocus]);
+
+const QUICKFIX_PANEL_KEY: &str = "QuickfixPanel";
+
+pub fn init(cx: &mut App) {
+    QuickfixPanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<QuickfixPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedQuickfixPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+/// Lists the entries of the workspace's quickfix list, opening the one
+/// that gets confirmed or clicked.
+pub struct QuickfixPanel {
+    workspace: WeakEntity<Workspace>,
+    quickfix_list: Entity<QuickfixList>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    selected_index: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    _quickfix_list_subscription: Subscription,
+}
+
+impl QuickfixPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(QUICKFIX_PANEL_KEY) })
+            .await
+            .context("loading quickfix panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedQuickfixPanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    panel.height = serialized_panel.height.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let quickfix_list = workspace.quickfix_list().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace_handle = cx.entity().downgrade();
+        cx.new(|cx| {
+            let subscription = cx.subscribe(&quickfix_list, |this, quickfix_list, event, cx| {
+                match event {
+                    QuickfixListEvent::EntriesReplaced { reveal } => {
+                        this.selected_index = None;
+                        this.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+                        if *reveal {
+                            cx.emit(PanelEvent::Activate);
+                        }
+                    }
+                    QuickfixListEvent::SelectionChanged => {
+                        this.selected_index = quickfix_list.read(cx).selected_index();
+                        if let Some(ix) = this.selected_index {
+                            this.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+                        }
+                    }
+                }
+                cx.notify();
+            });
+            Self {
+                workspace: workspace_handle,
+                quickfix_list,
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                height: None,
+                selected_index: None,
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                _quickfix_list_subscription: subscription,
+            }
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let height = self.height;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        QUICKFIX_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedQuickfixPanel { width, height })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_offset(1, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_offset(-1, cx);
+    }
+
+    fn select_offset(&mut self, offset: isize, cx: &mut Context<Self>) {
+        let entry_count = self.quickfix_list.read(cx).entries().len();
+        if entry_count == 0 {
+            return;
+        }
+        let ix = self.selected_index.map_or(0, |ix| {
+            ix.saturating_add_signed(offset).min(entry_count - 1)
+        });
+        self.selected_index = Some(ix);
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_entry(ix, window, cx);
+        }
+    }
+
+    fn open_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                editor::quickfix::open_quickfix_entry(workspace, ix, window, cx)
+                    .detach_and_log_err(cx);
+            })
+            .log_err();
+    }
+
+    fn render_entry(&self, ix: usize, entry: &QuickfixEntry, cx: &mut Context<Self>) -> ListItem {
+        let (icon, color) = match entry.severity {
+            Some(DiagnosticSeverity::ERROR) => (IconName::XCircle, Color::Error),
+            Some(DiagnosticSeverity::WARNING) => (IconName::Warning, Color::Warning),
+            Some(_) => (IconName::Info, Color::Muted),
+            None => (IconName::MagnifyingGlass, Color::Muted),
+        };
+        let (row, column) = entry.position.start(cx);
+        let location = format!(
+            "{}:{}:{}",
+            entry.path.path.to_string_lossy(),
+            row + 1,
+            column + 1
+        );
+
+        ListItem::new(("quickfix-entry", ix))
+            .toggle_state(self.selected_index == Some(ix))
+            .start_slot(Icon::new(icon).size(IconSize::Small).color(color))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_index = Some(ix);
+                this.open_entry(ix, window, cx);
+                cx.notify();
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(location)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(entry.message.clone())
+                            .size(LabelSize::Small)
+                            .single_line(),
+                    ),
+            )
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("QuickfixPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+}
+
+impl Panel for QuickfixPanel {
+    fn persistent_name() -> &'static str {
+        "Quickfix Panel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match QuickfixPanelSettings::get_global(cx).dock {
+            QuickfixPanelDockPosition::Left => DockPosition::Left,
+            QuickfixPanelDockPosition::Bottom => DockPosition::Bottom,
+            QuickfixPanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<QuickfixPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left => QuickfixPanelDockPosition::Left,
+                    DockPosition::Bottom => QuickfixPanelDockPosition::Bottom,
+                    DockPosition::Right => QuickfixPanelDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, window: &Window, cx: &App) -> Pixels {
+        let settings = QuickfixPanelSettings::get_global(cx);
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or(settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or(settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        QuickfixPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListX)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Quickfix Panel")
+    }
+
+    fn icon_label(&self, _: &Window, cx: &App) -> Option<String> {
+        let entry_count = self.quickfix_list.read(cx).entries().len();
+        (entry_count > 0).then(|| entry_count.to_string())
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
+
+impl Focusable for QuickfixPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for QuickfixPanel {}
+
+impl Render for QuickfixPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let quickfix_list = self.quickfix_list.read(cx);
+        let entry_count = quickfix_list.entries().len();
+        let title = if quickfix_list.title().is_empty() {
+            "Quickfix List".into()
+        } else {
+            quickfix_list.title().clone()
+        };
+
+        let header = h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+            .child(
+                Label::new(match entry_count {
+                    1 => "1 entry".to_string(),
+                    _ => format!("{entry_count} entries"),
+                })
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+            );
+
+        let content = if entry_count == 0 {
+            div()
+                .p_2()
+                .child(
+                    Label::new(
+                        "The quickfix list is empty. Send search results or diagnostics to it, \
+                        or run a task with a problem matcher.",
+                    )
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            uniform_list(
+                cx.entity().clone(),
+                "quickfix-entries",
+                entry_count,
+                |this, range, _, cx| {
+                    let entries = this
+                        .quickfix_list
+                        .read(cx)
+                        .entries()
+                        .get(range.clone())
+                        .unwrap_or_default()
+                        .to_vec();
+                    range
+                        .zip(entries)
+                        .map(|(ix, entry)| this.render_entry(ix, &entry, cx))
+                        .collect()
+                },
+            )
+            .size_full()
+            .track_scroll(self.scroll_handle.clone())
+            .into_any_element()
+        };
+
+        v_flex()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(header)
+            .child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    use editor::Editor;
+    use gpui::TestAppContext;
+    use language::Point;
+    use project::{FakeFs, Project, ProjectPath};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::quickfix::QuickfixPosition;
+
+    #[gpui::test]
+    async fn test_quickfix_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "main.rs": "fn main() {\n    let ü = 1;\n}\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let panel = workspace.update(cx, |workspace, cx| QuickfixPanel::new(workspace, cx));
+        let quickfix_list = workspace.update(cx, |workspace, _| workspace.quickfix_list().clone());
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let path = ProjectPath {
+            worktree_id,
+            path: Path::new("main.rs").into(),
+        };
+
+        quickfix_list.update(cx, |quickfix_list, cx| {
+            quickfix_list.set_entries(
+                "Problems",
+                vec![
+                    QuickfixEntry {
+                        path: path.clone(),
+                        position: QuickfixPosition::Printed { row: 1, column: 8 },
+                        message: "unused variable".into(),
+                        severity: Some(DiagnosticSeverity::WARNING),
+                    },
+                    QuickfixEntry {
+                        path: path.clone(),
+                        position: QuickfixPosition::Printed { row: 1, column: 12 },
+                        message: "literal".into(),
+                        severity: None,
+                    },
+                ],
+                false,
+                cx,
+            )
+        });
+        panel.update_in(cx, |panel, window, cx| {
+            assert_eq!(panel.icon_label(window, cx), Some("2".to_string()));
+            assert_eq!(panel.selected_index, None);
+            panel.select_next(&SelectNext, window, cx);
+            panel.select_next(&SelectNext, window, cx);
+            panel.select_next(&SelectNext, window, cx);
+            assert_eq!(panel.selected_index, Some(1));
+            panel.select_previous(&SelectPrevious, window, cx);
+            assert_eq!(panel.selected_index, Some(0));
+            panel.select_next(&SelectNext, window, cx);
+        });
+
+        // Confirming opens the entry and anchors the printed positions of its
+        // file, whose columns count characters.
+        panel.update_in(cx, |panel, window, cx| panel.confirm(&Confirm, window, cx));
+        cx.run_until_parked();
+        let editor = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<Editor>(cx).unwrap()
+        });
+        editor.update(cx, |editor, cx| {
+            let selection = editor.selections.newest::<Point>(cx);
+            assert_eq!(selection.start, Point::new(1, 13));
+        });
+        quickfix_list.read_with(cx, |quickfix_list, cx| {
+            assert_eq!(quickfix_list.selected_index(), Some(1));
+            assert!(quickfix_list
+                .entries()
+                .iter()
+                .all(|entry| matches!(entry.position, QuickfixPosition::Anchored { .. })));
+        });
+
+        // Anchored entries follow the edits of their buffer.
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+            });
+            editor.insert("// ü\n", window, cx);
+        });
+        quickfix_list.read_with(cx, |quickfix_list, cx| {
+            assert_eq!(
+                quickfix_list
+                    .entries()
+                    .iter()
+                    .map(|entry| entry.position.start(cx))
+                    .collect::<Vec<_>>(),
+                [(2, 8), (2, 12)]
+            );
+        });
+
+        // Replacing the entries resets the selection.
+        quickfix_list.update(cx, |quickfix_list, cx| {
+            quickfix_list.set_entries("Problems", Vec::new(), false, cx)
+        });
+        panel.update_in(cx, |panel, window, cx| {
+            assert_eq!(panel.selected_index, None);
+            assert_eq!(panel.icon_label(window, cx), None);
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            super::init(cx);
+        });
+    }
+}
@@ -0,0 +1,53 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickfixPanelDockPosition {
+    Left,
+    Bottom,
+    Right,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct QuickfixPanelSettings {
+    pub button: bool,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+    pub dock: QuickfixPanelDockPosition,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct QuickfixPanelSettingsContent {
+    /// Whether to show the quickfix panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Default width (in pixels) of the quickfix panel, when docked to the left or right.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+    /// Default height (in pixels) of the quickfix panel, when docked to the bottom.
+    ///
+    /// Default: 240
+    pub default_height: Option<f32>,
+    /// The position of the quickfix panel
+    ///
+    /// Default: bottom
+    pub dock: Option<QuickfixPanelDockPosition>,
+}
+
+impl Settings for QuickfixPanelSettings {
+    const KEY: Option<&'static str> = Some("quickfix_panel");
+
+    type FileContent = QuickfixPanelSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::App,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -40,6 +40,7 @@ use ui::{
 use util::paths::PathMatcher;
 use workspace::{
     item::{BreadcrumbText, Item, ItemEvent, ItemHandle},
+    quickfix::{QuickfixEntry, QuickfixPosition},
     searchable::{Direction, SearchableItem, SearchableItemHandle},
     DeploySearch, ItemNavHistory, NewSearch, ToolbarItemEvent, ToolbarItemLocation,
     ToolbarItemView, Workspace, WorkspaceId,
@@ -47,7 +48,13 @@ use workspace::{
 
 actions!(
     project_search,
-    [SearchInNew, ToggleFocus, NextField, ToggleFilters]
+    [
+        SearchInNew,
+        ToggleFocus,
+        NextField,
+        ToggleFilters,
+        SendToQuickfixList
+    ]
 );
 
 #[derive(Default)]
@@ -105,6 +112,9 @@ pub fn init(cx: &mut App) {
         register_workspace_action_for_present_search(workspace, |workspace, action, window, cx| {
             ProjectSearchView::search_in_new(workspace, action, window, cx)
         });
+        register_workspace_action_for_present_search(workspace, |workspace, action, window, cx| {
+            ProjectSearchView::send_to_quickfix_list(workspace, action, window, cx)
+        });
 
         // Both on present and dismissed search, we need to unconditionally handle those actions to focus from the editor.
         workspace.register_action(move |workspace, action: &DeploySearch, window, cx| {
@@ -876,6 +886,65 @@ impl ProjectSearchView {
         Self::existing_or_new_search(workspace, existing, action, window, cx);
     }
 
+    /// Replaces the quickfix list with the matches of the active project search.
+    fn send_to_quickfix_list(
+        workspace: &mut Workspace,
+        _: &SendToQuickfixList,
+        _: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(search_view) = workspace
+            .active_item(cx)
+            .and_then(|item| item.downcast::<ProjectSearchView>())
+        else {
+            return;
+        };
+        let (title, entries) = {
+            let search = search_view.read(cx).entity.read(cx);
+            let title = match &search.active_query {
+                Some(query) => format!("Search results for \"{}\"", query.as_str()),
+                None => "Search results".to_string(),
+            };
+            let excerpts = search.excerpts.read(cx);
+            let snapshot = excerpts.snapshot(cx);
+            let entries = search
+                .match_ranges
+                .iter()
+                .filter_map(|range| {
+                    let (buffer, range, _) = snapshot
+                        .range_to_buffer_ranges(range.clone())
+                        .into_iter()
+                        .next()?;
+                    let file = buffer.file()?;
+                    let start = buffer.offset_to_point(range.start);
+                    let line = buffer
+                        .text_for_range(
+                            language::Point::new(start.row, 0)
+                                ..language::Point::new(start.row, buffer.line_len(start.row)),
+                        )
+                        .collect::<String>();
+                    Some(QuickfixEntry {
+                        path: ProjectPath {
+                            worktree_id: file.worktree_id(cx),
+                            path: file.path().clone(),
+                        },
+                        position: QuickfixPosition::Anchored {
+                            buffer: excerpts.buffer(buffer.remote_id())?,
+                            range: buffer.anchor_before(range.start)
+                                ..buffer.anchor_after(range.end),
+                        },
+                        message: line.trim().to_string().into(),
+                        severity: None,
+                    })
+                })
+                .collect::<Vec<_>>();
+            (title, entries)
+        };
+        workspace.quickfix_list().update(cx, |quickfix_list, cx| {
+            quickfix_list.set_entries(title, entries, true, cx)
+        });
+    }
+
     fn search_in_new(
         workspace: &mut Workspace,
         _: &SearchInNew,
@@ -2370,6 +2439,90 @@ pub mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_send_to_quickfix_list(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+                "three.rs": "const THREE: usize = one::ONE + two::TWO;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = workspace.update_in(cx, |workspace, window, cx| {
+            let search_view = cx.new(|cx| {
+                ProjectSearchView::new(workspace.weak_handle(), search.clone(), window, cx, None)
+            });
+            workspace.add_item_to_active_pane(
+                Box::new(search_view.clone()),
+                None,
+                true,
+                window,
+                cx,
+            );
+            search_view
+        });
+
+        perform_project_search(&search_view, "TWO", cx);
+        workspace.update_in(cx, |workspace, window, cx| {
+            ProjectSearchView::send_to_quickfix_list(workspace, &SendToQuickfixList, window, cx)
+        });
+
+        // Editing a buffer afterwards moves its entries along.
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/three.rs"), cx)
+            })
+            .await
+            .unwrap();
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+
+        workspace.update(cx, |workspace, cx| {
+            let quickfix_list = workspace.quickfix_list().read(cx);
+            assert_eq!(quickfix_list.title().as_ref(), "Search results for \"TWO\"");
+            assert_eq!(
+                quickfix_list
+                    .entries()
+                    .iter()
+                    .map(|entry| (
+                        entry.path.path.to_string_lossy().to_string(),
+                        entry.position.start(cx),
+                        entry.message.to_string(),
+                        entry.severity,
+                    ))
+                    .collect::<Vec<_>>(),
+                [
+                    (
+                        "three.rs".to_string(),
+                        (1, 32),
+                        "const THREE: usize = one::ONE + two::TWO;".to_string(),
+                        None
+                    ),
+                    (
+                        "three.rs".to_string(),
+                        (1, 37),
+                        "const THREE: usize = one::ONE + two::TWO;".to_string(),
+                        None
+                    ),
+                    (
+                        "two.rs".to_string(),
+                        (0, 6),
+                        "const TWO: usize = one::ONE + one::ONE;".to_string(),
+                        None
+                    ),
+                ]
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_deploy_project_search_focus(cx: &mut TestAppContext) {
         init_test(cx);
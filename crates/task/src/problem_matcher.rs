@@ -0,0 +1,248 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Describes how to find problems, such as compiler errors, in the output of a task.
+/// Each output line matching [`ProblemMatcher::regexp`] reports one problem, whose
+/// parts are taken from the capture groups with the configured indices.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// Regular expression matching an output line that reports a problem.
+    pub regexp: String,
+    /// Index of the capture group with the path of the file, relative to the task's working directory.
+    pub file: usize,
+    /// Index of the capture group with the 1-based line of the problem.
+    pub line: usize,
+    /// Index of the capture group with the 1-based column of the problem, if any.
+    pub column: Option<usize>,
+    /// Index of the capture group with the severity of the problem (`error`, `warning`, ...), if any.
+    /// Problems without a severity are reported as errors.
+    pub severity: Option<usize>,
+    /// Index of the capture group with the message of the problem.
+    pub message: usize,
+    /// Regular expression matching a line with the severity and the message of the problem
+    /// located by the next line matching [`ProblemMatcher::regexp`], for tools like rustc that
+    /// print them on separate lines. Its first capture group is the severity and its second one
+    /// the message, which are used for the next problem whose own line has no message.
+    pub header_regexp: Option<String>,
+}
+
+impl Default for ProblemMatcher {
+    /// Matches the `file:line:column: severity: message` lines printed by most compilers and linters,
+    /// where the column and the severity are optional, and rustc's `severity: message` lines followed
+    /// by a ` --> file:line:column` line.
+    fn default() -> Self {
+        Self {
+            regexp: r"^\s*(?:-->\s*)?([^\s:][^:]*):(\d+)(?::(\d+))?:?\s*(?:(error|warning|info|note|help)(?:\[[^\]]*\])?:\s*)?(.*)$"
+                .to_string(),
+            file: 1,
+            line: 2,
+            column: Some(3),
+            severity: Some(4),
+            message: 5,
+            header_regexp: Some(
+                r"^(error|warning|info|note|help)(?:\[[^\]]*\])?:\s*(.*)$".to_string(),
+            ),
+        }
+    }
+}
+
+/// How serious a [`Problem`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProblemSeverity {
+    /// An error, also used for problems without a severity.
+    Error,
+    /// A warning.
+    Warning,
+    /// Any other severity, like notes and hints.
+    Info,
+}
+
+impl ProblemSeverity {
+    fn parse(severity: &str) -> Self {
+        let severity = severity.to_ascii_lowercase();
+        if severity.starts_with("err") {
+            Self::Error
+        } else if severity.starts_with("warn") {
+            Self::Warning
+        } else {
+            Self::Info
+        }
+    }
+}
+
+/// A problem reported in a task's output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// The path of the file, as printed by the task.
+    pub path: PathBuf,
+    /// The 0-based line of the problem.
+    pub row: u32,
+    /// The 0-based column of the problem, in characters.
+    pub column: u32,
+    /// How serious the problem is.
+    pub severity: ProblemSeverity,
+    /// The message describing the problem.
+    pub message: String,
+}
+
+impl ProblemMatcher {
+    /// Returns the problems reported by the given output lines, in order.
+    pub fn match_lines<'a>(
+        &self,
+        lines: impl IntoIterator<Item = &'a str>,
+    ) -> anyhow::Result<Vec<Problem>> {
+        let regex = Regex::new(&self.regexp)
+            .with_context(|| format!("invalid problem matcher regexp {:?}", self.regexp))?;
+        let header_regex = self
+            .header_regexp
+            .as_deref()
+            .map(|header_regexp| {
+                Regex::new(header_regexp).with_context(|| {
+                    format!("invalid problem matcher header regexp {header_regexp:?}")
+                })
+            })
+            .transpose()?;
+
+        let mut problems = Vec::new();
+        let mut header = None;
+        for line in lines {
+            if let Some(mut problem) = regex
+                .captures(line)
+                .and_then(|captures| self.match_line(&captures))
+            {
+                if let Some((severity, message)) = header.take() {
+                    if problem.message.is_empty() {
+                        problem.severity = severity;
+                        problem.message = message;
+                    }
+                }
+                problems.push(problem);
+            } else if let Some(captures) = header_regex
+                .as_ref()
+                .and_then(|header_regex| header_regex.captures(line))
+            {
+                let group = |ix: usize| captures.get(ix).map(|group| group.as_str().trim());
+                let severity = group(1).map_or(ProblemSeverity::Error, ProblemSeverity::parse);
+                header = Some((severity, group(2).unwrap_or_default().to_string()));
+            }
+        }
+        Ok(problems)
+    }
+
+    fn match_line(&self, captures: &Captures) -> Option<Problem> {
+        let group = |ix: usize| captures.get(ix).map(|group| group.as_str().trim());
+        let number = |ix: usize| group(ix)?.parse::<u32>().ok();
+
+        let path = group(self.file).filter(|path| !path.is_empty())?;
+        let row = number(self.line)?.saturating_sub(1);
+        let column = self
+            .column
+            .and_then(number)
+            .map_or(0, |column| column.saturating_sub(1));
+        let severity = self
+            .severity
+            .and_then(group)
+            .map_or(ProblemSeverity::Error, ProblemSeverity::parse);
+        Some(Problem {
+            path: PathBuf::from(path),
+            row,
+            column,
+            severity,
+            message: group(self.message).unwrap_or_default().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_problem_matcher() {
+        let output = [
+            "   Compiling app v0.1.0",
+            "src/main.c:12:5: error: use of undeclared identifier 'x'",
+            "src/lib.c:3: warning: unused variable",
+            "lib/util.js:7:1: Missing semicolon.",
+            "warning[unused_imports]: unused import: `std::fs`",
+            " --> src/main.rs:1:5",
+            "  |",
+            "1 | use std::fs;",
+            "Finished with 2 errors",
+        ];
+        let problems = ProblemMatcher::default().match_lines(output).unwrap();
+        assert_eq!(
+            problems,
+            [
+                Problem {
+                    path: PathBuf::from("src/main.c"),
+                    row: 11,
+                    column: 4,
+                    severity: ProblemSeverity::Error,
+                    message: "use of undeclared identifier 'x'".to_string(),
+                },
+                Problem {
+                    path: PathBuf::from("src/lib.c"),
+                    row: 2,
+                    column: 0,
+                    severity: ProblemSeverity::Warning,
+                    message: "unused variable".to_string(),
+                },
+                Problem {
+                    path: PathBuf::from("lib/util.js"),
+                    row: 6,
+                    column: 0,
+                    severity: ProblemSeverity::Error,
+                    message: "Missing semicolon.".to_string(),
+                },
+                Problem {
+                    path: PathBuf::from("src/main.rs"),
+                    row: 0,
+                    column: 4,
+                    severity: ProblemSeverity::Warning,
+                    message: "unused import: `std::fs`".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_problem_matcher() {
+        let matcher: ProblemMatcher = serde_json_lenient::from_str(
+            r#"{
+                "regexp": "^(\\w+) in (\\S+) at line (\\d+): (.*)$",
+                "severity": 1,
+                "file": 2,
+                "line": 3,
+                "column": null,
+                "message": 4,
+                "header_regexp": null
+            }"#,
+        )
+        .unwrap();
+        let problems = matcher
+            .match_lines(["Warning in app.py at line 4: shadowed name", "ok"])
+            .unwrap();
+        assert_eq!(
+            problems,
+            [Problem {
+                path: PathBuf::from("app.py"),
+                row: 3,
+                column: 0,
+                severity: ProblemSeverity::Warning,
+                message: "shadowed name".to_string(),
+            }]
+        );
+
+        let invalid = ProblemMatcher {
+            regexp: "(".to_string(),
+            ..ProblemMatcher::default()
+        };
+        assert!(invalid.match_lines(["a:1: b"]).is_err());
+    }
+}
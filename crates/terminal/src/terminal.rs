@@ -13,7 +13,7 @@ use alacritty_terminal::{
     selection::{Selection, SelectionRange, SelectionType},
     sync::FairMutex,
     term::{
-        cell::Cell,
+        cell::{Cell, Flags},
         search::{Match, RegexIter, RegexSearch},
         Config, RenderableCursor, TermMode,
     },
@@ -42,7 +42,7 @@ use pty_info::PtyProcessInfo;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
 use smol::channel::{Receiver, Sender};
-use task::{HideStrategy, Shell, TaskId};
+use task::{HideStrategy, ProblemMatcher, Shell, TaskId};
 use terminal_settings::{AlternateScroll, CursorShape, TerminalSettings};
 use theme::{ActiveTheme, Theme};
 use util::{paths::home_dir, truncate_and_trailoff};
@@ -109,6 +109,8 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
+    /// The task running in the terminal finished, and its summary was written to the output.
+    TaskFinished,
 }
 
 #[derive(Clone, Debug)]
@@ -643,6 +645,9 @@ pub struct TaskState {
     pub show_summary: bool,
     pub show_command: bool,
     pub show_rerun: bool,
+    /// The directory the task was spawned in, which relative paths in its output are resolved against.
+    pub cwd: Option<PathBuf>,
+    pub problem_matcher: Option<ProblemMatcher>,
 }
 
 /// A status of the current terminal tab's task.
@@ -1408,6 +1413,33 @@ impl Terminal {
         lines
     }
 
+    /// Returns all lines of the terminal's scrollback and screen, with the rows
+    /// that were soft-wrapped joined back into the line they belong to.
+    pub fn output_lines(&self, cx: &App) -> Task<Vec<String>> {
+        let term = self.term.clone();
+        cx.background_spawn(async move {
+            let terminal = term.lock();
+
+            let mut lines = Vec::new();
+            let mut line_buffer = String::new();
+            for line in terminal.topmost_line().0..=terminal.bottommost_line().0 {
+                let mut wrapped = false;
+                for cell in &terminal.grid()[Line(line)] {
+                    line_buffer.push(cell.c);
+                    wrapped = cell.flags.contains(Flags::WRAPLINE);
+                }
+                if !wrapped {
+                    lines.push(line_buffer.trim_end().to_string());
+                    line_buffer.clear();
+                }
+            }
+            if !line_buffer.is_empty() {
+                lines.push(line_buffer.trim_end().to_string());
+            }
+            lines
+        })
+    }
+
     pub fn focus_in(&self) {
         if self.last_content.mode.contains(TermMode::FOCUS_IN_OUT) {
             self.write_to_pty("\x1b[I".to_string());
@@ -1823,6 +1855,7 @@ impl Terminal {
             // After the task summary is output once, no more text is appended to the terminal.
             unsafe { append_text_to_term(&mut self.term.lock(), &lines_to_show) };
         }
+        cx.emit(Event::TaskFinished);
 
         match task.hide {
             HideStrategy::Never => {}
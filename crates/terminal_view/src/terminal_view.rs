@@ -11,8 +11,11 @@ use gpui::{
     Pixels, Render, ScrollWheelEvent, Stateful, Styled, Subscription, Task, WeakEntity,
 };
 use itertools::Itertools;
+use language::DiagnosticSeverity;
 use persistence::TERMINAL_DB;
-use project::{search::SearchQuery, terminals::TerminalKind, Entry, Metadata, Project};
+use project::{
+    search::SearchQuery, terminals::TerminalKind, Entry, Metadata, Project, ProjectPath,
+};
 use schemars::JsonSchema;
 use terminal::{
     alacritty_terminal::{
@@ -36,6 +39,7 @@ use workspace::{
     item::{
         BreadcrumbText, Item, ItemEvent, SerializableItem, TabContentParams, TabTooltipContent,
     },
+    quickfix::{QuickfixEntry, QuickfixPosition},
     register_serializable_item,
     searchable::{Direction, SearchEvent, SearchOptions, SearchableItem, SearchableItemHandle},
     CloseActiveItem, NewCenterTerminal, NewTerminal, OpenOptions, OpenVisible, ToolbarItemLocation,
@@ -46,6 +50,7 @@ use anyhow::Context as _;
 use serde::Deserialize;
 use settings::{Settings, SettingsStore};
 use smol::Timer;
+use task::{Problem, ProblemSeverity};
 use zed_actions::assistant::InlineAssist;
 
 use std::{
@@ -842,7 +847,7 @@ fn subscribe_for_terminal_events(
     let terminal_events_subscription = cx.subscribe_in(
         terminal,
         window,
-        move |this, _, event, window, cx| match event {
+        move |this, terminal, event, window, cx| match event {
             Event::Wakeup => {
                 cx.notify();
                 cx.emit(Event::Wakeup);
@@ -974,6 +979,7 @@ fn subscribe_for_terminal_events(
                     .detach_and_log_err(cx)
                 }
             },
+            Event::TaskFinished => set_quickfix_list_from_task(terminal, &workspace, cx),
             Event::BreadcrumbsChanged => cx.emit(ItemEvent::UpdateBreadcrumbs),
             Event::CloseTerminal => cx.emit(ItemEvent::CloseItem),
             Event::SelectionsChanged => {
@@ -985,6 +991,87 @@ fn subscribe_for_terminal_events(
     vec![terminal_subscription, terminal_events_subscription]
 }
 
+/// Replaces the quickfix list with the problems found in the output of the
+/// terminal's finished task, if the task has a problem matcher.
+fn set_quickfix_list_from_task(
+    terminal: &Entity<Terminal>,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut App,
+) {
+    let terminal = terminal.read(cx);
+    let Some(task) = terminal.task() else {
+        return;
+    };
+    let Some(problem_matcher) = task.problem_matcher.clone() else {
+        return;
+    };
+    let output = terminal.output_lines(cx);
+    let title = task.label.clone();
+    let cwd = task.cwd.clone();
+    let workspace = workspace.clone();
+
+    cx.spawn(|mut cx| async move {
+        let problems = cx
+            .background_spawn(async move {
+                let output = output.await;
+                problem_matcher.match_lines(output.iter().map(String::as_str))
+            })
+            .await?;
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let entries =
+                quickfix_entries_for_problems(problems, cwd.as_deref(), workspace.project(), cx);
+            workspace.quickfix_list().update(cx, |quickfix_list, cx| {
+                quickfix_list.set_entries(title, entries, false, cx)
+            });
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Returns the quickfix entries for the problems in the project's files,
+/// resolving relative paths against `cwd`. Files are only opened once their
+/// entries are, which is when the printed positions get anchored.
+fn quickfix_entries_for_problems(
+    problems: Vec<Problem>,
+    cwd: Option<&Path>,
+    project: &Entity<Project>,
+    cx: &App,
+) -> Vec<QuickfixEntry> {
+    let project = project.read(cx);
+    problems
+        .into_iter()
+        .filter_map(|problem| {
+            let path = match cwd {
+                Some(cwd) => cwd.join(&problem.path),
+                None => problem.path.clone(),
+            };
+            let path = if path.is_absolute() {
+                let (worktree, relative_path) = project.find_worktree(&path, cx)?;
+                ProjectPath {
+                    worktree_id: worktree.read(cx).id(),
+                    path: relative_path.into(),
+                }
+            } else {
+                project.find_project_path(&path, cx)?
+            };
+            Some(QuickfixEntry {
+                path,
+                position: QuickfixPosition::Printed {
+                    row: problem.row,
+                    column: problem.column,
+                },
+                message: problem.message.into(),
+                severity: Some(match problem.severity {
+                    ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                    ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                    ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                }),
+            })
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 enum OpenTarget {
     Worktree(PathWithPosition, Entry),
@@ -1767,7 +1854,7 @@ fn first_project_directory(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
 mod tests {
     use super::*;
     use gpui::TestAppContext;
-    use project::{Entry, Project, ProjectPath, Worktree};
+    use project::{Entry, Project, Worktree};
     use std::path::Path;
     use workspace::AppState;
 
@@ -1877,6 +1964,47 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_quickfix_entries_for_task_problems(cx: &mut TestAppContext) {
+        let (project, _workspace) = init_test(cx).await;
+        let (worktree, _entry) = create_folder_wt(project.clone(), "/root/", cx).await;
+        let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+
+        let output = [
+            "error[E0425]: cannot find value `x` in this scope",
+            " --> src/main.rs:2:5",
+            "src/lib.rs:7: warning: unused import",
+            "/elsewhere/main.c:1:1: error: outside of the project",
+        ];
+        let problems = task::ProblemMatcher::default().match_lines(output).unwrap();
+        let entries = cx.read(|cx| {
+            quickfix_entries_for_problems(problems, Some(Path::new("/root")), &project, cx)
+        });
+        assert_eq!(
+            entries,
+            [
+                QuickfixEntry {
+                    path: ProjectPath {
+                        worktree_id,
+                        path: Path::new("src/main.rs").into(),
+                    },
+                    position: QuickfixPosition::Printed { row: 1, column: 4 },
+                    message: "cannot find value `x` in this scope".into(),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                },
+                QuickfixEntry {
+                    path: ProjectPath {
+                        worktree_id,
+                        path: Path::new("src/lib.rs").into(),
+                    },
+                    position: QuickfixPosition::Printed { row: 6, column: 0 },
+                    message: "unused import".into(),
+                    severity: Some(DiagnosticSeverity::WARNING),
+                },
+            ]
+        );
+    }
+
     /// Creates a worktree with 1 file: /root.txt
     pub async fn init_test(cx: &mut TestAppContext) -> (Entity<Project>, Entity<Workspace>) {
         let params = cx.update(AppState::test);
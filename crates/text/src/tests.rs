@@ -623,6 +623,233 @@ fn test_history() {
     assert_eq!(buffer.text(), "X12cde6");
 }
 
+#[test]
+fn test_undo_history() {
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "1234".into());
+    buffer.set_group_interval(Duration::from_secs(0));
+
+    buffer.edit([(1..1, "abx")]);
+    buffer.edit([(3..4, "yzef")]);
+    buffer.edit([(0..1, ""), (5..7, "cd")]);
+    buffer.undo();
+    assert_eq!(buffer.text(), "1abyzef234");
+
+    let history = buffer.undo_history();
+    assert_eq!(
+        history,
+        UndoHistory {
+            undo_stack: vec![
+                UndoHistoryTransaction {
+                    edits: vec![(1..1, "abx".into())],
+                    inverse_edits: vec![(1..4, "".into())],
+                },
+                UndoHistoryTransaction {
+                    edits: vec![(3..4, "yzef".into())],
+                    inverse_edits: vec![(3..7, "x".into())],
+                },
+            ],
+            redo_stack: vec![UndoHistoryTransaction {
+                edits: vec![(0..1, "".into()), (5..7, "cd".into())],
+                inverse_edits: vec![(0..0, "1".into()), (4..6, "ef".into())],
+            }],
+        }
+    );
+    // Describing the history doesn't change the buffer.
+    assert_eq!(buffer.text(), "1abyzef234");
+    assert_eq!(buffer.history.undo_stack.len(), 2);
+    assert_eq!(buffer.history.redo_stack.len(), 1);
+
+    assert_eq!(history.base_text_for(&buffer.text()), Some("1234".into()));
+    assert_eq!(history.base_text_for("1abyzef23"), None);
+
+    // Restoring the history leaves the text alone until it's traveled.
+    let mut restored = Buffer::new(0, BufferId::new(2).unwrap(), buffer.text());
+    let version = restored.version();
+    assert!(restored.restore_undo_history(&history));
+    assert_eq!(restored.version(), version);
+    assert_eq!(restored.undo_history(), history);
+    assert_eq!(restored.version(), version);
+
+    restored.undo();
+    assert_eq!(restored.text(), "1abx234");
+    restored.redo();
+    restored.redo();
+    assert_eq!(restored.text(), "abyzcd234");
+    restored.undo();
+    restored.undo();
+    restored.undo();
+    assert_eq!(restored.text(), "1234");
+    assert!(!restored.restore_undo_history(&history));
+}
+
+#[test]
+fn test_undo_tree() {
+    let now = Instant::now();
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "".into());
+
+    buffer.start_transaction_at(now);
+    buffer.edit([(0..0, "a")]);
+    let (transaction_1, _) = buffer.end_transaction_at(now).unwrap();
+
+    buffer.start_transaction_at(now + Duration::from_secs(1));
+    buffer.edit([(1..1, "b")]);
+    let (transaction_2, _) = buffer
+        .end_transaction_at(now + Duration::from_secs(1))
+        .unwrap();
+    buffer.undo();
+
+    // Editing after an undo starts a new branch instead of dropping the old one.
+    buffer.start_transaction_at(now + Duration::from_secs(2));
+    buffer.edit([(1..1, "c")]);
+    let (transaction_3, _) = buffer
+        .end_transaction_at(now + Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(buffer.text(), "ac");
+    assert_eq!(
+        buffer
+            .undo_tree()
+            .into_iter()
+            .map(|node| (node.transaction_id, node.parent))
+            .collect::<Vec<_>>(),
+        [
+            (transaction_1, None),
+            (transaction_2, Some(transaction_1)),
+            (transaction_3, Some(transaction_1)),
+        ]
+    );
+    assert_eq!(buffer.undo_tree_position(), Some(transaction_3));
+
+    buffer.travel_to_transaction(Some(transaction_2));
+    assert_eq!(buffer.text(), "ab");
+    assert_eq!(buffer.undo_tree_position(), Some(transaction_2));
+    buffer.undo();
+    assert_eq!(buffer.text(), "a");
+    buffer.redo();
+    assert_eq!(buffer.text(), "ab");
+
+    // Traveling by steps follows the order in which states were created.
+    buffer.earlier(UndoTreeDistance::Steps(1));
+    assert_eq!(buffer.text(), "a");
+    buffer.earlier(UndoTreeDistance::Steps(5));
+    assert_eq!(buffer.text(), "");
+    assert_eq!(buffer.undo_tree_position(), None);
+    buffer.later(UndoTreeDistance::Steps(3));
+    assert_eq!(buffer.text(), "ac");
+
+    buffer.earlier(UndoTreeDistance::Time(Duration::from_millis(1500)));
+    assert_eq!(buffer.text(), "a");
+    buffer.later(UndoTreeDistance::Time(Duration::from_secs(1)));
+    assert_eq!(buffer.text(), "ab");
+    buffer.later(UndoTreeDistance::Time(Duration::from_secs(10)));
+    assert_eq!(buffer.text(), "ac");
+
+    buffer.travel_to_transaction(None);
+    assert_eq!(buffer.text(), "");
+    assert_eq!(buffer.undo_tree().len(), 3);
+
+    // States keep their number when others are dropped.
+    buffer.forget_transaction(transaction_2);
+    assert_eq!(
+        buffer
+            .undo_tree()
+            .into_iter()
+            .map(|node| (node.transaction_id, node.sequence))
+            .collect::<Vec<_>>(),
+        [(transaction_1, 1), (transaction_3, 3)]
+    );
+}
+
+#[test]
+fn test_undo_tree_time_travel_with_out_of_order_timestamps() {
+    let now = Instant::now();
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "".into());
+
+    buffer.start_transaction_at(now);
+    buffer.edit([(0..0, "a")]);
+    let (transaction_1, _) = buffer.end_transaction_at(now).unwrap();
+
+    buffer.start_transaction_at(now + Duration::from_secs(10));
+    buffer.edit([(1..1, "b")]);
+    let (transaction_2, _) = buffer
+        .end_transaction_at(now + Duration::from_secs(10))
+        .unwrap();
+    buffer.undo();
+
+    buffer.start_transaction_at(now + Duration::from_secs(20));
+    buffer.edit([(1..1, "c")]);
+    buffer.end_transaction_at(now + Duration::from_secs(20));
+
+    // Grouping makes the first transaction newer than the second one.
+    buffer.group_until_transaction(transaction_1);
+    assert_eq!(
+        buffer
+            .undo_tree()
+            .into_iter()
+            .map(|node| (node.transaction_id, node.edited_at))
+            .collect::<Vec<_>>(),
+        [
+            (transaction_1, now + Duration::from_secs(20)),
+            (transaction_2, now + Duration::from_secs(10)),
+        ]
+    );
+    assert_eq!(buffer.undo_tree_position(), Some(transaction_1));
+
+    buffer.earlier(UndoTreeDistance::Time(Duration::from_secs(5)));
+    assert_eq!(buffer.undo_tree_position(), Some(transaction_2));
+    buffer.later(UndoTreeDistance::Time(Duration::from_secs(5)));
+    assert_eq!(buffer.undo_tree_position(), Some(transaction_2));
+    buffer.later(UndoTreeDistance::Time(Duration::from_secs(10)));
+    assert_eq!(buffer.undo_tree_position(), Some(transaction_1));
+    buffer.earlier(UndoTreeDistance::Time(Duration::from_secs(30)));
+    assert_eq!(buffer.undo_tree_position(), None);
+}
+
+#[test]
+fn test_undo_tree_abandoned_transactions() {
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "".into());
+
+    // Only the most recently abandoned transactions are kept.
+    let mut transaction_ids = Vec::new();
+    for _ in 0..MAX_ABANDONED_TRANSACTIONS + 4 {
+        buffer.edit([(0..0, "a")]);
+        transaction_ids.push(buffer.undo().unwrap().0);
+    }
+    let nodes = buffer.undo_tree();
+    assert_eq!(nodes.len(), MAX_ABANDONED_TRANSACTIONS + 1);
+    assert_eq!(nodes[0].transaction_id, transaction_ids[3]);
+    assert_eq!(
+        nodes.last().unwrap().transaction_id,
+        transaction_ids.last().copied().unwrap()
+    );
+
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "".into());
+    buffer.edit([(0..0, "a")]);
+    let transaction_1 = buffer.undo_tree_position().unwrap();
+    buffer.edit([(1..1, "b")]);
+    buffer.undo();
+    buffer.undo();
+    buffer.edit([(0..0, "c")]);
+    let transaction_3 = buffer.undo_tree_position().unwrap();
+    buffer.edit([(1..1, "d")]);
+    let transaction_4 = buffer.undo_tree_position().unwrap();
+    buffer.edit([(2..2, "e")]);
+    let transaction_5 = buffer.undo_tree_position().unwrap();
+    assert_eq!(buffer.undo_tree().len(), 5);
+
+    // Forgetting a transaction drops the abandoned branches made on top of it.
+    buffer.forget_transaction(transaction_1);
+    // Forgetting a transaction of the undo stack keeps the ones after it.
+    buffer.forget_transaction(transaction_4);
+    assert_eq!(
+        buffer
+            .undo_tree()
+            .into_iter()
+            .map(|node| (node.transaction_id, node.parent))
+            .collect::<Vec<_>>(),
+        [(transaction_3, None), (transaction_5, Some(transaction_3))]
+    );
+}
+
 #[test]
 fn test_finalize_last_transaction() {
     let now = Instant::now();
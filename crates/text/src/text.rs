@@ -117,6 +117,11 @@ pub struct BufferSnapshot {
 #[derive(Clone, Debug)]
 pub struct HistoryEntry {
     transaction: Transaction,
+    /// The transaction that was on top of the undo stack when this one was
+    /// made, or `None` if it was made on top of the original text.
+    parent: Option<TransactionId>,
+    /// The order in which the transaction was made, starting from 1.
+    sequence: usize,
     first_edit_at: Instant,
     last_edit_at: Instant,
     suppress_grouping: bool,
@@ -135,11 +140,159 @@ impl HistoryEntry {
     }
 }
 
+/// A state of a buffer's undo tree: the text after applying a transaction on
+/// top of its parent's state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UndoTreeNode {
+    pub transaction_id: TransactionId,
+    /// The state this one branched off from, or `None` for the original text.
+    pub parent: Option<TransactionId>,
+    /// The order in which the state was made, starting from 1. Unlike its
+    /// position in the tree, it doesn't change as other states are dropped.
+    pub sequence: usize,
+    pub edited_at: Instant,
+}
+
+/// How far to travel chronologically through a buffer's undo tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UndoTreeDistance {
+    /// A number of states, regardless of the branch they're on.
+    Steps(usize),
+    /// An amount of time between the edits leading to the states.
+    Time(Duration),
+}
+
+/// A buffer's undo and redo stacks, described by the text edits of their
+/// transactions rather than by operations, so that they can outlive the
+/// buffer they were recorded in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UndoHistory {
+    /// The transactions that can be undone, oldest first.
+    pub undo_stack: Vec<UndoHistoryTransaction>,
+    /// The transactions that can be redone, in the order they'd be redone.
+    pub redo_stack: Vec<UndoHistoryTransaction>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UndoHistoryTransaction {
+    /// The edits applying the transaction, relative to the text before it.
+    pub edits: Vec<(Range<usize>, String)>,
+    /// The edits reverting the transaction, relative to the text after it.
+    pub inverse_edits: Vec<(Range<usize>, String)>,
+}
+
+impl UndoHistory {
+    pub fn is_empty(&self) -> bool {
+        self.undo_stack.is_empty() && self.redo_stack.is_empty()
+    }
+
+    /// Returns the text before the oldest transaction, if undoing and redoing
+    /// every transaction of this history in `text` leads back to it.
+    pub fn base_text_for(&self, text: &str) -> Option<String> {
+        let mut base_text = text.to_string();
+        for transaction in self.undo_stack.iter().rev() {
+            base_text = apply_text_edits(&base_text, &transaction.inverse_edits)?;
+        }
+
+        let mut current_text = base_text.clone();
+        for transaction in &self.undo_stack {
+            current_text = apply_text_edits(&current_text, &transaction.edits)?;
+        }
+        if current_text != text {
+            return None;
+        }
+
+        for transaction in &self.redo_stack {
+            current_text = apply_text_edits(&current_text, &transaction.edits)?;
+        }
+        for transaction in self.redo_stack.iter().rev() {
+            current_text = apply_text_edits(&current_text, &transaction.inverse_edits)?;
+        }
+        if current_text != text {
+            return None;
+        }
+
+        Some(base_text)
+    }
+}
+
+/// A copy of a buffer's text and its undo and redo stacks, from which its
+/// [`UndoHistory`] can be built on another thread.
+pub struct UndoHistorySnapshot {
+    buffer: Buffer,
+}
+
+impl UndoHistorySnapshot {
+    /// Undoes and redoes every transaction of the captured stacks to describe
+    /// them as text edits.
+    pub fn build(self) -> UndoHistory {
+        let mut history = UndoHistory::default();
+
+        let mut buffer = self.buffer.branch();
+        buffer.history.undo_stack = self.buffer.history.undo_stack.clone();
+        buffer.history.restored = self.buffer.history.restored.clone();
+        buffer.lamport_clock = self.buffer.lamport_clock;
+        loop {
+            let before = buffer.snapshot.clone();
+            if buffer.undo().is_none() {
+                break;
+            }
+            history
+                .undo_stack
+                .push(Buffer::undo_history_transaction(&before, &buffer.snapshot).1);
+        }
+        history.undo_stack.reverse();
+
+        let mut buffer = self.buffer;
+        loop {
+            let before = buffer.snapshot.clone();
+            if buffer.redo().is_none() {
+                break;
+            }
+            history
+                .redo_stack
+                .push(Buffer::undo_history_transaction(&before, &buffer.snapshot).0);
+        }
+
+        history
+    }
+}
+
+/// Applies sorted, non-overlapping edits to `text`, or returns `None` if they
+/// don't fit it.
+fn apply_text_edits(text: &str, edits: &[(Range<usize>, String)]) -> Option<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut offset = 0;
+    for (range, new_text) in edits {
+        if range.start < offset || range.start > range.end {
+            return None;
+        }
+        result.push_str(text.get(offset..range.start)?);
+        text.get(range.clone())?;
+        result.push_str(new_text);
+        offset = range.end;
+    }
+    result.push_str(text.get(offset..)?);
+    Some(result)
+}
+
+/// How many transactions of abandoned undo tree branches a buffer's history
+/// keeps, beyond which the oldest ones are dropped.
+const MAX_ABANDONED_TRANSACTIONS: usize = 1024;
+
+#[derive(Clone)]
 struct History {
     base_text: Rope,
     operations: TreeMap<clock::Lamport, Operation>,
     undo_stack: Vec<HistoryEntry>,
     redo_stack: Vec<HistoryEntry>,
+    /// Transactions dropped from the redo stack by new edits, kept so that the
+    /// branches of the undo tree they belong to can be returned to.
+    abandoned: Vec<HistoryEntry>,
+    /// Transactions restored from an [`UndoHistory`], described by their text
+    /// edits until they're first undone or redone.
+    restored: HashMap<TransactionId, UndoHistoryTransaction>,
+    next_sequence: usize,
     transaction_depth: usize,
     group_interval: Duration,
 }
@@ -184,6 +337,9 @@ impl History {
             operations: Default::default(),
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
+            abandoned: Vec::new(),
+            restored: HashMap::default(),
+            next_sequence: 1,
             transaction_depth: 0,
             // Don't group transactions in tests unless we opt in, because it's a footgun.
             #[cfg(any(test, feature = "test-support"))]
@@ -206,12 +362,16 @@ impl History {
         self.transaction_depth += 1;
         if self.transaction_depth == 1 {
             let id = clock.tick();
+            let parent = self.undo_stack.last().map(HistoryEntry::transaction_id);
+            let sequence = self.next_sequence();
             self.undo_stack.push(HistoryEntry {
                 transaction: Transaction {
                     id,
                     start,
                     edit_ids: Default::default(),
                 },
+                parent,
+                sequence,
                 first_edit_at: now,
                 last_edit_at: now,
                 suppress_grouping: false,
@@ -235,9 +395,10 @@ impl History {
                 .is_empty()
             {
                 self.undo_stack.pop();
+                self.next_sequence -= 1;
                 None
             } else {
-                self.redo_stack.clear();
+                self.abandon_redo_stack();
                 let entry = self.undo_stack.last_mut().unwrap();
                 entry.last_edit_at = now;
                 Some(entry)
@@ -247,6 +408,12 @@ impl History {
         }
     }
 
+    fn next_sequence(&mut self) -> usize {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        sequence
+    }
+
     fn group(&mut self) -> Option<TransactionId> {
         let mut count = 0;
         let mut entries = self.undo_stack.iter();
@@ -292,6 +459,25 @@ impl History {
             if let Some(entry) = entries_to_merge.last_mut() {
                 last_entry.last_edit_at = entry.last_edit_at;
             }
+
+            // Keep numbering the transactions that remain without gaps, when
+            // the merged ones are the latest.
+            for entry in entries_to_merge.iter().rev() {
+                if entry.sequence + 1 == self.next_sequence {
+                    self.next_sequence -= 1;
+                }
+            }
+
+            // Branches made on top of the merged transactions now start from
+            // the transaction they were merged into.
+            for abandoned in &mut self.abandoned {
+                if entries_to_merge
+                    .iter()
+                    .any(|entry| Some(entry.transaction.id) == abandoned.parent)
+                {
+                    abandoned.parent = Some(last_entry.transaction.id);
+                }
+            }
         }
 
         self.undo_stack.truncate(new_len);
@@ -307,13 +493,118 @@ impl History {
 
     fn push_transaction(&mut self, transaction: Transaction, now: Instant) {
         assert_eq!(self.transaction_depth, 0);
+        let parent = self.undo_stack.last().map(HistoryEntry::transaction_id);
+        let sequence = self.next_sequence();
         self.undo_stack.push(HistoryEntry {
             transaction,
+            parent,
+            sequence,
             first_edit_at: now,
             last_edit_at: now,
             suppress_grouping: false,
         });
-        self.redo_stack.clear();
+        self.abandon_redo_stack();
+    }
+
+    fn abandon_redo_stack(&mut self) {
+        self.abandoned.append(&mut self.redo_stack);
+        if self.abandoned.len() > MAX_ABANDONED_TRANSACTIONS {
+            let excess = self.abandoned.len() - MAX_ABANDONED_TRANSACTIONS;
+            self.abandoned.drain(..excess);
+            self.prune_abandoned();
+        }
+    }
+
+    /// Drops the abandoned transactions that can no longer be returned to,
+    /// because a transaction they were made on top of is gone.
+    fn prune_abandoned(&mut self) {
+        loop {
+            let transaction_ids = self
+                .entries()
+                .map(HistoryEntry::transaction_id)
+                .collect::<HashSet<_>>();
+            let len = self.abandoned.len();
+            self.abandoned.retain(|entry| {
+                entry
+                    .parent
+                    .map_or(true, |parent| transaction_ids.contains(&parent))
+            });
+            if self.abandoned.len() == len {
+                self.restored
+                    .retain(|transaction_id, _| transaction_ids.contains(transaction_id));
+                break;
+            }
+        }
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.undo_stack
+            .iter()
+            .chain(&self.redo_stack)
+            .chain(&self.abandoned)
+    }
+
+    /// Returns the transactions leading from the original text to the given
+    /// state of the undo tree, oldest first.
+    fn path_to(&self, transaction_id: Option<TransactionId>) -> Option<Vec<TransactionId>> {
+        let parents = self
+            .entries()
+            .map(|entry| (entry.transaction.id, entry.parent))
+            .collect::<HashMap<_, _>>();
+        let mut path = Vec::new();
+        let mut next = transaction_id;
+        while let Some(transaction_id) = next {
+            if path.len() > parents.len() {
+                return None;
+            }
+            path.push(transaction_id);
+            next = *parents.get(&transaction_id)?;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Moves the entries of the undo and redo stacks so that the undo stack
+    /// holds `path`, returning the transactions to undo and then the ones to
+    /// redo to get there.
+    fn travel(&mut self, path: &[TransactionId]) -> (Vec<Transaction>, Vec<Transaction>) {
+        assert_eq!(self.transaction_depth, 0);
+
+        let common_len = self
+            .undo_stack
+            .iter()
+            .zip(path)
+            .take_while(|(entry, transaction_id)| entry.transaction.id == **transaction_id)
+            .count();
+        let mut undone = Vec::new();
+        while self.undo_stack.len() > common_len {
+            undone.push(self.pop_undo().unwrap().transaction.clone());
+        }
+
+        let mut redone = Vec::new();
+        for transaction_id in &path[common_len..] {
+            if self
+                .redo_stack
+                .last()
+                .map_or(true, |entry| entry.transaction.id != *transaction_id)
+            {
+                self.abandon_redo_stack();
+            }
+            let entry = if self.redo_stack.is_empty() {
+                let ix = self
+                    .abandoned
+                    .iter()
+                    .position(|entry| entry.transaction.id == *transaction_id)
+                    .unwrap();
+                self.abandoned.remove(ix)
+            } else {
+                self.redo_stack.pop().unwrap()
+            };
+            redone.push(entry.transaction.clone());
+            self.undo_stack.push(entry);
+        }
+
+        (undone, redone)
     }
 
     fn push_undo(&mut self, op_id: clock::Lamport) {
@@ -363,21 +654,41 @@ impl History {
 
     fn forget(&mut self, transaction_id: TransactionId) -> Option<Transaction> {
         assert_eq!(self.transaction_depth, 0);
-        if let Some(entry_ix) = self
+        let entry = if let Some(entry_ix) = self
             .undo_stack
             .iter()
             .rposition(|entry| entry.transaction.id == transaction_id)
         {
-            Some(self.undo_stack.remove(entry_ix).transaction)
+            let entry = self.undo_stack.remove(entry_ix);
+            if let Some(child) = self.undo_stack.get_mut(entry_ix) {
+                child.parent = entry.parent;
+            }
+            entry
         } else if let Some(entry_ix) = self
             .redo_stack
             .iter()
             .rposition(|entry| entry.transaction.id == transaction_id)
         {
-            Some(self.redo_stack.remove(entry_ix).transaction)
+            let entry = self.redo_stack.remove(entry_ix);
+            if let Some(child) = entry_ix
+                .checked_sub(1)
+                .and_then(|ix| self.redo_stack.get_mut(ix))
+            {
+                child.parent = entry.parent;
+            }
+            entry
+        } else if let Some(entry_ix) = self
+            .abandoned
+            .iter()
+            .position(|entry| entry.transaction.id == transaction_id)
+        {
+            self.abandoned.remove(entry_ix)
         } else {
-            None
-        }
+            return None;
+        };
+        self.restored.remove(&transaction_id);
+        self.prune_abandoned();
+        Some(entry.transaction)
     }
 
     fn transaction(&self, transaction_id: TransactionId) -> Option<&Transaction> {
@@ -393,6 +704,14 @@ impl History {
         Some(&entry.transaction)
     }
 
+    fn entry_mut(&mut self, transaction_id: TransactionId) -> Option<&mut HistoryEntry> {
+        self.undo_stack
+            .iter_mut()
+            .chain(&mut self.redo_stack)
+            .chain(&mut self.abandoned)
+            .find(|entry| entry.transaction.id == transaction_id)
+    }
+
     fn transaction_mut(&mut self, transaction_id: TransactionId) -> Option<&mut Transaction> {
         let entry = self
             .undo_stack
@@ -407,6 +726,13 @@ impl History {
     }
 
     fn merge_transactions(&mut self, transaction: TransactionId, destination: TransactionId) {
+        // Branches made on top of the merged transaction now start from the
+        // transaction it's merged into.
+        for abandoned in &mut self.abandoned {
+            if abandoned.parent == Some(transaction) {
+                abandoned.parent = Some(destination);
+            }
+        }
         if let Some(transaction) = self.forget(transaction) {
             if let Some(destination) = self.transaction_mut(destination) {
                 destination.edit_ids.extend(transaction.edit_ids);
@@ -1388,6 +1714,100 @@ impl Buffer {
         &self.history.operations
     }
 
+    /// Describes the undo and redo stacks as text edits, by undoing and redoing
+    /// every transaction on a copy of this buffer.
+    pub fn undo_history(&self) -> UndoHistory {
+        self.undo_history_snapshot().build()
+    }
+
+    /// Captures the undo and redo stacks, so that their [`UndoHistory`] can be
+    /// built without holding on to this buffer.
+    pub fn undo_history_snapshot(&self) -> UndoHistorySnapshot {
+        let mut buffer = self.branch();
+        buffer.history.undo_stack = self.history.undo_stack.clone();
+        buffer.history.redo_stack = self.history.redo_stack.clone();
+        buffer.history.restored = self.history.restored.clone();
+        buffer.lamport_clock = self.lamport_clock;
+        UndoHistorySnapshot { buffer }
+    }
+
+    /// Fills the empty undo and redo stacks of this buffer with the
+    /// transactions of `history`, without changing the text. Each restored
+    /// transaction is applied as a new edit the first time it's undone or
+    /// redone.
+    ///
+    /// Returns false, leaving the buffer untouched, if it already has history
+    /// or undoing and redoing `history` doesn't fit its text.
+    pub fn restore_undo_history(&mut self, history: &UndoHistory) -> bool {
+        if history.is_empty()
+            || !self.history.undo_stack.is_empty()
+            || !self.history.redo_stack.is_empty()
+            || history.base_text_for(&self.text()).is_none()
+        {
+            return false;
+        }
+
+        let now = Instant::now();
+        let mut parent = None;
+        let mut redo_stack = Vec::new();
+        for (transaction, is_redo) in history
+            .undo_stack
+            .iter()
+            .map(|transaction| (transaction, false))
+            .chain(
+                history
+                    .redo_stack
+                    .iter()
+                    .map(|transaction| (transaction, true)),
+            )
+        {
+            let id = self.lamport_clock.tick();
+            let entry = HistoryEntry {
+                transaction: Transaction {
+                    id,
+                    edit_ids: Vec::new(),
+                    start: self.version.clone(),
+                },
+                parent,
+                sequence: self.history.next_sequence(),
+                first_edit_at: now,
+                last_edit_at: now,
+                suppress_grouping: true,
+            };
+            if is_redo {
+                redo_stack.push(entry);
+            } else {
+                self.history.undo_stack.push(entry);
+            }
+            self.history.restored.insert(id, transaction.clone());
+            parent = Some(id);
+        }
+        redo_stack.reverse();
+        self.history.redo_stack = redo_stack;
+        true
+    }
+
+    /// Returns the transaction leading from `old` to `new`, and the one
+    /// leading back from `new` to `old`.
+    fn undo_history_transaction(
+        old: &BufferSnapshot,
+        new: &BufferSnapshot,
+    ) -> (UndoHistoryTransaction, UndoHistoryTransaction) {
+        let mut forward = UndoHistoryTransaction::default();
+        let mut backward = UndoHistoryTransaction::default();
+        for edit in new.edits_since::<usize>(&old.version) {
+            let old_text = old.text_for_range(edit.old.clone()).collect::<String>();
+            let new_text = new.text_for_range(edit.new.clone()).collect::<String>();
+            forward.edits.push((edit.old.clone(), new_text.clone()));
+            forward
+                .inverse_edits
+                .push((edit.new.clone(), old_text.clone()));
+            backward.edits.push((edit.new, old_text));
+            backward.inverse_edits.push((edit.old, new_text));
+        }
+        (forward, backward)
+    }
+
     pub fn undo(&mut self) -> Option<(TransactionId, Operation)> {
         if let Some(entry) = self.history.pop_undo() {
             let transaction = entry.transaction.clone();
@@ -1455,7 +1875,113 @@ impl Buffer {
             .collect()
     }
 
+    /// Returns every state of the undo tree, in the order they were created.
+    pub fn undo_tree(&self) -> Vec<UndoTreeNode> {
+        let mut nodes = self
+            .history
+            .entries()
+            .map(|entry| UndoTreeNode {
+                transaction_id: entry.transaction.id,
+                parent: entry.parent,
+                sequence: entry.sequence,
+                edited_at: entry.last_edit_at,
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|node| node.transaction_id);
+        nodes
+    }
+
+    /// Returns the state of the undo tree the buffer is in, or `None` if it
+    /// has the original text.
+    pub fn undo_tree_position(&self) -> Option<TransactionId> {
+        self.history
+            .undo_stack
+            .last()
+            .map(HistoryEntry::transaction_id)
+    }
+
+    /// Undoes and redoes transactions until the buffer is in the given state
+    /// of the undo tree, which can be on a different branch. Returns the
+    /// transactions that were undone or redone, in order.
+    pub fn travel_to_transaction(
+        &mut self,
+        transaction_id: Option<TransactionId>,
+    ) -> Vec<(TransactionId, Operation)> {
+        let Some(path) = self.history.path_to(transaction_id) else {
+            return Vec::new();
+        };
+        let (undone, redone) = self.history.travel(&path);
+        undone
+            .into_iter()
+            .chain(redone)
+            .map(|transaction| (transaction.id, self.undo_or_redo(transaction)))
+            .collect()
+    }
+
+    /// Travels to an earlier state of the undo tree, in the order the states
+    /// were created rather than along the current branch.
+    pub fn earlier(&mut self, distance: UndoTreeDistance) -> Vec<(TransactionId, Operation)> {
+        let target = self.undo_tree_target(distance, false);
+        self.travel_to_transaction(target)
+    }
+
+    /// Travels to a later state of the undo tree, in the order the states
+    /// were created rather than along the current branch.
+    pub fn later(&mut self, distance: UndoTreeDistance) -> Vec<(TransactionId, Operation)> {
+        let target = self.undo_tree_target(distance, true);
+        self.travel_to_transaction(target)
+    }
+
+    fn undo_tree_target(&self, distance: UndoTreeDistance, later: bool) -> Option<TransactionId> {
+        let mut nodes = self.undo_tree();
+        if let UndoTreeDistance::Time(_) = distance {
+            // Grouping can make a transaction's last edit newer than those of
+            // transactions created after it, so order the states by time.
+            nodes.sort_by_key(|node| (node.edited_at, node.transaction_id));
+        }
+        let position = self.undo_tree_position();
+        // Index 0 stands for the original text.
+        let current_ix = nodes
+            .iter()
+            .position(|node| Some(node.transaction_id) == position)
+            .map_or(0, |ix| ix + 1);
+        let target_ix = match distance {
+            UndoTreeDistance::Steps(steps) if later => (current_ix + steps).min(nodes.len()),
+            UndoTreeDistance::Steps(steps) => current_ix.saturating_sub(steps),
+            UndoTreeDistance::Time(duration) => {
+                let Some(current_time) = nodes
+                    .get(current_ix.saturating_sub(1))
+                    .map(|node| node.edited_at)
+                else {
+                    return position;
+                };
+                let target_time = if later {
+                    current_time.checked_add(duration)
+                } else if current_ix == 0 {
+                    None
+                } else {
+                    current_time.checked_sub(duration)
+                };
+                let last_ix = target_time.map_or(0, |target_time| {
+                    nodes.partition_point(|node| node.edited_at <= target_time)
+                });
+                if later {
+                    last_ix.max(current_ix)
+                } else {
+                    last_ix.min(current_ix)
+                }
+            }
+        };
+        target_ix.checked_sub(1).map(|ix| nodes[ix].transaction_id)
+    }
+
     fn undo_or_redo(&mut self, transaction: Transaction) -> Operation {
+        if let Some(restored) = self.history.restored.remove(&transaction.id) {
+            if let Some(operation) = self.apply_restored_transaction(transaction.id, restored) {
+                return operation;
+            }
+        }
+
         let mut counts = HashMap::default();
         for edit_id in transaction.edit_ids {
             counts.insert(edit_id, self.undo_map.undo_count(edit_id).saturating_add(1));
@@ -1466,6 +1992,53 @@ impl Buffer {
         operation
     }
 
+    /// Undoes or redoes a transaction restored from an [`UndoHistory`] by
+    /// editing the text, after which the edit toggles it like any other.
+    /// Returns `None` if its edits don't fit the text anymore.
+    fn apply_restored_transaction(
+        &mut self,
+        transaction_id: TransactionId,
+        restored: UndoHistoryTransaction,
+    ) -> Option<Operation> {
+        // The history has already moved the transaction to the stack it ends
+        // up on.
+        let is_undo = !self
+            .history
+            .undo_stack
+            .iter()
+            .any(|entry| entry.transaction.id == transaction_id);
+        let edits = if is_undo {
+            restored.inverse_edits
+        } else {
+            restored.edits
+        };
+        let len = self.len();
+        let mut offset = 0;
+        for (range, _) in &edits {
+            if range.start < offset
+                || range.start > range.end
+                || range.end > len
+                || self.clip_offset(range.start, Bias::Left) != range.start
+                || self.clip_offset(range.end, Bias::Left) != range.end
+            {
+                return None;
+            }
+            offset = range.end;
+        }
+        if edits.is_empty() {
+            return None;
+        }
+
+        let timestamp = self.lamport_clock.tick();
+        let operation = Operation::Edit(self.apply_local_edit(edits.into_iter(), timestamp));
+        self.history.push(operation.clone());
+        self.snapshot.version.observe(timestamp);
+        if let Some(entry) = self.history.entry_mut(transaction_id) {
+            entry.transaction.edit_ids = vec![timestamp];
+        }
+        Some(operation)
+    }
+
     pub fn undo_operations(&mut self, counts: HashMap<clock::Lamport, u32>) -> Operation {
         let timestamp = self.lamport_clock.tick();
         let version = self.version();
@@ -1,6 +1,7 @@
 mod accents;
 mod colors;
 mod players;
+mod semantic_tokens;
 mod status;
 mod syntax;
 mod system;
@@ -8,6 +9,7 @@ mod system;
 pub use accents::*;
 pub use colors::*;
 pub use players::*;
+pub use semantic_tokens::*;
 pub use status::*;
 pub use syntax::*;
 pub use system::*;
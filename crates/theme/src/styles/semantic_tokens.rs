@@ -0,0 +1,93 @@
+use gpui::{FontWeight, HighlightStyle, StrikethroughStyle, UnderlineStyle};
+
+use crate::SyntaxTheme;
+
+/// The syntax theme entries used to style semantic token types that themes don't style directly.
+///
+/// Token types are matched against theme entries of the same name first, so a theme can style
+/// e.g. `parameter` differently from `variable` by defining a `parameter` entry.
+pub const SEMANTIC_TOKEN_TYPE_FALLBACKS: &[(&str, &str)] = &[
+    ("namespace", "type"),
+    ("class", "type"),
+    ("enum", "type"),
+    ("interface", "type"),
+    ("struct", "type"),
+    ("typeParameter", "type"),
+    ("builtinType", "type"),
+    ("typeAlias", "type"),
+    ("parameter", "variable"),
+    ("selfKeyword", "variable.special"),
+    ("enumMember", "variant"),
+    ("event", "property"),
+    ("method", "function.method"),
+    ("macro", "function"),
+    ("modifier", "keyword"),
+    ("regexp", "string.regex"),
+    ("decorator", "attribute"),
+    ("lifetime", "label"),
+];
+
+/// The default styles of semantic token modifiers, applied when a theme has no entry for the
+/// combination of a token type and modifier, e.g. `variable.mutable`.
+pub fn default_semantic_token_modifier_style(modifier: &str) -> Option<HighlightStyle> {
+    match modifier {
+        "mutable" => Some(HighlightStyle {
+            underline: Some(UnderlineStyle {
+                thickness: 1.0.into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        "unsafe" => Some(HighlightStyle {
+            font_weight: Some(FontWeight::BOLD),
+            ..Default::default()
+        }),
+        "deprecated" => Some(HighlightStyle {
+            strikethrough: Some(StrikethroughStyle {
+                thickness: 1.0.into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        _ => None,
+    }
+}
+
+impl SyntaxTheme {
+    fn style_for_name(&self, name: &str) -> Option<HighlightStyle> {
+        self.highlights
+            .iter()
+            .find_map(|(entry_name, style)| (entry_name == name).then_some(*style))
+    }
+
+    /// Returns the style of a semantic token, or `None` if the theme doesn't style it.
+    ///
+    /// The token type is looked up in the theme directly, then through
+    /// [`SEMANTIC_TOKEN_TYPE_FALLBACKS`]. Each modifier is layered on top, using the theme entry
+    /// named `<type>.<modifier>` if there is one, or [`default_semantic_token_modifier_style`].
+    pub fn semantic_token_style<'a>(
+        &self,
+        token_type: &str,
+        modifiers: impl IntoIterator<Item = &'a str>,
+    ) -> Option<HighlightStyle> {
+        let mut style = self.style_for_name(token_type).or_else(|| {
+            SEMANTIC_TOKEN_TYPE_FALLBACKS
+                .iter()
+                .find(|(fallback_type, _)| *fallback_type == token_type)
+                .and_then(|(_, name)| self.style_for_name(name))
+        });
+
+        for modifier in modifiers {
+            let modifier_style = self
+                .style_for_name(&format!("{token_type}.{modifier}"))
+                .or_else(|| default_semantic_token_modifier_style(modifier));
+            if let Some(modifier_style) = modifier_style {
+                style
+                    .get_or_insert_with(HighlightStyle::default)
+                    .highlight(modifier_style);
+            }
+        }
+
+        style
+    }
+}
@@ -180,6 +180,13 @@ pub enum IconName {
     CursorIBeam,
     Dash,
     DatabaseZap,
+    Debug,
+    DebugBreakpoint,
+    DebugContinue,
+    DebugPause,
+    DebugStepInto,
+    DebugStepOut,
+    DebugStepOver,
     Delete,
     Diff,
     Disconnected,
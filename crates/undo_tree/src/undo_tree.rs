@@ -0,0 +1,594 @@
+mod undo_tree_settings;
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, EditorMode};
+use gpui::{
+    actions, rems, uniform_list, Action, AsyncWindowContext, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, ScrollStrategy, Subscription, Task, UniformListScrollHandle, WeakEntity,
+};
+use language::{Buffer, BufferEvent, TransactionId, UndoTreeNode};
+use menu::{Cancel, Confirm, SelectNext, SelectPrevious};
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{prelude::*, ListItem};
+use undo_tree_settings::{UndoTreePanelDockPosition, UndoTreePanelSettings};
+use util::ResultExt;
+use workspace::{
+    dock::{DockPosition, Panel, PanelEvent},
+    Workspace,
+};
+
+actions!(undo_tree, [ToggleFocus]);
+
+const UNDO_TREE_PANEL_KEY: &str = "UndoTreePanel";
+
+pub fn init(cx: &mut App) {
+    UndoTreePanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<UndoTreePanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedUndoTreePanel {
+    width: Option<Pixels>,
+}
+
+/// Lists the states of the active editor's undo tree. Confirming a state
+/// restores it in the editor, even if it's on another branch.
+pub struct UndoTreePanel {
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    active_editor: Option<ActiveEditor>,
+    rows: Vec<UndoTreeRow>,
+    /// The row of the state the buffer is in.
+    current_index: Option<usize>,
+    /// The row that gets restored on confirm.
+    selected_index: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    _workspace_subscription: Subscription,
+}
+
+struct ActiveEditor {
+    editor: WeakEntity<Editor>,
+    buffer: Entity<Buffer>,
+    _buffer_subscription: Subscription,
+}
+
+impl UndoTreePanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let serialized_panel = cx
+            .background_spawn(async move { KEY_VALUE_STORE.read_kvp(UNDO_TREE_PANEL_KEY) })
+            .await
+            .context("loading undo tree panel")
+            .log_err()
+            .flatten()
+            .map(|panel| serde_json::from_str::<SerializedUndoTreePanel>(&panel))
+            .transpose()
+            .log_err()
+            .flatten();
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let active_editor = workspace_active_editor(workspace, cx);
+        let workspace_handle = cx.entity();
+        cx.new(|cx| {
+            let workspace_subscription = cx.subscribe(
+                &workspace_handle,
+                |this: &mut Self, workspace, event, cx| {
+                    if let workspace::Event::ActiveItemChanged = event {
+                        let active_editor = workspace_active_editor(workspace.read(cx), cx);
+                        this.set_active_editor(active_editor, cx);
+                    }
+                },
+            );
+            let mut this = Self {
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                active_editor: None,
+                rows: Vec::new(),
+                current_index: None,
+                selected_index: None,
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                _workspace_subscription: workspace_subscription,
+            };
+            this.set_active_editor(active_editor, cx);
+            this
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        UNDO_TREE_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedUndoTreePanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn set_active_editor(&mut self, editor: Option<Entity<Editor>>, cx: &mut Context<Self>) {
+        self.active_editor = editor.and_then(|editor| {
+            let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+            let buffer_subscription = cx.subscribe(&buffer, |this, _, event, cx| {
+                if let BufferEvent::Edited = event {
+                    this.update_rows(cx);
+                }
+            });
+            Some(ActiveEditor {
+                editor: editor.downgrade(),
+                buffer,
+                _buffer_subscription: buffer_subscription,
+            })
+        });
+        self.update_rows(cx);
+    }
+
+    fn update_rows(&mut self, cx: &mut Context<Self>) {
+        if let Some(active_editor) = &self.active_editor {
+            let buffer = active_editor.buffer.read(cx);
+            let position = buffer.undo_tree_position();
+            self.rows = undo_tree_rows(&buffer.undo_tree());
+            self.current_index = self
+                .rows
+                .iter()
+                .position(|row| row.transaction_id == position);
+        } else {
+            self.rows.clear();
+            self.current_index = None;
+        }
+        self.select_index(self.current_index, cx);
+    }
+
+    fn select_index(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.selected_index = ix;
+        if let Some(ix) = ix {
+            self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_offset(1, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_offset(-1, cx);
+    }
+
+    fn select_offset(&mut self, offset: isize, cx: &mut Context<Self>) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let ix = self.selected_index.map_or(0, |ix| {
+            ix.saturating_add_signed(offset).min(self.rows.len() - 1)
+        });
+        self.select_index(Some(ix), cx);
+    }
+
+    /// Moves the buffer to the state of the given row, which then becomes the
+    /// current one once the buffer is edited.
+    fn restore_state(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(row) = self.rows.get(ix) else {
+            return;
+        };
+        let Some(editor) = self
+            .active_editor
+            .as_ref()
+            .and_then(|active_editor| active_editor.editor.upgrade())
+        else {
+            return;
+        };
+        let transaction_id = row.transaction_id;
+        editor.update(cx, |editor, cx| {
+            editor.travel_to_transaction(transaction_id, window, cx);
+        });
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.restore_state(ix, window, cx);
+        }
+        self.focus_editor(window, cx);
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(self.current_index, cx);
+        self.focus_editor(window, cx);
+    }
+
+    fn focus_editor(&self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(editor) = self
+            .active_editor
+            .as_ref()
+            .and_then(|active_editor| active_editor.editor.upgrade())
+        {
+            window.focus(&editor.focus_handle(cx));
+        }
+    }
+
+    fn render_entry(&self, ix: usize, now: Instant, cx: &mut Context<Self>) -> ListItem {
+        let row = &self.rows[ix];
+        let selected = self.selected_index == Some(ix);
+        let current = self.current_index == Some(ix);
+        let label = if row.transaction_id.is_some() {
+            format!("Change {}", row.sequence)
+        } else {
+            "Original text".to_string()
+        };
+
+        ListItem::new(("undo-tree-state", ix))
+            .toggle_state(selected)
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.restore_state(ix, window, cx);
+            }))
+            .child(
+                h_flex()
+                    .w_full()
+                    .pl(rems(row.depth as f32))
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::Circle)
+                            .size(IconSize::XSmall)
+                            .color(if current { Color::Accent } else { Color::Muted }),
+                    )
+                    .child(Label::new(label).size(LabelSize::Small))
+                    .children(row.edited_at.map(|edited_at| {
+                        Label::new(format_elapsed(now.saturating_duration_since(edited_at)))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    })),
+            )
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("UndoTreePanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+}
+
+fn workspace_active_editor(workspace: &Workspace, cx: &App) -> Option<Entity<Editor>> {
+    workspace
+        .active_item(cx)?
+        .act_as::<Editor>(cx)
+        .filter(|editor| editor.read(cx).mode() == EditorMode::Full)
+}
+
+impl Panel for UndoTreePanel {
+    fn persistent_name() -> &'static str {
+        "Undo Tree Panel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match UndoTreePanelSettings::get_global(cx).dock {
+            UndoTreePanelDockPosition::Left => DockPosition::Left,
+            UndoTreePanelDockPosition::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<UndoTreePanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| {
+                let dock = match position {
+                    DockPosition::Left | DockPosition::Bottom => UndoTreePanelDockPosition::Left,
+                    DockPosition::Right => UndoTreePanelDockPosition::Right,
+                };
+                settings.dock = Some(dock);
+            },
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| UndoTreePanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        UndoTreePanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::HistoryRerun)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Undo Tree Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        12
+    }
+}
+
+impl Focusable for UndoTreePanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for UndoTreePanel {}
+
+impl Render for UndoTreePanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.rows.is_empty() {
+            div()
+                .p_2()
+                .child(
+                    Label::new("Open a file to show the states of its undo history.")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            let now = Instant::now();
+            uniform_list(
+                cx.entity().clone(),
+                "undo-tree-states",
+                self.rows.len(),
+                move |this, range, _, cx| range.map(|ix| this.render_entry(ix, now, cx)).collect(),
+            )
+            .size_full()
+            .track_scroll(self.scroll_handle.clone())
+            .into_any_element()
+        };
+
+        v_flex()
+            .key_context(self.dispatch_context())
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .size_full()
+            .child(content)
+    }
+}
+
+/// A state of the undo tree, as laid out in the panel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct UndoTreeRow {
+    /// The transaction leading to this state, or `None` for the original text.
+    transaction_id: Option<TransactionId>,
+    /// The number of the change leading to this state, which stays the same
+    /// as other states are dropped, or 0 for the original text.
+    sequence: usize,
+    /// How many branches away from the main line of the tree the state is.
+    depth: usize,
+    edited_at: Option<Instant>,
+}
+
+/// Lays out the undo tree depth-first. A state's newest child continues on its
+/// line, after the branches started by the older children.
+fn undo_tree_rows(nodes: &[UndoTreeNode]) -> Vec<UndoTreeRow> {
+    let mut children = HashMap::<Option<TransactionId>, Vec<usize>>::default();
+    for (ix, node) in nodes.iter().enumerate() {
+        children.entry(node.parent).or_default().push(ix);
+    }
+
+    let mut rows = Vec::with_capacity(nodes.len() + 1);
+    let mut stack = vec![(None, 0)];
+    while let Some((node_ix, depth)) = stack.pop() {
+        let node = node_ix.map(|ix| &nodes[ix]);
+        let transaction_id = node.map(|node| node.transaction_id);
+        rows.push(UndoTreeRow {
+            transaction_id,
+            sequence: node.map_or(0, |node| node.sequence),
+            depth,
+            edited_at: node.map(|node| node.edited_at),
+        });
+
+        if let Some((newest, older)) = children
+            .get(&transaction_id)
+            .and_then(|children| children.split_last())
+        {
+            stack.push((Some(*newest), depth));
+            stack.extend(older.iter().rev().map(|ix| (Some(*ix), depth + 1)));
+        }
+    }
+    rows
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    match seconds {
+        0..5 => "just now".to_string(),
+        5..60 => format!("{seconds} seconds ago"),
+        60..3600 => format!("{} minutes ago", seconds / 60),
+        3600..86400 => format!("{} hours ago", seconds / 3600),
+        _ => format!("{} days ago", seconds / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use project::{FakeFs, Project};
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    fn test_undo_tree_rows(cx: &mut App) {
+        let buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local("", cx);
+            buffer.edit([(0..0, "a")], None, cx);
+            buffer.edit([(1..1, "b")], None, cx);
+            buffer.undo(cx);
+            buffer.edit([(1..1, "c")], None, cx);
+            buffer.edit([(2..2, "d")], None, cx);
+            buffer.undo(cx);
+            buffer.undo(cx);
+            buffer.edit([(1..1, "e")], None, cx);
+            buffer
+        });
+
+        let rows = undo_tree_rows(&buffer.read(cx).undo_tree());
+        assert_eq!(
+            rows.iter()
+                .map(|row| (row.sequence, row.depth))
+                .collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (2, 1), (3, 1), (4, 1), (5, 0)]
+        );
+        assert_eq!(
+            rows.last().unwrap().transaction_id,
+            buffer.read(cx).undo_tree_position()
+        );
+    }
+
+    #[gpui::test]
+    async fn test_undo_tree_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let panel = workspace.update(cx, |workspace, cx| UndoTreePanel::new(workspace, cx));
+        panel.read_with(cx, |panel, _| assert!(panel.rows.is_empty()));
+
+        let buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local("", cx);
+            buffer.edit([(0..0, "a")], None, cx);
+            buffer.edit([(1..1, "b")], None, cx);
+            buffer.undo(cx);
+            buffer.edit([(1..1, "c")], None, cx);
+            buffer
+        });
+        let editor = cx.new_window_entity(|window, cx| {
+            Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx)
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+        });
+        panel.read_with(cx, |panel, _| {
+            assert_eq!(
+                panel
+                    .rows
+                    .iter()
+                    .map(|row| (row.sequence, row.depth))
+                    .collect::<Vec<_>>(),
+                [(0, 0), (1, 0), (2, 1), (3, 0)]
+            );
+            assert_eq!(panel.selected_index, Some(3));
+        });
+
+        // Moving the selection leaves the buffer alone until it's confirmed.
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_previous(&SelectPrevious, window, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "ac");
+        panel.read_with(cx, |panel, _| {
+            assert_eq!(panel.selected_index, Some(2));
+            assert_eq!(panel.current_index, Some(3));
+        });
+
+        // Confirming restores the selected state, even on another branch.
+        panel.update_in(cx, |panel, window, cx| panel.confirm(&Confirm, window, cx));
+        cx.run_until_parked();
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "ab");
+        panel.read_with(cx, |panel, _| {
+            assert_eq!(panel.selected_index, Some(2));
+            assert_eq!(panel.current_index, Some(2));
+        });
+        editor.update_in(cx, |editor, window, cx| {
+            assert!(editor.focus_handle(cx).is_focused(window));
+        });
+
+        panel.update_in(cx, |panel, window, cx| panel.restore_state(0, window, cx));
+        cx.run_until_parked();
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "");
+        panel.read_with(cx, |panel, _| assert_eq!(panel.current_index, Some(0)));
+
+        // New edits start another branch from the restored state.
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "d")], None, cx));
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, _| {
+            assert_eq!(panel.rows.len(), 5);
+            assert_eq!(panel.current_index, Some(4));
+        });
+
+        // Canceling goes back to the current state without traveling.
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_previous(&SelectPrevious, window, cx);
+            panel.cancel(&Cancel, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "d");
+        panel.read_with(cx, |panel, _| assert_eq!(panel.selected_index, Some(4)));
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(2)), "just now");
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42 seconds ago");
+        assert_eq!(format_elapsed(Duration::from_secs(5 * 60)), "5 minutes ago");
+        assert_eq!(format_elapsed(Duration::from_secs(7200)), "2 hours ago");
+        assert_eq!(format_elapsed(Duration::from_secs(3 * 86400)), "3 days ago");
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            super::init(cx);
+        });
+    }
+}
@@ -0,0 +1,47 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UndoTreePanelDockPosition {
+    Left,
+    Right,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct UndoTreePanelSettings {
+    pub button: bool,
+    pub default_width: Pixels,
+    pub dock: UndoTreePanelDockPosition,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct UndoTreePanelSettingsContent {
+    /// Whether to show the undo tree panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Default width (in pixels) of the undo tree panel.
+    ///
+    /// Default: 240
+    pub default_width: Option<f32>,
+    /// The position of the undo tree panel
+    ///
+    /// Default: right
+    pub dock: Option<UndoTreePanelDockPosition>,
+}
+
+impl Settings for UndoTreePanelSettings {
+    const KEY: Option<&'static str> = Some("undo_tree_panel");
+
+    type FileContent = UndoTreePanelSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::App,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
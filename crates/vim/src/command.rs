@@ -1,5 +1,5 @@
 use anyhow::{anyhow, Result};
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use command_palette_hooks::CommandInterceptResult;
 use editor::{
     actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive},
@@ -7,9 +7,13 @@ use editor::{
     scroll::Autoscroll,
     Bias, Editor, ToPoint,
 };
-use gpui::{actions, impl_internal_actions, Action, App, AppContext as _, Context, Global, Window};
+use futures::channel::oneshot;
+use gpui::{
+    actions, impl_internal_actions, Action, App, AppContext as _, Context, Focusable, Global,
+    Window,
+};
 use itertools::Itertools;
-use language::Point;
+use language::{Point, UndoTreeDistance};
 use multi_buffer::MultiBufferRow;
 use regex::Regex;
 use schemars::JsonSchema;
@@ -22,7 +26,7 @@ use std::{
     process::Stdio,
     str::Chars,
     sync::OnceLock,
-    time::Instant,
+    time::{Duration, Instant},
 };
 use task::{HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
 use ui::ActiveTheme;
@@ -65,6 +69,51 @@ pub struct WithCount {
     action: WrappedAction,
 }
 
+/// `:earlier` and `:later`, which travel chronologically through the undo
+/// tree, across its branches.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndoTreeTravel {
+    later: bool,
+    distance: UndoTreeDistance,
+}
+
+impl UndoTreeTravel {
+    fn parse(query: &str) -> Option<Self> {
+        let name_len = query
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(query.len());
+        let (name, argument) = query.split_at(name_len);
+        let later = if name.len() >= 2 && "earlier".starts_with(name) {
+            false
+        } else if name.len() >= 3 && "later".starts_with(name) {
+            true
+        } else {
+            return None;
+        };
+
+        let argument = argument.trim();
+        let unit_ix = argument
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(argument.len());
+        let distance = match argument.split_at(unit_ix) {
+            ("", "") => UndoTreeDistance::Steps(1),
+            (count, "") => UndoTreeDistance::Steps(count.parse().ok()?),
+            (count, unit) => {
+                let seconds = match unit {
+                    "s" => 1,
+                    "m" => 60,
+                    "h" => 60 * 60,
+                    "d" => 24 * 60 * 60,
+                    _ => return None,
+                };
+                let count = count.parse::<u64>().ok()?;
+                UndoTreeDistance::Time(Duration::from_secs(count.checked_mul(seconds)?))
+            }
+        };
+        Some(Self { later, distance })
+    }
+}
+
 #[derive(Clone, Deserialize, JsonSchema, PartialEq)]
 pub enum VimOption {
     Wrap(bool),
@@ -168,6 +217,8 @@ impl_internal_actions!(
         OnMatchingLines,
         ShellExec,
         VimSet,
+        UndoTreeTravel,
+        QuickfixDo,
     ]
 );
 
@@ -353,6 +404,21 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
 
     Vim::action(editor, cx, |vim, action: &ShellExec, window, cx| {
         action.run(vim, window, cx)
+    });
+
+    Vim::action(editor, cx, |vim, action: &QuickfixDo, window, cx| {
+        action.run(vim, window, cx)
+    });
+
+    Vim::action(editor, cx, |vim, action: &UndoTreeTravel, window, cx| {
+        vim.switch_mode(Mode::Normal, false, window, cx);
+        vim.update_editor(window, cx, |_, editor, window, cx| {
+            if action.later {
+                editor.undo_tree_later(action.distance, window, cx);
+            } else {
+                editor.undo_tree_earlier(action.distance, window, cx);
+            }
+        });
     })
 }
 
@@ -830,12 +896,17 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
             save_intent: Some(SaveIntent::Skip),
         }),
         VimCommand::str(("cl", "ist"), "diagnostics::Deploy"),
+        VimCommand::str(("cope", "n"), "quickfix_panel::ToggleFocus"),
+        VimCommand::str(("cw", "indow"), "quickfix_panel::ToggleFocus"),
+        VimCommand::str(("undot", "ree"), "undo_tree::ToggleFocus"),
         VimCommand::new(("cc", ""), editor::actions::Hover),
         VimCommand::new(("ll", ""), editor::actions::Hover),
-        VimCommand::new(("cn", "ext"), editor::actions::GoToDiagnostic).range(wrap_count),
-        VimCommand::new(("cp", "revious"), editor::actions::GoToPreviousDiagnostic)
-            .range(wrap_count),
-        VimCommand::new(("cN", "ext"), editor::actions::GoToPreviousDiagnostic).range(wrap_count),
+        VimCommand::new(("cn", "ext"), workspace::quickfix::Next).range(wrap_count),
+        VimCommand::new(("cp", "revious"), workspace::quickfix::Previous).range(wrap_count),
+        VimCommand::new(("cN", "ext"), workspace::quickfix::Previous).range(wrap_count),
+        VimCommand::new(("cfir", "st"), workspace::quickfix::First),
+        VimCommand::new(("cr", "ewind"), workspace::quickfix::First),
+        VimCommand::new(("cla", "st"), workspace::quickfix::Last),
         VimCommand::new(("lp", "revious"), editor::actions::GoToPreviousDiagnostic)
             .range(wrap_count),
         VimCommand::new(("lN", "ext"), editor::actions::GoToPreviousDiagnostic).range(wrap_count),
@@ -1000,6 +1071,10 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
         } else {
             None
         }
+    } else if let Some(quickfix_do) = QuickfixDo::parse(query, cx) {
+        Some(quickfix_do.boxed_clone())
+    } else if let Some(travel) = UndoTreeTravel::parse(query) {
+        Some(travel.boxed_clone())
     } else if query.contains('!') {
         ShellExec::parse(query, range.clone())
     } else {
@@ -1247,6 +1322,71 @@ impl OnMatchingLines {
     }
 }
 
+/// `:cdo`, which runs a command at each entry of the quickfix list, and
+/// `:cfdo`, which runs it once in each file of the list.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct QuickfixDo {
+    action: WrappedAction,
+    per_file: bool,
+}
+
+impl QuickfixDo {
+    fn parse(query: &str, cx: &App) -> Option<Self> {
+        let (command, per_file) = if let Some(command) = query.strip_prefix("cdo ") {
+            (command, false)
+        } else {
+            (query.strip_prefix("cfdo ")?, true)
+        };
+        let action = command_interceptor(command.trim(), cx)
+            .first()?
+            .action
+            .boxed_clone();
+        Some(Self {
+            action: WrappedAction(action),
+            per_file,
+        })
+    }
+
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let Some(workspace) = vim.workspace(window) else {
+            return;
+        };
+        let entries = workspace.read(cx).quickfix_list().read(cx).entries();
+        let mut visited_paths = HashSet::default();
+        let indices = (0..entries.len())
+            .filter(|&ix| !self.per_file || visited_paths.insert(entries[ix].path.clone()))
+            .collect::<Vec<_>>();
+        let action = self.action.boxed_clone();
+        cx.spawn_in(window, |_, mut cx| async move {
+            for ix in indices {
+                workspace
+                    .update_in(&mut cx, |workspace, window, cx| {
+                        editor::quickfix::open_quickfix_entry(workspace, ix, window, cx)
+                    })?
+                    .await?;
+                let (tx, rx) = oneshot::channel();
+                let action = action.boxed_clone();
+                workspace.update_in(&mut cx, |workspace, window, cx| {
+                    let run = move |window: &mut Window, cx: &mut App| {
+                        window.dispatch_action(action, cx);
+                        tx.send(()).ok();
+                    };
+                    // Actions are dispatched using the last rendered frame, so the
+                    // command can only run once the entry's editor has been drawn.
+                    if workspace.focus_handle(cx).contains_focused(window, cx) {
+                        run(window, cx);
+                    } else {
+                        window.on_next_frame(run);
+                    }
+                })?;
+                rx.await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ShellExec {
     command: String,
@@ -1442,6 +1582,7 @@ impl ShellExec {
                         show_summary: false,
                         show_command: false,
                         show_rerun: false,
+                        problem_matcher: None,
                     }),
                 });
             });
@@ -1582,17 +1723,23 @@ impl ShellExec {
 
 #[cfg(test)]
 mod test {
-    use std::path::Path;
+    use std::{path::Path, time::Duration};
 
     use crate::{
+        command::{command_interceptor, QuickfixDo, UndoTreeTravel},
         state::Mode,
         test::{NeovimBackedTestContext, VimTestContext},
     };
     use editor::Editor;
     use gpui::{Context, TestAppContext};
     use indoc::indoc;
+    use language::UndoTreeDistance;
+    use project::ProjectPath;
     use util::path;
-    use workspace::Workspace;
+    use workspace::{
+        quickfix::{QuickfixEntry, QuickfixPosition},
+        Workspace,
+    };
 
     #[gpui::test]
     async fn test_command_basics(cx: &mut TestAppContext) {
@@ -1725,6 +1872,136 @@ mod test {
         cx.workspace(|workspace, _, cx| assert_eq!(workspace.items(cx).count(), 0));
     }
 
+    #[gpui::test]
+    async fn test_command_earlier_later(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇa", Mode::Normal);
+        cx.simulate_keystrokes("i b escape");
+        cx.simulate_keystrokes("u");
+        cx.simulate_keystrokes("i c escape");
+        cx.assert_state("ˇca", Mode::Normal);
+
+        // The undone edit is still reachable after making another one.
+        cx.simulate_keystrokes(": e a r l i e r enter");
+        assert_eq!(cx.buffer_text(), "ba");
+        cx.simulate_keystrokes(": l a t e r enter");
+        assert_eq!(cx.buffer_text(), "ca");
+        cx.simulate_keystrokes(": e a space 2 enter");
+        assert_eq!(cx.buffer_text(), "a");
+
+        assert_eq!(
+            UndoTreeTravel::parse("ea 10m"),
+            Some(UndoTreeTravel {
+                later: false,
+                distance: UndoTreeDistance::Time(Duration::from_secs(600)),
+            })
+        );
+        assert_eq!(
+            UndoTreeTravel::parse("later3"),
+            Some(UndoTreeTravel {
+                later: true,
+                distance: UndoTreeDistance::Steps(3),
+            })
+        );
+        assert_eq!(UndoTreeTravel::parse("ea 2f"), None);
+        assert_eq!(UndoTreeTravel::parse("la"), None);
+    }
+
+    #[gpui::test]
+    async fn test_command_quickfix(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.update(|_, cx| {
+            let action = |query: &str| command_interceptor(query, cx).remove(0).action;
+            assert!(action("cn").partial_eq(&workspace::quickfix::Next));
+            assert!(action("cprev").partial_eq(&workspace::quickfix::Previous));
+            assert!(action("cfirst").partial_eq(&workspace::quickfix::First));
+            assert!(action("cla").partial_eq(&workspace::quickfix::Last));
+
+            let quickfix_do = QuickfixDo::parse("cdo s/a/b/", cx).unwrap();
+            assert!(action("cdo s/a/b/").partial_eq(&quickfix_do));
+            let quickfix_do = QuickfixDo::parse("cfdo s/a/b/", cx).unwrap();
+            assert!(action("cfdo s/a/b/").partial_eq(&quickfix_do));
+            assert_ne!(
+                QuickfixDo::parse("cdo s/a/b/", cx),
+                QuickfixDo::parse("cfdo s/a/b/", cx)
+            );
+            assert_eq!(QuickfixDo::parse("cdo", cx), None);
+            assert_eq!(QuickfixDo::parse("cfdo", cx), None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_command_quickfix_do(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        for name in ["a.rs", "b.rs"] {
+            fs.as_fake()
+                .insert_file(
+                    Path::new(path!("/root/dir")).join(name),
+                    b"one\ntwo\nthree\n".to_vec(),
+                )
+                .await;
+        }
+        cx.run_until_parked();
+
+        let set_entries = |cx: &mut VimTestContext, positions: &[(&str, u32)]| {
+            cx.workspace(|workspace, _, cx| {
+                let project = workspace.project().read(cx);
+                let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+                let entries = positions
+                    .iter()
+                    .map(|(name, row)| QuickfixEntry {
+                        path: ProjectPath {
+                            worktree_id,
+                            path: Path::new("dir").join(name).into(),
+                        },
+                        position: QuickfixPosition::Printed {
+                            row: *row,
+                            column: 0,
+                        },
+                        message: "".into(),
+                        severity: None,
+                    })
+                    .collect();
+                workspace.quickfix_list().update(cx, |quickfix_list, cx| {
+                    quickfix_list.set_entries("Test", entries, false, cx)
+                });
+            });
+        };
+        let buffer_text = |cx: &mut VimTestContext, name: &str| {
+            cx.workspace(|workspace, _, cx| {
+                let project = workspace.project().read(cx);
+                let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+                let path = ProjectPath {
+                    worktree_id,
+                    path: Path::new("dir").join(name).into(),
+                };
+                project.get_open_buffer(&path, cx).unwrap().read(cx).text()
+            })
+        };
+
+        // Every entry is visited, and entries keep pointing at their line
+        // after earlier lines of the same file are deleted.
+        set_entries(&mut cx, &[("a.rs", 0), ("a.rs", 2), ("b.rs", 1)]);
+        cx.simulate_keystrokes(": c d o space d enter");
+        assert_eq!(buffer_text(&mut cx, "a.rs"), "two\n");
+        assert_eq!(buffer_text(&mut cx, "b.rs"), "one\nthree\n");
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/b.rs"), "one\nthree\n", cx);
+        });
+
+        // Only the first entry of each file is visited.
+        set_entries(&mut cx, &[("b.rs", 1), ("b.rs", 0), ("a.rs", 0)]);
+        cx.simulate_keystrokes(": c f d o space d enter");
+        assert_eq!(buffer_text(&mut cx, "a.rs"), "");
+        assert_eq!(buffer_text(&mut cx, "b.rs"), "one\n");
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/a.rs"), "", cx);
+        });
+    }
+
     #[gpui::test]
     async fn test_offsets(cx: &mut TestAppContext) {
         let mut cx = NeovimBackedTestContext::new(cx).await;
@@ -0,0 +1,279 @@
+use std::ops::Range;
+
+use gpui::{actions, App, Context, Entity, EventEmitter, SharedString};
+use language::{Anchor, Buffer, BufferSnapshot, DiagnosticSeverity, Point, ToPoint as _};
+use project::ProjectPath;
+
+actions!(quickfix, [First, Last, Next, Previous]);
+
+/// A location in the quickfix list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuickfixEntry {
+    pub path: ProjectPath,
+    pub position: QuickfixPosition,
+    pub message: SharedString,
+    /// The severity of the problem at the location, `None` for plain locations like search results.
+    pub severity: Option<DiagnosticSeverity>,
+}
+
+/// Where a [`QuickfixEntry`] points in its file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuickfixPosition {
+    /// A range of an open buffer, which follows the buffer's edits.
+    Anchored {
+        buffer: Entity<Buffer>,
+        range: Range<Anchor>,
+    },
+    /// A 0-based row and column printed by a tool, the column counting
+    /// characters. It gets anchored once the file is opened.
+    Printed { row: u32, column: u32 },
+}
+
+impl QuickfixPosition {
+    /// Returns the 0-based row and column, in characters, where the position
+    /// starts.
+    pub fn start(&self, cx: &App) -> (u32, u32) {
+        match self {
+            Self::Anchored { buffer, range } => {
+                let buffer = buffer.read(cx);
+                let start = range.start.to_point(buffer);
+                let column = buffer
+                    .text_for_range(Point::new(start.row, 0)..start)
+                    .map(|chunk| chunk.chars().count() as u32)
+                    .sum();
+                (start.row, column)
+            }
+            Self::Printed { row, column } => (*row, *column),
+        }
+    }
+}
+
+/// Returns the point at a 0-based row and a column counted in characters,
+/// clipped to the end of the row and of the buffer.
+pub fn point_for_printed_position(buffer: &BufferSnapshot, row: u32, column: u32) -> Point {
+    let row = row.min(buffer.max_point().row);
+    let column = buffer
+        .chars_at(Point::new(row, 0))
+        .take_while(|ch| *ch != '\n')
+        .take(column as usize)
+        .map(char::len_utf8)
+        .sum::<usize>();
+    Point::new(row, column as u32)
+}
+
+pub enum QuickfixListEvent {
+    /// The entries of the list were replaced, and should be shown to the user if `reveal` is set.
+    EntriesReplaced {
+        reveal: bool,
+    },
+    SelectionChanged,
+}
+
+/// The locations to step through with the quickfix actions, populated from
+/// project search results, diagnostics or the problems found in a task's
+/// output. Each workspace has a single list, replaced by whatever populated
+/// it last.
+#[derive(Default)]
+pub struct QuickfixList {
+    title: SharedString,
+    entries: Vec<QuickfixEntry>,
+    selected_index: Option<usize>,
+}
+
+impl EventEmitter<QuickfixListEvent> for QuickfixList {}
+
+impl QuickfixList {
+    pub fn title(&self) -> &SharedString {
+        &self.title
+    }
+
+    pub fn entries(&self) -> &[QuickfixEntry] {
+        &self.entries
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected_index
+    }
+
+    pub fn set_entries(
+        &mut self,
+        title: impl Into<SharedString>,
+        entries: Vec<QuickfixEntry>,
+        reveal: bool,
+        cx: &mut Context<Self>,
+    ) {
+        self.title = title.into();
+        self.entries = entries;
+        self.selected_index = None;
+        cx.emit(QuickfixListEvent::EntriesReplaced { reveal });
+        cx.notify();
+    }
+
+    /// Selects the entry at `ix`, returning it if it exists.
+    pub fn select(&mut self, ix: usize, cx: &mut Context<Self>) -> Option<QuickfixEntry> {
+        let entry = self.entries.get(ix)?.clone();
+        if self.selected_index != Some(ix) {
+            self.selected_index = Some(ix);
+            cx.emit(QuickfixListEvent::SelectionChanged);
+            cx.notify();
+        }
+        Some(entry)
+    }
+
+    /// Anchors the printed positions of the entries in the file of `buffer`,
+    /// so that they follow its edits from now on.
+    pub fn anchor_entries(
+        &mut self,
+        path: &ProjectPath,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = buffer.read(cx).snapshot();
+        let mut anchored = false;
+        for entry in &mut self.entries {
+            if let QuickfixPosition::Printed { row, column } = entry.position {
+                if entry.path == *path {
+                    let point = point_for_printed_position(&snapshot, row, column);
+                    let anchor = snapshot.anchor_before(point);
+                    entry.position = QuickfixPosition::Anchored {
+                        buffer: buffer.clone(),
+                        range: anchor..anchor,
+                    };
+                    anchored = true;
+                }
+            }
+        }
+        if anchored {
+            cx.notify();
+        }
+    }
+
+    /// Returns the index of the entry `count` entries after the selected one,
+    /// the first entry counting as the one after no selection. Returns `None`
+    /// when the selected entry is the last one, and the last entry when the
+    /// count goes past it.
+    pub fn next_index(&self, count: usize) -> Option<usize> {
+        let last_ix = self.entries.len().checked_sub(1)?;
+        match self.selected_index {
+            Some(ix) if ix >= last_ix => None,
+            Some(ix) => Some(ix.saturating_add(count).min(last_ix)),
+            None => Some(count.saturating_sub(1).min(last_ix)),
+        }
+    }
+
+    /// Returns the index of the entry `count` entries before the selected
+    /// one, or `None` when the first entry or nothing is selected.
+    pub fn previous_index(&self, count: usize) -> Option<usize> {
+        match self.selected_index? {
+            0 => None,
+            ix => Some(ix.saturating_sub(count)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use project::WorktreeId;
+    use std::{path::Path, sync::Arc};
+
+    #[gpui::test]
+    fn test_quickfix_list_navigation(cx: &mut TestAppContext) {
+        let entry = |row| QuickfixEntry {
+            path: ProjectPath {
+                worktree_id: WorktreeId::from_usize(1),
+                path: Arc::from(Path::new("main.rs")),
+            },
+            position: QuickfixPosition::Printed { row, column: 0 },
+            message: format!("problem {row}").into(),
+            severity: Some(DiagnosticSeverity::ERROR),
+        };
+        let list = cx.new(|_| QuickfixList::default());
+
+        list.update(cx, |list, cx| {
+            assert_eq!(list.next_index(1), None);
+            list.set_entries("Problems", (0..4).map(entry).collect(), false, cx);
+            assert_eq!(list.selected_index(), None);
+            assert_eq!(list.next_index(1), Some(0));
+            assert_eq!(list.next_index(3), Some(2));
+            assert_eq!(list.previous_index(1), None);
+
+            assert_eq!(list.select(1, cx), Some(entry(1)));
+            assert_eq!(list.next_index(1), Some(2));
+            assert_eq!(list.next_index(10), Some(3));
+            assert_eq!(list.previous_index(1), Some(0));
+            assert_eq!(list.previous_index(5), Some(0));
+
+            assert_eq!(list.select(3, cx), Some(entry(3)));
+            assert_eq!(list.next_index(1), None);
+            assert_eq!(list.select(4, cx), None);
+            assert_eq!(list.selected_index(), Some(3));
+
+            list.set_entries("Search results", vec![entry(7)], true, cx);
+            assert_eq!(list.title().as_ref(), "Search results");
+            assert_eq!(list.selected_index(), None);
+            assert_eq!(list.next_index(1), Some(0));
+        });
+    }
+
+    #[gpui::test]
+    fn test_anchor_printed_positions(cx: &mut TestAppContext) {
+        let path = |name: &str| ProjectPath {
+            worktree_id: WorktreeId::from_usize(1),
+            path: Arc::from(Path::new(name)),
+        };
+        let entry = |name, row, column| QuickfixEntry {
+            path: path(name),
+            position: QuickfixPosition::Printed { row, column },
+            message: "problem".into(),
+            severity: Some(DiagnosticSeverity::ERROR),
+        };
+        let buffer = cx.new(|cx| Buffer::local("let é = \"ü\";\nx\n", cx));
+        let list = cx.new(|_| QuickfixList::default());
+
+        list.update(cx, |list, cx| {
+            list.set_entries(
+                "Problems",
+                vec![
+                    entry("main.rs", 0, 9),
+                    entry("main.rs", 1, 5),
+                    entry("main.rs", 7, 0),
+                    entry("lib.rs", 0, 9),
+                ],
+                false,
+                cx,
+            );
+            list.anchor_entries(&path("main.rs"), &buffer, cx);
+        });
+
+        // Columns count characters, and are clipped to their row.
+        let positions = list.read_with(cx, |list, cx| {
+            list.entries()
+                .iter()
+                .map(|entry| match &entry.position {
+                    QuickfixPosition::Anchored { range, .. } => {
+                        Some(range.start.to_point(buffer.read(cx)))
+                    }
+                    QuickfixPosition::Printed { .. } => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            positions,
+            [
+                Some(Point::new(0, 10)),
+                Some(Point::new(1, 1)),
+                Some(Point::new(2, 0)),
+                None
+            ]
+        );
+
+        // Anchored positions follow edits.
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+        list.read_with(cx, |list, cx| {
+            assert_eq!(list.entries()[0].position.start(cx), (1, 9));
+            assert_eq!(list.entries()[3].position.start(cx), (0, 9));
+        });
+    }
+}
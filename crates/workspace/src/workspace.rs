@@ -5,6 +5,7 @@ pub mod notifications;
 pub mod pane;
 pub mod pane_group;
 mod persistence;
+pub mod quickfix;
 pub mod searchable;
 pub mod shared_screen;
 mod status_bar;
@@ -67,6 +68,7 @@ use postage::stream::Stream;
 use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
 };
+use quickfix::QuickfixList;
 use remote::{ssh_session::ConnectionIdentifier, SshClientDelegate, SshConnectionOptions};
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -821,6 +823,7 @@ pub struct Workspace {
     status_bar: Entity<StatusBar>,
     modal_layer: Entity<ModalLayer>,
     toast_layer: Entity<ToastLayer>,
+    quickfix_list: Entity<QuickfixList>,
     titlebar_item: Option<AnyView>,
     notifications: Notifications,
     project: Entity<Project>,
@@ -1022,6 +1025,7 @@ impl Workspace {
         cx.emit(Event::WorkspaceCreated(weak_handle.clone()));
         let modal_layer = cx.new(|_| ModalLayer::new());
         let toast_layer = cx.new(|_| ToastLayer::new());
+        let quickfix_list = cx.new(|_| QuickfixList::default());
 
         let left_dock = Dock::new(DockPosition::Left, modal_layer.clone(), window, cx);
         let bottom_dock = Dock::new(DockPosition::Bottom, modal_layer.clone(), window, cx);
@@ -1117,6 +1121,7 @@ impl Workspace {
             status_bar,
             modal_layer,
             toast_layer,
+            quickfix_list,
             titlebar_item: None,
             notifications: Default::default(),
             left_dock,
@@ -1368,6 +1373,10 @@ impl Workspace {
         &self.status_bar
     }
 
+    pub fn quickfix_list(&self) -> &Entity<QuickfixList> {
+        &self.quickfix_list
+    }
+
     pub fn app_state(&self) -> &Arc<AppState> {
         &self.app_state
     }
@@ -18,6 +18,7 @@ use breadcrumbs::Breadcrumbs;
 use client::{zed_urls, ZED_URL_SCHEME};
 use collections::VecDeque;
 use command_palette_hooks::CommandPaletteFilter;
+use debugger_ui::debugger_panel::DebugPanel;
 use editor::ProposedChangesEditorToolbar;
 use editor::{scroll::Autoscroll, Editor, MultiBuffer};
 use feature_flags::FeatureFlagAppExt;
@@ -30,6 +31,7 @@ use gpui::{
     ReadGlobal, SharedString, Styled, Task, TitlebarOptions, UpdateGlobal, Window, WindowKind,
     WindowOptions,
 };
+use hierarchy_panel::HierarchyPanel;
 use image_viewer::ImageInfo;
 use migrate::{MigrationBanner, MigrationEvent, MigrationNotification, MigrationType};
 use migrator::{migrate_keymap, migrate_settings};
@@ -40,6 +42,7 @@ use project::{DirectoryLister, ProjectItem};
 use project_panel::ProjectPanel;
 use prompt_store::PromptBuilder;
 use quick_action_bar::QuickActionBar;
+use quickfix_panel::QuickfixPanel;
 use recent_projects::open_ssh_project;
 use release_channel::{AppCommitSha, ReleaseChannel};
 use rope::Rope;
@@ -57,6 +60,7 @@ use std::{borrow::Cow, ops::Deref, path::Path, sync::Arc};
 use terminal_view::terminal_panel::{self, TerminalPanel};
 use theme::{ActiveTheme, ThemeSettings};
 use ui::{prelude::*, PopoverMenuHandle};
+use undo_tree::UndoTreePanel;
 use util::markdown::MarkdownString;
 use util::{asset_str, ResultExt};
 use uuid::Uuid;
@@ -396,6 +400,10 @@ fn initialize_panels(
     cx.spawn_in(window, |workspace_handle, mut cx| async move {
         let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
         let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
+        let debug_panel = DebugPanel::load(workspace_handle.clone(), cx.clone());
+        let hierarchy_panel = HierarchyPanel::load(workspace_handle.clone(), cx.clone());
+        let quickfix_panel = QuickfixPanel::load(workspace_handle.clone(), cx.clone());
+        let undo_tree_panel = UndoTreePanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
         let channels_panel =
             collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
@@ -409,6 +417,10 @@ fn initialize_panels(
         let (
             project_panel,
             outline_panel,
+            debug_panel,
+            hierarchy_panel,
+            quickfix_panel,
+            undo_tree_panel,
             terminal_panel,
             channels_panel,
             chat_panel,
@@ -416,6 +428,10 @@ fn initialize_panels(
         ) = futures::try_join!(
             project_panel,
             outline_panel,
+            debug_panel,
+            hierarchy_panel,
+            quickfix_panel,
+            undo_tree_panel,
             terminal_panel,
             channels_panel,
             chat_panel,
@@ -425,6 +441,10 @@ fn initialize_panels(
         workspace_handle.update_in(&mut cx, |workspace, window, cx| {
             workspace.add_panel(project_panel, window, cx);
             workspace.add_panel(outline_panel, window, cx);
+            workspace.add_panel(debug_panel, window, cx);
+            workspace.add_panel(hierarchy_panel, window, cx);
+            workspace.add_panel(quickfix_panel, window, cx);
+            workspace.add_panel(undo_tree_panel, window, cx);
             workspace.add_panel(terminal_panel, window, cx);
             workspace.add_panel(channels_panel, window, cx);
             workspace.add_panel(chat_panel, window, cx);
@@ -4253,6 +4273,10 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            debugger_ui::init(cx);
+            hierarchy_panel::init(cx);
+            quickfix_panel::init(cx);
+            undo_tree::init(cx);
             terminal_view::init(cx);
             copilot::copilot_chat::init(
                 app_state.fs.clone(),
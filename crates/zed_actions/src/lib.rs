@@ -120,6 +120,25 @@ pub mod git {
     action_with_deprecated_aliases!(git, Branch, ["branches::OpenRecent"]);
 }
 
+pub mod diff {
+    use gpui::impl_actions;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+    use std::path::PathBuf;
+
+    /// Compares a file with another file or an untitled buffer picked from a list.
+    #[derive(Clone, Default, Debug, PartialEq, Deserialize, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct CompareWith {
+        /// The absolute path of the file to compare, defaulting to the
+        /// buffer of the active editor.
+        #[serde(default)]
+        pub path: Option<PathBuf>,
+    }
+
+    impl_actions!(diff, [CompareWith]);
+}
+
 pub mod command_palette {
     use gpui::actions;
 